@@ -4,6 +4,7 @@ mod flags;
 mod gc;
 mod heap;
 mod mailbox;
+mod max_heap_size;
 mod monitor;
 mod priority;
 
@@ -18,6 +19,7 @@ use core::ptr::{self, NonNull};
 use core::str::Chars;
 use core::sync::atomic::{AtomicU16, AtomicU64, AtomicUsize, Ordering};
 
+use ::alloc::collections::VecDeque;
 use ::alloc::sync::Arc;
 
 use hashbrown::{HashMap, HashSet};
@@ -30,7 +32,7 @@ use crate::erts::exception::runtime;
 use crate::erts::exception::system::Alloc;
 use crate::erts::process::alloc::layout_to_words;
 use crate::erts::term::{
-    atom_unchecked, pid, reference, Atom, Cons, Integer, Pid, ProcBin, Reference, Tuple,
+    atom_unchecked, pid, reference, Atom, Bitstring, Cons, Integer, Pid, ProcBin, Reference, Tuple,
 };
 
 use super::*;
@@ -43,9 +45,10 @@ use self::code::stack;
 use self::code::stack::frame::{Frame, Placement};
 pub use self::flags::*;
 pub use self::flags::*;
-pub use self::gc::{GcError, RootSet};
+pub use self::gc::{GcError, GcStatistics, RootSet};
 pub use self::heap::ProcessHeap;
 pub use self::mailbox::*;
+pub use self::max_heap_size::MaxHeapSize;
 pub use self::monitor::Monitor;
 pub use self::priority::Priority;
 use crate::erts::process::alloc::heap_alloc::MakePidError;
@@ -74,24 +77,29 @@ pub struct Process {
     /// ID of the scheduler that is running the process
     scheduler_id: Mutex<Option<scheduler::ID>>,
     /// The priority of the process in `scheduler`.
-    pub priority: Priority,
+    priority: RwLock<Priority>,
     /// Process flags, e.g. `Process.flag/1`
     flags: AtomicProcessFlags,
     /// Minimum size of the heap that this process will start with
-    min_heap_size: usize,
+    min_heap_size: AtomicUsize,
     /// The maximum size of the heap allowed for this process
-    max_heap_size: usize,
+    max_heap_size: RwLock<MaxHeapSize>,
     /// Minimum virtual heap size for this process
-    min_vheap_size: usize,
+    min_vheap_size: AtomicUsize,
     /// The percentage of used to unused space at which a collection is triggered
     gc_threshold: f64,
     /// The maximum number of minor collections before a full sweep occurs
-    max_gen_gcs: usize,
+    max_gen_gcs: AtomicUsize,
     /// off-heap allocations
     off_heap: SpinLock<LinkedList<HeapFragmentAdapter>>,
     off_heap_size: AtomicUsize,
     /// Process dictionary
     dictionary: Mutex<HashMap<Term, Term>>,
+    /// The module called when an undefined function or registered name is called
+    error_handler: RwLock<Atom>,
+    /// The number of calls saved in `last_calls`.  `0` disables saving calls.
+    save_calls: AtomicUsize,
+    last_calls: Mutex<VecDeque<Arc<ModuleFunctionArity>>>,
     /// The `pid` of the process that `spawn`ed this process.
     parent_pid: Option<Pid>,
     pid: Pid,
@@ -116,6 +124,9 @@ pub struct Process {
     heap: Mutex<ProcessHeap>,
 }
 impl Process {
+    /// The `fullsweep_after` of processes that are not spawned with one
+    pub const DEFAULT_FULLSWEEP_AFTER: usize = 65535;
+
     /// Creates a new PCB with a heap defined by the given pointer, and
     /// `heap_size`, which is the size of the heap in words.
     pub fn new(
@@ -131,21 +142,24 @@ impl Process {
 
         Self {
             flags: AtomicProcessFlags::new(ProcessFlags::Default),
-            min_heap_size: heap_size,
-            max_heap_size: 0,
-            min_vheap_size: 0,
+            min_heap_size: AtomicUsize::new(heap_size),
+            max_heap_size: Default::default(),
+            min_vheap_size: AtomicUsize::new(0),
             gc_threshold: 0.75,
-            max_gen_gcs: 65535,
+            max_gen_gcs: AtomicUsize::new(Self::DEFAULT_FULLSWEEP_AFTER),
             off_heap,
             off_heap_size: AtomicUsize::new(0),
             dictionary: Default::default(),
+            error_handler: RwLock::new(Atom::try_from_str("error_handler").unwrap()),
+            save_calls: AtomicUsize::new(0),
+            last_calls: Default::default(),
             pid,
             status: Default::default(),
             mailbox: Default::default(),
             heap: Mutex::new(heap),
             code_stack: Default::default(),
             scheduler_id: Mutex::new(None),
-            priority,
+            priority: RwLock::new(priority),
             parent_pid,
             initial_module_function_arity,
             run_reductions: Default::default(),
@@ -186,8 +200,32 @@ impl Process {
     }
 
     pub fn trap_exit(&self, value: bool) -> bool {
-        let flag = ProcessFlags::TrapExit;
+        self.put_flag(ProcessFlags::TrapExit, value)
+    }
+
+    pub fn traps_exit(&self) -> bool {
+        self.are_flags_set(ProcessFlags::TrapExit)
+    }
+
+    /// Returns the old value of `sensitive`
+    pub fn sensitive(&self, value: bool) -> bool {
+        self.put_flag(ProcessFlags::Sensitive, value)
+    }
+
+    pub fn is_sensitive(&self) -> bool {
+        self.are_flags_set(ProcessFlags::Sensitive)
+    }
 
+    /// Returns the old value of whether the message queue is stored off heap
+    pub fn message_queue_off_heap(&self, value: bool) -> bool {
+        self.put_flag(ProcessFlags::MessageQueueOffHeap, value)
+    }
+
+    pub fn is_message_queue_off_heap(&self) -> bool {
+        self.are_flags_set(ProcessFlags::MessageQueueOffHeap)
+    }
+
+    fn put_flag(&self, flag: ProcessFlags, value: bool) -> bool {
         let old_flags = if value {
             self.set_flags(flag)
         } else {
@@ -197,8 +235,70 @@ impl Process {
         old_flags.are_set(flag)
     }
 
-    pub fn traps_exit(&self) -> bool {
-        self.are_flags_set(ProcessFlags::TrapExit)
+    // Priority
+
+    pub fn priority(&self) -> Priority {
+        *self.priority.read()
+    }
+
+    /// Returns the old priority.  The caller is responsible for moving the process to the run
+    /// queue for the new priority if it is queued.
+    pub fn set_priority(&self, priority: Priority) -> Priority {
+        mem::replace(&mut *self.priority.write(), priority)
+    }
+
+    // Error Handler
+
+    pub fn error_handler(&self) -> Atom {
+        *self.error_handler.read()
+    }
+
+    /// Returns the old error handler
+    pub fn set_error_handler(&self, error_handler: Atom) -> Atom {
+        mem::replace(&mut *self.error_handler.write(), error_handler)
+    }
+
+    // Saved Calls
+
+    pub fn save_calls(&self) -> usize {
+        self.save_calls.load(Ordering::Acquire)
+    }
+
+    /// Returns the old number of calls saved.  Setting `0` discards any saved calls.
+    pub fn set_save_calls(&self, save_calls: usize) -> usize {
+        let mut last_calls = self.last_calls.lock();
+        let old_save_calls = self.save_calls.swap(save_calls, Ordering::AcqRel);
+
+        while save_calls < last_calls.len() {
+            last_calls.pop_front();
+        }
+
+        old_save_calls
+    }
+
+    /// The saved calls from oldest to newest, or `None` if calls are not being saved
+    pub fn last_calls(&self) -> Option<Vec<Arc<ModuleFunctionArity>>> {
+        let last_calls = self.last_calls.lock();
+
+        if 0 < self.save_calls() {
+            Some(last_calls.iter().cloned().collect())
+        } else {
+            None
+        }
+    }
+
+    fn save_call(&self, frame: &Frame) {
+        let save_calls = self.save_calls();
+
+        if 0 < save_calls {
+            let mut last_calls = self.last_calls.lock();
+
+            if save_calls <= last_calls.len() {
+                last_calls.pop_front();
+            }
+
+            last_calls.push_back(frame.module_function_arity());
+        }
     }
 
     // Alloc
@@ -278,6 +378,162 @@ impl Process {
         heap.virtual_alloc(bin)
     }
 
+    // Heap Statistics
+
+    /// The size (in words) of the young generation heap, which includes the stack
+    pub fn heap_size(&self) -> usize {
+        self.heap.lock().young.size()
+    }
+
+    /// The size (in words) of the old generation heap, which is `0` until the first minor
+    /// collection tenures terms
+    pub fn old_heap_size(&self) -> usize {
+        self.heap.lock().old.size()
+    }
+
+    /// The size (in words) of all heap generations and off-heap fragments
+    pub fn total_heap_size(&self) -> usize {
+        let heap_size = {
+            let heap = self.heap.lock();
+
+            heap.young.size() + heap.old.size()
+        };
+
+        heap_size + to_word_size(self.off_heap_size())
+    }
+
+    /// The size (in words) of the used part of all heap generations, the stack and off-heap
+    /// fragments
+    pub fn total_heap_used(&self) -> usize {
+        let heap_used = {
+            let heap = self.heap.lock();
+
+            heap.young.heap_used() + heap.stack_used() + heap.old.heap_used()
+        };
+
+        heap_used + to_word_size(self.off_heap_size())
+    }
+
+    /// The amount (in bytes) of binary data referenced from the virtual binary heaps of both
+    /// generations
+    pub fn virtual_heap_used(&self) -> usize {
+        let heap = self.heap.lock();
+
+        heap.young.virtual_heap_used() + heap.old.virtual_heap_used()
+    }
+
+    /// The `(id, byte_len, ref_count)` of each binary referenced from the virtual binary heaps
+    pub fn virtual_binaries(&self) -> Vec<(usize, usize, usize)> {
+        let heap = self.heap.lock();
+
+        heap.young
+            .virtual_heap_iter()
+            .chain(heap.old.virtual_heap_iter())
+            .map(|proc_bin| {
+                (
+                    proc_bin.id(),
+                    proc_bin.full_byte_len(),
+                    proc_bin.ref_count(),
+                )
+            })
+            .collect()
+    }
+
+    /// The number of minor collections since the last full sweep
+    pub fn minor_gcs(&self) -> usize {
+        self.heap.lock().gen_gc_count
+    }
+
+    pub fn gc_statistics(&self) -> GcStatistics {
+        self.heap.lock().statistics
+    }
+
+    /// Records the monotonic time, in milliseconds, of the collection that just finished
+    pub fn set_last_gc_time(&self, milliseconds: u64) {
+        self.heap.lock().statistics.last_gc_time = Some(milliseconds);
+    }
+
+    /// The size (in bytes) of the process, including the control block, heaps and off-heap
+    /// fragments
+    pub fn memory(&self) -> usize {
+        mem::size_of::<Self>() + self.total_heap_size() * mem::size_of::<Term>()
+    }
+
+    pub fn min_heap_size(&self) -> usize {
+        self.min_heap_size.load(Ordering::Acquire)
+    }
+
+    /// Returns the old minimum heap size.  The new size takes effect at the next collection.
+    pub fn set_min_heap_size(&self, min_heap_size: usize) -> usize {
+        self.min_heap_size.swap(min_heap_size, Ordering::AcqRel)
+    }
+
+    pub fn max_heap_size(&self) -> MaxHeapSize {
+        *self.max_heap_size.read()
+    }
+
+    /// Returns the old maximum heap size
+    pub fn set_max_heap_size(&self, max_heap_size: MaxHeapSize) -> MaxHeapSize {
+        mem::replace(&mut *self.max_heap_size.write(), max_heap_size)
+    }
+
+    /// Called by the garbage collector when the projected `heap_size` (in words) exceeds
+    /// `max_heap_size().size`.
+    ///
+    /// Logs an error report if `error_logger` is set.  Returns
+    /// `Err(GcError::MaxHeapSizeExceeded)` if `kill` is set, so that the collection is aborted;
+    /// otherwise, the collection continues and the heap is allowed to grow past the limit.
+    pub(crate) fn reached_max_heap_size(&self, heap_size: usize) -> Result<(), GcError> {
+        let MaxHeapSize {
+            size,
+            kill,
+            error_logger,
+        } = self.max_heap_size();
+
+        if error_logger {
+            log::error!(
+                concat!(
+                    "     Process:          {}\n",
+                    "     Context:          maximum heap size reached\n",
+                    "     Max Heap Size:    {}\n",
+                    "     Total Heap Size:  {}\n",
+                    "     Kill:             {}\n",
+                    "     Error Logger:     {}"
+                ),
+                self,
+                size,
+                heap_size,
+                kill,
+                error_logger
+            );
+        }
+
+        if kill {
+            Err(GcError::MaxHeapSizeExceeded)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn min_vheap_size(&self) -> usize {
+        self.min_vheap_size.load(Ordering::Acquire)
+    }
+
+    /// Returns the old minimum virtual binary heap size
+    pub fn set_min_vheap_size(&self, min_vheap_size: usize) -> usize {
+        self.min_vheap_size.swap(min_vheap_size, Ordering::AcqRel)
+    }
+
+    /// The maximum number of minor collections before a full sweep occurs
+    pub fn fullsweep_after(&self) -> usize {
+        self.max_gen_gcs.load(Ordering::Acquire)
+    }
+
+    /// Returns the old number of minor collections before a full sweep occurs
+    pub fn set_fullsweep_after(&self, fullsweep_after: usize) -> usize {
+        self.max_gen_gcs.swap(fullsweep_after, Ordering::AcqRel)
+    }
+
     // Stack
 
     /// Frees stack space occupied by the last term on the stack,
@@ -433,18 +689,7 @@ impl Process {
             }
         }
 
-        // status.write() scope
-        {
-            let mut writable_status = self.status.write();
-
-            if *writable_status == Status::Waiting {
-                *writable_status = Status::Runnable;
-
-                Ok(true)
-            } else {
-                Ok(false)
-            }
-        }
+        Ok(self.stop_waiting())
     }
 
     fn send_message(&self, message: Message) {
@@ -503,6 +748,21 @@ impl Process {
             .external_pid_with_node_id(node_id, number, serial)
     }
 
+    pub fn external_port_with_node_id(&self, node_id: usize, number: usize) -> Result<Term, Alloc> {
+        self.acquire_heap()
+            .external_port_with_node_id(node_id, number)
+    }
+
+    pub fn external_reference_with_node_id(
+        &self,
+        node_id: usize,
+        scheduler_id: scheduler::ID,
+        number: reference::Number,
+    ) -> Result<Term, Alloc> {
+        self.acquire_heap()
+            .external_reference_with_node_id(node_id, scheduler_id, number)
+    }
+
     pub fn float(&self, f: f64) -> Result<Term, Alloc> {
         self.acquire_heap().float(f)
     }
@@ -667,6 +927,39 @@ impl Process {
         }
     }
 
+    /// Returns list of all `{key, value}` entries from the process dictionary, copied into a new
+    /// heap fragment, so that another process can read them without referring to this process's
+    /// heap.
+    pub fn get_entries_to_fragment(&self) -> Result<(Term, NonNull<HeapFragment>), Alloc> {
+        // hold heap lock before dictionary lock, so that a collection cannot move the entries while
+        // they are copied
+        let _heap = self.heap.lock();
+        let dictionary = self.dictionary.lock();
+
+        let len = dictionary.len();
+        let entry_need_in_words = Tuple::need_in_words_from_len(2);
+        let need_in_words = dictionary.iter().fold(
+            Cons::need_in_words_from_len(len) + len * entry_need_in_words,
+            |acc, (key, value)| acc + key.size_in_words() + value.size_in_words(),
+        );
+
+        let mut non_null_heap_fragment =
+            unsafe { HeapFragment::new_from_word_size(need_in_words)? };
+        let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
+        let mut entry_vec = Vec::with_capacity(len);
+
+        for (key, value) in dictionary.iter() {
+            let heap_fragment_key = key.clone_to_heap(heap_fragment)?;
+            let heap_fragment_value = value.clone_to_heap(heap_fragment)?;
+            entry_vec
+                .push(heap_fragment.tuple_from_slice(&[heap_fragment_key, heap_fragment_value])?);
+        }
+
+        let entries = heap_fragment.list_from_slice(&entry_vec)?;
+
+        Ok((entries, non_null_heap_fragment))
+    }
+
     /// Returns list of all keys from the process dictionary.
     pub fn get_keys(&self) -> Result<Term, Alloc> {
         let mut heap = self.heap.lock();
@@ -756,8 +1049,9 @@ impl Process {
         heap.should_collect(self.gc_threshold)
     }
 
+    /// The size (in bytes) of the heap fragments attached to this process
     #[inline(always)]
-    fn off_heap_size(&self) -> usize {
+    pub fn off_heap_size(&self) -> usize {
         self.off_heap_size.load(Ordering::Acquire)
     }
 
@@ -809,7 +1103,15 @@ impl Process {
         let mut rootset = RootSet::new(roots);
         self.base_root_set(&mut rootset);
         // Initialize the collector with the given root set
-        heap.garbage_collect(self, need, rootset)
+        let result = heap.garbage_collect(self, need, rootset);
+        drop(heap);
+
+        // Only returned when `max_heap_size().kill` is set, see `reached_max_heap_size`
+        if let Err(GcError::MaxHeapSizeExceeded) = result {
+            self.exception(exit!(atom_unchecked("killed")));
+        }
+
+        result
     }
 
     /// Returns true if the given pointer belongs to memory owned by this process
@@ -876,6 +1178,20 @@ impl Process {
         }
     }
 
+    /// Returns `true` if the process was in the waiting status and is now runnable, so it needs to
+    /// be moved to a run queue by its scheduler.
+    pub fn stop_waiting(&self) -> bool {
+        let mut writable_status = self.status.write();
+
+        if *writable_status == Status::Waiting {
+            *writable_status = Status::Runnable;
+
+            true
+        } else {
+            false
+        }
+    }
+
     /// Puts the process in the waiting status
     pub fn wait(&self) {
         *self.status.write() = Status::Waiting;
@@ -909,6 +1225,19 @@ impl Process {
         self.code_stack.lock().len()
     }
 
+    /// Discards all `Frame`s and the terms on the stack, such as when hibernating, so that the
+    /// next `Frame` placed is the only code left to run
+    pub fn clear_code_stack(&self) {
+        self.code_stack.lock().clear();
+
+        let mut heap = self.heap.lock();
+        let stack_size = heap.stack_size();
+
+        if 0 < stack_size {
+            heap.stack_popn(stack_size);
+        }
+    }
+
     pub fn pop_code_stack(&self) {
         let mut locked_stack = self.code_stack.lock();
         locked_stack.pop().unwrap();
@@ -947,10 +1276,13 @@ impl Process {
     }
 
     pub fn push_frame(&self, frame: Frame) {
+        self.save_call(&frame);
         self.code_stack.lock().push(frame)
     }
 
     pub fn replace_frame(&self, frame: Frame) {
+        self.save_call(&frame);
+
         let mut locked_code_stack = self.code_stack.lock();
 
         // unwrap to ensure there is a frame to replace
@@ -1032,10 +1364,7 @@ impl fmt::Debug for Process {
 
 impl fmt::Display for Process {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let pid = self.pid;
-        let (number, serial) = (pid.number(), pid.serial());
-
-        write!(f, "#PID<0.{}.{}>", number, serial)?;
+        write!(f, "{}", self.pid)?;
 
         match *self.registered_name.read() {
             Some(registered_name_atom) => write!(f, "({})", registered_name_atom.name()),
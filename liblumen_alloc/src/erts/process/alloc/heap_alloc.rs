@@ -21,8 +21,9 @@ use crate::erts::term::binary::IterableBitstring;
 use crate::erts::term::reference::{self, Reference};
 use crate::erts::term::resource;
 use crate::erts::term::{
-    make_pid, pid, AsTerm, BinaryType, BytesFromBinaryError, Closure, Cons, ExternalPid, Float,
-    HeapBin, Integer, Map, ProcBin, StrFromBinaryError, SubBinary, Term, Tuple, TypedTerm,
+    make_pid, pid, AsTerm, BinaryType, BytesFromBinaryError, Closure, Cons, ExternalPid,
+    ExternalPort, ExternalReference, Float, HeapBin, Integer, Map, ProcBin, StrFromBinaryError,
+    SubBinary, Term, Tuple, TypedTerm,
 };
 use crate::{erts, ModuleFunctionArity};
 use crate::{scheduler, VirtualAlloc};
@@ -187,6 +188,27 @@ pub trait HeapAlloc {
         Ok(heap_external_pid)
     }
 
+    /// Creates an `ExternalPort` with the given `node` and `number`.
+    fn external_port_with_node_id(&mut self, node_id: usize, number: usize) -> Result<Term, Alloc>
+    where
+        Self: core::marker::Sized,
+    {
+        ExternalPort::with_node_id(node_id, number).clone_to_heap(self)
+    }
+
+    /// Creates an `ExternalReference` with the given `node`, `scheduler_id` and `number`.
+    fn external_reference_with_node_id(
+        &mut self,
+        node_id: usize,
+        scheduler_id: scheduler::ID,
+        number: reference::Number,
+    ) -> Result<Term, Alloc>
+    where
+        Self: core::marker::Sized,
+    {
+        ExternalReference::with_node_id(node_id, scheduler_id, number).clone_to_heap(self)
+    }
+
     fn float(&mut self, f: f64) -> Result<Term, Alloc> {
         let float = Float::new(f);
 
@@ -1,6 +1,7 @@
 pub mod frame;
 
 use core::fmt::{self, Debug, Display};
+use core::slice;
 
 use alloc::collections::vec_deque::{Iter, VecDeque};
 use alloc::sync::Arc;
@@ -14,6 +15,10 @@ use self::frame::Frame;
 pub struct Stack(VecDeque<Frame>);
 
 impl Stack {
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
     pub fn get(&self, index: usize) -> Option<&Frame> {
         self.0.get(index)
     }
@@ -62,6 +67,12 @@ impl Debug for Stack {
 
 pub struct Trace(Vec<Arc<ModuleFunctionArity>>);
 
+impl Trace {
+    pub fn iter(&self) -> slice::Iter<Arc<ModuleFunctionArity>> {
+        self.0.iter()
+    }
+}
+
 impl Debug for Trace {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for module_function_arity in self.0.iter() {
@@ -26,6 +26,17 @@ impl ProcessFlags {
     /// This flag indicates the processes linked to this process should send exit messages instead
     /// of causing this process to exit when they exit
     pub const TrapExit: Self = Self(1 << 6);
+    /// This flag indicates the process is sensitive, so its data should not be exposed to
+    /// tracing or saved calls
+    pub const Sensitive: Self = Self(1 << 7);
+    /// This flag indicates the process's message queue should be stored off heap
+    pub const MessageQueueOffHeap: Self = Self(1 << 8);
+    /// This flag indicates the next full sweep is for hibernation, so the heap should be shrunk
+    /// to fit only the live data
+    pub const Hibernate: Self = Self(1 << 9);
+    /// This flag indicates another process requested a collection, which the scheduler of this
+    /// process does before it next runs
+    pub const GcRequested: Self = Self(1 << 10);
 
     pub fn are_set(&self, flags: ProcessFlags) -> bool {
         (*self & flags) == flags
@@ -1,6 +1,7 @@
 mod collector;
 mod old_heap;
 mod rootset;
+mod statistics;
 mod virtual_heap;
 mod young_heap;
 
@@ -15,10 +16,11 @@ pub enum GcError {
     /// The system is out of memory, and there is not much you can do
     /// but panic, however this choice is left up to the caller
     Alloc(Alloc),
-    /// Occurs when a process is configured with a maximum heap size,
-    /// and a projected heap growth is found to exceed the limit. In
-    /// this situation the only meaningful thing to do is to kill the
-    /// process
+    /// Occurs when a process is configured with a maximum heap size
+    /// that has `kill` set, and a projected heap growth is found to
+    /// exceed the limit. `Process::garbage_collect` has already exited
+    /// the process with reason `killed`, so it only needs to be run
+    /// again for the exit to take effect
     MaxHeapSizeExceeded,
     /// Indicates that an allocation could not be filled without first
     /// performing a full sweep collection
@@ -28,5 +30,6 @@ pub enum GcError {
 pub(super) use self::collector::GarbageCollector;
 pub(super) use self::old_heap::OldHeap;
 pub use self::rootset::RootSet;
-pub(super) use self::virtual_heap::VirtualBinaryHeap;
+pub use self::statistics::GcStatistics;
+pub(super) use self::virtual_heap::{ProcBinAdapter, VirtualBinaryHeap};
 pub(super) use self::young_heap::YoungHeap;
@@ -118,14 +118,16 @@ impl<'p, 'h> GarbageCollector<'p, 'h> {
             new_size = alloc::next_heap_size(new_size);
         }
         // Verify that our projected heap size is not going to blow the max heap size, if set
-        // NOTE: When this happens, we will be left with no choice but to kill the process
-        if self.process.max_heap_size > 0 && self.process.max_heap_size < new_size {
-            return Err(GcError::MaxHeapSizeExceeded);
+        // NOTE: When this happens and `kill` is set, the process will be killed
+        let max_heap_size = self.process.max_heap_size().size;
+        if max_heap_size > 0 && max_heap_size < new_size {
+            self.process.reached_max_heap_size(new_size)?;
         }
-        // Unset heap_grow and need_fullsweep flags, because we are doing both
+        let hibernate = self.process.flags.are_set(ProcessFlags::Hibernate);
+        // Unset heap_grow, need_fullsweep and hibernate flags, because we are doing all of them
         self.process
             .flags
-            .clear(ProcessFlags::GrowHeap | ProcessFlags::NeedFullSweep);
+            .clear(ProcessFlags::GrowHeap | ProcessFlags::NeedFullSweep | ProcessFlags::Hibernate);
         // Allocate new heap
         let new_heap_start = alloc::heap(new_size).map_err(|alloc| GcError::Alloc(alloc))?;
         let mut new_heap = YoungHeap::new(new_heap_start, new_size);
@@ -221,6 +223,8 @@ impl<'p, 'h> GarbageCollector<'p, 'h> {
         let stack_used = self.heap.young.stack_used();
         let heap_used = self.heap.young.heap_used();
         let size_after = heap_used + stack_used + self.process.off_heap_size();
+        self.heap.statistics.major_gcs += 1;
+        self.heap.statistics.words_reclaimed += size_before.saturating_sub(size_after);
         if size_before >= size_after {
             trace!(
                 "Full sweep reclaimed {} words of garbage",
@@ -241,14 +245,21 @@ impl<'p, 'h> GarbageCollector<'p, 'h> {
             // that was smaller than even our worst case estimate, which means
             // we almost certainly have a bug
             panic!("Full sweep finished, but the needed size exceeds even the most pessimistic estimate, this must be a bug");
+        } else if hibernate {
+            // A hibernating process is not expected to allocate until it is woken up, so leave no
+            // free space beyond `need`, ignoring `min_heap_size`
+            if stack_used < need_after && need_after < total_size {
+                self.shrink_young_heap(need_after);
+                adjusted = true;
+            }
         } else if total_size * 3 < need_after * 4 {
             // `need_after` requires more than 75% of the current size, schedule some growth
             self.process.flags.set(ProcessFlags::GrowHeap);
-        } else if total_size > need_after * 4 && self.process.min_heap_size < total_size {
+        } else if total_size > need_after * 4 && self.process.min_heap_size() < total_size {
             // We need less than 25% of the current heap, shrink
             let wanted = need_after * 2;
-            let size = if wanted < self.process.min_heap_size {
-                self.process.min_heap_size
+            let size = if wanted < self.process.min_heap_size() {
+                self.process.min_heap_size()
             } else {
                 alloc::next_heap_size(wanted)
             };
@@ -312,6 +323,8 @@ impl<'p, 'h> GarbageCollector<'p, 'h> {
         self.sanity_check();
 
         self.heap.gen_gc_count += 1;
+        self.heap.statistics.minor_gcs += 1;
+        self.heap.statistics.words_reclaimed += size_before.saturating_sub(size_after);
         let need_after = heap_used + need + stack_size;
 
         // Excessively large heaps should be shrunk, but don't even bother on reasonable small heaps
@@ -333,8 +346,8 @@ impl<'p, 'h> GarbageCollector<'p, 'h> {
                 }
             }
 
-            wanted = if wanted < self.process.min_heap_size {
-                self.process.min_heap_size
+            wanted = if wanted < self.process.min_heap_size() {
+                self.process.min_heap_size()
             } else {
                 alloc::next_heap_size(wanted)
             };
@@ -514,7 +527,8 @@ impl<'p, 'h> GarbageCollector<'p, 'h> {
         let old = &self.heap.old;
 
         // If a max heap size is set, make sure we're not going to exceed it
-        if self.process.max_heap_size > 0 {
+        let max_heap_size = self.process.max_heap_size().size;
+        if max_heap_size > 0 {
             // First, check if we have exceeded the max heap size
             let mut heap_size = size_before;
             // Includes unused area between stack and heap
@@ -535,9 +549,9 @@ impl<'p, 'h> GarbageCollector<'p, 'h> {
             }
             heap_size += new_heap_size;
 
-            // When this error type is returned, a full sweep will be triggered
-            if heap_size > self.process.max_heap_size {
-                return Err(GcError::MaxHeapSizeExceeded);
+            // When `kill` is set, this aborts the collection and the process is killed
+            if heap_size > max_heap_size {
+                self.process.reached_max_heap_size(heap_size)?;
             }
         }
 
@@ -550,7 +564,7 @@ impl<'p, 'h> GarbageCollector<'p, 'h> {
         if process.needs_fullsweep() {
             return true;
         }
-        heap.gen_gc_count >= process.max_gen_gcs
+        heap.gen_gc_count >= process.fullsweep_after()
     }
 
     /// Determines if we should try and grow the heap even when not necessary
@@ -2,13 +2,15 @@ use core::fmt;
 use core::mem;
 use core::ptr;
 
+use intrusive_collections::linked_list;
+
 use liblumen_core::util::pointer::*;
 
 use crate::erts::term::binary_bytes;
 use crate::erts::term::{is_move_marker, Closure, Cons, HeapBin, MatchContext, ProcBin, SubBinary};
 use crate::erts::*;
 
-use super::{VirtualBinaryHeap, YoungHeap};
+use super::{ProcBinAdapter, VirtualBinaryHeap, YoungHeap};
 
 /// This type represents the old generation process heap
 ///
@@ -93,7 +95,6 @@ impl OldHeap {
     }
 
     /// Returns the used size of the virtual heap
-    #[allow(unused)]
     #[inline]
     pub fn virtual_heap_used(&self) -> usize {
         self.vheap.heap_used()
@@ -105,6 +106,12 @@ impl OldHeap {
         self.vheap.contains(term)
     }
 
+    /// Iterates the binaries referenced from this heap's virtual binary heap
+    #[inline]
+    pub fn virtual_heap_iter(&self) -> linked_list::Iter<ProcBinAdapter> {
+        self.vheap.iter()
+    }
+
     /// Unlinks the given ProcBin from the virtual binary heap, but does not free it
     #[inline]
     pub fn virtual_heap_unlink(&mut self, bin: &ProcBin) {
@@ -0,0 +1,15 @@
+/// Counters kept by the garbage collector over the lifetime of a process
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct GcStatistics {
+    /// The number of minor collections.  Unlike `Process::minor_gcs`, this is not reset by a full
+    /// sweep.
+    pub minor_gcs: usize,
+    /// The number of full sweeps
+    pub major_gcs: usize,
+    /// The total number of words freed by all collections
+    pub words_reclaimed: usize,
+    /// The monotonic time, in milliseconds, that the last collection finished.  `liblumen_alloc`
+    /// has no clock, so this is recorded by the caller of `Process::garbage_collect` with
+    /// `Process::set_last_gc_time`.
+    pub last_gc_time: Option<u64>,
+}
@@ -3,6 +3,7 @@ use core::mem;
 use core::ptr;
 
 use intrusive_collections::intrusive_adapter;
+use intrusive_collections::linked_list;
 use intrusive_collections::{LinkedList, LinkedListLink, UnsafeRef};
 
 use super::{OldHeap, YoungHeap};
@@ -67,6 +68,12 @@ impl VirtualBinaryHeap {
             .any(|bin_ref| ptr == bin_ref as *const _ as *const T)
     }
 
+    /// Iterates the binaries referenced from this virtual binary heap
+    #[inline]
+    pub fn iter(&self) -> linked_list::Iter<ProcBinAdapter> {
+        self.bins.iter()
+    }
+
     /// Adds the given `ProcBin` to the virtual binary heap
     ///
     /// Returns a box `Term` which wraps the pointer to the binary,
@@ -2,6 +2,8 @@ use core::fmt;
 use core::mem;
 use core::ptr::{self, NonNull};
 
+use intrusive_collections::linked_list;
+
 use liblumen_core::util::pointer::{distance_absolute, in_area, in_area_inclusive};
 
 use crate::erts::exception::system::Alloc;
@@ -407,6 +409,12 @@ impl YoungHeap {
         self.vheap.contains(term)
     }
 
+    /// Iterates the binaries referenced from this heap's virtual binary heap
+    #[inline]
+    pub fn virtual_heap_iter(&self) -> linked_list::Iter<ProcBinAdapter> {
+        self.vheap.iter()
+    }
+
     /// Unlinks the given ProcBin from the virtual binary heap, but does not free it
     #[inline]
     pub fn virtual_heap_unlink(&mut self, bin: &ProcBin) {
@@ -16,6 +16,8 @@ pub struct ProcessHeap {
     pub(super) young: YoungHeap,
     // old generation heap
     pub(super) old: OldHeap,
+    // counters updated by each collection
+    pub(super) statistics: GcStatistics,
 }
 impl ProcessHeap {
     pub fn new(heap: *mut Term, heap_size: usize) -> Self {
@@ -25,6 +27,7 @@ impl ProcessHeap {
             gen_gc_count: 0,
             young,
             old,
+            statistics: Default::default(),
         }
     }
 
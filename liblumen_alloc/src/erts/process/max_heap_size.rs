@@ -0,0 +1,21 @@
+/// The maximum size (in words) of a process's heap and what happens when a garbage collection
+/// projects that it will be exceeded.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MaxHeapSize {
+    /// `0` means there is no maximum
+    pub size: usize,
+    /// Whether the process is killed with reason `killed` when `size` is exceeded
+    pub kill: bool,
+    /// Whether an error report is logged when `size` is exceeded
+    pub error_logger: bool,
+}
+
+impl Default for MaxHeapSize {
+    fn default() -> MaxHeapSize {
+        MaxHeapSize {
+            size: 0,
+            kill: true,
+            error_logger: true,
+        }
+    }
+}
@@ -97,6 +97,118 @@ mod traps_exit {
     }
 }
 
+mod garbage_collect {
+    use super::*;
+
+    use crate::erts::term::atom_unchecked;
+
+    #[test]
+    fn with_max_heap_size_exceeded_and_kill_exits_killed() {
+        let process = process();
+        process.set_max_heap_size(MaxHeapSize {
+            size: 1,
+            kill: true,
+            error_logger: false,
+        });
+
+        assert_eq!(
+            process.garbage_collect(0, &mut []),
+            Err(GcError::MaxHeapSizeExceeded)
+        );
+        assert_eq!(
+            *process.status.read(),
+            Status::Exiting(exit!(atom_unchecked("killed")))
+        );
+    }
+
+    #[test]
+    fn with_max_heap_size_exceeded_without_kill_collects() {
+        let process = process();
+        process.set_max_heap_size(MaxHeapSize {
+            size: 1,
+            kill: false,
+            error_logger: false,
+        });
+
+        assert!(process.garbage_collect(0, &mut []).is_ok());
+        assert_eq!(*process.status.read(), Status::Runnable);
+    }
+
+    #[test]
+    fn with_hibernate_shrinks_heap_to_live_size() {
+        let process = process();
+        let live = process.tuple_from_slice(&[Term::NIL]).unwrap();
+
+        for _ in 0..10 {
+            process.tuple_from_slice(&[Term::NIL, Term::NIL]).unwrap();
+        }
+
+        let heap_size_before = process.heap_size();
+
+        process.set_flags(ProcessFlags::NeedFullSweep | ProcessFlags::Hibernate);
+
+        let mut roots = [live];
+
+        assert!(process.garbage_collect(0, &mut roots).is_ok());
+        assert!(process.heap_size() < heap_size_before);
+        assert_eq!(process.heap_size(), process.total_heap_used());
+        assert!(!process.are_flags_set(ProcessFlags::Hibernate));
+        assert!(roots[0].is_tuple());
+    }
+
+    #[test]
+    fn minor_collection_counts_minor_gc() {
+        let process = process();
+
+        assert_eq!(process.gc_statistics(), GcStatistics::default());
+
+        assert!(process.garbage_collect(0, &mut []).is_ok());
+
+        let statistics = process.gc_statistics();
+
+        assert_eq!(statistics.minor_gcs, 1);
+        assert_eq!(statistics.major_gcs, 0);
+        assert_eq!(statistics.last_gc_time, None);
+    }
+
+    #[test]
+    fn full_sweep_counts_major_gc_without_resetting_minor_gcs() {
+        let process = process();
+
+        assert!(process.garbage_collect(0, &mut []).is_ok());
+
+        process.set_flags(ProcessFlags::NeedFullSweep);
+
+        assert!(process.garbage_collect(0, &mut []).is_ok());
+        assert_eq!(process.minor_gcs(), 0);
+
+        let statistics = process.gc_statistics();
+
+        assert_eq!(statistics.minor_gcs, 1);
+        assert_eq!(statistics.major_gcs, 1);
+    }
+
+    #[test]
+    fn full_sweep_counts_words_reclaimed() {
+        let process = process();
+        // Garbage, as it is not a root
+        process.list_from_slice(&[Term::NIL; 8]).unwrap();
+        process.set_flags(ProcessFlags::NeedFullSweep);
+
+        assert!(process.garbage_collect(0, &mut []).is_ok());
+        assert!(0 < process.gc_statistics().words_reclaimed);
+    }
+
+    #[test]
+    fn set_last_gc_time_is_reported_in_statistics() {
+        let process = process();
+
+        process.set_last_gc_time(42);
+
+        assert_eq!(process.gc_statistics().last_gc_time, Some(42));
+    }
+}
+
 mod integer {
     use super::*;
 
@@ -114,6 +226,23 @@ mod integer {
     }
 }
 
+mod total_heap_used {
+    use super::*;
+
+    #[test]
+    fn increases_with_allocations_and_does_not_exceed_total_heap_size() {
+        let process = process();
+        let before = process.total_heap_used();
+
+        process.tuple_from_slice(&[Term::NIL, Term::NIL]).unwrap();
+
+        let after = process.total_heap_used();
+
+        assert!(before < after);
+        assert!(after <= process.total_heap_size());
+    }
+}
+
 mod tuple_from_slice {
     use super::*;
 
@@ -40,6 +40,16 @@ pub struct Atom(usize);
 impl Atom {
     pub const SIZE_IN_WORDS: usize = 1;
 
+    /// The number of atoms in the atom table
+    pub fn count() -> usize {
+        ATOMS.read().names.len()
+    }
+
+    /// The bytes `(allocated, used)` by the atom table, including the atom names
+    pub fn memory() -> (usize, usize) {
+        ATOMS.read().memory()
+    }
+
     /// Gets the identifier associated with this atom
     #[inline(always)]
     pub fn id(&self) -> usize {
@@ -249,6 +259,18 @@ impl AtomTable {
         self.names.get(id).cloned()
     }
 
+    fn memory(&self) -> (usize, usize) {
+        let name_size = mem::size_of::<&'static str>();
+        let id_size = mem::size_of::<(&'static str, usize)>();
+        let name_bytes: usize = self.names.iter().map(|name| name.len()).sum();
+
+        let allocated =
+            self.names.capacity() * name_size + self.ids.capacity() * id_size + name_bytes;
+        let used = self.names.len() * name_size + self.ids.len() * id_size + name_bytes;
+
+        (allocated, used)
+    }
+
     fn get_id_or_insert(&mut self, name: &str) -> Result<usize, AtomError> {
         match self.get_id(name) {
             Some(existing_id) => Ok(existing_id),
@@ -21,7 +21,7 @@ use crate::erts::term::{
 };
 use crate::erts::HeapAlloc;
 
-use super::{byte_offset, num_bytes, Bitstring, ByteIterator, MaybePartialByte};
+use super::{bit_offset, byte_offset, num_bytes, Bitstring, ByteIterator, MaybePartialByte};
 
 pub struct FullByteIter {}
 
@@ -153,6 +153,28 @@ impl MatchContext {
         &self.buffer.base as *const _ as *mut *mut u8
     }
 
+    /// The bits that have not been matched yet, as a sub-binary of the binary being matched, so
+    /// that they can be read at any bit offset.
+    pub fn unmatched_subbinary(&self) -> SubBinary {
+        let mut original = follow_moved(self.buffer.original);
+        let original_header = unsafe { *original.boxed_val() };
+
+        // `start_match` offsets a sub-binary's bits from the start of its own original
+        if original_header.is_subbinary_header() {
+            original = unsafe { &*(original.boxed_val() as *mut SubBinary) }.original();
+        }
+
+        let unmatched_bit_len = self.buffer.bit_len - self.buffer.bit_offset;
+
+        SubBinary::from_original(
+            original,
+            byte_offset(self.buffer.bit_offset),
+            bit_offset(self.buffer.bit_offset) as u8,
+            byte_offset(unmatched_bit_len),
+            bit_offset(unmatched_bit_len) as u8,
+        )
+    }
+
     #[inline]
     unsafe fn to_raw_parts(&self) -> (Term, usize, *mut u8, usize) {
         let real_bin_ptr = follow_moved(self.buffer.original).boxed_val();
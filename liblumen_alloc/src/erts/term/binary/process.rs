@@ -200,6 +200,7 @@ impl ProcBin {
                         bytes,
                     });
                     ptr::copy_nonoverlapping(s.as_ptr(), bytes, full_byte_len);
+                    TOTAL_BYTE_LEN.fetch_add(full_byte_len, atomic::Ordering::AcqRel);
 
                     Ok(Self {
                         header: Term::make_header(arity_of::<Self>(), Term::FLAG_PROCBIN),
@@ -231,6 +232,23 @@ impl ProcBin {
         }
     }
 
+    /// Identifies the underlying binary, which is shared by all `ProcBin`s that reference it
+    #[inline]
+    pub fn id(&self) -> usize {
+        self.inner.as_ptr() as usize
+    }
+
+    /// The bytes of data held by all binaries that are still referenced by a `ProcBin`
+    pub fn total_byte_len() -> usize {
+        TOTAL_BYTE_LEN.load(atomic::Ordering::Acquire)
+    }
+
+    /// The number of `ProcBin`s that reference the underlying binary
+    #[inline]
+    pub fn ref_count(&self) -> usize {
+        self.inner().refc.load(atomic::Ordering::Acquire)
+    }
+
     #[inline]
     fn inner(&self) -> &ProcBinInner {
         unsafe { self.inner.as_ref() }
@@ -248,6 +266,7 @@ impl ProcBin {
             atomic::fence(atomic::Ordering::Acquire);
             let bytes = self.inner().bytes();
             let size = self.inner().full_byte_len();
+            TOTAL_BYTE_LEN.fetch_sub(size, atomic::Ordering::AcqRel);
             sys_alloc::free(
                 bytes,
                 Layout::from_size_align_unchecked(size, mem::align_of::<usize>()),
@@ -448,3 +467,6 @@ impl TryInto<Vec<u8>> for ProcBin {
         Ok(self.as_bytes().to_vec())
     }
 }
+
+/// The sum of the `full_byte_len` of every `ProcBinInner` that has not been freed
+static TOTAL_BYTE_LEN: AtomicUsize = AtomicUsize::new(0);
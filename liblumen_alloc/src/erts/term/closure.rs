@@ -43,6 +43,11 @@ impl Closure {
         }
     }
 
+    /// The pid of the process that created the closure.
+    pub fn creator(&self) -> Term {
+        self.creator
+    }
+
     pub fn arity(&self) -> u8 {
         self.module_function_arity.arity
     }
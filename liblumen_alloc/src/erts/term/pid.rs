@@ -86,7 +86,7 @@ unsafe impl AsTerm for Pid {
 
 impl Display for Pid {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "#PID<0.{}.{}>", self.number(), self.serial())
+        write!(f, "<0.{}.{}>", self.number(), self.serial())
     }
 }
 
@@ -120,6 +120,14 @@ impl ExternalPid {
         Self::new(node, number, serial)
     }
 
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    pub fn pid(&self) -> Pid {
+        self.pid
+    }
+
     fn new(node: Node, number: usize, serial: usize) -> Result<Self, OutOfRange> {
         let pid = Pid::new(number, serial)?;
         let header = Term::make_header(arity_of::<Self>(), Term::FLAG_EXTERN_PID);
@@ -1,12 +1,13 @@
 use core::cmp;
 use core::fmt::{self, Debug, Display};
 use core::hash::{Hash, Hasher};
+use core::ptr;
 
 use crate::borrow::CloneToProcess;
 use crate::erts::exception::system::Alloc;
 use crate::erts::{HeapAlloc, Node};
 
-use super::{AsTerm, Term};
+use super::{arity_of, AsTerm, Term};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
@@ -17,6 +18,12 @@ impl Port {
     pub unsafe fn from_raw(port: usize) -> Self {
         Self(port)
     }
+
+    pub fn number(&self) -> usize {
+        self.0
+    }
+
+    pub const NUMBER_MAX: usize = Term::MAX_IMMEDIATE1_VALUE;
 }
 
 unsafe impl AsTerm for Port {
@@ -27,8 +34,8 @@ unsafe impl AsTerm for Port {
 }
 
 impl Display for Port {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#Port<0.{}>", self.0)
     }
 }
 
@@ -51,6 +58,24 @@ pub struct ExternalPort {
     next: *mut u8,
     port: Port,
 }
+impl ExternalPort {
+    pub(in crate::erts) fn with_node_id(node_id: usize, number: usize) -> Self {
+        Self {
+            header: Term::make_header(arity_of::<Self>(), Term::FLAG_EXTERN_PORT),
+            node: Node::new(node_id),
+            next: ptr::null_mut(),
+            port: unsafe { Port::from_raw(number) },
+        }
+    }
+
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    pub fn port(&self) -> Port {
+        self.port
+    }
+}
 
 unsafe impl AsTerm for ExternalPort {
     #[inline]
@@ -60,8 +85,13 @@ unsafe impl AsTerm for ExternalPort {
 }
 
 impl CloneToProcess for ExternalPort {
-    fn clone_to_heap<A: HeapAlloc>(&self, _heap: &mut A) -> Result<Term, Alloc> {
-        unimplemented!()
+    fn clone_to_heap<A: HeapAlloc>(&self, heap: &mut A) -> Result<Term, Alloc> {
+        unsafe {
+            let ptr = heap.alloc(self.size_in_words())?.as_ptr() as *mut Self;
+            ptr::copy_nonoverlapping(self as *const Self, ptr, 1);
+
+            Ok(Term::make_boxed(ptr))
+        }
     }
 }
 
@@ -77,8 +107,8 @@ impl Debug for ExternalPort {
 }
 
 impl Display for ExternalPort {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#Port<{}.{}>", self.node.id(), self.port.number())
     }
 }
 
@@ -87,7 +87,7 @@ impl Debug for Reference {
 }
 impl Display for Reference {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "#Reference<0.{}.{}>", self.scheduler_id, self.number)
+        write!(f, "#Ref<0.{}.{}>", self.scheduler_id, self.number)
     }
 }
 impl Hash for Reference {
@@ -150,6 +150,28 @@ pub struct ExternalReference {
     next: *mut u8,
     reference: Reference,
 }
+impl ExternalReference {
+    pub(in crate::erts) fn with_node_id(
+        node_id: usize,
+        scheduler_id: scheduler::ID,
+        number: Number,
+    ) -> Self {
+        Self {
+            header: Term::make_header(arity_of::<Self>(), Term::FLAG_EXTERN_REF),
+            node: Node::new(node_id),
+            next: ptr::null_mut(),
+            reference: Reference::new(scheduler_id, number),
+        }
+    }
+
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    pub fn reference(&self) -> &Reference {
+        &self.reference
+    }
+}
 
 unsafe impl AsTerm for ExternalReference {
     #[inline]
@@ -160,8 +182,13 @@ unsafe impl AsTerm for ExternalReference {
 
 impl CloneToProcess for ExternalReference {
     #[inline]
-    fn clone_to_heap<A: HeapAlloc>(&self, _heap: &mut A) -> Result<Term, Alloc> {
-        unimplemented!()
+    fn clone_to_heap<A: HeapAlloc>(&self, heap: &mut A) -> Result<Term, Alloc> {
+        unsafe {
+            let ptr = heap.alloc(self.size_in_words())?.as_ptr() as *mut Self;
+            ptr::copy_nonoverlapping(self as *const Self, ptr, 1);
+
+            Ok(Term::make_boxed(ptr))
+        }
     }
 }
 
@@ -177,8 +204,14 @@ impl Debug for ExternalReference {
 }
 
 impl Display for ExternalReference {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#Ref<{}.{}.{}>",
+            self.node.id(),
+            self.reference.scheduler_id,
+            self.reference.number
+        )
     }
 }
 
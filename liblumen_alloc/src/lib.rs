@@ -51,7 +51,7 @@ pub mod std_alloc;
 pub use liblumen_core::alloc::SysAlloc;
 
 /// A tracing allocator for tracking statistics about the allocator it wraps
-pub use self::stats_alloc::StatsAlloc;
+pub use self::stats_alloc::{Statistics, StatsAlloc};
 
 // An allocator that uses segmented sub-allocators to more efficiently manage
 // allocations of variable sizes that fall within predictable size ranges
@@ -71,4 +71,22 @@ pub use borrow::CloneToProcess;
 pub struct AllocatorInfo {
     num_multi_block_carriers: usize,
     num_single_block_carriers: usize,
+    carriers_size: usize,
+}
+impl AllocatorInfo {
+    #[inline]
+    pub fn num_multi_block_carriers(&self) -> usize {
+        self.num_multi_block_carriers
+    }
+
+    #[inline]
+    pub fn num_single_block_carriers(&self) -> usize {
+        self.num_single_block_carriers
+    }
+
+    /// The total size (in bytes) of all carriers, including their headers
+    #[inline]
+    pub fn carriers_size(&self) -> usize {
+        self.carriers_size
+    }
 }
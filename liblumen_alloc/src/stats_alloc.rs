@@ -99,6 +99,17 @@ pub struct Statistics<H: Histogram + Clone + Default> {
     tag: &'static str,
     histogram: H,
 }
+impl<H: Histogram + Clone + Default> Statistics<H> {
+    #[inline]
+    pub fn total_bytes_alloced(&self) -> usize {
+        self.total_bytes_alloced
+    }
+
+    #[inline]
+    pub fn total_bytes_freed(&self) -> usize {
+        self.total_bytes_freed
+    }
+}
 impl<H: Histogram + Clone + Default> fmt::Display for Statistics<H> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "## Allocator Statistics (tag = {})", self.tag)?;
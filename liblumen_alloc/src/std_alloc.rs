@@ -99,6 +99,25 @@ pub fn alloc_info() -> AllocatorInfo {
     STD_ALLOC.info()
 }
 
+/// Gets the number of bytes currently allocated by the global standard allocator
+#[cfg(feature = "instrument")]
+pub fn bytes_in_use() -> usize {
+    let stats = STD_ALLOC.stats();
+
+    stats
+        .total_bytes_alloced()
+        .saturating_sub(stats.total_bytes_freed())
+}
+
+/// Gets the number of bytes currently held by the global standard allocator
+///
+/// Without the `instrument` feature, allocations are not counted, so this is the size of all
+/// carriers, which includes their free blocks.
+#[cfg(not(feature = "instrument"))]
+pub fn bytes_in_use() -> usize {
+    alloc_info().carriers_size()
+}
+
 struct StandardAlloc {
     sbc_threshold: usize,
     sbc: CachePadded<SpinLock<SingleBlockCarrierList>>,
@@ -130,9 +149,11 @@ impl StandardAlloc {
     pub fn info(&self) -> AllocatorInfo {
         let num_mbc = self.count_mbc();
         let num_sbc = self.count_sbc();
+        let carriers_size = self.mbc_size() + self.sbc_size();
         AllocatorInfo {
             num_multi_block_carriers: num_mbc,
             num_single_block_carriers: num_sbc,
+            carriers_size,
         }
     }
 
@@ -148,6 +169,18 @@ impl StandardAlloc {
         sbc.iter().count()
     }
 
+    // Sums the size of the multi-block carriers this allocator holds
+    fn mbc_size(&self) -> usize {
+        let mbc = self.mbc.lock();
+        mbc.iter().map(|carrier| carrier.size).sum()
+    }
+
+    // Sums the size of the single-block carriers this allocator holds
+    fn sbc_size(&self) -> usize {
+        let sbc = self.sbc.lock();
+        sbc.iter().map(|carrier| carrier.size).sum()
+    }
+
     unsafe fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, exception::system::Alloc> {
         let size = layout.size();
         if size >= self.sbc_threshold {
@@ -22,6 +22,8 @@
 //! * [org.elixir_lang.beam.Beam in IntelliJ Elixir](https://github.
 //!   com/KronicDeth/intellij-elixir/blob/master/src/org/elixir_lang/beam/Beam.kt) in Kotlin
 
+pub mod code_path;
+pub mod docs;
 pub mod reader;
 
 pub use self::reader::chunk;
@@ -0,0 +1,438 @@
+//! Locates modules on a code path, the way `code:where_is_file/1` does.
+//!
+//! A code path is an ordered list of directories which contain `.beam` files, usually the
+//! `ebin` directories of applications. A directory may also live inside an `.ez` archive, in
+//! which case it is written as a path through the archive, e.g. `lib/foo-1.0.ez/foo-1.0/ebin`.
+//!
+//! # Examples
+//!
+//!     use liblumen_beam::beam::code_path::CodePath;
+//!     use liblumen_beam::beam::reader::RawBeamFile;
+//!
+//!     let mut code_path = CodePath::new();
+//!     code_path.append("tests/testdata/reader").unwrap();
+//!
+//!     let location = code_path.find_module("test").unwrap().unwrap();
+//!     assert_eq!("test", location.module());
+//!     let beam: RawBeamFile = location.read_beam().unwrap();
+//!     assert!(beam.atoms().is_some());
+//!
+#[cfg(test)]
+mod test;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use failure::Fail;
+
+use super::chunk::Chunk;
+use super::reader::{BeamFile, ReadError};
+
+/// The extension of archives which contain applications
+pub const ARCHIVE_EXTENSION: &str = "ez";
+
+/// The extension of compiled modules
+pub const BEAM_EXTENSION: &str = "beam";
+
+#[derive(Fail, Debug)]
+pub enum CodePathError {
+    #[fail(display = "i/o error: {}", _0)]
+    IO(#[fail(cause)] std::io::Error),
+
+    #[fail(display = "invalid archive {}: {}", archive, error)]
+    Archive {
+        archive: String,
+        #[fail(cause)]
+        error: zip::result::ZipError,
+    },
+
+    #[fail(display = "invalid beam file: {}", _0)]
+    BeamFile(#[fail(cause)] ReadError),
+
+    #[fail(display = "{} is not a directory or archive", _0)]
+    InvalidPath(String),
+}
+impl std::convert::From<std::io::Error> for CodePathError {
+    fn from(err: std::io::Error) -> Self {
+        CodePathError::IO(err)
+    }
+}
+impl std::convert::From<ReadError> for CodePathError {
+    fn from(err: ReadError) -> Self {
+        CodePathError::BeamFile(err)
+    }
+}
+
+pub type CodePathResult<T> = Result<T, CodePathError>;
+
+/// A directory on the code path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodePathEntry {
+    /// A directory on disk
+    Dir(PathBuf),
+    /// A directory inside of an archive, where `dir` is relative to the archive root
+    Archive { archive: PathBuf, dir: String },
+}
+impl CodePathEntry {
+    /// Interprets a path which is either a directory, or a path through an `.ez` archive.
+    ///
+    /// An archive path without a directory part, e.g. `foo-1.0.ez`, refers to the `ebin`
+    /// directory of the application it contains, e.g. `foo-1.0/ebin`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> CodePathResult<Self> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            return Ok(CodePathEntry::Dir(path.to_path_buf()));
+        }
+
+        let mut archive = PathBuf::new();
+        let mut components = path.components();
+        while let Some(component) = components.next() {
+            archive.push(component);
+            if is_archive(&archive) && archive.is_file() {
+                let dir = components
+                    .filter_map(|c| match c {
+                        Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let dir = if dir.is_empty() {
+                    format!("{}/ebin", file_stem(&archive))
+                } else {
+                    dir
+                };
+                return Ok(CodePathEntry::Archive { archive, dir });
+            }
+        }
+        Err(CodePathError::InvalidPath(path.display().to_string()))
+    }
+
+    pub fn is_dir(&self) -> bool {
+        match *self {
+            CodePathEntry::Dir(_) => true,
+            CodePathEntry::Archive { .. } => false,
+        }
+    }
+
+    /// Returns the location of `filename` in this directory, if it exists
+    pub fn where_is_file(&self, filename: &str) -> CodePathResult<Option<ModuleLocation>> {
+        match *self {
+            CodePathEntry::Dir(ref dir) => {
+                let path = dir.join(filename);
+                Ok(if path.is_file() {
+                    Some(ModuleLocation::File(path))
+                } else {
+                    None
+                })
+            }
+            CodePathEntry::Archive {
+                ref archive,
+                ref dir,
+            } => {
+                let member = format!("{}/{}", dir, filename);
+                let found = archive_members(archive)?.into_iter().any(|m| m == member);
+                Ok(if found {
+                    Some(ModuleLocation::Archive {
+                        archive: archive.clone(),
+                        member,
+                    })
+                } else {
+                    None
+                })
+            }
+        }
+    }
+
+    /// Returns the locations of every module in this directory, sorted by module name
+    pub fn modules(&self) -> CodePathResult<Vec<ModuleLocation>> {
+        let mut modules = match *self {
+            CodePathEntry::Dir(ref dir) => {
+                let mut modules = Vec::new();
+                for entry in fs::read_dir(dir)? {
+                    let path = entry?.path();
+                    if is_beam(&path) && path.is_file() {
+                        modules.push(ModuleLocation::File(path));
+                    }
+                }
+                modules
+            }
+            CodePathEntry::Archive {
+                ref archive,
+                ref dir,
+            } => {
+                let prefix = format!("{}/", dir);
+                archive_members(archive)?
+                    .into_iter()
+                    .filter(|m| {
+                        m.starts_with(&prefix)
+                            && !m[prefix.len()..].contains('/')
+                            && is_beam(Path::new(m))
+                    })
+                    .map(|member| ModuleLocation::Archive {
+                        archive: archive.clone(),
+                        member,
+                    })
+                    .collect()
+            }
+        };
+        modules.sort_by(|a, b| a.module().cmp(b.module()));
+        Ok(modules)
+    }
+}
+impl fmt::Display for CodePathEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CodePathEntry::Dir(ref dir) => write!(f, "{}", dir.display()),
+            CodePathEntry::Archive {
+                ref archive,
+                ref dir,
+            } => write!(f, "{}/{}", archive.display(), dir),
+        }
+    }
+}
+
+/// The location of a module found on the code path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleLocation {
+    /// A `.beam` file on disk
+    File(PathBuf),
+    /// A `.beam` file inside of an archive
+    Archive { archive: PathBuf, member: String },
+}
+impl ModuleLocation {
+    /// Returns the module name, derived from the file name
+    pub fn module(&self) -> &str {
+        let filename = match *self {
+            ModuleLocation::File(ref path) => path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(""),
+            ModuleLocation::Archive { ref member, .. } => {
+                member.rsplit('/').next().unwrap_or(member)
+            }
+        };
+        let extension = BEAM_EXTENSION.len() + 1;
+        if is_beam(Path::new(filename)) && filename.len() > extension {
+            &filename[..filename.len() - extension]
+        } else {
+            filename
+        }
+    }
+
+    /// Reads the contents of the module
+    pub fn read(&self) -> CodePathResult<Vec<u8>> {
+        let mut buf = Vec::new();
+        match *self {
+            ModuleLocation::File(ref path) => {
+                File::open(path)?.read_to_end(&mut buf)?;
+            }
+            ModuleLocation::Archive {
+                ref archive,
+                ref member,
+            } => {
+                let mut zip = open_archive(archive)?;
+                let mut file = zip.by_name(member).map_err(|e| archive_error(archive, e))?;
+                file.read_to_end(&mut buf)?;
+            }
+        }
+        Ok(buf)
+    }
+
+    /// Reads the module as a BEAM file, straight out of its archive if necessary
+    pub fn read_beam<C: Chunk>(&self) -> CodePathResult<BeamFile<C>> {
+        match *self {
+            ModuleLocation::File(ref path) => Ok(BeamFile::from_file(path)?),
+            ModuleLocation::Archive {
+                ref archive,
+                ref member,
+            } => {
+                let mut zip = open_archive(archive)?;
+                let file = zip.by_name(member).map_err(|e| archive_error(archive, e))?;
+                Ok(BeamFile::from_reader(file)?)
+            }
+        }
+    }
+}
+impl fmt::Display for ModuleLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ModuleLocation::File(ref path) => write!(f, "{}", path.display()),
+            ModuleLocation::Archive {
+                ref archive,
+                ref member,
+            } => write!(f, "{}/{}", archive.display(), member),
+        }
+    }
+}
+
+/// An ordered list of directories searched for modules.
+#[derive(Debug, Clone, Default)]
+pub struct CodePath {
+    entries: Vec<CodePathEntry>,
+}
+impl CodePath {
+    pub fn new() -> Self {
+        CodePath::default()
+    }
+
+    pub fn entries(&self) -> &[CodePathEntry] {
+        &self.entries
+    }
+
+    /// Adds a directory or archive path to the beginning of the code path, like `-pa`
+    pub fn prepend<P: AsRef<Path>>(&mut self, path: P) -> CodePathResult<()> {
+        let entry = CodePathEntry::from_path(path)?;
+        self.entries.insert(0, entry);
+        Ok(())
+    }
+
+    /// Adds a directory or archive path to the end of the code path, like `-pz`
+    pub fn append<P: AsRef<Path>>(&mut self, path: P) -> CodePathResult<()> {
+        let entry = CodePathEntry::from_path(path)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Appends the `ebin` directory of every application in a `lib` directory.
+    ///
+    /// Applications are either `<app>-<vsn>` directories or `<app>-<vsn>.ez` archives.
+    /// When several versions of an application are present, only the latest is added,
+    /// preferring a directory over an archive of the same version.
+    pub fn append_lib_dir<P: AsRef<Path>>(&mut self, lib_dir: P) -> CodePathResult<()> {
+        let mut apps: Vec<(String, String, CodePathEntry)> = Vec::new();
+        for entry in fs::read_dir(lib_dir)? {
+            let path = entry?.path();
+            let (app_vsn, entry) = if path.is_dir() {
+                let ebin = path.join("ebin");
+                if !ebin.is_dir() {
+                    continue;
+                }
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                (name, CodePathEntry::Dir(ebin))
+            } else if is_archive(&path) {
+                let name = file_stem(&path);
+                let dir = format!("{}/ebin", name);
+                (
+                    name,
+                    CodePathEntry::Archive {
+                        archive: path.clone(),
+                        dir,
+                    },
+                )
+            } else {
+                continue;
+            };
+            let (app, vsn) = split_app_vsn(&app_vsn);
+            let newer = match apps.iter().position(|(a, _, _)| *a == app) {
+                None => true,
+                Some(i) => {
+                    let ordering = compare_versions(&vsn, &apps[i].1);
+                    let replace = ordering == Ordering::Greater
+                        || (ordering == Ordering::Equal && entry.is_dir());
+                    if replace {
+                        apps.remove(i);
+                    }
+                    replace
+                }
+            };
+            if newer {
+                apps.push((app, vsn, entry));
+            }
+        }
+        apps.sort_by(|a, b| a.0.cmp(&b.0));
+        self.entries
+            .extend(apps.into_iter().map(|(_, _, entry)| entry));
+        Ok(())
+    }
+
+    /// Returns the first location of `filename` on the code path, like `code:where_is_file/1`
+    pub fn where_is_file(&self, filename: &str) -> CodePathResult<Option<ModuleLocation>> {
+        for entry in &self.entries {
+            if let Some(location) = entry.where_is_file(filename)? {
+                return Ok(Some(location));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the location of the given module, if it is on the code path
+    pub fn find_module(&self, module: &str) -> CodePathResult<Option<ModuleLocation>> {
+        self.where_is_file(&format!("{}.{}", module, BEAM_EXTENSION))
+    }
+
+    /// Returns every module on the code path, sorted by name.
+    ///
+    /// Modules which are shadowed by a module of the same name earlier on the code path
+    /// are not included.
+    pub fn modules(&self) -> CodePathResult<Vec<ModuleLocation>> {
+        let mut modules: Vec<ModuleLocation> = Vec::new();
+        for entry in &self.entries {
+            for location in entry.modules()? {
+                if !modules.iter().any(|m| m.module() == location.module()) {
+                    modules.push(location);
+                }
+            }
+        }
+        modules.sort_by(|a, b| a.module().cmp(b.module()));
+        Ok(modules)
+    }
+}
+fn is_archive(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |ext| ext == ARCHIVE_EXTENSION)
+}
+
+fn is_beam(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext == BEAM_EXTENSION)
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn open_archive(archive: &Path) -> CodePathResult<zip::ZipArchive<File>> {
+    let file = File::open(archive)?;
+    zip::ZipArchive::new(file).map_err(|e| archive_error(archive, e))
+}
+
+fn archive_members(archive: &Path) -> CodePathResult<Vec<String>> {
+    let mut zip = open_archive(archive)?;
+    let mut members = Vec::with_capacity(zip.len());
+    for i in 0..zip.len() {
+        let file = zip.by_index(i).map_err(|e| archive_error(archive, e))?;
+        members.push(file.name().to_string());
+    }
+    Ok(members)
+}
+
+fn archive_error(archive: &Path, error: zip::result::ZipError) -> CodePathError {
+    CodePathError::Archive {
+        archive: archive.display().to_string(),
+        error,
+    }
+}
+
+/// Splits `<app>-<vsn>` into its name and version; the version is empty if there is none
+fn split_app_vsn(name: &str) -> (String, String) {
+    match name.rfind('-') {
+        Some(i) if name[i + 1..].starts_with(|c: char| c.is_ascii_digit()) => {
+            (name[..i].to_string(), name[i + 1..].to_string())
+        }
+        _ => (name.to_string(), String::new()),
+    }
+}
+
+/// Compares versions such as `1.10.2` numerically, segment by segment
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let segments = |v: &str| -> Vec<u64> {
+        v.split(&['.', '-'][..])
+            .map(|s| s.parse().unwrap_or(0))
+            .collect()
+    };
+    segments(a).cmp(&segments(b))
+}
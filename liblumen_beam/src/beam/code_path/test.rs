@@ -0,0 +1,148 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use crate::beam::chunk::Chunk;
+use crate::beam::code_path::*;
+use crate::beam::reader::RawBeamFile;
+
+const TEST_BEAM: &str = "tests/testdata/reader/test.beam";
+
+fn write_archive(path: &Path, members: &[&str]) {
+    let beam = fs::read(TEST_BEAM).unwrap();
+    let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+    for member in members {
+        zip.start_file(*member, zip::write::FileOptions::default())
+            .unwrap();
+        zip.write_all(&beam).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+fn write_ebin(dir: &Path, modules: &[&str]) {
+    fs::create_dir_all(dir).unwrap();
+    for module in modules {
+        fs::copy(TEST_BEAM, dir.join(format!("{}.beam", module))).unwrap();
+    }
+}
+
+fn names(locations: &[ModuleLocation]) -> Vec<&str> {
+    locations.iter().map(|l| l.module()).collect()
+}
+
+#[test]
+fn archive_test() {
+    let tmp = tempfile::tempdir().unwrap();
+    let archive = tmp.path().join("foo-1.0.ez");
+    write_archive(
+        &archive,
+        &[
+            "foo-1.0/ebin/foo.beam",
+            "foo-1.0/ebin/foo_sup.beam",
+            "foo-1.0/ebin/foo.app",
+            "foo-1.0/priv/nested/ignored.beam",
+        ],
+    );
+
+    // The ebin directory is implied for a bare archive
+    let entry = CodePathEntry::from_path(&archive).unwrap();
+    assert_eq!(
+        CodePathEntry::Archive {
+            archive: archive.clone(),
+            dir: "foo-1.0/ebin".to_string()
+        },
+        entry
+    );
+    assert_eq!(
+        entry,
+        CodePathEntry::from_path(archive.join("foo-1.0/ebin")).unwrap()
+    );
+    assert_eq!(vec!["foo", "foo_sup"], names(&entry.modules().unwrap()));
+
+    let location = entry.where_is_file("foo_sup.beam").unwrap().unwrap();
+    assert_eq!(
+        ModuleLocation::Archive {
+            archive: archive.clone(),
+            member: "foo-1.0/ebin/foo_sup.beam".to_string()
+        },
+        location
+    );
+    assert_eq!(fs::read(TEST_BEAM).unwrap(), location.read().unwrap());
+
+    let expected = RawBeamFile::from_file(TEST_BEAM).unwrap();
+    let beam: RawBeamFile = location.read_beam().unwrap();
+    assert_eq!(
+        expected.chunks().iter().map(|c| c.id()).collect::<Vec<_>>(),
+        beam.chunks().iter().map(|c| c.id()).collect::<Vec<_>>()
+    );
+
+    assert_eq!(None, entry.where_is_file("bar.beam").unwrap());
+    match CodePathEntry::from_path(tmp.path().join("missing")) {
+        Err(CodePathError::InvalidPath(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn code_path_test() {
+    let tmp = tempfile::tempdir().unwrap();
+    let a = tmp.path().join("a");
+    let b = tmp.path().join("b");
+    write_ebin(&a, &["shared", "only_a"]);
+    write_ebin(&b, &["shared", "only_b"]);
+
+    let mut code_path = CodePath::new();
+    code_path.append(&a).unwrap();
+    code_path.append(&b).unwrap();
+    assert_eq!(
+        Some(ModuleLocation::File(a.join("shared.beam"))),
+        code_path.find_module("shared").unwrap()
+    );
+    assert_eq!(
+        Some(ModuleLocation::File(b.join("only_b.beam"))),
+        code_path.where_is_file("only_b.beam").unwrap()
+    );
+    assert_eq!(None, code_path.find_module("missing").unwrap());
+
+    // Shadowed modules are only listed once
+    let modules = code_path.modules().unwrap();
+    assert_eq!(vec!["only_a", "only_b", "shared"], names(&modules));
+    assert_eq!(ModuleLocation::File(a.join("shared.beam")), modules[2]);
+
+    // -pa takes precedence over everything else
+    code_path.prepend(&b).unwrap();
+    assert_eq!(
+        Some(ModuleLocation::File(b.join("shared.beam"))),
+        code_path.find_module("shared").unwrap()
+    );
+}
+
+#[test]
+fn lib_dir_test() {
+    let tmp = tempfile::tempdir().unwrap();
+    let lib = tmp.path().join("lib");
+    write_ebin(&lib.join("foo-1.9/ebin"), &["foo"]);
+    write_ebin(&lib.join("foo-1.10/ebin"), &["foo"]);
+    write_ebin(&lib.join("no_ebin-1.0/src"), &["ignored"]);
+    write_archive(&lib.join("bar-2.0.ez"), &["bar-2.0/ebin/bar.beam"]);
+    write_archive(&lib.join("baz-1.0.ez"), &["baz-1.0/ebin/baz.beam"]);
+    write_ebin(&lib.join("baz-1.0/ebin"), &["baz"]);
+
+    let mut code_path = CodePath::new();
+    code_path.append_lib_dir(&lib).unwrap();
+    assert_eq!(
+        &[
+            CodePathEntry::Archive {
+                archive: lib.join("bar-2.0.ez"),
+                dir: "bar-2.0/ebin".to_string()
+            },
+            CodePathEntry::Dir(lib.join("baz-1.0/ebin")),
+            CodePathEntry::Dir(lib.join("foo-1.10/ebin")),
+        ],
+        code_path.entries()
+    );
+    assert_eq!(
+        vec!["bar", "baz", "foo"],
+        names(&code_path.modules().unwrap())
+    );
+}
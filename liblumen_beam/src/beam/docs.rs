@@ -0,0 +1,292 @@
+//! A structured model of the [EEP-48] `docs_v1` documentation stored in the `"Docs"` chunk.
+//!
+//! [EEP-48]: https://www.erlang.org/eeps/eep-0048.html
+//!
+//! # Examples
+//!
+//!     use liblumen_beam::beam::docs::{Doc, ModuleDocs};
+//!     use liblumen_beam::beam::reader::chunk::StandardChunk;
+//!     use liblumen_beam::beam::reader::StandardBeamFile;
+//!
+//!     let beam = StandardBeamFile::from_file("tests/testdata/reader/Elixir.Unicode.beam").unwrap();
+//!     let docs = match beam.get_chunk(b"Docs") {
+//!         Some(StandardChunk::Docs(chunk)) => chunk.docs().unwrap(),
+//!         _ => unreachable!(),
+//!     };
+//!     assert_eq!("elixir", docs.beam_language);
+//!     assert_eq!(Doc::Hidden, docs.module_doc);
+//!     assert_eq!("add1/1", docs.entries[0].to_string());
+//!
+#[cfg(test)]
+mod test;
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use failure::Fail;
+
+use crate::serialization::etf::{self, Term};
+
+/// The MIME type of Markdown documentation, as emitted by Elixir
+pub const FORMAT_MARKDOWN: &str = "text/markdown";
+
+/// The MIME type of the structured documentation emitted by Erlang/OTP
+pub const FORMAT_ERLANG_HTML: &str = "application/erlang+html";
+
+#[derive(Fail, Debug)]
+pub enum DocsError {
+    #[fail(display = "unable to decode documentation: {}", _0)]
+    Decode(#[fail(cause)] etf::DecodeError),
+
+    #[fail(
+        display = "unexpected term in documentation! {} is not {}",
+        term, expected
+    )]
+    UnexpectedTerm { term: Term, expected: &'static str },
+}
+impl std::convert::From<etf::DecodeError> for DocsError {
+    fn from(err: etf::DecodeError) -> Self {
+        DocsError::Decode(err)
+    }
+}
+
+pub type DocsResult<T> = Result<T, DocsError>;
+
+/// The decoded `docs_v1` term of a module.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ModuleDocs {
+    /// The annotation (usually the line) of the module definition
+    pub anno: Term,
+    /// The language the module was written in, e.g. `erlang` or `elixir`
+    pub beam_language: String,
+    /// The MIME type of every documentation value, e.g. [`FORMAT_MARKDOWN`](FORMAT_MARKDOWN)
+    pub format: String,
+    pub module_doc: Doc,
+    pub metadata: etf::Map,
+    pub entries: Vec<DocEntry>,
+}
+impl ModuleDocs {
+    /// Decodes the External Term Format contents of a `"Docs"` chunk
+    pub fn decode(bytes: &[u8]) -> DocsResult<Self> {
+        let term = Term::decode(bytes)?;
+        Self::from_term(&term)
+    }
+
+    pub fn from_term(term: &Term) -> DocsResult<Self> {
+        const EXPECTED: &str = "a docs_v1 tuple";
+        let elements = match *term {
+            Term::Tuple(ref t) if t.elements.len() == 7 => &t.elements,
+            _ => return Err(unexpected(term, EXPECTED)),
+        };
+        if atom(&elements[0]) != Some("docs_v1") {
+            return Err(unexpected(term, EXPECTED));
+        }
+        let entries = list(&elements[6])?
+            .iter()
+            .map(DocEntry::from_term)
+            .collect::<DocsResult<Vec<_>>>()?;
+        Ok(ModuleDocs {
+            anno: elements[1].clone(),
+            beam_language: atom(&elements[2])
+                .ok_or_else(|| unexpected(&elements[2], "an atom"))?
+                .to_string(),
+            format: string(&elements[3])?,
+            module_doc: Doc::from_term(&elements[4])?,
+            metadata: map(&elements[5])?.clone(),
+            entries,
+        })
+    }
+
+    /// Returns the value of a metadata key, e.g. `since` or `deprecated`
+    pub fn meta(&self, key: &str) -> Option<&Term> {
+        meta(&self.metadata, key)
+    }
+}
+
+/// The kind of a documented entry.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub enum DocKind {
+    Type,
+    Callback,
+    Function,
+    Macro,
+    Other(String),
+}
+impl DocKind {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "type" => DocKind::Type,
+            "callback" => DocKind::Callback,
+            "function" => DocKind::Function,
+            "macro" => DocKind::Macro,
+            other => DocKind::Other(other.to_string()),
+        }
+    }
+    pub fn name(&self) -> &str {
+        match *self {
+            DocKind::Type => "type",
+            DocKind::Callback => "callback",
+            DocKind::Function => "function",
+            DocKind::Macro => "macro",
+            DocKind::Other(ref name) => name,
+        }
+    }
+}
+impl fmt::Display for DocKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The documentation of a single function, type, callback or macro.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DocEntry {
+    pub kind: DocKind,
+    pub name: String,
+    pub arity: u32,
+    pub anno: Term,
+    /// The rendered signatures, e.g. `add(a, b)`
+    pub signature: Vec<String>,
+    pub doc: Doc,
+    pub metadata: etf::Map,
+}
+impl DocEntry {
+    pub fn from_term(term: &Term) -> DocsResult<Self> {
+        const EXPECTED: &str = "a {{Kind, Name, Arity}, Anno, Signature, Doc, Metadata} tuple";
+        let elements = match *term {
+            Term::Tuple(ref t) if t.elements.len() == 5 => &t.elements,
+            _ => return Err(unexpected(term, EXPECTED)),
+        };
+        let (kind, name, arity) = match elements[0] {
+            Term::Tuple(ref t) if t.elements.len() == 3 => {
+                match (atom(&t.elements[0]), atom(&t.elements[1]), &t.elements[2]) {
+                    (Some(kind), Some(name), Term::FixInteger(arity)) if arity.value >= 0 => {
+                        (kind, name, arity.value as u32)
+                    }
+                    _ => return Err(unexpected(&elements[0], "a {Kind, Name, Arity} tuple")),
+                }
+            }
+            _ => return Err(unexpected(&elements[0], "a {Kind, Name, Arity} tuple")),
+        };
+        let signature = list(&elements[2])?
+            .iter()
+            .map(string)
+            .collect::<DocsResult<Vec<_>>>()?;
+        Ok(DocEntry {
+            kind: DocKind::from_name(kind),
+            name: name.to_string(),
+            arity,
+            anno: elements[1].clone(),
+            signature,
+            doc: Doc::from_term(&elements[3])?,
+            metadata: map(&elements[4])?.clone(),
+        })
+    }
+
+    /// Returns the value of a metadata key, e.g. `since` or `deprecated`
+    pub fn meta(&self, key: &str) -> Option<&Term> {
+        meta(&self.metadata, key)
+    }
+}
+impl fmt::Display for DocEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.name, self.arity)
+    }
+}
+
+/// The documentation of a module or entry, per language.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Doc {
+    /// No documentation was written
+    None,
+    /// The entry was explicitly hidden from documentation
+    Hidden,
+    /// The documentation keyed by language, e.g. `en`
+    Docs(BTreeMap<String, DocValue>),
+}
+impl Doc {
+    pub fn from_term(term: &Term) -> DocsResult<Self> {
+        match *term {
+            Term::Atom(ref a) if a.name == "none" => Ok(Doc::None),
+            Term::Atom(ref a) if a.name == "hidden" => Ok(Doc::Hidden),
+            Term::Map(ref m) => {
+                let mut docs = BTreeMap::new();
+                for (k, v) in &m.entries {
+                    let value = match *v {
+                        Term::Binary(_) => DocValue::Text(string(v)?),
+                        _ => DocValue::Term(v.clone()),
+                    };
+                    docs.insert(string(k)?, value);
+                }
+                Ok(Doc::Docs(docs))
+            }
+            _ => Err(unexpected(term, "none, hidden or a map")),
+        }
+    }
+
+    /// Returns the documentation in the given language, falling back to any other language
+    pub fn get(&self, language: &str) -> Option<&DocValue> {
+        match *self {
+            Doc::Docs(ref docs) => docs.get(language).or_else(|| docs.values().next()),
+            _ => None,
+        }
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        *self == Doc::Hidden
+    }
+}
+
+/// A documentation value.
+#[derive(Debug, PartialEq, Clone)]
+pub enum DocValue {
+    /// Text in the module's format, e.g. Markdown
+    Text(String),
+    /// A structured value, such as the element tree of `application/erlang+html`
+    Term(Term),
+}
+
+fn unexpected(term: &Term, expected: &'static str) -> DocsError {
+    DocsError::UnexpectedTerm {
+        term: term.clone(),
+        expected,
+    }
+}
+
+fn atom(term: &Term) -> Option<&str> {
+    match *term {
+        Term::Atom(ref a) => Some(&a.name),
+        _ => None,
+    }
+}
+
+fn string(term: &Term) -> DocsResult<String> {
+    match *term {
+        Term::Binary(ref b) => {
+            String::from_utf8(b.bytes.clone()).map_err(|_| unexpected(term, "a UTF-8 binary"))
+        }
+        _ => Err(unexpected(term, "a binary")),
+    }
+}
+
+fn list(term: &Term) -> DocsResult<&[Term]> {
+    match *term {
+        Term::List(ref l) => Ok(&l.elements),
+        _ => Err(unexpected(term, "a list")),
+    }
+}
+
+fn map(term: &Term) -> DocsResult<&etf::Map> {
+    match *term {
+        Term::Map(ref m) => Ok(m),
+        _ => Err(unexpected(term, "a map")),
+    }
+}
+
+fn meta<'a>(metadata: &'a etf::Map, key: &str) -> Option<&'a Term> {
+    metadata
+        .entries
+        .iter()
+        .find(|(k, _)| atom(k) == Some(key))
+        .map(|(_, v)| v)
+}
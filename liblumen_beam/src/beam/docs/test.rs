@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use crate::beam::docs::*;
+use crate::beam::reader::chunk::StandardChunk;
+use crate::beam::reader::StandardBeamFile;
+use crate::serialization::etf::text;
+
+#[test]
+fn elixir_docs_test() {
+    let beam = StandardBeamFile::from_file("tests/testdata/reader/Elixir.Unicode.beam").unwrap();
+    let docs = match beam.get_chunk(b"Docs") {
+        Some(StandardChunk::Docs(chunk)) => chunk.docs().unwrap(),
+        other => panic!("unexpected chunk: {:?}", other),
+    };
+    assert_eq!("elixir", docs.beam_language);
+    assert_eq!(FORMAT_MARKDOWN, docs.format);
+    assert_eq!(Doc::Hidden, docs.module_doc);
+    assert!(docs.metadata.entries.is_empty());
+
+    let names = docs
+        .entries
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec!["add1/1", "ascii_atom/0", "string/0", "utf8_atom/0"],
+        names
+    );
+    assert_eq!(DocKind::Function, docs.entries[0].kind);
+    assert_eq!(vec!["add1(n)".to_string()], docs.entries[0].signature);
+    assert_eq!(Doc::None, docs.entries[0].doc);
+}
+
+#[test]
+fn erlang_docs_test() {
+    let term = text::parse_term(
+        r#"{docs_v1, [{file, "lists.erl"}, {location, 1}], erlang, <<"application/erlang+html">>,
+            #{<<"en">> => [{p, [], [<<"List processing.">>]}]},
+            #{otp_doc_vsn => {1, 0, 0}},
+            [{{type, seq, 0}, 10, [<<"seq()">>], hidden, #{}},
+             {{function, map, 2}, 20, [<<"map(Fun, List1)">>],
+              #{<<"en">> => <<"Maps.">>, <<"sv">> => <<"Avbildar.">>},
+              #{since => <<"OTP 18.0">>}},
+             {{macro, 'MAX', 0}, 30, [], none, #{}}]}"#,
+    )
+    .unwrap();
+    let docs = ModuleDocs::from_term(&term).unwrap();
+    assert_eq!("erlang", docs.beam_language);
+    assert_eq!(FORMAT_ERLANG_HTML, docs.format);
+    match docs.module_doc.get("en") {
+        Some(DocValue::Term(t)) => {
+            assert_eq!(r#"[{p,[],[<<"List processing.">>]}]"#, text::to_string(t))
+        }
+        other => panic!("unexpected doc: {:?}", other),
+    }
+    assert_eq!(
+        Some(r#"{1,0,0}"#.to_string()),
+        docs.meta("otp_doc_vsn").map(text::to_string)
+    );
+
+    assert!(docs.entries[0].doc.is_hidden());
+    assert_eq!(DocKind::Type, docs.entries[0].kind);
+
+    let map = &docs.entries[1];
+    let mut expected = BTreeMap::new();
+    expected.insert("en".to_string(), DocValue::Text("Maps.".to_string()));
+    expected.insert("sv".to_string(), DocValue::Text("Avbildar.".to_string()));
+    assert_eq!(Doc::Docs(expected), map.doc);
+    assert_eq!(
+        Some(&DocValue::Text("Avbildar.".to_string())),
+        map.doc.get("sv")
+    );
+    // Falls back to any available language
+    assert_eq!(
+        Some(&DocValue::Text("Maps.".to_string())),
+        map.doc.get("de")
+    );
+    assert_eq!(
+        Some(r#"<<"OTP 18.0">>"#.to_string()),
+        map.meta("since").map(text::to_string)
+    );
+    assert_eq!(None, map.meta("deprecated"));
+
+    assert_eq!(DocKind::Macro, docs.entries[2].kind);
+    assert_eq!("MAX/0", docs.entries[2].to_string());
+
+    let mut bytes = Vec::new();
+    term.encode(&mut bytes).unwrap();
+    assert_eq!(docs, ModuleDocs::decode(&bytes).unwrap());
+}
+
+#[test]
+fn malformed_docs_test() {
+    let parse = |s: &str| ModuleDocs::from_term(&text::parse_term(s).unwrap());
+    match parse("{docs_v2, 1, erlang, <<>>, none, #{}, []}") {
+        Err(DocsError::UnexpectedTerm { expected, .. }) => assert_eq!("a docs_v1 tuple", expected),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match parse("{docs_v1, 1, erlang, <<>>, none, #{}, [{{function, f}, 1, [], none, #{}}]}") {
+        Err(DocsError::UnexpectedTerm { expected, .. }) => {
+            assert_eq!("a {Kind, Name, Arity} tuple", expected)
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match parse("{docs_v1, 1, erlang, <<>>, unknown, #{}, []}") {
+        Err(DocsError::UnexpectedTerm { expected, .. }) => {
+            assert_eq!("none, hidden or a map", expected)
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match ModuleDocs::decode(&[131, 255]) {
+        Err(DocsError::Decode(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
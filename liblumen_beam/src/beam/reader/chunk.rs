@@ -24,6 +24,8 @@ use libflate::zlib;
 use super::parts;
 use super::Result;
 
+use crate::beam::docs::{DocsResult, ModuleDocs};
+
 /// The identifier which indicates the type of a chunk.
 pub type Id = [u8; 4];
 
@@ -765,13 +767,6 @@ pub struct DocsChunk {
     /// represents a term in the following format:
     ///
     /// ```erlang
-    /// {Module, [{"Docs", DocsBin}]}
-    /// ```
-    ///
-    /// Where `Module` is the documented module, and `DocsBin` is a binary in External Term Format
-    /// containing the documentation. Currently, that decodes to:
-    ///
-    /// ```erlang
     /// {docs_v1, Anno, BeamLang, Format, ModuleDoc, Metadata, Docs}
     ///   where Anno :: erl_anno:anno(),
     ///         BeamLang :: erlang | elixir | lfe | alpaca | atom(),
@@ -783,8 +778,16 @@ pub struct DocsChunk {
     ///         doc_content :: map(binary(), binary()) | none | hidden,
     ///         doc_element :: {{kind :: atom(), function :: atom(), arity}, Anno, signature, doc_content(), Metadata}
     /// ```
+    ///
+    /// Use [`docs`](#method.docs) to decode it into a [`ModuleDocs`](../../docs/struct.ModuleDocs.html).
     pub term: parts::ExternalTermFormatBinary,
 }
+impl DocsChunk {
+    /// Decodes the documentation into its structured EEP-48 model
+    pub fn docs(&self) -> DocsResult<ModuleDocs> {
+        ModuleDocs::decode(&self.term)
+    }
+}
 impl Chunk for DocsChunk {
     fn id(&self) -> &Id {
         b"Docs"
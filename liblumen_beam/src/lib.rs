@@ -1,3 +1,6 @@
+// Lets `#[derive(EtfPattern)]` refer to `::liblumen_beam` within this crate
+extern crate self as liblumen_beam;
+
 pub mod beam;
 pub mod serialization;
 pub mod syntax;
@@ -26,7 +26,10 @@
 //!
 mod codec;
 pub mod convert;
+pub mod distribution;
 pub mod pattern;
+pub mod serde;
+pub mod text;
 
 #[cfg(test)]
 mod test;
@@ -35,8 +38,9 @@ use std::convert::From;
 
 use num::bigint::BigInt;
 
-pub use self::codec::{DecodeError, DecodeResult};
-pub use self::codec::{EncodeError, EncodeResult};
+pub use self::codec::{DecodeError, DecodeOptions, DecodeResult};
+pub use self::codec::{EncodeError, EncodeOptions, EncodeResult};
+pub use self::distribution::{AtomCache, DistributionMessage};
 
 /// Term.
 #[derive(Debug, PartialEq, Clone)]
@@ -56,16 +60,47 @@ pub enum Term {
     ImproperList(ImproperList),
     Tuple(Tuple),
     Map(Map),
+    Local(Local),
 }
 impl Term {
     /// Decodes a term.
     pub fn decode<R: std::io::Read>(reader: R) -> DecodeResult {
-        codec::Decoder::new(reader).decode()
+        Term::decode_with_options(reader, &DecodeOptions::default())
+    }
+
+    /// Decodes a term, applying the limits of `options`.
+    ///
+    /// A term starting with a distribution header can only refer to the atoms that the
+    /// header adds. Use `decode_with_cache` for terms received on a distribution connection.
+    pub fn decode_with_options<R: std::io::Read>(
+        reader: R,
+        options: &DecodeOptions,
+    ) -> DecodeResult {
+        Term::decode_with_cache(reader, &mut AtomCache::new(), options)
+    }
+
+    /// Decodes a term like `decode_with_options`, updating `cache` with the new entries
+    /// of its distribution header, if any.
+    pub fn decode_with_cache<R: std::io::Read>(
+        reader: R,
+        cache: &mut AtomCache,
+        options: &DecodeOptions,
+    ) -> DecodeResult {
+        codec::Decoder::new(reader, options).decode(cache)
     }
 
     /// Encodes the term.
     pub fn encode<W: std::io::Write>(&self, writer: W) -> EncodeResult {
-        codec::Encoder::new(writer).encode(self)
+        self.encode_with_options(writer, &EncodeOptions::default())
+    }
+
+    /// Encodes the term, as specified by `options`.
+    pub fn encode_with_options<W: std::io::Write>(
+        &self,
+        writer: W,
+        options: &EncodeOptions,
+    ) -> EncodeResult {
+        codec::Encoder::new(writer, options).encode(self)
     }
 
     pub fn as_match<'a, P>(&'a self, pattern: P) -> pattern::Result<P::Output>
@@ -93,6 +128,7 @@ impl std::fmt::Display for Term {
             Term::ImproperList(ref x) => x.fmt(f),
             Term::Tuple(ref x) => x.fmt(f),
             Term::Map(ref x) => x.fmt(f),
+            Term::Local(ref x) => x.fmt(f),
         }
     }
 }
@@ -171,6 +207,11 @@ impl From<Map> for Term {
         Term::Map(x)
     }
 }
+impl From<Local> for Term {
+    fn from(x: Local) -> Self {
+        Term::Local(x)
+    }
+}
 
 /// Atom.
 #[derive(Debug, PartialEq, Clone)]
@@ -364,10 +405,10 @@ pub struct Pid {
     pub node: Atom,
     pub id: u32,
     pub serial: u32,
-    pub creation: u8,
+    pub creation: u32,
 }
 impl Pid {
-    pub fn new<T>(node: T, id: u32, serial: u32, creation: u8) -> Self
+    pub fn new<T>(node: T, id: u32, serial: u32, creation: u32) -> Self
     where
         Atom: From<T>,
     {
@@ -400,8 +441,8 @@ impl<'a> From<(&'a str, u32, u32)> for Pid {
 #[derive(Debug, PartialEq, Clone)]
 pub struct Port {
     pub node: Atom,
-    pub id: u32,
-    pub creation: u8,
+    pub id: u64,
+    pub creation: u32,
 }
 impl std::fmt::Display for Port {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -412,7 +453,7 @@ impl<'a> From<(&'a str, u32)> for Port {
     fn from((node, id): (&'a str, u32)) -> Self {
         Port {
             node: Atom::from(node),
-            id,
+            id: u64::from(id),
             creation: 0,
         }
     }
@@ -423,7 +464,7 @@ impl<'a> From<(&'a str, u32)> for Port {
 pub struct Reference {
     pub node: Atom,
     pub id: Vec<u32>,
-    pub creation: u8,
+    pub creation: u32,
 }
 impl std::fmt::Display for Reference {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -716,6 +757,25 @@ impl From<Vec<(Term, Term)>> for Map {
     }
 }
 
+/// A term in the node-local format (`LOCAL_EXT`).
+///
+/// Only the node which encoded it can decode it, so it is kept as the opaque bytes
+/// following the tag, and is encoded back as-is.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Local {
+    pub bytes: Vec<u8>,
+}
+impl std::fmt::Display for Local {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "#Local<{}>", self.bytes.len())
+    }
+}
+impl From<Vec<u8>> for Local {
+    fn from(bytes: Vec<u8>) -> Self {
+        Local { bytes }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::pattern::any;
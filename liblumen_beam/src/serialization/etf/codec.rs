@@ -1,11 +1,15 @@
 mod auxiliary;
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Read;
 use std::io::Write;
 
 use byteorder::BigEndian;
 use byteorder::ReadBytesExt;
 use byteorder::WriteBytesExt;
 
+use libflate::lz77;
 use libflate::zlib;
 
 use num::bigint::BigInt;
@@ -36,10 +40,111 @@ pub enum DecodeError {
         value: i32,
         range: std::ops::Range<i32>,
     },
+
+    #[fail(display = "expected a distribution header, found tag '{}'", tag)]
+    MissingDistributionHeader { tag: u8 },
+
+    #[fail(
+        display = "atom cache reference {} is not in the distribution header",
+        index
+    )]
+    UnknownAtomCacheRef { index: usize },
+
+    #[fail(display = "atom cache entry {} is empty", index)]
+    MissingAtomCacheEntry { index: usize },
+
+    #[fail(display = "term exceeds the maximum size of {} bytes", limit)]
+    SizeLimitExceeded { limit: usize },
+
+    #[fail(display = "term exceeds the maximum nesting depth of {}", limit)]
+    DepthLimitExceeded { limit: usize },
+
+    #[fail(display = "length {} exceeds the maximum of {}", length, limit)]
+    LengthLimitExceeded { length: usize, limit: usize },
+
+    #[fail(
+        display = "compressed term does not match its declared size of {} bytes",
+        declared
+    )]
+    UncompressedSizeMismatch { declared: usize },
+
+    #[fail(display = "unknown atom '{}'", name)]
+    UnknownAtom { name: String },
+
+    #[fail(display = "a local term can only be the whole encoded term")]
+    NestedLocal,
+
+    #[fail(
+        display = "invalid number of bits in the last byte of a {}-byte bitstring: {}",
+        size, tail_bits_size
+    )]
+    InvalidTailBitsSize { size: usize, tail_bits_size: u8 },
 }
 impl std::convert::From<std::io::Error> for DecodeError {
     fn from(err: std::io::Error) -> DecodeError {
-        DecodeError::IO(err)
+        let limit = err
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<auxiliary::ReadLimitExceeded>())
+            .map(|e| e.limit);
+        match limit {
+            Some(limit) => DecodeError::SizeLimitExceeded { limit },
+            None => DecodeError::IO(err),
+        }
+    }
+}
+
+/// Limits applied when decoding a term.
+///
+/// The default options trust the input completely. When decoding untrusted data, set limits
+/// so that malicious input fails with a `DecodeError` instead of exhausting memory or the stack.
+///
+/// # Examples
+///
+///     use std::io::Cursor;
+///     use liblumen_beam::serialization::etf::{DecodeOptions, Term};
+///
+///     let options = DecodeOptions::new().max_size(1024).max_depth(32);
+///     // A list claiming 2^32 - 1 elements
+///     let bytes = vec![131, 108, 255, 255, 255, 255, 106];
+///     assert!(Term::decode_with_options(Cursor::new(&bytes), &options).is_err());
+///
+#[derive(Debug, Clone, Default)]
+pub struct DecodeOptions {
+    /// The maximum number of bytes read from the input,
+    /// and the maximum decompressed size of a compressed term.
+    pub max_size: Option<usize>,
+    /// The maximum nesting depth of terms.
+    pub max_depth: Option<usize>,
+    /// The maximum number of elements of a list, tuple or map.
+    pub max_length: Option<usize>,
+    /// Whether compressed terms must decompress to exactly their declared size.
+    pub verify_uncompressed_size: bool,
+    /// If set, atoms which are not in this set are rejected.
+    pub known_atoms: Option<HashSet<String>>,
+}
+impl DecodeOptions {
+    pub fn new() -> Self {
+        DecodeOptions::default()
+    }
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+    pub fn verify_uncompressed_size(mut self, verify: bool) -> Self {
+        self.verify_uncompressed_size = verify;
+        self
+    }
+    pub fn known_atoms(mut self, known_atoms: HashSet<String>) -> Self {
+        self.known_atoms = Some(known_atoms);
+        self
     }
 }
 
@@ -60,6 +165,18 @@ pub enum EncodeError {
     // r.id.len() * 4
     #[fail(display = "reference is too large, exceeds maximum byte size")]
     TooLargeReferenceId(Reference),
+
+    #[fail(display = "float can not be encoded as text: {}", _0)]
+    NonFiniteFloat(Float),
+
+    #[fail(display = "invalid compression level: {}", _0)]
+    InvalidCompressionLevel(u8),
+
+    #[fail(display = "invalid minor version: {}", _0)]
+    InvalidMinorVersion(u8),
+
+    #[fail(display = "a local term can only be the whole encoded term")]
+    NestedLocal,
 }
 impl std::convert::From<std::io::Error> for EncodeError {
     fn from(err: std::io::Error) -> EncodeError {
@@ -67,6 +184,54 @@ impl std::convert::From<std::io::Error> for EncodeError {
     }
 }
 
+/// Options applied when encoding a term, as accepted by `term_to_binary/2`.
+///
+/// # Examples
+///
+///     use liblumen_beam::serialization::etf::{EncodeOptions, Term, Atom};
+///
+///     let options = EncodeOptions::new().compressed(9).minor_version(0);
+///     let mut buf = Vec::new();
+///     Term::from(Atom::from("foo"))
+///         .encode_with_options(&mut buf, &options)
+///         .unwrap();
+///     assert_eq!(&buf[..2], &[131, 80]);
+///
+#[derive(Debug, Clone)]
+pub struct EncodeOptions {
+    /// The zlib compression level (`0..=9`) of the encoded term, if it is to be compressed.
+    ///
+    /// Level `0` stores the term without compressing it, and higher levels use larger
+    /// LZ77 windows.
+    pub compressed: Option<u8>,
+    /// The minor version of the encoding (`0..=2`).
+    ///
+    /// Version `0` encodes floats as `FLOAT_EXT`, later versions use `NEW_FLOAT_EXT`.
+    /// Version `2` always encodes atoms as UTF-8.
+    pub minor_version: u8,
+}
+impl EncodeOptions {
+    pub fn new() -> Self {
+        EncodeOptions::default()
+    }
+    pub fn compressed(mut self, level: u8) -> Self {
+        self.compressed = Some(level);
+        self
+    }
+    pub fn minor_version(mut self, minor_version: u8) -> Self {
+        self.minor_version = minor_version;
+        self
+    }
+}
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        EncodeOptions {
+            compressed: None,
+            minor_version: 1,
+        }
+    }
+}
+
 pub type DecodeResult = Result<Term, DecodeError>;
 pub type EncodeResult = Result<(), EncodeError>;
 
@@ -77,6 +242,9 @@ const NEW_FLOAT_EXT: u8 = 70;
 const BIT_BINARY_EXT: u8 = 77;
 const COMPRESSED_TERM: u8 = 80;
 const ATOM_CACHE_REF: u8 = 82;
+const NEW_PID_EXT: u8 = 88;
+const NEW_PORT_EXT: u8 = 89;
+const NEWER_REFERENCE_EXT: u8 = 90;
 const SMALL_INTEGER_EXT: u8 = 97;
 const INTEGER_EXT: u8 = 98;
 const FLOAT_EXT: u8 = 99;
@@ -100,19 +268,29 @@ const MAP_EXT: u8 = 116;
 const FUN_EXT: u8 = 117;
 const ATOM_UTF8_EXT: u8 = 118;
 const SMALL_ATOM_UTF8_EXT: u8 = 119;
+const V4_PORT_EXT: u8 = 120;
+const LOCAL_EXT: u8 = 121;
 
-pub struct Decoder<R> {
-    reader: R,
+pub struct Decoder<'a, R> {
+    reader: auxiliary::LimitedReader<R>,
+    options: &'a DecodeOptions,
+    depth: usize,
     buf: Vec<u8>,
+    // The atoms referenced by the distribution header, in header order
+    atom_refs: Vec<Atom>,
 }
-impl<R: std::io::Read> Decoder<R> {
-    pub fn new(reader: R) -> Self {
+impl<'a, R: std::io::Read> Decoder<'a, R> {
+    pub fn new(reader: R, options: &'a DecodeOptions) -> Self {
         Decoder {
-            reader,
+            reader: auxiliary::LimitedReader::new(reader, options.max_size),
+            options,
+            depth: 0,
             buf: Vec::new(),
+            atom_refs: Vec::new(),
         }
     }
-    pub fn decode(mut self) -> DecodeResult {
+    /// Decodes a term, which may start with a distribution header that updates `cache`.
+    pub fn decode(mut self, cache: &mut AtomCache) -> DecodeResult {
         let version = self.reader.read_u8()?;
         if version != VERSION {
             return Err(DecodeError::UnsupportedVersion { version });
@@ -120,19 +298,91 @@ impl<R: std::io::Read> Decoder<R> {
         let tag = self.reader.read_u8()?;
         match tag {
             COMPRESSED_TERM => self.decode_compressed_term(),
-            DISTRIBUTION_HEADER => unimplemented!(),
+            DISTRIBUTION_HEADER => {
+                self.decode_distribution_header(cache)?;
+                self.decode_term()
+            }
+            LOCAL_EXT => self.decode_local_ext(),
             _ => self.decode_term_with_tag(tag),
         }
     }
+    pub fn decode_distribution(
+        mut self,
+        cache: &mut AtomCache,
+    ) -> Result<DistributionMessage, DecodeError> {
+        let version = self.reader.read_u8()?;
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion { version });
+        }
+        let tag = self.reader.read_u8()?;
+        if tag != DISTRIBUTION_HEADER {
+            return Err(DecodeError::MissingDistributionHeader { tag });
+        }
+        self.decode_distribution_header(cache)?;
+        // Terms following the header are encoded without the version number
+        let control = self.decode_term()?;
+        let payload = match self.reader.read_u8() {
+            Ok(tag) => Some(self.decode_term_with_tag(tag)?),
+            Err(ref e) if e.kind() == std::io::ErrorKind::UnexpectedEof => None,
+            Err(e) => return Err(DecodeError::from(e)),
+        };
+        Ok(DistributionMessage { control, payload })
+    }
+    fn decode_distribution_header(&mut self, cache: &mut AtomCache) -> Result<(), DecodeError> {
+        self.atom_refs.clear();
+        let count = self.reader.read_u8()? as usize;
+        if count == 0 {
+            return Ok(());
+        }
+        // Two half-byte flags per byte: one for each reference, plus one for the header itself
+        let mut flags = vec![0; count / 2 + 1];
+        self.reader.read_exact(&mut flags)?;
+        let flag = |i: usize| (flags[i / 2] >> ((i % 2) * 4)) & 0x0F;
+        let long_atoms = flag(count) & 0x01 != 0;
+        for i in 0..count {
+            let segment_index = (flag(i) & 0x07) as usize;
+            let index = (segment_index << 8) | self.reader.read_u8()? as usize;
+            let atom = if flag(i) & 0x08 != 0 {
+                let len = if long_atoms {
+                    self.reader.read_u16::<BigEndian>()? as usize
+                } else {
+                    self.reader.read_u8()? as usize
+                };
+                self.buf.resize(len, 0);
+                self.reader.read_exact(&mut self.buf)?;
+                let name = std::str::from_utf8(&self.buf)
+                    .or_else(|e| auxiliary::invalid_data_error(e.to_string()))?;
+                let atom = self.new_atom(name)?;
+                cache.insert(index, atom.clone());
+                atom
+            } else {
+                cache
+                    .get(index)
+                    .cloned()
+                    .ok_or(DecodeError::MissingAtomCacheEntry { index })?
+            };
+            self.atom_refs.push(atom);
+        }
+        Ok(())
+    }
     fn decode_term(&mut self) -> DecodeResult {
         let tag = self.reader.read_u8()?;
         self.decode_term_with_tag(tag)
     }
     fn decode_term_with_tag(&mut self, tag: u8) -> DecodeResult {
-        match tag {
+        self.depth += 1;
+        if let Some(limit) = self.options.max_depth {
+            if self.depth > limit {
+                return Err(DecodeError::DepthLimitExceeded { limit });
+            }
+        }
+        let result = match tag {
             NEW_FLOAT_EXT => self.decode_new_float_ext(),
             BIT_BINARY_EXT => self.decode_bit_binary_ext(),
-            ATOM_CACHE_REF => unimplemented!(),
+            ATOM_CACHE_REF => self.decode_atom_cache_ref(),
+            NEW_PID_EXT => self.decode_new_pid_ext(),
+            NEW_PORT_EXT => self.decode_new_port_ext(),
+            NEWER_REFERENCE_EXT => self.decode_newer_reference_ext(),
             SMALL_INTEGER_EXT => self.decode_small_integer_ext(),
             INTEGER_EXT => self.decode_integer_ext(),
             FLOAT_EXT => self.decode_float_ext(),
@@ -156,20 +406,96 @@ impl<R: std::io::Read> Decoder<R> {
             FUN_EXT => self.decode_fun_ext(),
             ATOM_UTF8_EXT => self.decode_atom_utf8_ext(),
             SMALL_ATOM_UTF8_EXT => self.decode_small_atom_utf8_ext(),
+            V4_PORT_EXT => self.decode_v4_port_ext(),
+            // The bytes of a local term extend to the end of the input, so the term can not be
+            // followed by other terms
+            LOCAL_EXT => Err(DecodeError::NestedLocal),
             _ => Err(DecodeError::UnknownTag { tag }),
+        };
+        self.depth -= 1;
+        result
+    }
+    // Fails if `size` bytes can not be read within the size limit
+    fn check_size(&self, size: usize) -> Result<(), DecodeError> {
+        match self.options.max_size {
+            Some(limit) if size > self.reader.remaining() => {
+                Err(DecodeError::SizeLimitExceeded { limit })
+            }
+            _ => Ok(()),
         }
     }
+    // Fails if `length` elements, each encoded in at least `element_size` bytes,
+    // can not be decoded within the length and size limits
+    fn check_length(&self, length: usize, element_size: usize) -> Result<(), DecodeError> {
+        if let Some(limit) = self.options.max_length {
+            if length > limit {
+                return Err(DecodeError::LengthLimitExceeded { length, limit });
+            }
+        }
+        self.check_size(length.saturating_mul(element_size))
+    }
+    fn new_atom(&self, name: &str) -> Result<Atom, DecodeError> {
+        if let Some(ref known_atoms) = self.options.known_atoms {
+            if !known_atoms.contains(name) {
+                return Err(DecodeError::UnknownAtom {
+                    name: name.to_string(),
+                });
+            }
+        }
+        Ok(Atom::from(name))
+    }
     fn decode_compressed_term(&mut self) -> DecodeResult {
-        let _uncompressed_size = self.reader.read_u32::<BigEndian>()? as usize;
+        let uncompressed_size = self.reader.read_u32::<BigEndian>()? as usize;
+        if let Some(limit) = self.options.max_size {
+            if uncompressed_size > limit {
+                return Err(DecodeError::SizeLimitExceeded { limit });
+            }
+        }
+        let options = self.options;
+        let verify = options.verify_uncompressed_size;
+        let limit = if verify {
+            Some(uncompressed_size)
+        } else {
+            options.max_size
+        };
         let zlib_decoder = zlib::Decoder::new(&mut self.reader)?;
-        let mut decoder = Decoder::new(zlib_decoder);
-        decoder.decode_term()
+        let mut decoder = Decoder {
+            reader: auxiliary::LimitedReader::new(zlib_decoder, limit),
+            options,
+            depth: self.depth,
+            buf: Vec::new(),
+            atom_refs: Vec::new(),
+        };
+        let term = decoder.decode_term().map_err(|e| match e {
+            DecodeError::SizeLimitExceeded { limit } if verify && limit == uncompressed_size => {
+                DecodeError::UncompressedSizeMismatch {
+                    declared: uncompressed_size,
+                }
+            }
+            e => e,
+        })?;
+        if verify && decoder.reader.count() != uncompressed_size {
+            return Err(DecodeError::UncompressedSizeMismatch {
+                declared: uncompressed_size,
+            });
+        }
+        Ok(term)
+    }
+    fn decode_atom_cache_ref(&mut self) -> DecodeResult {
+        let index = self.reader.read_u8()? as usize;
+        let atom = self
+            .atom_refs
+            .get(index)
+            .cloned()
+            .ok_or(DecodeError::UnknownAtomCacheRef { index })?;
+        Ok(Term::from(atom))
     }
     fn decode_nil_ext(&mut self) -> DecodeResult {
         Ok(Term::from(List::nil()))
     }
     fn decode_string_ext(&mut self) -> DecodeResult {
         let size = self.reader.read_u16::<BigEndian>()? as usize;
+        self.check_length(size, 1)?;
         let mut elements = Vec::with_capacity(size);
         for _ in 0..size {
             elements.push(Term::from(FixInteger::from(self.reader.read_u8()? as i32)));
@@ -178,6 +504,7 @@ impl<R: std::io::Read> Decoder<R> {
     }
     fn decode_list_ext(&mut self) -> DecodeResult {
         let count = self.reader.read_u32::<BigEndian>()? as usize;
+        self.check_length(count, 1)?;
         let mut elements = Vec::with_capacity(count);
         for _ in 0..count {
             elements.push(self.decode_term()?);
@@ -195,6 +522,7 @@ impl<R: std::io::Read> Decoder<R> {
     }
     fn decode_small_tuple_ext(&mut self) -> DecodeResult {
         let count = self.reader.read_u8()? as usize;
+        self.check_length(count, 1)?;
         let mut elements = Vec::with_capacity(count);
         for _ in 0..count {
             elements.push(self.decode_term()?);
@@ -203,6 +531,7 @@ impl<R: std::io::Read> Decoder<R> {
     }
     fn decode_large_tuple_ext(&mut self) -> DecodeResult {
         let count = self.reader.read_u32::<BigEndian>()? as usize;
+        self.check_length(count, 1)?;
         let mut elements = Vec::with_capacity(count);
         for _ in 0..count {
             elements.push(self.decode_term()?);
@@ -211,6 +540,7 @@ impl<R: std::io::Read> Decoder<R> {
     }
     fn decode_map_ext(&mut self) -> DecodeResult {
         let count = self.reader.read_u32::<BigEndian>()? as usize;
+        self.check_length(count, 2)?;
         let mut entries = Vec::with_capacity(count);
         for _ in 0..count {
             let k = self.decode_term()?;
@@ -221,6 +551,7 @@ impl<R: std::io::Read> Decoder<R> {
     }
     fn decode_binary_ext(&mut self) -> DecodeResult {
         let size = self.reader.read_u32::<BigEndian>()? as usize;
+        self.check_size(size)?;
         let mut buf = vec![0; size];
         self.reader.read_exact(&mut buf)?;
         Ok(Term::from(Binary::from(buf)))
@@ -228,12 +559,20 @@ impl<R: std::io::Read> Decoder<R> {
     fn decode_bit_binary_ext(&mut self) -> DecodeResult {
         let size = self.reader.read_u32::<BigEndian>()? as usize;
         let tail_bits_size = self.reader.read_u8()?;
+        match (size, tail_bits_size) {
+            (0, 0) => return Ok(Term::from(Binary::from(Vec::new()))),
+            (0, _) | (_, 0) | (_, 9..=255) => {
+                return Err(DecodeError::InvalidTailBitsSize {
+                    size,
+                    tail_bits_size,
+                })
+            }
+            _ => {}
+        }
+        self.check_size(size)?;
         let mut buf = vec![0; size];
         self.reader.read_exact(&mut buf)?;
-        if !buf.is_empty() {
-            let last = buf[size - 1] >> (8 - tail_bits_size);
-            buf[size - 1] = last;
-        }
+        buf[size - 1] >>= 8 - tail_bits_size;
         Ok(Term::from(BitBinary::from((buf, tail_bits_size))))
     }
     fn decode_pid_ext(&mut self) -> DecodeResult {
@@ -242,7 +581,16 @@ impl<R: std::io::Read> Decoder<R> {
             node,
             id: self.reader.read_u32::<BigEndian>()?,
             serial: self.reader.read_u32::<BigEndian>()?,
-            creation: self.reader.read_u8()?,
+            creation: u32::from(self.reader.read_u8()?),
+        }))
+    }
+    fn decode_new_pid_ext(&mut self) -> DecodeResult {
+        let node = self.decode_term().and_then(auxiliary::term_into_atom)?;
+        Ok(Term::from(Pid {
+            node,
+            id: self.reader.read_u32::<BigEndian>()?,
+            serial: self.reader.read_u32::<BigEndian>()?,
+            creation: self.reader.read_u32::<BigEndian>()?,
         }))
     }
     fn decode_port_ext(&mut self) -> DecodeResult {
@@ -254,8 +602,24 @@ impl<R: std::io::Read> Decoder<R> {
         })?;
         Ok(Term::from(Port {
             node,
-            id: self.reader.read_u32::<BigEndian>()?,
-            creation: self.reader.read_u8()?,
+            id: u64::from(self.reader.read_u32::<BigEndian>()?),
+            creation: u32::from(self.reader.read_u8()?),
+        }))
+    }
+    fn decode_new_port_ext(&mut self) -> DecodeResult {
+        let node = self.decode_term().and_then(auxiliary::term_into_atom)?;
+        Ok(Term::from(Port {
+            node,
+            id: u64::from(self.reader.read_u32::<BigEndian>()?),
+            creation: self.reader.read_u32::<BigEndian>()?,
+        }))
+    }
+    fn decode_v4_port_ext(&mut self) -> DecodeResult {
+        let node = self.decode_term().and_then(auxiliary::term_into_atom)?;
+        Ok(Term::from(Port {
+            node,
+            id: self.reader.read_u64::<BigEndian>()?,
+            creation: self.reader.read_u32::<BigEndian>()?,
         }))
     }
     fn decode_reference_ext(&mut self) -> DecodeResult {
@@ -263,19 +627,38 @@ impl<R: std::io::Read> Decoder<R> {
         Ok(Term::from(Reference {
             node,
             id: vec![self.reader.read_u32::<BigEndian>()?],
-            creation: self.reader.read_u8()?,
+            creation: u32::from(self.reader.read_u8()?),
         }))
     }
     fn decode_new_reference_ext(&mut self) -> DecodeResult {
         let id_count = self.reader.read_u16::<BigEndian>()? as usize;
         let node = self.decode_term().and_then(auxiliary::term_into_atom)?;
-        let creation = self.reader.read_u8()?;
+        let creation = u32::from(self.reader.read_u8()?);
+        self.check_size(id_count * 4)?;
         let mut id = Vec::with_capacity(id_count);
         for _ in 0..id_count {
             id.push(self.reader.read_u32::<BigEndian>()?);
         }
         Ok(Term::from(Reference { node, id, creation }))
     }
+    fn decode_newer_reference_ext(&mut self) -> DecodeResult {
+        let id_count = self.reader.read_u16::<BigEndian>()? as usize;
+        let node = self.decode_term().and_then(auxiliary::term_into_atom)?;
+        let creation = self.reader.read_u32::<BigEndian>()?;
+        self.check_size(id_count * 4)?;
+        let mut id = Vec::with_capacity(id_count);
+        for _ in 0..id_count {
+            id.push(self.reader.read_u32::<BigEndian>()?);
+        }
+        Ok(Term::from(Reference { node, id, creation }))
+    }
+    fn decode_local_ext(&mut self) -> DecodeResult {
+        // The length of a local term is not known without decoding it,
+        // so it extends to the end of the input
+        let mut bytes = Vec::new();
+        self.reader.read_to_end(&mut bytes)?;
+        Ok(Term::from(Local::from(bytes)))
+    }
     fn decode_export_ext(&mut self) -> DecodeResult {
         let module = self.decode_term().and_then(auxiliary::term_into_atom)?;
         let function = self.decode_term().and_then(auxiliary::term_into_atom)?;
@@ -298,6 +681,7 @@ impl<R: std::io::Read> Decoder<R> {
         let uniq = self
             .decode_term()
             .and_then(auxiliary::term_into_fix_integer)?;
+        self.check_length(num_free as usize, 1)?;
         let mut vars = Vec::with_capacity(num_free as usize);
         for _ in 0..num_free {
             vars.push(self.decode_term()?);
@@ -325,6 +709,7 @@ impl<R: std::io::Read> Decoder<R> {
             .decode_term()
             .and_then(auxiliary::term_into_fix_integer)?;
         let pid = self.decode_term().and_then(auxiliary::term_into_pid)?;
+        self.check_length(num_free as usize, 1)?;
         let mut vars = Vec::with_capacity(num_free as usize);
         for _ in 0..num_free {
             vars.push(self.decode_term()?);
@@ -351,9 +736,9 @@ impl<R: std::io::Read> Decoder<R> {
             .or_else(|e| auxiliary::invalid_data_error(e.to_string()))?
             .trim_end_matches(0 as char);
         let value = float_str
-            .parse::<f32>()
+            .parse::<f64>()
             .or_else(|e| auxiliary::invalid_data_error(e.to_string()))?;
-        Ok(Term::from(Float::from(value as f64)))
+        Ok(Term::from(Float::from(value)))
     }
     fn decode_small_integer_ext(&mut self) -> DecodeResult {
         let value = self.reader.read_u8()?;
@@ -366,6 +751,7 @@ impl<R: std::io::Read> Decoder<R> {
     fn decode_small_big_ext(&mut self) -> DecodeResult {
         let count = self.reader.read_u8()? as usize;
         let sign = self.reader.read_u8()?;
+        self.check_size(count)?;
         self.buf.resize(count, 0);
         self.reader.read_exact(&mut self.buf)?;
         let value = BigInt::from_bytes_le(auxiliary::byte_to_sign(sign)?, &self.buf);
@@ -374,6 +760,7 @@ impl<R: std::io::Read> Decoder<R> {
     fn decode_large_big_ext(&mut self) -> DecodeResult {
         let count = self.reader.read_u32::<BigEndian>()? as usize;
         let sign = self.reader.read_u8()?;
+        self.check_size(count)?;
         self.buf.resize(count, 0);
         self.reader.read_exact(&mut self.buf)?;
         let value = BigInt::from_bytes_le(auxiliary::byte_to_sign(sign)?, &self.buf);
@@ -384,14 +771,14 @@ impl<R: std::io::Read> Decoder<R> {
         self.buf.resize(len as usize, 0);
         self.reader.read_exact(&mut self.buf)?;
         let name = auxiliary::latin1_bytes_to_string(&self.buf)?;
-        Ok(Term::from(Atom { name }))
+        Ok(Term::from(self.new_atom(&name)?))
     }
     fn decode_small_atom_ext(&mut self) -> DecodeResult {
         let len = self.reader.read_u8()?;
         self.buf.resize(len as usize, 0);
         self.reader.read_exact(&mut self.buf)?;
         let name = auxiliary::latin1_bytes_to_string(&self.buf)?;
-        Ok(Term::from(Atom { name }))
+        Ok(Term::from(self.new_atom(&name)?))
     }
     fn decode_atom_utf8_ext(&mut self) -> DecodeResult {
         let len = self.reader.read_u16::<BigEndian>()?;
@@ -399,7 +786,7 @@ impl<R: std::io::Read> Decoder<R> {
         self.reader.read_exact(&mut self.buf)?;
         let name = std::str::from_utf8(&self.buf)
             .or_else(|e| auxiliary::invalid_data_error(e.to_string()))?;
-        Ok(Term::from(Atom::from(name)))
+        Ok(Term::from(self.new_atom(name)?))
     }
     fn decode_small_atom_utf8_ext(&mut self) -> DecodeResult {
         let len = self.reader.read_u8()?;
@@ -407,20 +794,108 @@ impl<R: std::io::Read> Decoder<R> {
         self.reader.read_exact(&mut self.buf)?;
         let name = std::str::from_utf8(&self.buf)
             .or_else(|e| auxiliary::invalid_data_error(e.to_string()))?;
-        Ok(Term::from(Atom::from(name)))
+        Ok(Term::from(self.new_atom(name)?))
     }
 }
 
-pub struct Encoder<W> {
+pub struct Encoder<'a, W> {
     writer: W,
+    options: &'a EncodeOptions,
+    // Maps atom names to their reference index in the distribution header
+    atom_refs: HashMap<String, u8>,
 }
-impl<W: std::io::Write> Encoder<W> {
-    pub fn new(writer: W) -> Self {
-        Encoder { writer }
+impl<'a, W: std::io::Write> Encoder<'a, W> {
+    pub fn new(writer: W, options: &'a EncodeOptions) -> Self {
+        Encoder {
+            writer,
+            options,
+            atom_refs: HashMap::new(),
+        }
     }
     pub fn encode(mut self, term: &Term) -> EncodeResult {
+        if self.options.minor_version > 2 {
+            return Err(EncodeError::InvalidMinorVersion(self.options.minor_version));
+        }
+        match self.options.compressed {
+            Some(level) if level > 9 => return Err(EncodeError::InvalidCompressionLevel(level)),
+            _ => {}
+        }
+        self.writer.write_u8(VERSION)?;
+        match (term, self.options.compressed) {
+            // Local terms are opaque, so they are never compressed
+            (Term::Local(ref x), _) => self.encode_local(x),
+            (_, Some(level)) => self.encode_compressed_term(term, level),
+            (_, None) => self.encode_term(term),
+        }
+    }
+    pub fn encode_distribution(
+        mut self,
+        message: &DistributionMessage,
+        cache: &mut AtomCache,
+    ) -> EncodeResult {
+        let mut atoms = Vec::new();
+        auxiliary::collect_atoms(&message.control, &mut atoms);
+        if let Some(ref payload) = message.payload {
+            auxiliary::collect_atoms(payload, &mut atoms);
+        }
+
+        // (cache index, is new entry, atom)
+        let mut refs = Vec::new();
+        let mut used_indices = HashSet::new();
+        for atom in atoms {
+            if refs.len() == std::u8::MAX as usize {
+                break;
+            }
+            if atom.name.len() > 0xFFFF || self.atom_refs.contains_key(&atom.name) {
+                continue;
+            }
+            // Atoms colliding with one already referenced by this message are encoded inline
+            let index = AtomCache::index_of(atom);
+            if !used_indices.insert(index) {
+                continue;
+            }
+            let is_new = cache.get(index) != Some(atom);
+            if is_new {
+                cache.insert(index, atom.clone());
+            }
+            self.atom_refs.insert(atom.name.clone(), refs.len() as u8);
+            refs.push((index, is_new, atom));
+        }
+
         self.writer.write_u8(VERSION)?;
-        self.encode_term(term)
+        self.writer.write_u8(DISTRIBUTION_HEADER)?;
+        self.writer.write_u8(refs.len() as u8)?;
+        if !refs.is_empty() {
+            let long_atoms = refs
+                .iter()
+                .any(|&(_, is_new, atom)| is_new && atom.name.len() > 0xFF);
+            let mut flags = vec![0; refs.len() / 2 + 1];
+            for (i, &(index, is_new, _)) in refs.iter().enumerate() {
+                let flag = if is_new { 0x08 } else { 0 } | (index >> 8) as u8;
+                flags[i / 2] |= flag << ((i % 2) * 4);
+            }
+            if long_atoms {
+                flags[refs.len() / 2] |= 0x01 << ((refs.len() % 2) * 4);
+            }
+            self.writer.write_all(&flags)?;
+            for &(index, is_new, atom) in &refs {
+                self.writer.write_u8(index as u8)?;
+                if is_new {
+                    if long_atoms {
+                        self.writer.write_u16::<BigEndian>(atom.name.len() as u16)?;
+                    } else {
+                        self.writer.write_u8(atom.name.len() as u8)?;
+                    }
+                    self.writer.write_all(atom.name.as_bytes())?;
+                }
+            }
+        }
+        // Terms following the header are encoded without the version number
+        self.encode_term(&message.control)?;
+        if let Some(ref payload) = message.payload {
+            self.encode_term(payload)?;
+        }
+        Ok(())
     }
     fn encode_term(&mut self, term: &Term) -> EncodeResult {
         match *term {
@@ -439,6 +914,7 @@ impl<W: std::io::Write> Encoder<W> {
             Term::ImproperList(ref x) => self.encode_improper_list(x),
             Term::Tuple(ref x) => self.encode_tuple(x),
             Term::Map(ref x) => self.encode_map(x),
+            Term::Local(_) => Err(EncodeError::NestedLocal),
         }
     }
     fn encode_nil(&mut self) -> EncodeResult {
@@ -523,18 +999,62 @@ impl<W: std::io::Write> Encoder<W> {
         }
         Ok(())
     }
+    fn encode_compressed_term(&mut self, term: &Term, level: u8) -> EncodeResult {
+        let mut buf = Vec::new();
+        Encoder::new(&mut buf, self.options).encode_term(term)?;
+
+        let options = if level == 0 {
+            zlib::EncodeOptions::new().no_compression()
+        } else {
+            let window_size = 1 << (level + 6);
+            zlib::EncodeOptions::with_lz77(lz77::DefaultLz77Encoder::with_window_size(window_size))
+        };
+        self.writer.write_u8(COMPRESSED_TERM)?;
+        self.writer.write_u32::<BigEndian>(buf.len() as u32)?;
+        let mut encoder = zlib::Encoder::with_options(&mut self.writer, options)?;
+        encoder.write_all(&buf)?;
+        encoder.finish().into_result()?;
+        Ok(())
+    }
     fn encode_float(&mut self, x: &Float) -> EncodeResult {
-        self.writer.write_u8(NEW_FLOAT_EXT)?;
-        self.writer.write_f64::<BigEndian>(x.value)?;
+        if self.options.minor_version == 0 {
+            if !x.value.is_finite() {
+                return Err(EncodeError::NonFiniteFloat(x.clone()));
+            }
+            // Formatted like `printf("%.20e")`, and padded with zeros
+            let formatted = format!("{:.20e}", x.value);
+            let (mantissa, exponent) = formatted.split_at(formatted.find('e').unwrap());
+            let exponent = exponent[1..].parse::<i32>().unwrap();
+            let sign = if exponent < 0 { '-' } else { '+' };
+            let mut buf = format!("{}e{}{:02}", mantissa, sign, exponent.abs()).into_bytes();
+            buf.resize(31, 0);
+            self.writer.write_u8(FLOAT_EXT)?;
+            self.writer.write_all(&buf)?;
+        } else {
+            self.writer.write_u8(NEW_FLOAT_EXT)?;
+            self.writer.write_f64::<BigEndian>(x.value)?;
+        }
         Ok(())
     }
     fn encode_atom(&mut self, x: &Atom) -> EncodeResult {
+        if let Some(&index) = self.atom_refs.get(&x.name) {
+            self.writer.write_u8(ATOM_CACHE_REF)?;
+            self.writer.write_u8(index)?;
+            return Ok(());
+        }
         if x.name.len() > 0xFFFF {
             return Err(EncodeError::TooLongAtomName(x.clone()));
         }
 
+        if self.options.minor_version >= 2 && x.name.len() <= std::u8::MAX as usize {
+            self.writer.write_u8(SMALL_ATOM_UTF8_EXT)?;
+            self.writer.write_u8(x.name.len() as u8)?;
+            self.writer.write_all(x.name.as_bytes())?;
+            return Ok(());
+        }
+
         let is_ascii = x.name.as_bytes().iter().all(|&c| c < 0x80);
-        if is_ascii {
+        if is_ascii && self.options.minor_version < 2 {
             self.writer.write_u8(ATOM_EXT)?;
         } else {
             self.writer.write_u8(ATOM_UTF8_EXT)?;
@@ -568,34 +1088,70 @@ impl<W: std::io::Write> Encoder<W> {
         self.writer.write_all(&bytes)?;
         Ok(())
     }
+    // Identifiers use the 8-bit `creation` tags when the value fits, and the 32-bit ones otherwise
     fn encode_pid(&mut self, x: &Pid) -> EncodeResult {
-        self.writer.write_u8(PID_EXT)?;
+        let is_small_creation = x.creation <= std::u8::MAX as u32;
+        if is_small_creation {
+            self.writer.write_u8(PID_EXT)?;
+        } else {
+            self.writer.write_u8(NEW_PID_EXT)?;
+        }
         self.encode_atom(&x.node)?;
         self.writer.write_u32::<BigEndian>(x.id)?;
         self.writer.write_u32::<BigEndian>(x.serial)?;
-        self.writer.write_u8(x.creation)?;
+        if is_small_creation {
+            self.writer.write_u8(x.creation as u8)?;
+        } else {
+            self.writer.write_u32::<BigEndian>(x.creation)?;
+        }
         Ok(())
     }
     fn encode_port(&mut self, x: &Port) -> EncodeResult {
-        self.writer.write_u8(PORT_EXT)?;
-        self.encode_atom(&x.node)?;
-        self.writer.write_u32::<BigEndian>(x.id)?;
-        self.writer.write_u8(x.creation)?;
+        if x.id > std::u32::MAX as u64 {
+            self.writer.write_u8(V4_PORT_EXT)?;
+            self.encode_atom(&x.node)?;
+            self.writer.write_u64::<BigEndian>(x.id)?;
+            self.writer.write_u32::<BigEndian>(x.creation)?;
+        } else if x.creation > std::u8::MAX as u32 {
+            self.writer.write_u8(NEW_PORT_EXT)?;
+            self.encode_atom(&x.node)?;
+            self.writer.write_u32::<BigEndian>(x.id as u32)?;
+            self.writer.write_u32::<BigEndian>(x.creation)?;
+        } else {
+            self.writer.write_u8(PORT_EXT)?;
+            self.encode_atom(&x.node)?;
+            self.writer.write_u32::<BigEndian>(x.id as u32)?;
+            self.writer.write_u8(x.creation as u8)?;
+        }
         Ok(())
     }
     fn encode_reference(&mut self, x: &Reference) -> EncodeResult {
-        self.writer.write_u8(NEW_REFERENCE_EXT)?;
+        let is_small_creation = x.creation <= std::u8::MAX as u32;
+        if is_small_creation {
+            self.writer.write_u8(NEW_REFERENCE_EXT)?;
+        } else {
+            self.writer.write_u8(NEWER_REFERENCE_EXT)?;
+        }
         if x.id.len() > std::u16::MAX as usize {
             return Err(EncodeError::TooLargeReferenceId(x.clone()));
         }
         self.writer.write_u16::<BigEndian>(x.id.len() as u16)?;
         self.encode_atom(&x.node)?;
-        self.writer.write_u8(x.creation)?;
+        if is_small_creation {
+            self.writer.write_u8(x.creation as u8)?;
+        } else {
+            self.writer.write_u32::<BigEndian>(x.creation)?;
+        }
         for n in &x.id {
             self.writer.write_u32::<BigEndian>(*n)?;
         }
         Ok(())
     }
+    fn encode_local(&mut self, x: &Local) -> EncodeResult {
+        self.writer.write_u8(LOCAL_EXT)?;
+        self.writer.write_all(&x.bytes)?;
+        Ok(())
+    }
     fn encode_external_fun(&mut self, x: &ExternalFun) -> EncodeResult {
         self.writer.write_u8(EXPORT_EXT)?;
         self.encode_atom(&x.module)?;
@@ -636,7 +1192,8 @@ impl<W: std::io::Write> Encoder<W> {
 
                 let mut buf = Vec::new();
                 {
-                    let mut tmp = Encoder::new(&mut buf);
+                    let mut tmp = Encoder::new(&mut buf, self.options);
+                    tmp.atom_refs = self.atom_refs.clone();
                     tmp.writer.write_u8(arity)?;
                     tmp.writer.write_all(uniq)?;
                     tmp.writer.write_u32::<BigEndian>(index)?;
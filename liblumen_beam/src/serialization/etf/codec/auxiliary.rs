@@ -1,3 +1,4 @@
+use std::io::Read;
 use std::ops::Range;
 
 use num::bigint::Sign;
@@ -5,6 +6,63 @@ use num::bigint::Sign;
 use self::convert::TryInto;
 use super::*;
 
+/// The error returned by `LimitedReader` when its limit is reached
+#[derive(Debug)]
+pub struct ReadLimitExceeded {
+    pub limit: usize,
+}
+impl std::fmt::Display for ReadLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "read limit of {} bytes exceeded", self.limit)
+    }
+}
+impl std::error::Error for ReadLimitExceeded {}
+
+/// A reader which counts the bytes read, and fails with `ReadLimitExceeded`
+/// when more than `limit` bytes are available.
+pub struct LimitedReader<R> {
+    inner: R,
+    count: usize,
+    limit: Option<usize>,
+}
+impl<R> LimitedReader<R> {
+    pub fn new(inner: R, limit: Option<usize>) -> Self {
+        LimitedReader {
+            inner,
+            count: 0,
+            limit,
+        }
+    }
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    pub fn remaining(&self) -> usize {
+        self.limit
+            .map_or(std::usize::MAX, |limit| limit.saturating_sub(self.count))
+    }
+}
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = self.remaining();
+        if remaining == 0 && !buf.is_empty() {
+            // Reaching the limit at the end of the input is fine
+            let mut probe = [0];
+            if self.inner.read(&mut probe)? == 0 {
+                return Ok(0);
+            }
+            let limit = self.limit.unwrap_or(self.count);
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                ReadLimitExceeded { limit },
+            ));
+        }
+        let len = std::cmp::min(buf.len(), remaining);
+        let n = self.inner.read(&mut buf[..len])?;
+        self.count += n;
+        Ok(n)
+    }
+}
+
 pub fn term_into_atom(t: Term) -> Result<Atom, DecodeError> {
     t.try_into().map_err(|t| DecodeError::UnexpectedType {
         value: t,
@@ -62,3 +120,69 @@ pub fn sign_to_byte(sign: Sign) -> u8 {
         0
     }
 }
+/// Collects every atom contained in `term` (including node names and fun modules),
+/// in encoding order.
+pub fn collect_atoms<'a>(term: &'a Term, atoms: &mut Vec<&'a Atom>) {
+    match *term {
+        Term::Atom(ref x) => atoms.push(x),
+        Term::Pid(ref x) => atoms.push(&x.node),
+        Term::Port(ref x) => atoms.push(&x.node),
+        Term::Reference(ref x) => atoms.push(&x.node),
+        Term::ExternalFun(ref x) => {
+            atoms.push(&x.module);
+            atoms.push(&x.function);
+        }
+        Term::InternalFun(InternalFun::Old {
+            ref module,
+            ref pid,
+            ref free_vars,
+            ..
+        }) => {
+            atoms.push(&pid.node);
+            atoms.push(module);
+            for v in free_vars {
+                collect_atoms(v, atoms);
+            }
+        }
+        Term::InternalFun(InternalFun::New {
+            ref module,
+            ref pid,
+            ref free_vars,
+            ..
+        }) => {
+            atoms.push(module);
+            atoms.push(&pid.node);
+            for v in free_vars {
+                collect_atoms(v, atoms);
+            }
+        }
+        Term::List(ref x) => {
+            for e in &x.elements {
+                collect_atoms(e, atoms);
+            }
+        }
+        Term::ImproperList(ref x) => {
+            for e in &x.elements {
+                collect_atoms(e, atoms);
+            }
+            collect_atoms(&x.last, atoms);
+        }
+        Term::Tuple(ref x) => {
+            for e in &x.elements {
+                collect_atoms(e, atoms);
+            }
+        }
+        Term::Map(ref x) => {
+            for &(ref k, ref v) in &x.entries {
+                collect_atoms(k, atoms);
+                collect_atoms(v, atoms);
+            }
+        }
+        Term::FixInteger(_)
+        | Term::BigInteger(_)
+        | Term::Float(_)
+        | Term::Binary(_)
+        | Term::BitBinary(_)
+        | Term::Local(_) => {}
+    }
+}
@@ -39,6 +39,7 @@ impl_term_try_as_ref!(List);
 impl_term_try_as_ref!(ImproperList);
 impl_term_try_as_ref!(Tuple);
 impl_term_try_as_ref!(Map);
+impl_term_try_as_ref!(Local);
 
 pub trait TryInto<T> {
     fn try_into(self) -> Result<T, Self>
@@ -85,6 +86,7 @@ impl_term_try_into!(List);
 impl_term_try_into!(ImproperList);
 impl_term_try_into!(Tuple);
 impl_term_try_into!(Map);
+impl_term_try_into!(Local);
 
 pub trait AsOption {
     fn as_option(&self) -> Option<&Self>;
@@ -0,0 +1,113 @@
+//! Messages exchanged between connected Erlang nodes.
+//!
+//! When the `DFLAG_DIST_HDR_ATOM_CACHE` distribution flag is negotiated, every message
+//! starts with a distribution header which updates an atom cache shared by both ends of
+//! the connection. Atoms in the control message and payload then refer to the header
+//! (`ATOM_CACHE_REF`) instead of being encoded inline.
+//!
+//! # Examples
+//!
+//!     use std::io::Cursor;
+//!     use liblumen_beam::serialization::etf::{Atom, AtomCache, DistributionMessage, Term};
+//!
+//!     let message = DistributionMessage {
+//!         control: Term::from(Atom::from("foo")),
+//!         payload: None,
+//!     };
+//!
+//!     let mut sender = AtomCache::new();
+//!     let mut buf = Vec::new();
+//!     message.encode(&mut buf, &mut sender).unwrap();
+//!
+//!     let mut receiver = AtomCache::new();
+//!     let decoded = DistributionMessage::decode(Cursor::new(&buf), &mut receiver).unwrap();
+//!     assert_eq!(message, decoded);
+//!
+//! # Reference
+//!
+//! - [Distribution Header](http://erlang.org/doc/apps/erts/erl_ext_dist.html#distribution-header)
+//!
+use super::codec;
+use super::*;
+
+/// The number of entries in an atom cache.
+pub const ATOM_CACHE_SIZE: usize = 2048;
+
+/// The atom cache of one direction of a distribution connection.
+///
+/// The same cache must be used for every message sent (or received) on the connection.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AtomCache {
+    entries: Vec<Option<Atom>>,
+}
+impl AtomCache {
+    pub fn new() -> Self {
+        AtomCache {
+            entries: vec![None; ATOM_CACHE_SIZE],
+        }
+    }
+
+    /// Returns the atom stored at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&Atom> {
+        self.entries.get(index).and_then(|e| e.as_ref())
+    }
+
+    /// Stores `atom` at `index`, returning the atom previously stored there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `ATOM_CACHE_SIZE`.
+    pub fn insert(&mut self, index: usize, atom: Atom) -> Option<Atom> {
+        self.entries[index].replace(atom)
+    }
+
+    /// Removes every entry from the cache.
+    pub fn clear(&mut self) {
+        for entry in &mut self.entries {
+            *entry = None;
+        }
+    }
+
+    /// Returns the index in which the encoder stores `atom`.
+    pub(crate) fn index_of(atom: &Atom) -> usize {
+        // FNV-1a
+        let hash = atom.name.bytes().fold(0x811c_9dc5_u32, |hash, b| {
+            (hash ^ u32::from(b)).wrapping_mul(0x0100_0193)
+        });
+        hash as usize % ATOM_CACHE_SIZE
+    }
+}
+impl Default for AtomCache {
+    fn default() -> Self {
+        AtomCache::new()
+    }
+}
+
+/// A message sent between connected nodes: a control message and an optional payload.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DistributionMessage {
+    pub control: Term,
+    pub payload: Option<Term>,
+}
+impl DistributionMessage {
+    /// Decodes a message which starts with a distribution header, updating `cache`
+    /// with the new entries of the header.
+    pub fn decode<R: std::io::Read>(reader: R, cache: &mut AtomCache) -> Result<Self, DecodeError> {
+        DistributionMessage::decode_with_options(reader, cache, &DecodeOptions::default())
+    }
+
+    /// Decodes a message like `decode`, applying the limits of `options`.
+    pub fn decode_with_options<R: std::io::Read>(
+        reader: R,
+        cache: &mut AtomCache,
+        options: &DecodeOptions,
+    ) -> Result<Self, DecodeError> {
+        codec::Decoder::new(reader, options).decode_distribution(cache)
+    }
+
+    /// Encodes the message with a distribution header, referring to atoms through `cache`
+    /// and updating it with any new entries.
+    pub fn encode<W: std::io::Write>(&self, writer: W, cache: &mut AtomCache) -> EncodeResult {
+        codec::Encoder::new(writer, &EncodeOptions::default()).encode_distribution(self, cache)
+    }
+}
@@ -1,9 +1,49 @@
+//! Matching of terms against patterns.
+//!
+//! Besides the combinators in this module, `#[derive(EtfPattern)]` generates
+//! [`FromTerm`](FromTerm) and [`Pattern`](Pattern) implementations for a struct or enum,
+//! which can then be matched with [`typed`](typed), or used as a pattern itself that only
+//! matches terms converting to an equal value. As with every pattern, the type must also
+//! implement `Debug` and `Clone`, and the derived `Pattern` also needs `PartialEq`.
+//!
+//! # Examples
+//!
+//!     use liblumen_beam::serialization::etf::pattern::{typed, EtfPattern, Pattern};
+//!     use liblumen_beam::serialization::etf::text;
+//!
+//!     #[derive(Debug, Clone, PartialEq, EtfPattern)]
+//!     #[etf(record = "point")]
+//!     struct Point {
+//!         x: i32,
+//!         y: i32,
+//!     }
+//!
+//!     let term = text::parse_term("{point, 1, 2}").unwrap();
+//!     assert_eq!(Point { x: 1, y: 2 }, typed::<Point>().try_match(&term).unwrap());
+//!
+//!     let term = text::parse_term("{point, 1, foo}").unwrap();
+//!     assert_eq!("y", typed::<Point>().try_match(&term).unwrap_err().field_path());
+//!
+//!     #[derive(Debug, Clone, PartialEq, EtfPattern)]
+//!     #[etf(tag = "ok")]
+//!     struct Success;
+//!
+//!     let term = text::parse_term("{ok, {point, 1, 2}}").unwrap();
+//!     let pattern = (Success, typed::<Point>());
+//!     assert_eq!((Success, Point { x: 1, y: 2 }), pattern.try_match(&term).unwrap());
+//!
+#[cfg(test)]
+mod test;
+
 use std::fmt::Debug;
+use std::marker::PhantomData;
 
 use num::bigint::ToBigInt;
 use num::bigint::ToBigUint;
 use num::traits::ToPrimitive;
 
+pub use liblumen_beam_macros::EtfPattern;
+
 use self::convert::AsOption;
 use self::convert::TryAsRef;
 use super::*;
@@ -22,6 +62,7 @@ pub trait Pattern<'a>: Debug + Clone {
             input,
             pattern: Box::new(self.clone()),
             cause: None,
+            field: None,
         }
     }
 }
@@ -31,12 +72,30 @@ pub struct Unmatch<'a> {
     pub input: &'a Term,
     pub pattern: Box<dyn Debug>,
     pub cause: Option<Box<Unmatch<'a>>>,
+    /// The field or variant of the pattern in which `cause` occurred
+    pub field: Option<String>,
 }
 impl<'a> Unmatch<'a> {
     pub fn cause(mut self, cause: Unmatch<'a>) -> Self {
         self.cause = Some(Box::new(cause));
         self
     }
+    pub fn in_field<S: Into<String>>(mut self, field: S) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+    /// Returns the fields leading to the innermost failure, separated by dots, e.g. `Line.1.x`
+    pub fn field_path(&self) -> String {
+        let mut fields = Vec::new();
+        let mut curr = Some(self);
+        while let Some(unmatch) = curr {
+            if let Some(ref field) = unmatch.field {
+                fields.push(field.as_str());
+            }
+            curr = unmatch.cause.as_ref().map(|c| &**c);
+        }
+        fields.join(".")
+    }
     pub fn depth(&self) -> usize {
         let mut depth = 0;
         let mut curr = &self.cause;
@@ -866,3 +925,170 @@ impl<'a> Pattern<'a> for F64 {
         input.to_f64().ok_or_else(|| self.unmatched(input))
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct AtomName;
+impl<'a> Pattern<'a> for AtomName {
+    type Output = String;
+    fn try_match(&self, input: &'a Term) -> Result<'a, Self::Output> {
+        let a: &Atom = input.try_as_ref().ok_or_else(|| self.unmatched(input))?;
+        Ok(a.name.clone())
+    }
+}
+
+/// A type which can be matched from a term.
+///
+/// This is usually implemented with `#[derive(EtfPattern)]`, which supports the following
+/// attributes:
+///
+/// - `#[etf(record = "name")]` on a struct matches the record `{name, Field1, ...}`.
+///   Otherwise a struct with named fields matches a map with atom keys, a tuple struct
+///   matches a tuple, and a newtype struct matches its field.
+/// - `#[etf(tag = "name")]` on a unit struct or an enum variant sets the atom tag, which
+///   defaults to the snake case name. Unit variants match the tag and other variants
+///   match `{tag, Field1, ...}`.
+/// - `#[etf(atom)]` on a `String` field matches the name of an atom.
+/// - `#[etf(pattern = "expr")]` on a field matches the pattern `expr`, converting its output
+///   with `From`.
+pub trait FromTerm<'a>: Sized {
+    fn try_from_term(term: &'a Term) -> Result<'a, Self>;
+}
+
+/// Matches a type which implements [`FromTerm`](FromTerm).
+pub struct Typed<T>(PhantomData<T>);
+impl<T> Typed<T> {
+    pub fn new() -> Self {
+        Typed(PhantomData)
+    }
+}
+impl<T> Default for Typed<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> Clone for Typed<T> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+impl<T> Debug for Typed<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Typed<{}>", std::any::type_name::<T>())
+    }
+}
+pub fn typed<T>() -> Typed<T> {
+    Typed::new()
+}
+impl<'a, T> Pattern<'a> for Typed<T>
+where
+    T: FromTerm<'a> + 'static,
+{
+    type Output = T;
+    fn try_match(&self, input: &'a Term) -> Result<'a, Self::Output> {
+        T::try_from_term(input)
+    }
+}
+
+macro_rules! impl_from_term {
+    ($t:ty, $pattern:expr) => {
+        impl<'a> FromTerm<'a> for $t {
+            fn try_from_term(term: &'a Term) -> Result<'a, Self> {
+                $pattern.try_match(term)
+            }
+        }
+    };
+}
+impl_from_term!(u8, U8);
+impl_from_term!(i8, I8);
+impl_from_term!(u16, U16);
+impl_from_term!(i16, I16);
+impl_from_term!(u32, U32);
+impl_from_term!(i32, I32);
+impl_from_term!(u64, U64);
+impl_from_term!(i64, I64);
+impl_from_term!(f32, F32);
+impl_from_term!(f64, F64);
+impl_from_term!(num::BigInt, Int);
+impl_from_term!(num::BigUint, Uint);
+impl_from_term!(char, Unicode);
+
+impl<'a> FromTerm<'a> for bool {
+    fn try_from_term(term: &'a Term) -> Result<'a, Self> {
+        match Or(("true", "false")).try_match(term)? {
+            Union2::A(_) => Ok(true),
+            Union2::B(_) => Ok(false),
+        }
+    }
+}
+
+/// Matches a UTF-8 binary or a list of unicode code points
+impl<'a> FromTerm<'a> for String {
+    fn try_from_term(term: &'a Term) -> Result<'a, Self> {
+        if let Term::Binary(ref b) = *term {
+            return String::from_utf8(b.bytes.clone())
+                .map_err(|_| typed::<String>().unmatched(term));
+        }
+        Str(Unicode).try_match(term)
+    }
+}
+
+impl<'a> FromTerm<'a> for Term {
+    fn try_from_term(term: &'a Term) -> Result<'a, Self> {
+        Ok(term.clone())
+    }
+}
+
+impl<'a> FromTerm<'a> for &'a Term {
+    fn try_from_term(term: &'a Term) -> Result<'a, Self> {
+        Ok(term)
+    }
+}
+
+impl<'a, T> FromTerm<'a> for Box<T>
+where
+    T: FromTerm<'a>,
+{
+    fn try_from_term(term: &'a Term) -> Result<'a, Self> {
+        T::try_from_term(term).map(Box::new)
+    }
+}
+
+impl<'a, T> FromTerm<'a> for Vec<T>
+where
+    T: FromTerm<'a> + 'static,
+{
+    fn try_from_term(term: &'a Term) -> Result<'a, Self> {
+        VarList(typed::<T>()).try_match(term)
+    }
+}
+
+/// Matches `undefined` as `None`
+impl<'a, T> FromTerm<'a> for Option<T>
+where
+    T: FromTerm<'a> + 'static,
+{
+    fn try_from_term(term: &'a Term) -> Result<'a, Self> {
+        match Or(("undefined", typed::<T>())).try_match(term)? {
+            Union2::A(_) => Ok(None),
+            Union2::B(value) => Ok(Some(value)),
+        }
+    }
+}
+
+macro_rules! impl_tuple_from_term {
+    ($($t:ident),*) => {
+        impl<'a, $($t),*> FromTerm<'a> for ($($t,)*)
+        where
+            $($t: FromTerm<'a> + 'static),*
+        {
+            fn try_from_term(term: &'a Term) -> Result<'a, Self> {
+                ($(typed::<$t>(),)*).try_match(term)
+            }
+        }
+    };
+}
+impl_tuple_from_term!(A, B);
+impl_tuple_from_term!(A, B, C);
+impl_tuple_from_term!(A, B, C, D);
+impl_tuple_from_term!(A, B, C, D, E);
+impl_tuple_from_term!(A, B, C, D, E, F);
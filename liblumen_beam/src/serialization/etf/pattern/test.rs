@@ -0,0 +1,177 @@
+use crate::serialization::etf::pattern::*;
+use crate::serialization::etf::text;
+
+#[derive(Debug, Clone, PartialEq, EtfPattern)]
+#[etf(record = "point")]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, EtfPattern)]
+enum Shape {
+    Empty,
+    #[etf(tag = "circle")]
+    Round {
+        center: Point,
+        radius: u32,
+    },
+    Line(Point, Point),
+    PolyLine(Vec<Point>),
+}
+
+#[derive(Debug, Clone, PartialEq, EtfPattern)]
+struct Options {
+    #[etf(atom)]
+    name: String,
+    label: Option<String>,
+    #[etf(pattern = "Or((\"left\", \"right\"))")]
+    side: Side,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Side {
+    Left,
+    Right,
+}
+impl From<Union2<&'static str, &'static str>> for Side {
+    fn from(side: Union2<&'static str, &'static str>) -> Self {
+        match side {
+            Union2::A(_) => Side::Left,
+            Union2::B(_) => Side::Right,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, EtfPattern)]
+struct Pair(u8, bool);
+
+#[derive(Debug, Clone, PartialEq, EtfPattern)]
+struct Wrapper(Pair);
+
+#[derive(Debug, Clone, PartialEq, EtfPattern)]
+struct HttpServer;
+
+fn parse<T>(s: &str) -> std::result::Result<T, String>
+where
+    T: for<'a> FromTerm<'a> + 'static,
+{
+    let term = text::parse_term(s).unwrap();
+    typed::<T>().try_match(&term).map_err(|e| e.field_path())
+}
+
+#[test]
+fn derive_struct_test() {
+    assert_eq!(Ok(Point { x: 1, y: -2 }), parse("{point, 1, -2}"));
+    assert_eq!(Err("y".to_string()), parse::<Point>("{point, 1, foo}"));
+    assert_eq!(Err("".to_string()), parse::<Point>("{pt, 1, 2}"));
+    assert_eq!(Err("".to_string()), parse::<Point>("{point, 1}"));
+
+    assert_eq!(Ok(Pair(7, true)), parse("{7, true}"));
+    assert_eq!(Err("0".to_string()), parse::<Pair>("{256, true}"));
+    assert_eq!(Ok(Wrapper(Pair(1, false))), parse("{1, false}"));
+    assert_eq!(Err("0.1".to_string()), parse::<Wrapper>("{1, nope}"));
+
+    assert_eq!(Ok(HttpServer), parse("http_server"));
+    assert_eq!(Err("".to_string()), parse::<HttpServer>("httpserver"));
+}
+
+#[test]
+fn derive_pattern_test() {
+    let term = text::parse_term("{http_server, {point, 1, 2}}").unwrap();
+    assert_eq!(
+        (HttpServer, Point { x: 1, y: 2 }),
+        (HttpServer, typed::<Point>()).try_match(&term).unwrap()
+    );
+
+    let term = text::parse_term("[{7, true}, {8, false}]").unwrap();
+    assert_eq!(
+        vec![Pair(7, true), Pair(8, false)],
+        VarList(typed::<Pair>()).try_match(&term).unwrap()
+    );
+
+    // A value only matches terms equal to it
+    let term = text::parse_term("{7, true}").unwrap();
+    assert_eq!(Pair(7, true), Pair(7, true).try_match(&term).unwrap());
+    assert!(Pair(8, true).try_match(&term).is_err());
+    assert!(Pair(7, false).try_match(&term).is_err());
+    let term = text::parse_term("{point, 1, 2}").unwrap();
+    assert!(Point { x: 2, y: 1 }.try_match(&term).is_err());
+
+    let term = text::parse_term("{httpserver, {point, 1, 2}}").unwrap();
+    assert!((HttpServer, typed::<Point>()).try_match(&term).is_err());
+}
+
+#[test]
+fn derive_map_test() {
+    assert_eq!(
+        Ok(Options {
+            name: "foo".to_string(),
+            label: Some("Foo".to_string()),
+            side: Side::Right,
+        }),
+        parse(r#"#{name => foo, label => <<"Foo">>, side => right, extra => 1}"#)
+    );
+    assert_eq!(
+        Ok(Options {
+            name: "foo".to_string(),
+            label: None,
+            side: Side::Left,
+        }),
+        parse(r#"#{name => foo, label => undefined, side => left}"#)
+    );
+    assert_eq!(
+        Err("name".to_string()),
+        parse::<Options>(r#"#{name => "foo", label => undefined, side => left}"#)
+    );
+    assert_eq!(
+        Err("side".to_string()),
+        parse::<Options>(r#"#{name => foo, label => undefined}"#)
+    );
+    assert_eq!(
+        Err("side".to_string()),
+        parse::<Options>(r#"#{name => foo, label => undefined, side => up}"#)
+    );
+}
+
+#[test]
+fn derive_enum_test() {
+    assert_eq!(Ok(Shape::Empty), parse("empty"));
+    assert_eq!(
+        Ok(Shape::Round {
+            center: Point { x: 0, y: 0 },
+            radius: 3
+        }),
+        parse("{circle, {point, 0, 0}, 3}")
+    );
+    assert_eq!(
+        Ok(Shape::Line(Point { x: 0, y: 0 }, Point { x: 1, y: 1 })),
+        parse("{line, {point, 0, 0}, {point, 1, 1}}")
+    );
+    assert_eq!(
+        Ok(Shape::PolyLine(vec![Point { x: 0, y: 0 }])),
+        parse("{poly_line, [{point, 0, 0}]}")
+    );
+
+    // The failing field of the variant whose tag matched is reported
+    assert_eq!(
+        Err("Line.1.x".to_string()),
+        parse::<Shape>("{line, {point, 0, 0}, {point, a, 1}}")
+    );
+    assert_eq!(
+        Err("Round.radius".to_string()),
+        parse::<Shape>("{circle, {point, 0, 0}, -1}")
+    );
+    assert_eq!(
+        Err("PolyLine.0".to_string()),
+        parse::<Shape>("{poly_line, [{point, 0, 0}, {point, 0}]}")
+    );
+    assert_eq!(Err("".to_string()), parse::<Shape>("{square, 1}"));
+
+    let term = text::parse_term("{line, {point, 0, 0}, {point, a, 1}}").unwrap();
+    let e = typed::<Shape>().try_match(&term).unwrap_err();
+    assert_eq!(2, e.depth());
+    let point = e.cause.unwrap();
+    assert_eq!("{point,a,1}", text::to_string(point.input));
+    assert_eq!("a", text::to_string(point.cause.unwrap().input));
+}
@@ -0,0 +1,158 @@
+//! Serde support for the External Term Format.
+//!
+//! Rust values are mapped to terms as follows:
+//!
+//! | Rust                          | Erlang                                   |
+//! |-------------------------------|------------------------------------------|
+//! | `bool`                        | `true` or `false`                        |
+//! | integers                      | integers (big integers if needed)        |
+//! | `f32`, `f64`                  | floats                                   |
+//! | `char`                        | the code point as an integer             |
+//! | `String`, `&str`              | UTF-8 binaries                           |
+//! | `Vec<u8>`, bytes              | binaries                                 |
+//! | `None`                        | `undefined`                              |
+//! | `Some(x)`                     | `x`                                      |
+//! | `()`                          | `{}`                                     |
+//! | sequences                     | lists                                    |
+//! | tuples, tuple structs         | tuples                                   |
+//! | maps                          | maps                                     |
+//! | structs                       | maps with atom keys                      |
+//! | unit variants                 | atoms                                    |
+//! | other variants                | tuples tagged with the variant name      |
+//!
+//! Structs and struct variants whose (serde) name starts with `#` are encoded as records
+//! instead: a tuple tagged with the rest of the name, followed by the field values in
+//! declaration order. Tuple structs named like this are tagged the same way.
+//!
+//! A sequence is encoded as a binary if all of its elements serialize as `u8`, so an empty
+//! `Vec<u8>` is encoded as `[]`.
+//!
+//! When deserializing, lists of integers are accepted as strings and bytes,
+//! and binaries are accepted as sequences of integers.
+//!
+//! # Examples
+//!
+//!     use serde::{Deserialize, Serialize};
+//!     use liblumen_beam::serialization::etf::{self, Atom, FixInteger, Term, Tuple};
+//!
+//!     #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//!     #[serde(rename = "#point")]
+//!     struct Point {
+//!         x: i32,
+//!         y: i32,
+//!     }
+//!
+//!     let point = Point { x: 1, y: 2 };
+//!     let term = etf::serde::to_term(&point).unwrap();
+//!     assert_eq!(
+//!         term,
+//!         Term::from(Tuple::from(vec![
+//!             Term::from(Atom::from("point")),
+//!             Term::from(FixInteger::from(1)),
+//!             Term::from(FixInteger::from(2)),
+//!         ]))
+//!     );
+//!
+//!     let bytes = etf::serde::to_vec(&point).unwrap();
+//!     assert_eq!(point, etf::serde::from_slice(&bytes).unwrap());
+//!
+mod de;
+mod ser;
+
+#[cfg(test)]
+mod test;
+
+use ::serde::de::DeserializeOwned;
+use ::serde::Serialize;
+
+use super::*;
+
+pub use self::de::Deserializer;
+pub use self::ser::Serializer;
+
+/// The prefix of struct and variant names which are encoded as records.
+pub const RECORD_PREFIX: &str = "#";
+
+/// The atom used for `None`.
+pub const UNDEFINED: &str = "undefined";
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors which can occur while converting between Rust values and terms.
+#[derive(Debug)]
+pub enum Error {
+    Message(String),
+    Encode(EncodeError),
+    Decode(DecodeError),
+}
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Error::Message(ref msg) => write!(f, "{}", msg),
+            Error::Encode(ref err) => write!(f, "{}", err),
+            Error::Decode(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+impl std::error::Error for Error {}
+impl ::serde::ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+impl ::serde::de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+impl From<EncodeError> for Error {
+    fn from(err: EncodeError) -> Self {
+        Error::Encode(err)
+    }
+}
+impl From<DecodeError> for Error {
+    fn from(err: DecodeError) -> Self {
+        Error::Decode(err)
+    }
+}
+
+/// Converts `value` to a term.
+pub fn to_term<T: Serialize + ?Sized>(value: &T) -> Result<Term> {
+    value.serialize(Serializer)
+}
+
+/// Converts `term` to a value of type `T`.
+pub fn from_term<T: DeserializeOwned>(term: Term) -> Result<T> {
+    T::deserialize(Deserializer::new(term))
+}
+
+/// Encodes `value` to `writer`.
+pub fn to_writer<W: std::io::Write, T: Serialize + ?Sized>(writer: W, value: &T) -> Result<()> {
+    to_term(value)?.encode(writer)?;
+    Ok(())
+}
+
+/// Encodes `value` to a byte vector.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    to_writer(&mut buf, value)?;
+    Ok(buf)
+}
+
+/// Decodes a value of type `T` from `reader`.
+pub fn from_reader<R: std::io::Read, T: DeserializeOwned>(reader: R) -> Result<T> {
+    from_term(Term::decode(reader)?)
+}
+
+/// Decodes a value of type `T` from `bytes`.
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    from_reader(std::io::Cursor::new(bytes))
+}
+
+fn record_name(name: &str) -> Option<&str> {
+    if name.starts_with(RECORD_PREFIX) {
+        Some(&name[RECORD_PREFIX.len()..])
+    } else {
+        None
+    }
+}
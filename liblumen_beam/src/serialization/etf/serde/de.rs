@@ -0,0 +1,439 @@
+use ::serde::de::{self, DeserializeSeed, IntoDeserializer, Unexpected, Visitor};
+use num::ToPrimitive;
+
+use super::*;
+
+/// A deserializer which converts a term into Rust values.
+pub struct Deserializer {
+    term: Term,
+}
+impl Deserializer {
+    pub fn new(term: Term) -> Self {
+        Deserializer { term }
+    }
+
+    fn invalid_type(&self, expected: &dyn de::Expected) -> Error {
+        de::Error::invalid_type(unexpected(&self.term), expected)
+    }
+}
+
+fn unexpected(term: &Term) -> Unexpected {
+    match *term {
+        Term::Atom(ref x) => Unexpected::Other(match x.name.as_str() {
+            "true" | "false" => "boolean atom",
+            _ => "atom",
+        }),
+        Term::FixInteger(ref x) => Unexpected::Signed(i64::from(x.value)),
+        Term::BigInteger(_) => Unexpected::Other("big integer"),
+        Term::Float(ref x) => Unexpected::Float(x.value),
+        Term::Pid(_) => Unexpected::Other("pid"),
+        Term::Port(_) => Unexpected::Other("port"),
+        Term::Reference(_) => Unexpected::Other("reference"),
+        Term::ExternalFun(_) | Term::InternalFun(_) => Unexpected::Other("fun"),
+        Term::Binary(ref x) => Unexpected::Bytes(&x.bytes),
+        Term::BitBinary(_) => Unexpected::Other("bitstring"),
+        Term::List(_) => Unexpected::Seq,
+        Term::ImproperList(_) => Unexpected::Other("improper list"),
+        Term::Tuple(_) => Unexpected::Other("tuple"),
+        Term::Map(_) => Unexpected::Map,
+        Term::Local(_) => Unexpected::Other("local term"),
+    }
+}
+
+fn is_atom(term: &Term, name: &str) -> bool {
+    match *term {
+        Term::Atom(ref x) => x.name == name,
+        _ => false,
+    }
+}
+
+// Converts a list of integers, such as an Erlang string, into bytes
+fn list_to_bytes(elements: &[Term]) -> Option<Vec<u8>> {
+    elements
+        .iter()
+        .map(|e| match *e {
+            Term::FixInteger(ref x) if x.value >= 0 && x.value <= i32::from(std::u8::MAX) => {
+                Some(x.value as u8)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// Converts a list of code points, such as an Erlang string, into a string
+fn list_to_string(elements: &[Term]) -> Option<String> {
+    elements
+        .iter()
+        .map(|e| match *e {
+            Term::FixInteger(ref x) => std::char::from_u32(x.value as u32),
+            _ => None,
+        })
+        .collect()
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.term {
+            Term::Atom(x) => match x.name.as_str() {
+                "true" => visitor.visit_bool(true),
+                "false" => visitor.visit_bool(false),
+                _ => visitor.visit_string(x.name),
+            },
+            Term::FixInteger(x) => visitor.visit_i32(x.value),
+            Term::BigInteger(x) => {
+                if let Some(v) = x.value.to_i64() {
+                    visitor.visit_i64(v)
+                } else if let Some(v) = x.value.to_u64() {
+                    visitor.visit_u64(v)
+                } else {
+                    Err(de::Error::invalid_value(
+                        Unexpected::Other("big integer"),
+                        &"an integer which fits in 64 bits",
+                    ))
+                }
+            }
+            Term::Float(x) => visitor.visit_f64(x.value),
+            Term::Binary(x) => visitor.visit_byte_buf(x.bytes),
+            Term::List(x) => visit_seq(x.elements, visitor),
+            Term::Tuple(x) => visit_seq(x.elements, visitor),
+            Term::Map(x) => visit_map(x.entries, visitor),
+            ref term => Err(de::Error::invalid_type(unexpected(term), &visitor)),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.term {
+            Term::Atom(ref x) if x.name == "true" => visitor.visit_bool(true),
+            Term::Atom(ref x) if x.name == "false" => visitor.visit_bool(false),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let c = match self.term {
+            Term::FixInteger(ref x) => std::char::from_u32(x.value as u32),
+            Term::Binary(ref x) => std::str::from_utf8(&x.bytes).ok().and_then(|s| {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(c),
+                    _ => None,
+                }
+            }),
+            _ => None,
+        };
+        match c {
+            Some(c) => visitor.visit_char(c),
+            None => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.term {
+            Term::Binary(x) => match String::from_utf8(x.bytes) {
+                Ok(s) => visitor.visit_string(s),
+                Err(e) => Err(de::Error::invalid_value(
+                    Unexpected::Bytes(e.as_bytes()),
+                    &visitor,
+                )),
+            },
+            Term::Atom(x) => visitor.visit_string(x.name),
+            Term::List(ref x) => match list_to_string(&x.elements) {
+                Some(s) => visitor.visit_string(s),
+                None => Err(self.invalid_type(&visitor)),
+            },
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.term {
+            Term::Binary(x) => visitor.visit_byte_buf(x.bytes),
+            Term::List(ref x) => match list_to_bytes(&x.elements) {
+                Some(bytes) => visitor.visit_byte_buf(bytes),
+                None => Err(self.invalid_type(&visitor)),
+            },
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if is_atom(&self.term, UNDEFINED) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.term {
+            Term::Tuple(ref x) if x.elements.is_empty() => visitor.visit_unit(),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        let matches = match record_name(name) {
+            Some(name) => match self.term {
+                Term::Tuple(ref x) => x.elements.len() == 1 && is_atom(&x.elements[0], name),
+                _ => false,
+            },
+            None => is_atom(&self.term, name),
+        };
+        if matches {
+            visitor.visit_unit()
+        } else {
+            Err(self.invalid_type(&visitor))
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.term {
+            Term::List(x) => visit_seq(x.elements, visitor),
+            Term::Tuple(x) => visit_seq(x.elements, visitor),
+            Term::Binary(x) => visit_seq(
+                x.bytes
+                    .into_iter()
+                    .map(|b| Term::from(FixInteger::from(b)))
+                    .collect(),
+                visitor,
+            ),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        match record_name(name) {
+            Some(name) => match self.term {
+                Term::Tuple(x) => visit_record(name, x.elements, visitor),
+                _ => Err(self.invalid_type(&visitor)),
+            },
+            None => self.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.term {
+            Term::Map(x) => visit_map(x.entries, visitor),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match (record_name(name), self.term) {
+            (Some(name), Term::Tuple(x)) => visit_record(name, x.elements, visitor),
+            (None, Term::Map(x)) => visit_map(x.entries, visitor),
+            (_, ref term) => Err(de::Error::invalid_type(unexpected(term), &visitor)),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let (tag, payload) = match self.term {
+            Term::Atom(x) => (x.name, Vec::new()),
+            Term::Tuple(x) => {
+                let mut elements = x.elements.into_iter();
+                match elements.next() {
+                    Some(Term::Atom(tag)) => (tag.name, elements.collect()),
+                    _ => {
+                        return Err(de::Error::invalid_type(
+                            Unexpected::Other("tuple"),
+                            &"a tuple tagged with an atom",
+                        ));
+                    }
+                }
+            }
+            ref term => return Err(de::Error::invalid_type(unexpected(term), &visitor)),
+        };
+        let variant = *variants
+            .iter()
+            .find(|v| **v == tag || record_name(v) == Some(tag.as_str()))
+            .ok_or_else(|| <Error as de::Error>::unknown_variant(&tag, variants))?;
+        visitor.visit_enum(EnumDeserializer { variant, payload })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64
+    }
+}
+
+fn visit_seq<'de, V: Visitor<'de>>(elements: Vec<Term>, visitor: V) -> Result<V::Value> {
+    let len = elements.len();
+    let mut seq = SeqDeserializer {
+        iter: elements.into_iter(),
+    };
+    let value = visitor.visit_seq(&mut seq)?;
+    if seq.iter.len() == 0 {
+        Ok(value)
+    } else {
+        Err(de::Error::invalid_length(len, &"fewer elements"))
+    }
+}
+
+// Visits the fields of a record, checking its tag
+fn visit_record<'de, V: Visitor<'de>>(
+    name: &str,
+    mut elements: Vec<Term>,
+    visitor: V,
+) -> Result<V::Value> {
+    if elements.is_empty() || !is_atom(&elements[0], name) {
+        return Err(de::Error::invalid_value(
+            Unexpected::Other("tuple"),
+            &format!("a '{}' record", name).as_str(),
+        ));
+    }
+    elements.remove(0);
+    visit_seq(elements, visitor)
+}
+
+fn visit_map<'de, V: Visitor<'de>>(entries: Vec<(Term, Term)>, visitor: V) -> Result<V::Value> {
+    let mut map = MapDeserializer {
+        iter: entries.into_iter(),
+        value: None,
+    };
+    visitor.visit_map(&mut map)
+}
+
+struct SeqDeserializer {
+    iter: std::vec::IntoIter<Term>,
+}
+impl<'de> de::SeqAccess<'de> for SeqDeserializer {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.iter.next() {
+            Some(term) => seed.deserialize(Deserializer::new(term)).map(Some),
+            None => Ok(None),
+        }
+    }
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapDeserializer {
+    iter: std::vec::IntoIter<(Term, Term)>,
+    value: Option<Term>,
+}
+impl<'de> de::MapAccess<'de> for MapDeserializer {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(Deserializer::new(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(Deserializer::new(value))
+    }
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct EnumDeserializer {
+    variant: &'static str,
+    payload: Vec<Term>,
+}
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let variant: de::value::StrDeserializer<Error> = self.variant.into_deserializer();
+        seed.deserialize(variant).map(|v| (v, self))
+    }
+}
+impl<'de> de::VariantAccess<'de> for EnumDeserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        if self.payload.is_empty() {
+            Ok(())
+        } else {
+            Err(de::Error::invalid_length(
+                self.payload.len(),
+                &"a variant without fields",
+            ))
+        }
+    }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        let mut payload = self.payload;
+        if payload.len() != 1 {
+            return Err(de::Error::invalid_length(payload.len(), &"one field"));
+        }
+        seed.deserialize(Deserializer::new(payload.remove(0)))
+    }
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        visit_seq(self.payload, visitor)
+    }
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        if record_name(self.variant).is_some() {
+            return visit_seq(self.payload, visitor);
+        }
+        let mut payload = self.payload;
+        match (payload.len(), payload.pop()) {
+            (1, Some(Term::Map(x))) => visit_map(x.entries, visitor),
+            (len, _) => Err(de::Error::invalid_length(len, &"a single map")),
+        }
+    }
+}
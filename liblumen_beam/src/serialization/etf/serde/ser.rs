@@ -0,0 +1,482 @@
+use ::serde::ser::{self, Serialize};
+
+use super::*;
+
+/// A serializer which converts Rust values into terms.
+pub struct Serializer;
+
+fn atom(name: &str) -> Term {
+    Term::from(Atom::from(name))
+}
+
+fn integer(value: i64) -> Term {
+    if value >= i64::from(std::i32::MIN) && value <= i64::from(std::i32::MAX) {
+        Term::from(FixInteger::from(value as i32))
+    } else {
+        Term::from(BigInteger::from(value))
+    }
+}
+
+fn unsigned_integer(value: u64) -> Term {
+    if value <= std::i32::MAX as u64 {
+        Term::from(FixInteger::from(value as i32))
+    } else {
+        Term::from(BigInteger::from(value))
+    }
+}
+
+fn tagged(tag: &str, mut elements: Vec<Term>) -> Term {
+    elements.insert(0, atom(tag));
+    Term::from(Tuple::from(elements))
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Term;
+    type Error = Error;
+
+    type SerializeSeq = SerializeSeq;
+    type SerializeTuple = SerializeTuple;
+    type SerializeTupleStruct = SerializeTuple;
+    type SerializeTupleVariant = SerializeTuple;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeStruct;
+    type SerializeStructVariant = SerializeStruct;
+
+    fn serialize_bool(self, v: bool) -> Result<Term> {
+        Ok(atom(if v { "true" } else { "false" }))
+    }
+    fn serialize_i8(self, v: i8) -> Result<Term> {
+        Ok(integer(i64::from(v)))
+    }
+    fn serialize_i16(self, v: i16) -> Result<Term> {
+        Ok(integer(i64::from(v)))
+    }
+    fn serialize_i32(self, v: i32) -> Result<Term> {
+        Ok(integer(i64::from(v)))
+    }
+    fn serialize_i64(self, v: i64) -> Result<Term> {
+        Ok(integer(v))
+    }
+    fn serialize_u8(self, v: u8) -> Result<Term> {
+        Ok(unsigned_integer(u64::from(v)))
+    }
+    fn serialize_u16(self, v: u16) -> Result<Term> {
+        Ok(unsigned_integer(u64::from(v)))
+    }
+    fn serialize_u32(self, v: u32) -> Result<Term> {
+        Ok(unsigned_integer(u64::from(v)))
+    }
+    fn serialize_u64(self, v: u64) -> Result<Term> {
+        Ok(unsigned_integer(v))
+    }
+    fn serialize_f32(self, v: f32) -> Result<Term> {
+        Ok(Term::from(Float::from(v)))
+    }
+    fn serialize_f64(self, v: f64) -> Result<Term> {
+        Ok(Term::from(Float::from(v)))
+    }
+    fn serialize_char(self, v: char) -> Result<Term> {
+        Ok(unsigned_integer(u64::from(v as u32)))
+    }
+    fn serialize_str(self, v: &str) -> Result<Term> {
+        Ok(Term::from(Binary::from(v.as_bytes())))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Term> {
+        Ok(Term::from(Binary::from(v)))
+    }
+    fn serialize_none(self) -> Result<Term> {
+        Ok(atom(UNDEFINED))
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Term> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Term> {
+        Ok(Term::from(Tuple::nil()))
+    }
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Term> {
+        match record_name(name) {
+            Some(name) => Ok(tagged(name, Vec::new())),
+            None => Ok(atom(name)),
+        }
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Term> {
+        match record_name(variant) {
+            Some(variant) => Ok(tagged(variant, Vec::new())),
+            None => Ok(atom(variant)),
+        }
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Term> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Term> {
+        let variant = record_name(variant).unwrap_or(variant);
+        Ok(tagged(variant, vec![value.serialize(self)?]))
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeSeq> {
+        Ok(SerializeSeq {
+            elements: Vec::with_capacity(len.unwrap_or(0)),
+            bytes: Some(Vec::with_capacity(len.unwrap_or(0))),
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<SerializeTuple> {
+        Ok(SerializeTuple {
+            elements: Vec::with_capacity(len),
+        })
+    }
+    fn serialize_tuple_struct(self, name: &'static str, len: usize) -> Result<SerializeTuple> {
+        let mut elements = Vec::with_capacity(len + 1);
+        if let Some(name) = record_name(name) {
+            elements.push(atom(name));
+        }
+        Ok(SerializeTuple { elements })
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTuple> {
+        let variant = record_name(variant).unwrap_or(variant);
+        let mut elements = Vec::with_capacity(len + 1);
+        elements.push(atom(variant));
+        Ok(SerializeTuple { elements })
+    }
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeMap> {
+        Ok(SerializeMap {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<SerializeStruct> {
+        Ok(SerializeStruct::new(None, record_name(name), len))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeStruct> {
+        match record_name(variant) {
+            Some(variant) => Ok(SerializeStruct::new(None, Some(variant), len)),
+            None => Ok(SerializeStruct::new(Some(variant), None, len)),
+        }
+    }
+}
+
+/// Serializes sequences as lists, or as binaries if every element is a `u8`.
+pub struct SerializeSeq {
+    elements: Vec<Term>,
+    // The elements so far, if all of them were serialized as `u8`
+    bytes: Option<Vec<u8>>,
+}
+impl ser::SerializeSeq for SerializeSeq {
+    type Ok = Term;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        if let Some(ref mut bytes) = self.bytes {
+            if let Ok(byte) = value.serialize(ByteSerializer) {
+                bytes.push(byte);
+                return Ok(());
+            }
+        }
+        if let Some(bytes) = self.bytes.take() {
+            self.elements = bytes
+                .into_iter()
+                .map(|b| unsigned_integer(u64::from(b)))
+                .collect();
+        }
+        self.elements.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Term> {
+        match self.bytes {
+            Some(ref bytes) if !bytes.is_empty() => Ok(Term::from(Binary::from(bytes.as_slice()))),
+            _ => Ok(Term::from(List::from(self.elements))),
+        }
+    }
+}
+
+/// Serializes tuples, tuple structs and tuple variants.
+pub struct SerializeTuple {
+    elements: Vec<Term>,
+}
+impl ser::SerializeTuple for SerializeTuple {
+    type Ok = Term;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.elements.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Term> {
+        Ok(Term::from(Tuple::from(self.elements)))
+    }
+}
+impl ser::SerializeTupleStruct for SerializeTuple {
+    type Ok = Term;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeTuple::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Term> {
+        ser::SerializeTuple::end(self)
+    }
+}
+impl ser::SerializeTupleVariant for SerializeTuple {
+    type Ok = Term;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeTuple::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Term> {
+        ser::SerializeTuple::end(self)
+    }
+}
+
+/// Serializes maps.
+pub struct SerializeMap {
+    entries: Vec<(Term, Term)>,
+    key: Option<Term>,
+}
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Term;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.entries.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Term> {
+        Ok(Term::from(Map::from(self.entries)))
+    }
+}
+
+/// Serializes structs and struct variants, either as maps or as records.
+pub struct SerializeStruct {
+    variant: Option<&'static str>,
+    record: Option<&'static str>,
+    fields: Vec<(Term, Term)>,
+}
+impl SerializeStruct {
+    fn new(variant: Option<&'static str>, record: Option<&'static str>, len: usize) -> Self {
+        SerializeStruct {
+            variant,
+            record,
+            fields: Vec::with_capacity(len),
+        }
+    }
+}
+impl ser::SerializeStruct for SerializeStruct {
+    type Ok = Term;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.fields.push((atom(key), value.serialize(Serializer)?));
+        Ok(())
+    }
+    fn skip_field(&mut self, key: &'static str) -> Result<()> {
+        // Records are positional, so skipped fields must keep their place
+        if self.record.is_some() {
+            self.fields.push((atom(key), atom(UNDEFINED)));
+        }
+        Ok(())
+    }
+    fn end(self) -> Result<Term> {
+        let term = match self.record {
+            Some(name) => tagged(name, self.fields.into_iter().map(|(_, v)| v).collect()),
+            None => Term::from(Map::from(self.fields)),
+        };
+        match self.variant {
+            Some(variant) => Ok(tagged(variant, vec![term])),
+            None => Ok(term),
+        }
+    }
+}
+impl ser::SerializeStructVariant for SerializeStruct {
+    type Ok = Term;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+    fn skip_field(&mut self, key: &'static str) -> Result<()> {
+        ser::SerializeStruct::skip_field(self, key)
+    }
+    fn end(self) -> Result<Term> {
+        ser::SerializeStruct::end(self)
+    }
+}
+
+/// Succeeds only for values which serialize as a `u8`.
+///
+/// This is how `SerializeSeq` tells a `Vec<u8>` from other sequences.
+struct ByteSerializer;
+
+fn not_a_byte<T>() -> Result<T> {
+    Err(Error::Message("not a byte".to_string()))
+}
+
+impl ser::Serializer for ByteSerializer {
+    type Ok = u8;
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<u8, Error>;
+    type SerializeTuple = ser::Impossible<u8, Error>;
+    type SerializeTupleStruct = ser::Impossible<u8, Error>;
+    type SerializeTupleVariant = ser::Impossible<u8, Error>;
+    type SerializeMap = ser::Impossible<u8, Error>;
+    type SerializeStruct = ser::Impossible<u8, Error>;
+    type SerializeStructVariant = ser::Impossible<u8, Error>;
+
+    fn serialize_u8(self, v: u8) -> Result<u8> {
+        Ok(v)
+    }
+    fn serialize_bool(self, _v: bool) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_i8(self, _v: i8) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_i16(self, _v: i16) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_i32(self, _v: i32) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_i64(self, _v: i64) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_u16(self, _v: u16) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_u32(self, _v: u32) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_u64(self, _v: u64) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_f32(self, _v: f32) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_f64(self, _v: f64) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_char(self, _v: char) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_str(self, _v: &str) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_none(self) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_unit(self) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _value: &T,
+    ) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<u8> {
+        not_a_byte()
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        not_a_byte()
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        not_a_byte()
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        not_a_byte()
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        not_a_byte()
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        not_a_byte()
+    }
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        not_a_byte()
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        not_a_byte()
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+
+use ::serde::{Deserialize, Serialize};
+
+use crate::serialization::etf::serde::*;
+use crate::serialization::etf::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct User {
+    name: String,
+    age: u32,
+    email: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "#point")]
+struct Point {
+    x: i32,
+    y: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Line(Point, Point),
+    Rect {
+        width: u32,
+        height: u32,
+    },
+    #[serde(rename = "#square")]
+    Square {
+        side: u32,
+    },
+}
+
+// Serialized with `serialize_bytes` rather than as a sequence
+struct Bytes<'a>(&'a [u8]);
+impl<'a> Serialize for Bytes<'a> {
+    fn serialize<S: ::serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+fn atom(name: &str) -> Term {
+    Term::from(Atom::from(name))
+}
+
+fn int(value: i32) -> Term {
+    Term::from(FixInteger::from(value))
+}
+
+fn binary(bytes: &[u8]) -> Term {
+    Term::from(Binary::from(bytes))
+}
+
+fn tuple(elements: Vec<Term>) -> Term {
+    Term::from(Tuple::from(elements))
+}
+
+fn roundtrip<T>(value: &T) -> T
+where
+    T: Serialize + ::serde::de::DeserializeOwned,
+{
+    from_slice(&to_vec(value).unwrap()).unwrap()
+}
+
+#[test]
+fn primitive_test() {
+    assert_eq!(atom("true"), to_term(&true).unwrap());
+    assert_eq!(int(-1), to_term(&-1i8).unwrap());
+    assert_eq!(int(97), to_term(&'a').unwrap());
+    assert_eq!(binary(b"foo"), to_term("foo").unwrap());
+    assert_eq!(atom("undefined"), to_term(&None::<i32>).unwrap());
+    assert_eq!(int(1), to_term(&Some(1)).unwrap());
+    assert_eq!(tuple(vec![]), to_term(&()).unwrap());
+    assert_eq!(Term::from(Float::from(1.5)), to_term(&1.5f64).unwrap());
+
+    assert_eq!(Ok(false), from_term::<bool>(atom("false")).map_err(|_| ()));
+    assert_eq!(Some(1), from_term::<Option<u8>>(int(1)).unwrap());
+    assert_eq!(None, from_term::<Option<u8>>(atom("undefined")).unwrap());
+    assert_eq!("foo", from_term::<String>(atom("foo")).unwrap());
+    assert_eq!(
+        "foo",
+        from_term::<String>(Term::from(List::from(vec![int(102), int(111), int(111)]))).unwrap()
+    );
+    assert!(from_term::<u8>(int(256)).is_err());
+    assert!(from_term::<bool>(int(1)).is_err());
+}
+
+#[test]
+fn integer_promotion_test() {
+    assert_eq!(int(std::i32::MAX), to_term(&std::i32::MAX).unwrap());
+    assert_eq!(
+        Term::from(BigInteger::from(i64::from(std::i32::MAX) + 1)),
+        to_term(&(i64::from(std::i32::MAX) + 1)).unwrap()
+    );
+    assert_eq!(
+        Term::from(BigInteger::from(std::u64::MAX)),
+        to_term(&std::u64::MAX).unwrap()
+    );
+    assert_eq!(std::u64::MAX, roundtrip(&std::u64::MAX));
+    assert_eq!(std::i64::MIN, roundtrip(&std::i64::MIN));
+    assert!(from_term::<i32>(Term::from(BigInteger::from(std::u64::MAX))).is_err());
+}
+
+#[test]
+fn sequence_test() {
+    assert_eq!(binary(&[1, 2, 3]), to_term(&Bytes(&[1, 2, 3])).unwrap());
+    assert_eq!(binary(&[]), to_term(&Bytes(&[])).unwrap());
+    assert_eq!(binary(&[1, 2, 3]), to_term(&vec![1u8, 2, 3]).unwrap());
+    assert_eq!(Term::from(List::nil()), to_term(&Vec::<u8>::new()).unwrap());
+    assert_eq!(
+        Term::from(List::from(vec![int(1), int(2)])),
+        to_term(&vec![1u16, 2]).unwrap()
+    );
+    assert_eq!(
+        vec![1u8, 2, 3],
+        from_term::<Vec<u8>>(binary(&[1, 2, 3])).unwrap()
+    );
+    assert_eq!(Vec::<u8>::new(), roundtrip(&Vec::<u8>::new()));
+    assert_eq!(vec![1u8, 2, 3], roundtrip(&vec![1u8, 2, 3]));
+    assert_eq!(
+        Vec::<u8>::new(),
+        from_slice::<Vec<u8>>(&to_vec(&Bytes(&[])).unwrap()).unwrap()
+    );
+    assert_eq!(
+        tuple(vec![int(1), binary(b"a")]),
+        to_term(&(1, "a")).unwrap()
+    );
+    assert_eq!((1, "a".to_string()), roundtrip(&(1, "a".to_string())));
+
+    let mut map = BTreeMap::new();
+    map.insert("a".to_string(), 1);
+    map.insert("b".to_string(), 2);
+    assert_eq!(
+        Term::from(Map::from(vec![
+            (binary(b"a"), int(1)),
+            (binary(b"b"), int(2)),
+        ])),
+        to_term(&map).unwrap()
+    );
+    assert_eq!(map, roundtrip(&map));
+}
+
+#[test]
+fn struct_test() {
+    // Map
+    let user = User {
+        name: "joe".to_string(),
+        age: 42,
+        email: None,
+    };
+    assert_eq!(
+        Term::from(Map::from(vec![
+            (atom("name"), binary(b"joe")),
+            (atom("age"), int(42)),
+            (atom("email"), atom("undefined")),
+        ])),
+        to_term(&user).unwrap()
+    );
+    assert_eq!(user, roundtrip(&user));
+
+    // Record
+    let point = Point {
+        x: 1,
+        y: 2,
+        label: None,
+    };
+    assert_eq!(
+        tuple(vec![atom("point"), int(1), int(2), atom("undefined")]),
+        to_term(&point).unwrap()
+    );
+    assert_eq!(point, roundtrip(&point));
+    assert!(from_term::<Point>(tuple(vec![
+        atom("other"),
+        int(1),
+        int(2),
+        atom("undefined")
+    ]))
+    .is_err());
+    assert!(
+        from_term::<Point>(tuple(vec![atom("point"), int(1), int(2), int(3), int(4)])).is_err()
+    );
+}
+
+#[test]
+fn enum_test() {
+    assert_eq!(atom("empty"), to_term(&Shape::Empty).unwrap());
+    assert_eq!(
+        tuple(vec![atom("circle"), Term::from(Float::from(1.0))]),
+        to_term(&Shape::Circle(1.0)).unwrap()
+    );
+    assert_eq!(
+        tuple(vec![
+            atom("rect"),
+            Term::from(Map::from(vec![
+                (atom("width"), int(1)),
+                (atom("height"), int(2)),
+            ])),
+        ]),
+        to_term(&Shape::Rect {
+            width: 1,
+            height: 2
+        })
+        .unwrap()
+    );
+    assert_eq!(
+        tuple(vec![atom("square"), int(3)]),
+        to_term(&Shape::Square { side: 3 }).unwrap()
+    );
+
+    let shapes = vec![
+        Shape::Empty,
+        Shape::Circle(1.0),
+        Shape::Line(
+            Point {
+                x: 0,
+                y: 0,
+                label: Some("origin".to_string()),
+            },
+            Point {
+                x: 1,
+                y: 1,
+                label: None,
+            },
+        ),
+        Shape::Rect {
+            width: 1,
+            height: 2,
+        },
+        Shape::Square { side: 3 },
+    ];
+    assert_eq!(shapes, roundtrip(&shapes));
+
+    match from_term::<Shape>(atom("hexagon")) {
+        Err(Error::Message(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -103,7 +103,7 @@ fn float_test() {
 
     // Decode
     assert_eq!(
-        Ok(Float::from("1.23".parse::<f32>().unwrap() as f64)),
+        Ok(Float::from(1.23)),
         decode(&[
             131, 99, 49, 46, 50, 50, 57, 57, 57, 57, 57, 57, 57, 57, 57, 57, 57, 57, 57, 57, 56,
             50, 50, 52, 101, 43, 48, 48, 0, 0, 0, 0, 0
@@ -154,6 +154,15 @@ fn pid_test() {
         ],
         encode(Term::from(Pid::from(("nonode@nohost", 49, 0))))
     );
+
+    // NEW_PID_EXT
+    let bytes = vec![
+        131, 88, 100, 0, 13, 110, 111, 110, 111, 100, 101, 64, 110, 111, 104, 111, 115, 116, 0, 0,
+        0, 49, 0, 0, 0, 0, 94, 176, 21, 130,
+    ];
+    let pid = Pid::new("nonode@nohost", 49, 0, 0x5EB0_1582);
+    assert_eq!(Ok(pid.clone()), decode(&bytes).try_into());
+    assert_eq!(bytes, encode(Term::from(pid)));
 }
 
 #[test]
@@ -182,6 +191,31 @@ fn port_test() {
         ],
         encode(Term::from(Port::from(("nonode@nohost", 366))))
     );
+
+    // NEW_PORT_EXT
+    let bytes = vec![131, 89, 115, 3, 102, 111, 111, 0, 0, 1, 110, 0, 0, 1, 0];
+    let port = Port {
+        node: Atom::from("foo"),
+        id: 366,
+        creation: 256,
+    };
+    assert_eq!(Ok(port.clone()), decode(&bytes).try_into());
+    assert_eq!(
+        vec![131, 89, 100, 0, 3, 102, 111, 111, 0, 0, 1, 110, 0, 0, 1, 0],
+        encode(Term::from(port))
+    );
+
+    // V4_PORT_EXT
+    let bytes = vec![
+        131, 120, 100, 0, 3, 102, 111, 111, 0, 0, 0, 1, 0, 0, 1, 110, 0, 0, 0, 2,
+    ];
+    let port = Port {
+        node: Atom::from("foo"),
+        id: 0x1_0000_016E,
+        creation: 2,
+    };
+    assert_eq!(Ok(port.clone()), decode(&bytes).try_into());
+    assert_eq!(bytes, encode(Term::from(port)));
 }
 
 #[test]
@@ -212,6 +246,48 @@ fn reference_test() {
         vec![131, 114, 0, 1, 100, 0, 3, 102, 111, 111, 0, 0, 0, 0, 123],
         encode(Term::from(Reference::from(("foo", 123))))
     );
+
+    // NEWER_REFERENCE_EXT
+    let bytes = vec![
+        131, 90, 0, 2, 100, 0, 3, 102, 111, 111, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2,
+    ];
+    let reference = Reference {
+        node: Atom::from("foo"),
+        id: vec![1, 2],
+        creation: 0x10000,
+    };
+    assert_eq!(Ok(reference.clone()), decode(&bytes).try_into());
+    assert_eq!(bytes, encode(Term::from(reference)));
+}
+
+#[test]
+fn local_test() {
+    let bytes = vec![131, 121, 1, 2, 3, 4, 97, 1];
+
+    // Decode
+    assert_eq!(
+        Ok(Local::from(vec![1, 2, 3, 4, 97, 1])),
+        decode(&bytes).try_into()
+    );
+
+    // Encode
+    assert_eq!(bytes, encode(decode(&bytes)));
+    let mut buf = Vec::new();
+    decode(&bytes)
+        .encode_with_options(&mut buf, &EncodeOptions::new().compressed(9))
+        .unwrap();
+    assert_eq!(bytes, buf);
+
+    // Only the whole term can be local
+    match Term::decode(Cursor::new(&[131, 108, 0, 0, 0, 1, 121, 1, 2, 106])) {
+        Err(DecodeError::NestedLocal) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    let list = Term::from(List::from(vec![decode(&bytes)]));
+    match list.encode(&mut Vec::new()) {
+        Err(EncodeError::NestedLocal) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
 }
 
 #[test]
@@ -295,6 +371,21 @@ fn bit_binary_test() {
         Ok(BitBinary::from((vec![1, 2, 3], 5))),
         decode(&[131, 77, 0, 0, 0, 3, 5, 1, 2, 24]).try_into()
     );
+    assert_eq!(
+        Ok(Binary::from(Vec::new())),
+        decode(&[131, 77, 0, 0, 0, 0, 0]).try_into()
+    );
+    for &(size, tail_bits_size) in &[(3, 0), (3, 9), (3, 255), (0, 1)] {
+        let mut bytes = vec![131, 77, 0, 0, 0, size, tail_bits_size];
+        bytes.extend(std::iter::repeat(1).take(size as usize));
+        match Term::decode(Cursor::new(&bytes)) {
+            Err(DecodeError::InvalidTailBitsSize {
+                size: s,
+                tail_bits_size: t,
+            }) if s == size as usize && t == tail_bits_size => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
 
     // Encode
     assert_eq!(
@@ -471,6 +562,274 @@ fn compressed_term_test() {
     );
 }
 
+#[test]
+fn distribution_header_test() {
+    // Decode: one new cache entry (index 5) referenced by the control message
+    assert_eq!(
+        Ok(Atom::from("foo")),
+        decode(&[131, 68, 1, 8, 5, 3, 102, 111, 111, 82, 0]).try_into()
+    );
+
+    // Decode with the cache of a connection
+    let mut cache = AtomCache::new();
+    let options = DecodeOptions::new();
+    let term = Term::decode_with_cache(
+        Cursor::new(&[131, 68, 1, 8, 5, 3, 102, 111, 111, 82, 0]),
+        &mut cache,
+        &options,
+    )
+    .unwrap();
+    assert_eq!(Term::from(Atom::from("foo")), term);
+    assert_eq!(Some(&Atom::from("foo")), cache.get(5));
+    let term = Term::decode_with_cache(
+        Cursor::new(&[131, 68, 1, 0, 5, 82, 0]),
+        &mut cache,
+        &options,
+    )
+    .unwrap();
+    assert_eq!(Term::from(Atom::from("foo")), term);
+
+    let mut cache = AtomCache::new();
+    let message = DistributionMessage::decode(
+        Cursor::new(&[131, 68, 1, 8, 5, 3, 102, 111, 111, 82, 0, 82, 0]),
+        &mut cache,
+    )
+    .unwrap();
+    assert_eq!(Term::from(Atom::from("foo")), message.control);
+    assert_eq!(Some(Term::from(Atom::from("foo"))), message.payload);
+    assert_eq!(Some(&Atom::from("foo")), cache.get(5));
+
+    // A later message on the same connection refers to the cached entry
+    let message =
+        DistributionMessage::decode(Cursor::new(&[131, 68, 1, 0, 5, 82, 0]), &mut cache).unwrap();
+    assert_eq!(Term::from(Atom::from("foo")), message.control);
+    assert_eq!(None, message.payload);
+
+    // Segment index in the flags, and long atoms
+    let mut cache = AtomCache::new();
+    let mut bytes = vec![131, 68, 1, 8 | 2 | 0x10, 7, 1, 0];
+    bytes.extend(std::iter::repeat(b'a').take(256));
+    bytes.extend(&[82, 0]);
+    let message = DistributionMessage::decode(Cursor::new(&bytes), &mut cache).unwrap();
+    assert_eq!(Term::from(Atom::from("a".repeat(256))), message.control);
+    assert_eq!(Some(&Atom::from("a".repeat(256))), cache.get(2 * 256 + 7));
+
+    // Errors
+    assert!(Term::decode(Cursor::new(&[131, 68, 0, 82, 0])).is_err());
+    assert!(DistributionMessage::decode(
+        Cursor::new(&[131, 68, 1, 0, 5, 82, 0]),
+        &mut AtomCache::new()
+    )
+    .is_err());
+    assert!(
+        DistributionMessage::decode(Cursor::new(&[131, 97, 1]), &mut AtomCache::new()).is_err()
+    );
+
+    // Encode
+    let message = DistributionMessage {
+        control: Term::from(Tuple::from(vec![
+            Term::from(FixInteger::from(2)),
+            Term::from(Atom::from("")),
+            Term::from(Pid::new("nonode@nohost", 1, 0, 0)),
+        ])),
+        payload: Some(Term::from(List::from(vec![
+            Term::from(Atom::from("hello")),
+            Term::from(Atom::from("nonode@nohost")),
+        ]))),
+    };
+    let mut sender = AtomCache::new();
+    let mut receiver = AtomCache::new();
+    for _ in 0..2 {
+        let mut buf = Vec::new();
+        message.encode(&mut buf, &mut sender).unwrap();
+        assert_eq!(
+            message,
+            DistributionMessage::decode(Cursor::new(&buf), &mut receiver).unwrap()
+        );
+        assert_eq!(sender, receiver);
+    }
+
+    // The second message only refers to existing entries
+    let mut buf = Vec::new();
+    message.encode(&mut buf, &mut sender).unwrap();
+    assert!(!buf.windows(5).any(|w| w == b"hello"));
+}
+
+#[test]
+fn decode_options_test() {
+    fn decode_with(bytes: &[u8], options: &DecodeOptions) -> DecodeResult {
+        Term::decode_with_options(Cursor::new(bytes), options)
+    }
+
+    // Declared lengths larger than the input
+    let options = DecodeOptions::new().max_size(64);
+    let huge_list = [131, 108, 255, 255, 255, 255, 106];
+    match decode_with(&huge_list, &options) {
+        Err(DecodeError::SizeLimitExceeded { limit: 64 }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    let huge_binary = [131, 109, 255, 255, 255, 255, 0];
+    match decode_with(&huge_binary, &options) {
+        Err(DecodeError::SizeLimitExceeded { limit: 64 }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    let input = encode(Term::from(Binary::from(vec![0; 100])));
+    match decode_with(&input, &options) {
+        Err(DecodeError::SizeLimitExceeded { limit: 64 }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    let input = encode(Term::from(Binary::from(vec![0; 50])));
+    assert!(decode_with(&input, &options).is_ok());
+
+    // Nesting depth
+    let mut nested = Term::from(List::nil());
+    for _ in 0..10 {
+        nested = Term::from(List::from(vec![nested]));
+    }
+    let input = encode(nested.clone());
+    assert_eq!(
+        nested,
+        decode_with(&input, &DecodeOptions::new().max_depth(11)).unwrap()
+    );
+    match decode_with(&input, &DecodeOptions::new().max_depth(10)) {
+        Err(DecodeError::DepthLimitExceeded { limit: 10 }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // Element count
+    let input = encode(Term::from(Tuple::from(vec![
+        Term::from(FixInteger::from(1)),
+        Term::from(FixInteger::from(2)),
+        Term::from(FixInteger::from(3)),
+    ])));
+    match decode_with(&input, &DecodeOptions::new().max_length(2)) {
+        Err(DecodeError::LengthLimitExceeded {
+            length: 3,
+            limit: 2,
+        }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(decode_with(&input, &DecodeOptions::new().max_length(3)).is_ok());
+
+    // Known atoms
+    let known_atoms = ["ok"].iter().map(|a| a.to_string()).collect();
+    let options = DecodeOptions::new().known_atoms(known_atoms);
+    assert!(decode_with(&encode(Term::from(Atom::from("ok"))), &options).is_ok());
+    match decode_with(&encode(Term::from(Atom::from("error"))), &options) {
+        Err(DecodeError::UnknownAtom { ref name }) if name == "error" => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // Declared uncompressed size
+    let compressed = |declared: u32| {
+        let term = encode(Term::from(Binary::from(vec![1; 100])));
+        let mut encoder = libflate::zlib::Encoder::new(Vec::new()).unwrap();
+        std::io::Write::write_all(&mut encoder, &term[1..]).unwrap();
+        let mut buf = vec![131, 80];
+        buf.extend_from_slice(&declared.to_be_bytes());
+        buf.extend(encoder.finish().into_result().unwrap());
+        buf
+    };
+    let options = DecodeOptions::new().verify_uncompressed_size(true);
+    assert!(decode_with(&compressed(105), &options).is_ok());
+    assert!(decode_with(&compressed(10), &DecodeOptions::new()).is_ok());
+    for &declared in &[10, 200] {
+        match decode_with(&compressed(declared), &options) {
+            Err(DecodeError::UncompressedSizeMismatch { declared: d })
+                if d == declared as usize => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+    match decode_with(&compressed(1_000_000), &DecodeOptions::new().max_size(1024)) {
+        Err(DecodeError::SizeLimitExceeded { limit: 1024 }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn encode_options_test() {
+    fn encode_with(term: Term, options: &EncodeOptions) -> Vec<u8> {
+        let mut buf = Vec::new();
+        term.encode_with_options(&mut buf, options).unwrap();
+        buf
+    }
+
+    // FLOAT_EXT
+    let options = EncodeOptions::new().minor_version(0);
+    let mut expected = vec![131, 99];
+    expected.extend_from_slice(b"-1.50000000000000000000e+02");
+    expected.resize(33, 0);
+    assert_eq!(
+        expected,
+        encode_with(Term::from(Float::from(-150.0)), &options)
+    );
+    for &value in &[
+        0.0,
+        0.1,
+        0.25,
+        -1024.5,
+        4_294_967_296.0,
+        1.0e-300,
+        0.1 + 0.2,
+    ] {
+        let term = Term::from(Float::from(value));
+        assert_eq!(term, decode(&encode_with(term.clone(), &options)));
+    }
+    for &value in &[std::f64::INFINITY, std::f64::NEG_INFINITY, std::f64::NAN] {
+        let mut buf = Vec::new();
+        match Term::from(Float::from(value)).encode_with_options(&mut buf, &options) {
+            Err(EncodeError::NonFiniteFloat(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+    assert_eq!(
+        vec![131, 70, 63, 248, 0, 0, 0, 0, 0, 0],
+        encode_with(Term::from(Float::from(1.5)), &EncodeOptions::new())
+    );
+
+    // UTF-8 atoms
+    assert_eq!(
+        vec![131, 119, 3, 102, 111, 111],
+        encode_with(
+            Term::from(Atom::from("foo")),
+            &EncodeOptions::new().minor_version(2)
+        )
+    );
+
+    // COMPRESSED_TERM
+    let term = Term::from(List::from(
+        (1..257)
+            .map(|i| Term::from(FixInteger::from(i)))
+            .collect::<Vec<_>>(),
+    ));
+    let uncompressed = encode(term.clone());
+    assert_eq!(0x209, uncompressed.len() - 1);
+    for level in 0..10 {
+        let bytes = encode_with(term.clone(), &EncodeOptions::new().compressed(level));
+        assert_eq!(&[131, 80, 0, 0, 2, 9], &bytes[..6]);
+        if level > 0 {
+            assert!(bytes.len() < uncompressed.len());
+        }
+        let options = DecodeOptions::new().verify_uncompressed_size(true);
+        assert_eq!(
+            term,
+            Term::decode_with_options(Cursor::new(&bytes), &options).unwrap()
+        );
+    }
+
+    // Invalid options are rejected before anything is written
+    let mut buf = Vec::new();
+    match term.encode_with_options(&mut buf, &EncodeOptions::new().compressed(10)) {
+        Err(EncodeError::InvalidCompressionLevel(10)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match term.encode_with_options(&mut buf, &EncodeOptions::new().minor_version(3)) {
+        Err(EncodeError::InvalidMinorVersion(3)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(buf.is_empty());
+}
+
 fn encode(term: Term) -> Vec<u8> {
     let mut buf = Vec::new();
     term.encode(&mut buf).unwrap();
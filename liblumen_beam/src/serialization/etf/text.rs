@@ -0,0 +1,113 @@
+//! Erlang term text, as read by `file:consult/1`.
+//!
+//! Supported syntax: atoms (bare and quoted), integers (including `Base#Digits`),
+//! floats, characters (`$a`), strings, binaries (`<<"..">>`, `<<1,2:4>>`, `/utf8` segments),
+//! tuples, lists (including improper lists) and maps. Comments start with `%`.
+//!
+//! The printer produces canonical text which the parser reads back into the same term.
+//! Pids, ports, references, funs and local terms have no literal syntax and are printed
+//! like their `Display` implementations, which the parser does not accept.
+//!
+//! # Examples
+//!
+//!     use liblumen_beam::serialization::etf::text;
+//!
+//!     let terms = text::parse_terms("{app, [{env, #{port => 16#1F90}}]}.\n").unwrap();
+//!     assert_eq!(1, terms.len());
+//!     assert_eq!("{app,[{env,#{port => 8080}}]}", text::to_string(&terms[0]));
+//!
+mod parser;
+mod printer;
+
+#[cfg(test)]
+mod test;
+
+use std::path::Path;
+
+use failure::Fail;
+
+use super::*;
+
+pub use self::printer::Printer;
+
+#[derive(Fail, Debug)]
+pub enum ParseError {
+    #[fail(display = "{}:{}: unexpected end of input", line, column)]
+    UnexpectedEof { line: usize, column: usize },
+
+    #[fail(
+        display = "{}:{}: unexpected character '{}', expected {}",
+        line, column, found, expected
+    )]
+    UnexpectedChar {
+        line: usize,
+        column: usize,
+        found: char,
+        expected: &'static str,
+    },
+
+    #[fail(display = "{}:{}: variables are not allowed in terms", line, column)]
+    Variable { line: usize, column: usize },
+
+    #[fail(display = "{}:{}: invalid number", line, column)]
+    InvalidNumber { line: usize, column: usize },
+
+    #[fail(display = "{}:{}: invalid escape sequence", line, column)]
+    InvalidEscape { line: usize, column: usize },
+
+    #[fail(display = "{}:{}: invalid binary segment: {}", line, column, reason)]
+    InvalidSegment {
+        line: usize,
+        column: usize,
+        reason: &'static str,
+    },
+
+    #[fail(
+        display = "{}:{}: term exceeds the maximum nesting depth of {}",
+        line, column, limit
+    )]
+    DepthLimitExceeded {
+        line: usize,
+        column: usize,
+        limit: usize,
+    },
+
+    #[fail(display = "i/o error: {}", _0)]
+    IO(#[fail(cause)] std::io::Error),
+}
+impl std::convert::From<std::io::Error> for ParseError {
+    fn from(err: std::io::Error) -> ParseError {
+        ParseError::IO(err)
+    }
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Parses a single term, optionally terminated by a dot.
+pub fn parse_term(text: &str) -> ParseResult<Term> {
+    parser::Parser::new(text).parse_term()
+}
+
+/// Parses a sequence of dot-terminated terms, like `file:consult/1`.
+pub fn parse_terms(text: &str) -> ParseResult<Vec<Term>> {
+    parser::Parser::new(text).parse_terms()
+}
+
+/// Reads the dot-terminated terms of a file, like `file:consult/1`.
+pub fn consult<P: AsRef<Path>>(path: P) -> ParseResult<Vec<Term>> {
+    let text = std::fs::read_to_string(path)?;
+    parse_terms(&text)
+}
+
+/// Prints a term as canonical text.
+pub fn to_string(term: &Term) -> String {
+    Printer::new(term).to_string()
+}
+
+/// Prints terms in the format read by `parse_terms`, one term per line.
+pub fn to_string_terms(terms: &[Term]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("{}.\n", Printer::new(term)))
+        .collect()
+}
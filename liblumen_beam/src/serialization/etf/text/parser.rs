@@ -0,0 +1,628 @@
+use num::bigint::BigInt;
+use num::ToPrimitive;
+
+use super::*;
+
+/// The maximum size of a binary, in bits.  A segment size such as in `<<1:99999999999>>` is
+/// not bounded by the length of the text, so it is checked against this instead.
+const MAX_BINARY_BITS: usize = 1024 * 1024 * 8;
+
+/// The maximum nesting depth of terms.  Terms are parsed recursively, so deeper nesting such as
+/// a long run of `[` would overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+pub struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    depth: usize,
+}
+impl Parser {
+    pub fn new(text: &str) -> Self {
+        Parser {
+            chars: text.chars().collect(),
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    pub fn parse_term(mut self) -> ParseResult<Term> {
+        let term = self.term()?;
+        self.skip_whitespace();
+        if self.peek() == Some('.') {
+            self.end_of_term()?;
+            self.skip_whitespace();
+        }
+        match self.peek() {
+            None => Ok(term),
+            Some(c) => Err(self.unexpected(c, "end of input")),
+        }
+    }
+
+    pub fn parse_terms(mut self) -> ParseResult<Vec<Term>> {
+        let mut terms = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek().is_none() {
+                return Ok(terms);
+            }
+            terms.push(self.term()?);
+            self.skip_whitespace();
+            self.end_of_term()?;
+        }
+    }
+
+    // Expects a dot followed by whitespace, a comment or the end of input
+    fn end_of_term(&mut self) -> ParseResult<()> {
+        self.expect('.', "'.'")?;
+        match self.peek() {
+            Some(c) if !c.is_whitespace() && c != '%' => Err(self.unexpected(c, "whitespace")),
+            _ => Ok(()),
+        }
+    }
+
+    fn term(&mut self) -> ParseResult<Term> {
+        self.skip_whitespace();
+        if self.depth >= MAX_DEPTH {
+            let (line, column) = self.position();
+            return Err(ParseError::DepthLimitExceeded {
+                line,
+                column,
+                limit: MAX_DEPTH,
+            });
+        }
+        self.depth += 1;
+        let result = self.term_inner();
+        self.depth -= 1;
+        result
+    }
+
+    fn term_inner(&mut self) -> ParseResult<Term> {
+        let c = self.peek().ok_or_else(|| self.eof())?;
+        match c {
+            '{' => {
+                self.pos += 1;
+                let elements = self.sequence('}')?;
+                Ok(Term::from(Tuple::from(elements)))
+            }
+            '[' => {
+                self.pos += 1;
+                self.list()
+            }
+            '#' => {
+                self.pos += 1;
+                self.expect('{', "'{'")?;
+                self.map()
+            }
+            '<' => {
+                self.pos += 1;
+                self.expect('<', "'<'")?;
+                self.binary()
+            }
+            '"' => {
+                let chars = self.strings()?;
+                Ok(Term::from(List::from(
+                    chars
+                        .into_iter()
+                        .map(|c| Term::from(FixInteger::from(c as i32)))
+                        .collect::<Vec<_>>(),
+                )))
+            }
+            '\'' => {
+                self.pos += 1;
+                let name = self.quoted('\'')?;
+                Ok(Term::from(Atom::from(name)))
+            }
+            '$' => {
+                self.pos += 1;
+                let c = self.char()?;
+                Ok(Term::from(FixInteger::from(c as i32)))
+            }
+            '-' | '+' => {
+                self.pos += 1;
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(d) if d.is_ascii_digit() => self.number(c == '-'),
+                    Some('$') => {
+                        self.pos += 1;
+                        let value = self.char()? as i32;
+                        Ok(Term::from(FixInteger::from(if c == '-' {
+                            -value
+                        } else {
+                            value
+                        })))
+                    }
+                    Some(d) => Err(self.unexpected(d, "a number")),
+                    None => Err(self.eof()),
+                }
+            }
+            c if c.is_ascii_digit() => self.number(false),
+            c if c.is_lowercase() => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '@');
+                Ok(Term::from(Atom::from(name)))
+            }
+            c if c.is_uppercase() || c == '_' => {
+                let (line, column) = self.position();
+                Err(ParseError::Variable { line, column })
+            }
+            c => Err(self.unexpected(c, "a term")),
+        }
+    }
+
+    // Parses comma separated terms up to `close`; the opening delimiter is already consumed
+    fn sequence(&mut self, close: char) -> ParseResult<Vec<Term>> {
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(close) {
+            self.pos += 1;
+            return Ok(elements);
+        }
+        loop {
+            elements.push(self.term()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some(c) if c == close => return Ok(elements),
+                Some(c) => {
+                    self.pos -= 1;
+                    return Err(self.unexpected(c, "',' or a closing delimiter"));
+                }
+                None => return Err(self.eof()),
+            }
+        }
+    }
+
+    fn list(&mut self) -> ParseResult<Term> {
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Term::from(List::nil()));
+        }
+        loop {
+            elements.push(self.term()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Term::from(List::from(elements))),
+                Some('|') => {
+                    let tail = self.term()?;
+                    self.skip_whitespace();
+                    self.expect(']', "']'")?;
+                    return Ok(match tail {
+                        Term::List(tail) => {
+                            elements.extend(tail.elements);
+                            Term::from(List::from(elements))
+                        }
+                        Term::ImproperList(tail) => {
+                            elements.extend(tail.elements);
+                            Term::from(ImproperList::from((elements, *tail.last)))
+                        }
+                        tail => Term::from(ImproperList::from((elements, tail))),
+                    });
+                }
+                Some(c) => {
+                    self.pos -= 1;
+                    return Err(self.unexpected(c, "',', '|' or ']'"));
+                }
+                None => return Err(self.eof()),
+            }
+        }
+    }
+
+    fn map(&mut self) -> ParseResult<Term> {
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Term::from(Map::from(entries)));
+        }
+        loop {
+            let key = self.term()?;
+            self.skip_whitespace();
+            self.expect('=', "'=>'")?;
+            self.expect('>', "'=>'")?;
+            let value = self.term()?;
+            entries.push((key, value));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Term::from(Map::from(entries))),
+                Some(c) => {
+                    self.pos -= 1;
+                    return Err(self.unexpected(c, "',' or '}'"));
+                }
+                None => return Err(self.eof()),
+            }
+        }
+    }
+
+    fn binary(&mut self) -> ParseResult<Term> {
+        let mut bits = BitWriter::new();
+        self.skip_whitespace();
+        if self.peek() == Some('>') {
+            self.pos += 1;
+            self.expect('>', "'>>'")?;
+            return Ok(Term::from(Binary::from(Vec::new())));
+        }
+        loop {
+            self.segment(&mut bits)?;
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some('>') => {
+                    self.expect('>', "'>>'")?;
+                    return Ok(bits.into_term());
+                }
+                Some(c) => {
+                    self.pos -= 1;
+                    return Err(self.unexpected(c, "',' or '>>'"));
+                }
+                None => return Err(self.eof()),
+            }
+        }
+    }
+
+    fn segment(&mut self, bits: &mut BitWriter) -> ParseResult<()> {
+        self.skip_whitespace();
+        let (line, column) = self.position();
+        let invalid = |reason| ParseError::InvalidSegment {
+            line,
+            column,
+            reason,
+        };
+
+        let value = if self.peek() == Some('"') {
+            Segment::String(self.strings()?)
+        } else {
+            match self.term()? {
+                Term::FixInteger(x) => Segment::Integer(i64::from(x.value)),
+                Term::BigInteger(x) => Segment::Integer(
+                    x.value
+                        .to_i64()
+                        .ok_or_else(|| invalid("integer is too large"))?,
+                ),
+                _ => return Err(invalid("expected an integer or a string")),
+            }
+        };
+        self.skip_whitespace();
+        let size = if self.peek() == Some(':') {
+            self.pos += 1;
+            self.skip_whitespace();
+            let digits = self.take_while(|c| c.is_ascii_digit());
+            Some(
+                digits
+                    .parse::<usize>()
+                    .map_err(|_| invalid("expected a size"))?,
+            )
+        } else {
+            None
+        };
+        self.skip_whitespace();
+        let utf8 = if self.peek() == Some('/') {
+            self.pos += 1;
+            self.skip_whitespace();
+            match self.take_while(|c| c.is_alphanumeric()).as_str() {
+                "utf8" => true,
+                "integer" => false,
+                _ => return Err(invalid("unsupported type")),
+            }
+        } else {
+            false
+        };
+
+        match value {
+            Segment::String(chars) => {
+                if size.is_some() {
+                    return Err(invalid("strings can not have a size"));
+                }
+                for c in chars {
+                    if utf8 {
+                        let mut buf = [0; 4];
+                        for &b in c.encode_utf8(&mut buf).as_bytes() {
+                            bits.push(i64::from(b), 8);
+                        }
+                    } else if (c as u32) <= u32::from(std::u8::MAX) {
+                        bits.push(i64::from(c as u32), 8);
+                    } else {
+                        return Err(invalid("character does not fit in a byte"));
+                    }
+                }
+            }
+            Segment::Integer(value) => {
+                if utf8 {
+                    if size.is_some() {
+                        return Err(invalid("utf8 segments can not have a size"));
+                    }
+                    let c = std::char::from_u32(value as u32)
+                        .filter(|_| value >= 0)
+                        .ok_or_else(|| invalid("invalid code point"))?;
+                    let mut buf = [0; 4];
+                    for &b in c.encode_utf8(&mut buf).as_bytes() {
+                        bits.push(i64::from(b), 8);
+                    }
+                } else {
+                    let size = size.unwrap_or(8);
+                    if bits.bits.saturating_add(size) > MAX_BINARY_BITS {
+                        return Err(invalid("binary is too large"));
+                    }
+                    bits.push(value, size);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn number(&mut self, negative: bool) -> ParseResult<Term> {
+        let (line, column) = self.position();
+        let invalid = || ParseError::InvalidNumber { line, column };
+
+        let digits = self.digits(10);
+        let is_float = self.peek() == Some('.')
+            && self
+                .chars
+                .get(self.pos + 1)
+                .map_or(false, |c| c.is_ascii_digit());
+        if is_float {
+            self.pos += 1;
+            let mut text = format!("{}.{}", digits, self.digits(10));
+            if let Some(e) = self.peek().filter(|&c| c == 'e' || c == 'E') {
+                self.pos += 1;
+                text.push(e);
+                if let Some(sign) = self.peek().filter(|&c| c == '-' || c == '+') {
+                    self.pos += 1;
+                    text.push(sign);
+                }
+                let exponent = self.digits(10);
+                if exponent.is_empty() {
+                    return Err(invalid());
+                }
+                text.push_str(&exponent);
+            }
+            let value = text.parse::<f64>().map_err(|_| invalid())?;
+            return Ok(Term::from(Float::from(if negative {
+                -value
+            } else {
+                value
+            })));
+        }
+
+        let (radix, digits) = if self.peek() == Some('#') {
+            self.pos += 1;
+            let radix = digits.parse::<u32>().map_err(|_| invalid())?;
+            if !(2..=36).contains(&radix) {
+                return Err(invalid());
+            }
+            (radix, self.digits(radix))
+        } else {
+            (10, digits)
+        };
+        let mut value = BigInt::parse_bytes(digits.as_bytes(), radix).ok_or_else(invalid)?;
+        if negative {
+            value = -value;
+        }
+        Ok(match value.to_i32() {
+            Some(value) => Term::from(FixInteger::from(value)),
+            None => Term::from(BigInteger { value }),
+        })
+    }
+
+    // Reads digits of the given radix, which may be separated by underscores
+    fn digits(&mut self, radix: u32) -> String {
+        let mut digits = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_digit(radix) {
+                digits.push(c);
+            } else if c == '_'
+                && !digits.is_empty()
+                && self
+                    .chars
+                    .get(self.pos + 1)
+                    .map_or(false, |c| c.is_digit(radix))
+            {
+                // Separator
+            } else {
+                break;
+            }
+            self.pos += 1;
+        }
+        digits
+    }
+
+    // Reads adjacent string literals, which are concatenated
+    fn strings(&mut self) -> ParseResult<Vec<char>> {
+        let mut chars = Vec::new();
+        while self.peek() == Some('"') {
+            self.pos += 1;
+            chars.extend(self.quoted('"')?.chars());
+            self.skip_whitespace();
+        }
+        Ok(chars)
+    }
+
+    // Reads the rest of a quoted atom or string; the opening quote is already consumed
+    fn quoted(&mut self, quote: char) -> ParseResult<String> {
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some(c) if c == quote => return Ok(text),
+                Some('\\') => text.push(self.escape()?),
+                Some(c) => text.push(c),
+                None => return Err(self.eof()),
+            }
+        }
+    }
+
+    // Reads the character of a `$c` literal
+    fn char(&mut self) -> ParseResult<char> {
+        match self.next() {
+            Some('\\') => self.escape(),
+            Some(c) => Ok(c),
+            None => Err(self.eof()),
+        }
+    }
+
+    // Reads an escape sequence; the backslash is already consumed
+    fn escape(&mut self) -> ParseResult<char> {
+        let (line, column) = self.position();
+        let invalid = || ParseError::InvalidEscape { line, column };
+        let c = self.next().ok_or_else(|| self.eof())?;
+        let code = match c {
+            'b' => 8,
+            'd' => 127,
+            'e' => 27,
+            'f' => 12,
+            'n' => 10,
+            'r' => 13,
+            's' => 32,
+            't' => 9,
+            'v' => 11,
+            '^' => {
+                let c = self.next().ok_or_else(|| self.eof())?;
+                (c as u32) & 0x1F
+            }
+            'x' => {
+                let digits = if self.peek() == Some('{') {
+                    self.pos += 1;
+                    let digits = self.take_while(|c| c.is_ascii_hexdigit());
+                    self.expect('}', "'}'")?;
+                    digits
+                } else {
+                    let digits = self.take_while_max(2, |c| c.is_ascii_hexdigit());
+                    if digits.len() != 2 {
+                        return Err(invalid());
+                    }
+                    digits
+                };
+                u32::from_str_radix(&digits, 16).map_err(|_| invalid())?
+            }
+            '0'..='7' => {
+                self.pos -= 1;
+                let digits = self.take_while_max(3, |c| c.is_digit(8));
+                u32::from_str_radix(&digits, 8).map_err(|_| invalid())?
+            }
+            c => c as u32,
+        };
+        std::char::from_u32(code).ok_or_else(invalid)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '%' {
+                while self.peek().map_or(false, |c| c != '\n') {
+                    self.pos += 1;
+                }
+            } else if c.is_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn take_while<F: Fn(char) -> bool>(&mut self, f: F) -> String {
+        self.take_while_max(std::usize::MAX, f)
+    }
+
+    fn take_while_max<F: Fn(char) -> bool>(&mut self, max: usize, f: F) -> String {
+        let start = self.pos;
+        while self.pos - start < max && self.peek().map_or(false, &f) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn expect(&mut self, expected: char, description: &'static str) -> ParseResult<()> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.unexpected(c, description)),
+            None => Err(self.eof()),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    // The line and column of the current position, starting from 1
+    fn position(&self) -> (usize, usize) {
+        let consumed = &self.chars[..std::cmp::min(self.pos, self.chars.len())];
+        let line = consumed.iter().filter(|&&c| c == '\n').count() + 1;
+        let column = consumed.iter().rev().take_while(|&&c| c != '\n').count() + 1;
+        (line, column)
+    }
+
+    fn unexpected(&self, found: char, expected: &'static str) -> ParseError {
+        let (line, column) = self.position();
+        ParseError::UnexpectedChar {
+            line,
+            column,
+            found,
+            expected,
+        }
+    }
+
+    fn eof(&self) -> ParseError {
+        let (line, column) = self.position();
+        ParseError::UnexpectedEof { line, column }
+    }
+}
+
+enum Segment {
+    Integer(i64),
+    String(Vec<char>),
+}
+
+// Packs binary segments, most significant bit first.  The bits of an incomplete last byte are
+// stored in its low bits, as in `BIT_BINARY_EXT`.
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: usize,
+}
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter {
+            bytes: Vec::new(),
+            bits: 0,
+        }
+    }
+
+    fn push(&mut self, value: i64, size: usize) {
+        for i in (0..size).rev() {
+            let bit = if i < 64 {
+                (value >> i) & 1
+            } else if value < 0 {
+                1
+            } else {
+                0
+            };
+            if self.bits % 8 == 0 {
+                self.bytes.push(0);
+            }
+            if bit == 1 {
+                *self.bytes.last_mut().unwrap() |= 0x80 >> (self.bits % 8);
+            }
+            self.bits += 1;
+        }
+    }
+
+    fn into_term(mut self) -> Term {
+        match self.bits % 8 {
+            0 => Term::from(Binary::from(self.bytes)),
+            tail_bits_size => {
+                *self.bytes.last_mut().unwrap() >>= 8 - tail_bits_size;
+                Term::from(BitBinary::from((self.bytes, tail_bits_size as u8)))
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::fmt;
+
+use super::*;
+
+const RESERVED_WORDS: &[&str] = &[
+    "after", "and", "andalso", "band", "begin", "bnot", "bor", "bsl", "bsr", "bxor", "case",
+    "catch", "cond", "div", "else", "end", "fun", "if", "let", "maybe", "not", "of", "or",
+    "orelse", "receive", "rem", "try", "when", "xor",
+];
+
+/// Prints a term as canonical Erlang text through `Display`.
+///
+/// Strings are printed as `"..."` when every element of a list is a printable
+/// Latin-1 character, and binaries as `<<"...">>` (or `<<"..."/utf8>>`) when
+/// their content is printable.
+pub struct Printer<'a> {
+    term: &'a Term,
+}
+impl<'a> Printer<'a> {
+    pub fn new(term: &'a Term) -> Self {
+        Printer { term }
+    }
+}
+impl<'a> fmt::Display for Printer<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_term(f, self.term)
+    }
+}
+
+fn write_term(f: &mut fmt::Formatter, term: &Term) -> fmt::Result {
+    match *term {
+        Term::Atom(ref x) => write_atom(f, &x.name),
+        Term::FixInteger(ref x) => write!(f, "{}", x.value),
+        Term::BigInteger(ref x) => write!(f, "{}", x.value),
+        Term::Float(ref x) => write_float(f, x.value),
+        Term::Binary(ref x) => write_binary(f, &x.bytes, None),
+        Term::BitBinary(ref x) => match x.bytes.split_last() {
+            Some((_, bytes)) if x.tail_bits_size == 0 => write_binary(f, bytes, None),
+            Some((last, bytes)) if x.tail_bits_size < 8 => {
+                let mask = (1u8 << x.tail_bits_size) - 1;
+                write_binary(f, bytes, Some((*last & mask, x.tail_bits_size)))
+            }
+            _ => write_binary(f, &x.bytes, None),
+        },
+        Term::List(ref x) => match printable_string(&x.elements) {
+            Some(s) => write_quoted(f, &s, '"'),
+            None => {
+                write!(f, "[")?;
+                write_elements(f, &x.elements)?;
+                write!(f, "]")
+            }
+        },
+        Term::ImproperList(ref x) => {
+            write!(f, "[")?;
+            write_elements(f, &x.elements)?;
+            write!(f, "|")?;
+            write_term(f, &x.last)?;
+            write!(f, "]")
+        }
+        Term::Tuple(ref x) => {
+            write!(f, "{{")?;
+            write_elements(f, &x.elements)?;
+            write!(f, "}}")
+        }
+        Term::Map(ref x) => {
+            write!(f, "#{{")?;
+            for (i, &(ref k, ref v)) in x.entries.iter().enumerate() {
+                if i != 0 {
+                    write!(f, ",")?;
+                }
+                write_term(f, k)?;
+                write!(f, " => ")?;
+                write_term(f, v)?;
+            }
+            write!(f, "}}")
+        }
+        Term::Pid(ref x) => fmt::Display::fmt(x, f),
+        Term::Port(ref x) => fmt::Display::fmt(x, f),
+        Term::Reference(ref x) => fmt::Display::fmt(x, f),
+        Term::ExternalFun(ref x) => fmt::Display::fmt(x, f),
+        Term::InternalFun(ref x) => fmt::Display::fmt(x, f),
+        Term::Local(ref x) => fmt::Display::fmt(x, f),
+    }
+}
+
+fn write_elements(f: &mut fmt::Formatter, elements: &[Term]) -> fmt::Result {
+    for (i, e) in elements.iter().enumerate() {
+        if i != 0 {
+            write!(f, ",")?;
+        }
+        write_term(f, e)?;
+    }
+    Ok(())
+}
+
+fn write_atom(f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+    let mut chars = name.chars();
+    let bare = chars.next().map_or(false, |c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '@')
+        && !RESERVED_WORDS.contains(&name);
+    if bare {
+        write!(f, "{}", name)
+    } else {
+        write_quoted(f, name, '\'')
+    }
+}
+
+fn write_quoted(f: &mut fmt::Formatter, text: &str, quote: char) -> fmt::Result {
+    write!(f, "{}", quote)?;
+    for c in text.chars() {
+        match c {
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            '\u{0b}' => write!(f, "\\v")?,
+            '\u{08}' => write!(f, "\\b")?,
+            '\u{0c}' => write!(f, "\\f")?,
+            '\u{1b}' => write!(f, "\\e")?,
+            '\u{7f}' => write!(f, "\\d")?,
+            c if c == quote => write!(f, "\\{}", c)?,
+            c if c.is_control() => write!(f, "\\x{{{:X}}}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "{}", quote)
+}
+
+fn write_float(f: &mut fmt::Formatter, value: f64) -> fmt::Result {
+    // Erlang floats always have a fractional part, e.g. `1.0e30` rather than `1e30`
+    let text = format!("{:?}", value);
+    match text.find('e') {
+        Some(i) if !text[..i].contains('.') => write!(f, "{}.0{}", &text[..i], &text[i..]),
+        _ => write!(f, "{}", text),
+    }
+}
+
+fn write_binary(f: &mut fmt::Formatter, bytes: &[u8], tail: Option<(u8, u8)>) -> fmt::Result {
+    write!(f, "<<")?;
+    if tail.is_none() && !bytes.is_empty() {
+        let is_ascii = bytes.iter().all(|b| b.is_ascii());
+        if !is_ascii {
+            if let Ok(text) = std::str::from_utf8(bytes) {
+                if text.chars().all(|c| !c.is_control() || is_printable(c)) {
+                    write_quoted(f, text, '"')?;
+                    return write!(f, "/utf8>>");
+                }
+            }
+        }
+        if bytes.iter().all(|&b| is_printable(char::from(b))) {
+            let text = bytes.iter().map(|&b| char::from(b)).collect::<String>();
+            write_quoted(f, &text, '"')?;
+            return write!(f, ">>");
+        }
+    }
+    for (i, b) in bytes.iter().enumerate() {
+        if i != 0 {
+            write!(f, ",")?;
+        }
+        write!(f, "{}", b)?;
+    }
+    if let Some((last, bits)) = tail {
+        if !bytes.is_empty() {
+            write!(f, ",")?;
+        }
+        write!(f, "{}:{}", last, bits)?;
+    }
+    write!(f, ">>")
+}
+
+// Returns the elements as a string, if they are all printable Latin-1 characters
+fn printable_string(elements: &[Term]) -> Option<String> {
+    if elements.is_empty() {
+        return None;
+    }
+    elements
+        .iter()
+        .map(|e| match *e {
+            Term::FixInteger(ref x) if x.value >= 0 && x.value <= 0xFF => {
+                Some(char::from(x.value as u8)).filter(|&c| is_printable(c))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_printable(c: char) -> bool {
+    match c as u32 {
+        0x20..=0x7E | 0xA0..=0xFF => true,
+        _ => "\n\r\t\u{0b}\u{08}\u{0c}\u{1b}".contains(c),
+    }
+}
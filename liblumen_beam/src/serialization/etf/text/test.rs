@@ -0,0 +1,232 @@
+use num::bigint::BigInt;
+
+use crate::serialization::etf::text::*;
+use crate::serialization::etf::*;
+
+fn atom(name: &str) -> Term {
+    Term::from(Atom::from(name))
+}
+
+fn int(value: i32) -> Term {
+    Term::from(FixInteger::from(value))
+}
+
+fn list(elements: Vec<Term>) -> Term {
+    Term::from(List::from(elements))
+}
+
+fn tuple(elements: Vec<Term>) -> Term {
+    Term::from(Tuple::from(elements))
+}
+
+fn parse(text: &str) -> Term {
+    parse_term(text).unwrap()
+}
+
+fn print(term: &Term) -> String {
+    to_string(term)
+}
+
+#[test]
+fn atom_test() {
+    assert_eq!(atom("foo"), parse("foo"));
+    assert_eq!(atom("foo@bar_Baz1"), parse("foo@bar_Baz1"));
+    assert_eq!(atom("Foo Bar"), parse("'Foo Bar'"));
+    assert_eq!(atom("it's\n"), parse(r"'it\'s\n'"));
+    assert_eq!(atom("\u{1F600}"), parse(r"'\x{1F600}'"));
+
+    assert_eq!("foo", print(&atom("foo")));
+    assert_eq!("'Foo'", print(&atom("Foo")));
+    assert_eq!("''", print(&atom("")));
+    assert_eq!("'case'", print(&atom("case")));
+    assert_eq!(r"'it\'s\n'", print(&atom("it's\n")));
+}
+
+#[test]
+fn number_test() {
+    assert_eq!(int(42), parse("42"));
+    assert_eq!(int(-42), parse("-42"));
+    assert_eq!(int(1_000_000), parse("1_000_000"));
+    assert_eq!(int(255), parse("16#FF"));
+    assert_eq!(int(255), parse("16#ff"));
+    assert_eq!(int(-5), parse("-2#101"));
+    assert_eq!(int(97), parse("$a"));
+    assert_eq!(int(10), parse(r"$\n"));
+    assert_eq!(int(3), parse(r"$\^c"));
+    assert_eq!(int(8), parse(r"$\10"));
+    assert_eq!(
+        Term::from(BigInteger {
+            value: BigInt::from(1) << 64
+        }),
+        parse("18446744073709551616")
+    );
+    assert_eq!(Term::from(Float::from(1.5)), parse("1.5"));
+    assert_eq!(Term::from(Float::from(-2.5e-3)), parse("-2.5E-3"));
+    assert_eq!(Term::from(Float::from(1.0e30)), parse("1.0e+30"));
+
+    assert_eq!("-42", print(&int(-42)));
+    assert_eq!(
+        "18446744073709551616",
+        print(&parse("16#10000000000000000"))
+    );
+    assert_eq!("1.5", print(&Term::from(Float::from(1.5))));
+    assert_eq!("1.0", print(&Term::from(Float::from(1.0))));
+    assert_eq!("1.0e30", print(&Term::from(Float::from(1.0e30))));
+    assert_eq!("-1.5e-7", print(&Term::from(Float::from(-1.5e-7))));
+
+    assert!(parse_term("37#1").is_err());
+    assert!(parse_term("1.e5").is_err());
+}
+
+#[test]
+fn string_test() {
+    assert_eq!(list(vec![int(104), int(105)]), parse(r#""hi""#));
+    assert_eq!(list(vec![int(104), int(105)]), parse(r#""h" "i""#));
+    assert_eq!(Term::from(List::nil()), parse(r#""""#));
+    assert_eq!(list(vec![int(34), int(9)]), parse(r#""\"\t""#));
+
+    assert_eq!(r#""hi""#, print(&list(vec![int(104), int(105)])));
+    assert_eq!(r#""a\"b\n""#, print(&parse(r#""a\"b\n""#)));
+    assert_eq!("[1,2]", print(&list(vec![int(1), int(2)])));
+    assert_eq!("[]", print(&Term::from(List::nil())));
+}
+
+#[test]
+fn binary_test() {
+    let binary = |bytes: &[u8]| Term::from(Binary::from(bytes));
+    assert_eq!(binary(b""), parse("<<>>"));
+    assert_eq!(binary(b"abc"), parse(r#"<<"abc">>"#));
+    assert_eq!(binary(&[1, 2, 3]), parse("<<1,2, 3>>"));
+    assert_eq!(binary(&[0xE9]), parse(r#"<<"é">>"#));
+    assert_eq!(binary("é".as_bytes()), parse(r#"<<"é"/utf8>>"#));
+    assert_eq!(binary(&[0x01, 0x02]), parse("<<258:16>>"));
+    assert_eq!(binary(&[0xFF]), parse("<<-1>>"));
+    assert_eq!(
+        Term::from(BitBinary::from((vec![1, 5], 3))),
+        parse("<<1,5:3>>")
+    );
+    assert!(parse_term(r#"<<"€">>"#).is_err());
+    for text in &["<<1:99999999999>>", "<<1, 0:8388608>>"] {
+        match parse_term(text) {
+            Err(ParseError::InvalidSegment {
+                reason: "binary is too large",
+                ..
+            }) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+    assert_eq!(binary(&vec![0; 1024 * 1024]), parse("<<0:8388608>>"));
+
+    assert_eq!("<<>>", print(&binary(b"")));
+    assert_eq!(r#"<<"abc">>"#, print(&binary(b"abc")));
+    assert_eq!("<<1,2,3>>", print(&binary(&[1, 2, 3])));
+    assert_eq!(r#"<<"é"/utf8>>"#, print(&binary("é".as_bytes())));
+    assert_eq!("<<1,5:3>>", print(&parse("<<1,5:3>>")));
+    assert_eq!(
+        "<<1,2:4>>",
+        print(&Term::from(BitBinary::from((vec![1, 2], 4))))
+    );
+    assert_eq!(
+        "<<1>>",
+        print(&Term::from(BitBinary::from((vec![1, 2], 0))))
+    );
+
+    // The text and the external term format agree on the layout of the tail bits
+    for text in &["<<1,2:4>>", "<<5:3>>", "<<1,2,3,1:1>>"] {
+        let mut buf = Vec::new();
+        parse(text).encode(&mut buf).unwrap();
+        let decoded = Term::decode(std::io::Cursor::new(&buf)).unwrap();
+        assert_eq!(*text, print(&decoded));
+    }
+}
+
+#[test]
+fn compound_test() {
+    assert_eq!(tuple(vec![]), parse("{}"));
+    assert_eq!(tuple(vec![atom("a"), int(1)]), parse("{ a , 1 }"));
+    assert_eq!(list(vec![atom("a"), atom("b")]), parse("[a|[b]]"));
+    assert_eq!(
+        Term::from(ImproperList::from((vec![atom("a"), atom("b")], atom("c")))),
+        parse("[a, b | c]")
+    );
+    assert_eq!(
+        Term::from(Map::from(vec![(atom("a"), int(1)), (int(2), list(vec![]))])),
+        parse("#{a => 1, 2 => []}")
+    );
+    assert_eq!(Term::from(Map::from(vec![])), parse("#{}"));
+
+    assert_eq!("{a,1}", print(&parse("{ a , 1 }")));
+    assert_eq!("[a,b|c]", print(&parse("[a, b | c]")));
+    assert_eq!("#{a => 1,2 => []}", print(&parse("#{a => 1, 2 => []}")));
+}
+
+#[test]
+fn consult_test() {
+    let text = r#"
+        %% Application resource file
+        {application, lumen,
+         [{description, "Lumen"},   % the description
+          {vsn, "0.1.0"},
+          {env, [{port, 8080}]}]}.
+
+        [{kernel, [{logger_level, info}]}].
+    "#;
+    let terms = parse_terms(text).unwrap();
+    assert_eq!(2, terms.len());
+    assert_eq!(
+        "{application,lumen,[{description,\"Lumen\"},{vsn,\"0.1.0\"},{env,[{port,8080}]}]}",
+        print(&terms[0])
+    );
+
+    let printed = to_string_terms(&terms);
+    assert_eq!(terms, parse_terms(&printed).unwrap());
+
+    let path = std::env::temp_dir().join("liblumen_beam_text_consult_test.config");
+    std::fs::write(&path, &printed).unwrap();
+    assert_eq!(terms, consult(&path).unwrap());
+    std::fs::remove_file(&path).unwrap();
+
+    // Terms must be dot terminated
+    assert!(parse_terms("{a, b}").is_err());
+    assert!(parse_terms("a.b.").is_err());
+    assert_eq!(vec![atom("a"), atom("b")], parse_terms("a. b.").unwrap());
+}
+
+#[test]
+fn error_test() {
+    match parse_term("{a,\n X}") {
+        Err(ParseError::Variable { line: 2, column: 2 }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match parse_term("[a b]") {
+        Err(ParseError::UnexpectedChar {
+            line: 1,
+            column: 4,
+            found: 'b',
+            ..
+        }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match parse_term("{a,") {
+        Err(ParseError::UnexpectedEof { .. }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match parse_term(r"'\x1'") {
+        Err(ParseError::InvalidEscape { .. }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match parse_term(&"[".repeat(200_000)) {
+        Err(ParseError::DepthLimitExceeded { .. }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    let nested = format!("{}{}", "{".repeat(100), "}".repeat(100));
+    assert!(parse_term(&nested).is_ok());
+}
+
+#[test]
+fn roundtrip_test() {
+    let text = r#"{'Quoted atom', -1, 1.0e-10, 16#FFFFFFFFFFFFFFFF, $\t, "str\n",
+                   <<"bin">>, <<0, 255>>, <<1:1>>, [1, [2] | 3], #{<<"k">> => {v}}}"#;
+    let term = parse(text);
+    assert_eq!(term, parse(&print(&term)));
+}
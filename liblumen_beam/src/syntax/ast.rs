@@ -16,6 +16,8 @@
 pub mod ast;
 pub mod error;
 pub mod format;
+pub mod visit;
+pub mod visit_mut;
 
 #[cfg(test)]
 mod test;
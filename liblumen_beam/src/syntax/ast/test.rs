@@ -9,3 +9,62 @@ fn it_works() {
         })
         .unwrap();
 }
+
+#[test]
+fn collects_remote_calls() {
+    use crate::syntax::ast::visit::collectors::RemoteCallCollector;
+    use crate::syntax::ast::visit::Visit;
+
+    let ast = AST::from_beam_file("tests/testdata/ast/test.beam").unwrap();
+    let mut collector = RemoteCallCollector::new();
+    collector.visit_module_decl(&ast.module);
+    let calls = collector
+        .calls
+        .iter()
+        .map(|c| format!("{}:{}/{}", c.module, c.function, c.arity))
+        .collect::<Vec<_>>();
+    assert!(calls.contains(&"io:format/2".to_string()), "{:?}", calls);
+}
+
+#[test]
+fn collects_clause_bindings() {
+    use crate::syntax::ast::visit::collectors::ClauseBindingCollector;
+    use crate::syntax::ast::visit::Visit;
+
+    let ast = AST::from_beam_file("tests/testdata/ast/test.beam").unwrap();
+    let mut collector = ClauseBindingCollector::new();
+    collector.visit_module_decl(&ast.module);
+    let bindings = collector
+        .clauses
+        .iter()
+        .map(|c| c.vars.iter().cloned().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert!(bindings.contains(&vec!["Fun", "List"]));
+    assert!(bindings.contains(&vec!["H", "T"]));
+    assert!(bindings.contains(&vec!["Bin"]));
+    assert!(bindings.contains(&vec!["X", "Xs"]));
+}
+
+#[test]
+fn visit_mut_rewrites_in_place() {
+    use crate::syntax::ast::ast::common::Var;
+    use crate::syntax::ast::visit::collectors::ClauseBindingCollector;
+    use crate::syntax::ast::visit::Visit;
+    use crate::syntax::ast::visit_mut::VisitMut;
+
+    struct Upcase;
+    impl VisitMut for Upcase {
+        fn visit_var_mut(&mut self, node: &mut Var) {
+            node.name = node.name.to_uppercase();
+        }
+    }
+
+    let mut ast = AST::from_beam_file("tests/testdata/ast/test.beam").unwrap();
+    Upcase.visit_module_decl_mut(&mut ast.module);
+    let mut collector = ClauseBindingCollector::new();
+    collector.visit_module_decl(&ast.module);
+    assert!(collector
+        .clauses
+        .iter()
+        .any(|c| c.vars.iter().cloned().collect::<Vec<_>>() == vec!["FUN", "LIST"]));
+}
@@ -0,0 +1,847 @@
+//! Read-only traversal of the Abstract Syntax Tree.
+//!
+//! Every method of `Visit` has a default implementation which recursively
+//! visits the children of the node by calling the corresponding `walk_*` function.
+//! Implementors override only the methods for the nodes they are interested in,
+//! and call the `walk_*` function from the override to keep descending.
+//!
+//! See also `visit_mut` for the mutable counterpart.
+//!
+//! # Examples
+//!
+//!     use liblumen_beam::syntax::ast::AST;
+//!     use liblumen_beam::syntax::ast::ast::common::Var;
+//!     use liblumen_beam::syntax::ast::visit::Visit;
+//!
+//!     struct VarCount(usize);
+//!     impl<'ast> Visit<'ast> for VarCount {
+//!         fn visit_var(&mut self, _var: &'ast Var) {
+//!             self.0 += 1;
+//!         }
+//!     }
+//!
+//!     let ast = AST::from_beam_file("tests/testdata/ast/test.beam").unwrap();
+//!     let mut count = VarCount(0);
+//!     count.visit_module_decl(&ast.module);
+//!     assert!(count.0 > 0);
+//!
+use crate::syntax::ast::ast::clause;
+use crate::syntax::ast::ast::common;
+use crate::syntax::ast::ast::expr;
+use crate::syntax::ast::ast::form;
+use crate::syntax::ast::ast::guard;
+use crate::syntax::ast::ast::literal;
+use crate::syntax::ast::ast::pat;
+use crate::syntax::ast::ast::ty;
+use crate::syntax::ast::ast::ModuleDecl;
+
+pub mod collectors;
+
+pub trait Visit<'ast> {
+    // Module and forms
+    fn visit_module_decl(&mut self, node: &'ast ModuleDecl) {
+        walk_module_decl(self, node)
+    }
+    fn visit_form(&mut self, node: &'ast form::Form) {
+        walk_form(self, node)
+    }
+    fn visit_module_attr(&mut self, _node: &'ast form::ModuleAttr) {}
+    fn visit_behaviour_attr(&mut self, _node: &'ast form::BehaviourAttr) {}
+    fn visit_export_attr(&mut self, _node: &'ast form::ExportAttr) {}
+    fn visit_import_attr(&mut self, _node: &'ast form::ImportAttr) {}
+    fn visit_export_type_attr(&mut self, _node: &'ast form::ExportTypeAttr) {}
+    fn visit_compile_options_attr(&mut self, _node: &'ast form::CompileOptionsAttr) {}
+    fn visit_file_attr(&mut self, _node: &'ast form::FileAttr) {}
+    fn visit_record_decl(&mut self, node: &'ast form::RecordDecl) {
+        walk_record_decl(self, node)
+    }
+    fn visit_record_field_decl(&mut self, node: &'ast form::RecordFieldDecl) {
+        walk_record_field_decl(self, node)
+    }
+    fn visit_type_decl(&mut self, node: &'ast form::TypeDecl) {
+        walk_type_decl(self, node)
+    }
+    fn visit_fun_spec(&mut self, node: &'ast form::FunSpec) {
+        walk_fun_spec(self, node)
+    }
+    fn visit_wild_attr(&mut self, _node: &'ast form::WildAttr) {}
+    fn visit_fun_decl(&mut self, node: &'ast form::FunDecl) {
+        walk_fun_decl(self, node)
+    }
+    fn visit_eof(&mut self, _node: &'ast form::Eof) {}
+
+    // Clauses
+    fn visit_clause(&mut self, node: &'ast clause::Clause) {
+        walk_clause(self, node)
+    }
+
+    // Nodes shared by expressions, patterns, guards and types
+    fn visit_integer(&mut self, _node: &'ast literal::Integer) {}
+    fn visit_float(&mut self, _node: &'ast literal::Float) {}
+    fn visit_str(&mut self, _node: &'ast literal::Str) {}
+    fn visit_char(&mut self, _node: &'ast literal::Char) {}
+    fn visit_atom(&mut self, _node: &'ast literal::Atom) {}
+    fn visit_var(&mut self, _node: &'ast common::Var) {}
+    fn visit_nil(&mut self, _node: &'ast common::Nil) {}
+
+    // Expressions
+    fn visit_expression(&mut self, node: &'ast expr::Expression) {
+        walk_expression(self, node)
+    }
+    fn visit_expr_match(&mut self, node: &'ast expr::Match) {
+        walk_expr_match(self, node)
+    }
+    fn visit_expr_tuple(&mut self, node: &'ast expr::Tuple) {
+        walk_expr_tuple(self, node)
+    }
+    fn visit_expr_cons(&mut self, node: &'ast expr::Cons) {
+        walk_expr_cons(self, node)
+    }
+    fn visit_expr_binary(&mut self, node: &'ast expr::Binary) {
+        walk_expr_binary(self, node)
+    }
+    fn visit_expr_unary_op(&mut self, node: &'ast expr::UnaryOp) {
+        walk_expr_unary_op(self, node)
+    }
+    fn visit_expr_binary_op(&mut self, node: &'ast expr::BinaryOp) {
+        walk_expr_binary_op(self, node)
+    }
+    fn visit_expr_record(&mut self, node: &'ast expr::Record) {
+        walk_expr_record(self, node)
+    }
+    fn visit_expr_record_index(&mut self, node: &'ast expr::RecordIndex) {
+        walk_expr_record_index(self, node)
+    }
+    fn visit_expr_map(&mut self, node: &'ast expr::Map) {
+        walk_expr_map(self, node)
+    }
+    fn visit_catch(&mut self, node: &'ast expr::Catch) {
+        walk_catch(self, node)
+    }
+    fn visit_local_call(&mut self, node: &'ast expr::LocalCall) {
+        walk_local_call(self, node)
+    }
+    fn visit_remote_call(&mut self, node: &'ast expr::RemoteCall) {
+        walk_remote_call(self, node)
+    }
+    fn visit_comprehension(&mut self, node: &'ast expr::Comprehension) {
+        walk_comprehension(self, node)
+    }
+    fn visit_qualifier(&mut self, node: &'ast expr::Qualifier) {
+        walk_qualifier(self, node)
+    }
+    fn visit_generator(&mut self, node: &'ast expr::Generator) {
+        walk_generator(self, node)
+    }
+    fn visit_block(&mut self, node: &'ast expr::Block) {
+        walk_block(self, node)
+    }
+    fn visit_if(&mut self, node: &'ast expr::If) {
+        walk_if(self, node)
+    }
+    fn visit_case(&mut self, node: &'ast expr::Case) {
+        walk_case(self, node)
+    }
+    fn visit_try(&mut self, node: &'ast expr::Try) {
+        walk_try(self, node)
+    }
+    fn visit_receive(&mut self, node: &'ast expr::Receive) {
+        walk_receive(self, node)
+    }
+    fn visit_internal_fun(&mut self, _node: &'ast common::InternalFun) {}
+    fn visit_external_fun(&mut self, node: &'ast common::ExternalFun) {
+        walk_external_fun(self, node)
+    }
+    fn visit_anonymous_fun(&mut self, node: &'ast expr::AnonymousFun) {
+        walk_anonymous_fun(self, node)
+    }
+
+    // Patterns
+    fn visit_pattern(&mut self, node: &'ast pat::Pattern) {
+        walk_pattern(self, node)
+    }
+    fn visit_pat_match(&mut self, node: &'ast pat::Match) {
+        walk_pat_match(self, node)
+    }
+    fn visit_pat_tuple(&mut self, node: &'ast pat::Tuple) {
+        walk_pat_tuple(self, node)
+    }
+    fn visit_pat_cons(&mut self, node: &'ast pat::Cons) {
+        walk_pat_cons(self, node)
+    }
+    fn visit_pat_binary(&mut self, node: &'ast pat::Binary) {
+        walk_pat_binary(self, node)
+    }
+    fn visit_pat_unary_op(&mut self, node: &'ast pat::UnaryOp) {
+        walk_pat_unary_op(self, node)
+    }
+    fn visit_pat_binary_op(&mut self, node: &'ast pat::BinaryOp) {
+        walk_pat_binary_op(self, node)
+    }
+    fn visit_pat_record(&mut self, node: &'ast pat::Record) {
+        walk_pat_record(self, node)
+    }
+    fn visit_pat_record_index(&mut self, node: &'ast pat::RecordIndex) {
+        walk_pat_record_index(self, node)
+    }
+    fn visit_pat_map(&mut self, node: &'ast pat::Map) {
+        walk_pat_map(self, node)
+    }
+
+    // Guards
+    fn visit_or_guard(&mut self, node: &'ast guard::OrGuard) {
+        walk_or_guard(self, node)
+    }
+    fn visit_guard(&mut self, node: &'ast guard::Guard) {
+        walk_guard(self, node)
+    }
+    fn visit_guard_tuple(&mut self, node: &'ast guard::Tuple) {
+        walk_guard_tuple(self, node)
+    }
+    fn visit_guard_cons(&mut self, node: &'ast guard::Cons) {
+        walk_guard_cons(self, node)
+    }
+    fn visit_guard_binary(&mut self, node: &'ast guard::Binary) {
+        walk_guard_binary(self, node)
+    }
+    fn visit_guard_unary_op(&mut self, node: &'ast guard::UnaryOp) {
+        walk_guard_unary_op(self, node)
+    }
+    fn visit_guard_binary_op(&mut self, node: &'ast guard::BinaryOp) {
+        walk_guard_binary_op(self, node)
+    }
+    fn visit_guard_record(&mut self, node: &'ast guard::Record) {
+        walk_guard_record(self, node)
+    }
+    fn visit_guard_record_index(&mut self, node: &'ast guard::RecordIndex) {
+        walk_guard_record_index(self, node)
+    }
+    fn visit_guard_local_call(&mut self, node: &'ast guard::LocalCall) {
+        walk_guard_local_call(self, node)
+    }
+    fn visit_guard_remote_call(&mut self, node: &'ast guard::RemoteCall) {
+        walk_guard_remote_call(self, node)
+    }
+
+    // Types
+    fn visit_type(&mut self, node: &'ast ty::Type) {
+        walk_type(self, node)
+    }
+    fn visit_ty_annotated(&mut self, node: &'ast ty::Annotated) {
+        walk_ty_annotated(self, node)
+    }
+    fn visit_ty_unary_op(&mut self, node: &'ast ty::UnaryOp) {
+        walk_ty_unary_op(self, node)
+    }
+    fn visit_ty_binary_op(&mut self, node: &'ast ty::BinaryOp) {
+        walk_ty_binary_op(self, node)
+    }
+    fn visit_ty_bit_string(&mut self, _node: &'ast ty::BitString) {}
+    fn visit_ty_any_fun(&mut self, node: &'ast ty::AnyFun) {
+        walk_ty_any_fun(self, node)
+    }
+    fn visit_ty_fun(&mut self, node: &'ast ty::Fun) {
+        walk_ty_fun(self, node)
+    }
+    fn visit_ty_constraint(&mut self, node: &'ast ty::Constraint) {
+        walk_ty_constraint(self, node)
+    }
+    fn visit_ty_range(&mut self, node: &'ast ty::Range) {
+        walk_ty_range(self, node)
+    }
+    fn visit_ty_map(&mut self, node: &'ast ty::Map) {
+        walk_ty_map(self, node)
+    }
+    fn visit_ty_map_pair(&mut self, node: &'ast ty::MapPair) {
+        walk_ty_map_pair(self, node)
+    }
+    fn visit_ty_built_in(&mut self, node: &'ast ty::BuiltInType) {
+        walk_ty_built_in(self, node)
+    }
+    fn visit_ty_record(&mut self, node: &'ast ty::Record) {
+        walk_ty_record(self, node)
+    }
+    fn visit_ty_record_field(&mut self, node: &'ast ty::RecordField) {
+        walk_ty_record_field(self, node)
+    }
+    fn visit_ty_remote(&mut self, node: &'ast ty::RemoteType) {
+        walk_ty_remote(self, node)
+    }
+    fn visit_ty_any_tuple(&mut self, _node: &'ast ty::AnyTuple) {}
+    fn visit_ty_tuple(&mut self, node: &'ast ty::Tuple) {
+        walk_ty_tuple(self, node)
+    }
+    fn visit_ty_union(&mut self, node: &'ast ty::Union) {
+        walk_ty_union(self, node)
+    }
+    fn visit_ty_user(&mut self, node: &'ast ty::UserType) {
+        walk_ty_user(self, node)
+    }
+}
+
+pub fn walk_module_decl<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ModuleDecl) {
+    for form in &node.forms {
+        v.visit_form(form);
+    }
+}
+
+pub fn walk_form<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast form::Form) {
+    match *node {
+        form::Form::Module(ref x) => v.visit_module_attr(x),
+        form::Form::Behaviour(ref x) => v.visit_behaviour_attr(x),
+        form::Form::Export(ref x) => v.visit_export_attr(x),
+        form::Form::Import(ref x) => v.visit_import_attr(x),
+        form::Form::ExportType(ref x) => v.visit_export_type_attr(x),
+        form::Form::Compile(ref x) => v.visit_compile_options_attr(x),
+        form::Form::File(ref x) => v.visit_file_attr(x),
+        form::Form::Record(ref x) => v.visit_record_decl(x),
+        form::Form::Type(ref x) => v.visit_type_decl(x),
+        form::Form::Spec(ref x) => v.visit_fun_spec(x),
+        form::Form::Attr(ref x) => v.visit_wild_attr(x),
+        form::Form::Fun(ref x) => v.visit_fun_decl(x),
+        form::Form::Eof(ref x) => v.visit_eof(x),
+    }
+}
+
+pub fn walk_record_decl<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast form::RecordDecl) {
+    for field in &node.fields {
+        v.visit_record_field_decl(field);
+    }
+}
+
+pub fn walk_record_field_decl<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast form::RecordFieldDecl,
+) {
+    v.visit_type(&node.ty);
+    v.visit_expression(&node.default_value);
+}
+
+pub fn walk_type_decl<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast form::TypeDecl) {
+    for var in &node.vars {
+        v.visit_var(var);
+    }
+    v.visit_type(&node.ty);
+}
+
+pub fn walk_fun_spec<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast form::FunSpec) {
+    for ty in &node.types {
+        v.visit_ty_fun(ty);
+    }
+}
+
+pub fn walk_fun_decl<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast form::FunDecl) {
+    for clause in &node.clauses {
+        v.visit_clause(clause);
+    }
+}
+
+pub fn walk_clause<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast clause::Clause) {
+    for pattern in &node.patterns {
+        v.visit_pattern(pattern);
+    }
+    for guard in &node.guards {
+        v.visit_or_guard(guard);
+    }
+    for expr in &node.body {
+        v.visit_expression(expr);
+    }
+}
+
+pub fn walk_expression<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Expression) {
+    use crate::syntax::ast::ast::expr::Expression as E;
+    match *node {
+        E::Integer(ref x) => v.visit_integer(x),
+        E::Float(ref x) => v.visit_float(x),
+        E::String(ref x) => v.visit_str(x),
+        E::Char(ref x) => v.visit_char(x),
+        E::Atom(ref x) => v.visit_atom(x),
+        E::Match(ref x) => v.visit_expr_match(x),
+        E::Var(ref x) => v.visit_var(x),
+        E::Tuple(ref x) => v.visit_expr_tuple(x),
+        E::Nil(ref x) => v.visit_nil(x),
+        E::Cons(ref x) => v.visit_expr_cons(x),
+        E::Binary(ref x) => v.visit_expr_binary(x),
+        E::UnaryOp(ref x) => v.visit_expr_unary_op(x),
+        E::BinaryOp(ref x) => v.visit_expr_binary_op(x),
+        E::Record(ref x) => v.visit_expr_record(x),
+        E::RecordIndex(ref x) => v.visit_expr_record_index(x),
+        E::Map(ref x) => v.visit_expr_map(x),
+        E::Catch(ref x) => v.visit_catch(x),
+        E::LocalCall(ref x) => v.visit_local_call(x),
+        E::RemoteCall(ref x) => v.visit_remote_call(x),
+        E::Comprehension(ref x) => v.visit_comprehension(x),
+        E::Block(ref x) => v.visit_block(x),
+        E::If(ref x) => v.visit_if(x),
+        E::Case(ref x) => v.visit_case(x),
+        E::Try(ref x) => v.visit_try(x),
+        E::Receive(ref x) => v.visit_receive(x),
+        E::InternalFun(ref x) => v.visit_internal_fun(x),
+        E::ExternalFun(ref x) => v.visit_external_fun(x),
+        E::AnonymousFun(ref x) => v.visit_anonymous_fun(x),
+    }
+}
+
+pub fn walk_expr_match<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Match) {
+    v.visit_pattern(&node.left);
+    v.visit_expression(&node.right);
+}
+
+pub fn walk_expr_tuple<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Tuple) {
+    for element in &node.elements {
+        v.visit_expression(element);
+    }
+}
+
+pub fn walk_expr_cons<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Cons) {
+    v.visit_expression(&node.head);
+    v.visit_expression(&node.tail);
+}
+
+pub fn walk_expr_binary<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Binary) {
+    for element in &node.elements {
+        v.visit_expression(&element.element);
+        if let Some(ref size) = element.size {
+            v.visit_expression(size);
+        }
+    }
+}
+
+pub fn walk_expr_unary_op<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::UnaryOp) {
+    v.visit_expression(&node.operand);
+}
+
+pub fn walk_expr_binary_op<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::BinaryOp) {
+    v.visit_expression(&node.left_operand);
+    v.visit_expression(&node.right_operand);
+}
+
+pub fn walk_expr_record<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Record) {
+    if let Some(ref base) = node.base {
+        v.visit_expression(base);
+    }
+    for field in &node.fields {
+        v.visit_expression(&field.value);
+    }
+}
+
+pub fn walk_expr_record_index<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast expr::RecordIndex,
+) {
+    if let Some(ref base) = node.base {
+        v.visit_expression(base);
+    }
+}
+
+pub fn walk_expr_map<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Map) {
+    if let Some(ref base) = node.base {
+        v.visit_expression(base);
+    }
+    for pair in &node.pairs {
+        v.visit_expression(&pair.key);
+        v.visit_expression(&pair.value);
+    }
+}
+
+pub fn walk_catch<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Catch) {
+    v.visit_expression(&node.expr);
+}
+
+pub fn walk_local_call<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::LocalCall) {
+    v.visit_expression(&node.function);
+    for arg in &node.args {
+        v.visit_expression(arg);
+    }
+}
+
+pub fn walk_remote_call<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::RemoteCall) {
+    v.visit_expression(&node.module);
+    v.visit_expression(&node.function);
+    for arg in &node.args {
+        v.visit_expression(arg);
+    }
+}
+
+pub fn walk_comprehension<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast expr::Comprehension,
+) {
+    for qualifier in &node.qualifiers {
+        v.visit_qualifier(qualifier);
+    }
+    v.visit_expression(&node.expr);
+}
+
+pub fn walk_qualifier<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Qualifier) {
+    match *node {
+        expr::Qualifier::Generator(ref x) => v.visit_generator(x),
+        expr::Qualifier::BitStringGenerator(ref x) => v.visit_generator(x),
+        expr::Qualifier::Filter(ref x) => v.visit_expression(x),
+    }
+}
+
+pub fn walk_generator<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Generator) {
+    v.visit_expression(&node.expr);
+    v.visit_pattern(&node.pattern);
+}
+
+pub fn walk_block<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Block) {
+    for expr in &node.body {
+        v.visit_expression(expr);
+    }
+}
+
+pub fn walk_if<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::If) {
+    for clause in &node.clauses {
+        v.visit_clause(clause);
+    }
+}
+
+pub fn walk_case<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Case) {
+    v.visit_expression(&node.expr);
+    for clause in &node.clauses {
+        v.visit_clause(clause);
+    }
+}
+
+pub fn walk_try<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Try) {
+    for expr in &node.body {
+        v.visit_expression(expr);
+    }
+    for clause in &node.case_clauses {
+        v.visit_clause(clause);
+    }
+    for clause in &node.catch_clauses {
+        v.visit_clause(clause);
+    }
+    for expr in &node.after {
+        v.visit_expression(expr);
+    }
+}
+
+pub fn walk_receive<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast expr::Receive) {
+    for clause in &node.clauses {
+        v.visit_clause(clause);
+    }
+    if let Some(ref timeout) = node.timeout {
+        v.visit_expression(timeout);
+    }
+    for expr in &node.after {
+        v.visit_expression(expr);
+    }
+}
+
+pub fn walk_external_fun<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast common::ExternalFun,
+) {
+    v.visit_expression(&node.module);
+    v.visit_expression(&node.function);
+    v.visit_expression(&node.arity);
+}
+
+pub fn walk_anonymous_fun<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast expr::AnonymousFun,
+) {
+    for clause in &node.clauses {
+        v.visit_clause(clause);
+    }
+}
+
+pub fn walk_pattern<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast pat::Pattern) {
+    use crate::syntax::ast::ast::pat::Pattern as P;
+    match *node {
+        P::Integer(ref x) => v.visit_integer(x),
+        P::Float(ref x) => v.visit_float(x),
+        P::String(ref x) => v.visit_str(x),
+        P::Char(ref x) => v.visit_char(x),
+        P::Atom(ref x) => v.visit_atom(x),
+        P::Var(ref x) => v.visit_var(x),
+        P::Match(ref x) => v.visit_pat_match(x),
+        P::Tuple(ref x) => v.visit_pat_tuple(x),
+        P::Nil(ref x) => v.visit_nil(x),
+        P::Cons(ref x) => v.visit_pat_cons(x),
+        P::Binary(ref x) => v.visit_pat_binary(x),
+        P::UnaryOp(ref x) => v.visit_pat_unary_op(x),
+        P::BinaryOp(ref x) => v.visit_pat_binary_op(x),
+        P::Record(ref x) => v.visit_pat_record(x),
+        P::RecordIndex(ref x) => v.visit_pat_record_index(x),
+        P::Map(ref x) => v.visit_pat_map(x),
+    }
+}
+
+pub fn walk_pat_match<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast pat::Match) {
+    v.visit_pattern(&node.left);
+    v.visit_pattern(&node.right);
+}
+
+pub fn walk_pat_tuple<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast pat::Tuple) {
+    for element in &node.elements {
+        v.visit_pattern(element);
+    }
+}
+
+pub fn walk_pat_cons<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast pat::Cons) {
+    v.visit_pattern(&node.head);
+    v.visit_pattern(&node.tail);
+}
+
+pub fn walk_pat_binary<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast pat::Binary) {
+    for element in &node.elements {
+        v.visit_pattern(&element.element);
+        if let Some(ref size) = element.size {
+            v.visit_pattern(size);
+        }
+    }
+}
+
+pub fn walk_pat_unary_op<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast pat::UnaryOp) {
+    v.visit_pattern(&node.operand);
+}
+
+pub fn walk_pat_binary_op<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast pat::BinaryOp) {
+    v.visit_pattern(&node.left_operand);
+    v.visit_pattern(&node.right_operand);
+}
+
+pub fn walk_pat_record<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast pat::Record) {
+    if let Some(ref base) = node.base {
+        v.visit_expression(base);
+    }
+    for field in &node.fields {
+        v.visit_pattern(&field.value);
+    }
+}
+
+pub fn walk_pat_record_index<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast pat::RecordIndex,
+) {
+    if let Some(ref base) = node.base {
+        v.visit_pattern(base);
+    }
+}
+
+pub fn walk_pat_map<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast pat::Map) {
+    if let Some(ref base) = node.base {
+        v.visit_expression(base);
+    }
+    for pair in &node.pairs {
+        v.visit_pattern(&pair.key);
+        v.visit_pattern(&pair.value);
+    }
+}
+
+pub fn walk_or_guard<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast guard::OrGuard) {
+    for guard in &node.and_guards {
+        v.visit_guard(guard);
+    }
+}
+
+pub fn walk_guard<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast guard::Guard) {
+    use crate::syntax::ast::ast::guard::Guard as G;
+    match *node {
+        G::Integer(ref x) => v.visit_integer(x),
+        G::Float(ref x) => v.visit_float(x),
+        G::String(ref x) => v.visit_str(x),
+        G::Char(ref x) => v.visit_char(x),
+        G::Atom(ref x) => v.visit_atom(x),
+        G::Var(ref x) => v.visit_var(x),
+        G::Tuple(ref x) => v.visit_guard_tuple(x),
+        G::Nil(ref x) => v.visit_nil(x),
+        G::Cons(ref x) => v.visit_guard_cons(x),
+        G::Binary(ref x) => v.visit_guard_binary(x),
+        G::UnaryOp(ref x) => v.visit_guard_unary_op(x),
+        G::BinaryOp(ref x) => v.visit_guard_binary_op(x),
+        G::Record(ref x) => v.visit_guard_record(x),
+        G::RecordIndex(ref x) => v.visit_guard_record_index(x),
+        G::LocalCall(ref x) => v.visit_guard_local_call(x),
+        G::RemoteCall(ref x) => v.visit_guard_remote_call(x),
+    }
+}
+
+pub fn walk_guard_tuple<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast guard::Tuple) {
+    for element in &node.elements {
+        v.visit_guard(element);
+    }
+}
+
+pub fn walk_guard_cons<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast guard::Cons) {
+    v.visit_guard(&node.head);
+    v.visit_guard(&node.tail);
+}
+
+pub fn walk_guard_binary<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast guard::Binary) {
+    for element in &node.elements {
+        v.visit_guard(&element.element);
+        if let Some(ref size) = element.size {
+            v.visit_guard(size);
+        }
+    }
+}
+
+pub fn walk_guard_unary_op<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast guard::UnaryOp) {
+    v.visit_guard(&node.operand);
+}
+
+pub fn walk_guard_binary_op<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast guard::BinaryOp) {
+    v.visit_guard(&node.left_operand);
+    v.visit_guard(&node.right_operand);
+}
+
+pub fn walk_guard_record<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast guard::Record) {
+    if let Some(ref base) = node.base {
+        v.visit_expression(base);
+    }
+    for field in &node.fields {
+        v.visit_guard(&field.value);
+    }
+}
+
+pub fn walk_guard_record_index<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast guard::RecordIndex,
+) {
+    if let Some(ref base) = node.base {
+        v.visit_guard(base);
+    }
+}
+
+pub fn walk_guard_local_call<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast guard::LocalCall,
+) {
+    v.visit_guard(&node.function);
+    for arg in &node.args {
+        v.visit_guard(arg);
+    }
+}
+
+pub fn walk_guard_remote_call<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast guard::RemoteCall,
+) {
+    v.visit_guard(&node.module);
+    v.visit_guard(&node.function);
+    for arg in &node.args {
+        v.visit_guard(arg);
+    }
+}
+
+pub fn walk_type<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::Type) {
+    use crate::syntax::ast::ast::ty::Type as T;
+    match *node {
+        T::Atom(ref x) => v.visit_atom(x),
+        T::Integer(ref x) => v.visit_integer(x),
+        T::Var(ref x) => v.visit_var(x),
+        T::Annotated(ref x) => v.visit_ty_annotated(x),
+        T::UnaryOp(ref x) => v.visit_ty_unary_op(x),
+        T::BinaryOp(ref x) => v.visit_ty_binary_op(x),
+        T::BitString(ref x) => v.visit_ty_bit_string(x),
+        T::Nil(ref x) => v.visit_nil(x),
+        T::AnyFun(ref x) => v.visit_ty_any_fun(x),
+        T::Function(ref x) => v.visit_ty_fun(x),
+        T::Range(ref x) => v.visit_ty_range(x),
+        T::Map(ref x) => v.visit_ty_map(x),
+        T::BuiltIn(ref x) => v.visit_ty_built_in(x),
+        T::Record(ref x) => v.visit_ty_record(x),
+        T::Remote(ref x) => v.visit_ty_remote(x),
+        T::AnyTuple(ref x) => v.visit_ty_any_tuple(x),
+        T::Tuple(ref x) => v.visit_ty_tuple(x),
+        T::Union(ref x) => v.visit_ty_union(x),
+        T::User(ref x) => v.visit_ty_user(x),
+    }
+}
+
+pub fn walk_ty_annotated<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::Annotated) {
+    v.visit_var(&node.name);
+    v.visit_type(&node.ty);
+}
+
+pub fn walk_ty_unary_op<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::UnaryOp) {
+    v.visit_type(&node.operand);
+}
+
+pub fn walk_ty_binary_op<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::BinaryOp) {
+    v.visit_type(&node.left_operand);
+    v.visit_type(&node.right_operand);
+}
+
+pub fn walk_ty_any_fun<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::AnyFun) {
+    if let Some(ref return_type) = node.return_type {
+        v.visit_type(return_type);
+    }
+}
+
+pub fn walk_ty_fun<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::Fun) {
+    for arg in &node.args {
+        v.visit_type(arg);
+    }
+    v.visit_type(&node.return_type);
+    for constraint in &node.constraints {
+        v.visit_ty_constraint(constraint);
+    }
+}
+
+pub fn walk_ty_constraint<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::Constraint) {
+    v.visit_var(&node.var);
+    v.visit_type(&node.subtype);
+}
+
+pub fn walk_ty_range<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::Range) {
+    v.visit_type(&node.low);
+    v.visit_type(&node.high);
+}
+
+pub fn walk_ty_map<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::Map) {
+    for pair in &node.pairs {
+        v.visit_ty_map_pair(pair);
+    }
+}
+
+pub fn walk_ty_map_pair<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::MapPair) {
+    v.visit_type(&node.key);
+    v.visit_type(&node.value);
+}
+
+pub fn walk_ty_built_in<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::BuiltInType) {
+    for arg in &node.args {
+        v.visit_type(arg);
+    }
+}
+
+pub fn walk_ty_record<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::Record) {
+    for field in &node.fields {
+        v.visit_ty_record_field(field);
+    }
+}
+
+pub fn walk_ty_record_field<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::RecordField) {
+    v.visit_type(&node.ty);
+}
+
+pub fn walk_ty_remote<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::RemoteType) {
+    for arg in &node.args {
+        v.visit_type(arg);
+    }
+}
+
+pub fn walk_ty_tuple<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::Tuple) {
+    for element in &node.elements {
+        v.visit_type(element);
+    }
+}
+
+pub fn walk_ty_union<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::Union) {
+    for ty in &node.types {
+        v.visit_type(ty);
+    }
+}
+
+pub fn walk_ty_user<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ty::UserType) {
+    for arg in &node.args {
+        v.visit_type(arg);
+    }
+}
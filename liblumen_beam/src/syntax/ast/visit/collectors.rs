@@ -0,0 +1,139 @@
+//! Ready-made visitors for common analyses.
+use std::collections::BTreeSet;
+
+use crate::syntax::ast::ast::clause;
+use crate::syntax::ast::ast::common;
+use crate::syntax::ast::ast::expr;
+use crate::syntax::ast::ast::guard;
+use crate::syntax::ast::ast::pat;
+use crate::syntax::ast::ast::{Arity, LineNum};
+use crate::syntax::ast::visit::{self, Visit};
+
+/// A statically resolvable `Module:Function(Args...)` call site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteCallSite {
+    pub line: LineNum,
+    pub module: String,
+    pub function: String,
+    pub arity: Arity,
+}
+
+/// Collects every remote call whose module and function are literal atoms.
+///
+/// Calls in guards are included. Dynamic calls such as `M:F(X)` are skipped,
+/// but their arguments are still visited.
+///
+/// # Examples
+///
+///     use liblumen_beam::syntax::ast::AST;
+///     use liblumen_beam::syntax::ast::visit::Visit;
+///     use liblumen_beam::syntax::ast::visit::collectors::RemoteCallCollector;
+///
+///     let ast = AST::from_beam_file("tests/testdata/ast/test.beam").unwrap();
+///     let mut collector = RemoteCallCollector::new();
+///     collector.visit_module_decl(&ast.module);
+///     assert!(collector.calls.iter().any(|c| c.module == "io" && c.function == "format"));
+///
+#[derive(Debug, Default)]
+pub struct RemoteCallCollector {
+    pub calls: Vec<RemoteCallSite>,
+}
+impl RemoteCallCollector {
+    pub fn new() -> Self {
+        RemoteCallCollector { calls: Vec::new() }
+    }
+}
+impl<'ast> Visit<'ast> for RemoteCallCollector {
+    fn visit_remote_call(&mut self, node: &'ast expr::RemoteCall) {
+        if let (expr::Expression::Atom(ref m), expr::Expression::Atom(ref f)) =
+            (&node.module, &node.function)
+        {
+            self.calls.push(RemoteCallSite {
+                line: node.line,
+                module: m.value.clone(),
+                function: f.value.clone(),
+                arity: node.args.len() as Arity,
+            });
+        }
+        visit::walk_remote_call(self, node);
+    }
+    fn visit_guard_remote_call(&mut self, node: &'ast guard::RemoteCall) {
+        if let (guard::Guard::Atom(ref m), guard::Guard::Atom(ref f)) =
+            (&node.module, &node.function)
+        {
+            self.calls.push(RemoteCallSite {
+                line: node.line,
+                module: m.value.clone(),
+                function: f.value.clone(),
+                arity: node.args.len() as Arity,
+            });
+        }
+        visit::walk_guard_remote_call(self, node);
+    }
+}
+
+/// The variables bound by a single clause.
+#[derive(Debug)]
+pub struct ClauseBindings<'ast> {
+    pub clause: &'ast clause::Clause,
+    pub vars: BTreeSet<&'ast str>,
+}
+
+/// Collects, for every clause in the tree, the variables it binds.
+///
+/// A variable is considered bound by a clause if it occurs in one of the clause's
+/// head patterns, or in the left-hand side of a match expression in its body.
+/// Nested clauses (e.g. of `case` or `fun`) get their own entry, and the patterns
+/// of comprehension generators are not attributed to any clause.
+/// The anonymous variable `_` is never reported.
+///
+/// Entries are ordered by the position of the clause in the tree (pre-order).
+#[derive(Debug, Default)]
+pub struct ClauseBindingCollector<'ast> {
+    pub clauses: Vec<ClauseBindings<'ast>>,
+    stack: Vec<usize>,
+    in_pattern: bool,
+}
+impl<'ast> ClauseBindingCollector<'ast> {
+    pub fn new() -> Self {
+        ClauseBindingCollector {
+            clauses: Vec::new(),
+            stack: Vec::new(),
+            in_pattern: false,
+        }
+    }
+}
+impl<'ast> Visit<'ast> for ClauseBindingCollector<'ast> {
+    fn visit_clause(&mut self, node: &'ast clause::Clause) {
+        self.stack.push(self.clauses.len());
+        self.clauses.push(ClauseBindings {
+            clause: node,
+            vars: BTreeSet::new(),
+        });
+        visit::walk_clause(self, node);
+        self.stack.pop();
+    }
+    fn visit_pattern(&mut self, node: &'ast pat::Pattern) {
+        let in_pattern = self.in_pattern;
+        self.in_pattern = true;
+        visit::walk_pattern(self, node);
+        self.in_pattern = in_pattern;
+    }
+    fn visit_expression(&mut self, node: &'ast expr::Expression) {
+        let in_pattern = self.in_pattern;
+        self.in_pattern = false;
+        visit::walk_expression(self, node);
+        self.in_pattern = in_pattern;
+    }
+    fn visit_generator(&mut self, node: &'ast expr::Generator) {
+        self.visit_expression(&node.expr);
+    }
+    fn visit_var(&mut self, node: &'ast common::Var) {
+        if !self.in_pattern || node.is_anonymous() {
+            return;
+        }
+        if let Some(&index) = self.stack.last() {
+            self.clauses[index].vars.insert(&node.name);
+        }
+    }
+}
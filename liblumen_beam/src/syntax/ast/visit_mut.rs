@@ -0,0 +1,816 @@
+//! Mutable traversal of the Abstract Syntax Tree.
+//!
+//! This is the counterpart of `visit` for in-place rewriting: every method of `VisitMut`
+//! takes a mutable reference to the node, and defaults to recursively visiting its
+//! children via the corresponding `walk_*_mut` function.
+//!
+//! # Examples
+//!
+//!     use liblumen_beam::syntax::ast::AST;
+//!     use liblumen_beam::syntax::ast::ast::common::Var;
+//!     use liblumen_beam::syntax::ast::visit_mut::VisitMut;
+//!
+//!     struct Rename;
+//!     impl VisitMut for Rename {
+//!         fn visit_var_mut(&mut self, var: &mut Var) {
+//!             var.name = format!("_{}", var.name);
+//!         }
+//!     }
+//!
+//!     let mut ast = AST::from_beam_file("tests/testdata/ast/test.beam").unwrap();
+//!     Rename.visit_module_decl_mut(&mut ast.module);
+//!
+use crate::syntax::ast::ast::clause;
+use crate::syntax::ast::ast::common;
+use crate::syntax::ast::ast::expr;
+use crate::syntax::ast::ast::form;
+use crate::syntax::ast::ast::guard;
+use crate::syntax::ast::ast::literal;
+use crate::syntax::ast::ast::pat;
+use crate::syntax::ast::ast::ty;
+use crate::syntax::ast::ast::ModuleDecl;
+
+pub trait VisitMut {
+    // Module and forms
+    fn visit_module_decl_mut(&mut self, node: &mut ModuleDecl) {
+        walk_module_decl_mut(self, node)
+    }
+    fn visit_form_mut(&mut self, node: &mut form::Form) {
+        walk_form_mut(self, node)
+    }
+    fn visit_module_attr_mut(&mut self, _node: &mut form::ModuleAttr) {}
+    fn visit_behaviour_attr_mut(&mut self, _node: &mut form::BehaviourAttr) {}
+    fn visit_export_attr_mut(&mut self, _node: &mut form::ExportAttr) {}
+    fn visit_import_attr_mut(&mut self, _node: &mut form::ImportAttr) {}
+    fn visit_export_type_attr_mut(&mut self, _node: &mut form::ExportTypeAttr) {}
+    fn visit_compile_options_attr_mut(&mut self, _node: &mut form::CompileOptionsAttr) {}
+    fn visit_file_attr_mut(&mut self, _node: &mut form::FileAttr) {}
+    fn visit_record_decl_mut(&mut self, node: &mut form::RecordDecl) {
+        walk_record_decl_mut(self, node)
+    }
+    fn visit_record_field_decl_mut(&mut self, node: &mut form::RecordFieldDecl) {
+        walk_record_field_decl_mut(self, node)
+    }
+    fn visit_type_decl_mut(&mut self, node: &mut form::TypeDecl) {
+        walk_type_decl_mut(self, node)
+    }
+    fn visit_fun_spec_mut(&mut self, node: &mut form::FunSpec) {
+        walk_fun_spec_mut(self, node)
+    }
+    fn visit_wild_attr_mut(&mut self, _node: &mut form::WildAttr) {}
+    fn visit_fun_decl_mut(&mut self, node: &mut form::FunDecl) {
+        walk_fun_decl_mut(self, node)
+    }
+    fn visit_eof_mut(&mut self, _node: &mut form::Eof) {}
+
+    // Clauses
+    fn visit_clause_mut(&mut self, node: &mut clause::Clause) {
+        walk_clause_mut(self, node)
+    }
+
+    // Nodes shared by expressions, patterns, guards and types
+    fn visit_integer_mut(&mut self, _node: &mut literal::Integer) {}
+    fn visit_float_mut(&mut self, _node: &mut literal::Float) {}
+    fn visit_str_mut(&mut self, _node: &mut literal::Str) {}
+    fn visit_char_mut(&mut self, _node: &mut literal::Char) {}
+    fn visit_atom_mut(&mut self, _node: &mut literal::Atom) {}
+    fn visit_var_mut(&mut self, _node: &mut common::Var) {}
+    fn visit_nil_mut(&mut self, _node: &mut common::Nil) {}
+
+    // Expressions
+    fn visit_expression_mut(&mut self, node: &mut expr::Expression) {
+        walk_expression_mut(self, node)
+    }
+    fn visit_expr_match_mut(&mut self, node: &mut expr::Match) {
+        walk_expr_match_mut(self, node)
+    }
+    fn visit_expr_tuple_mut(&mut self, node: &mut expr::Tuple) {
+        walk_expr_tuple_mut(self, node)
+    }
+    fn visit_expr_cons_mut(&mut self, node: &mut expr::Cons) {
+        walk_expr_cons_mut(self, node)
+    }
+    fn visit_expr_binary_mut(&mut self, node: &mut expr::Binary) {
+        walk_expr_binary_mut(self, node)
+    }
+    fn visit_expr_unary_op_mut(&mut self, node: &mut expr::UnaryOp) {
+        walk_expr_unary_op_mut(self, node)
+    }
+    fn visit_expr_binary_op_mut(&mut self, node: &mut expr::BinaryOp) {
+        walk_expr_binary_op_mut(self, node)
+    }
+    fn visit_expr_record_mut(&mut self, node: &mut expr::Record) {
+        walk_expr_record_mut(self, node)
+    }
+    fn visit_expr_record_index_mut(&mut self, node: &mut expr::RecordIndex) {
+        walk_expr_record_index_mut(self, node)
+    }
+    fn visit_expr_map_mut(&mut self, node: &mut expr::Map) {
+        walk_expr_map_mut(self, node)
+    }
+    fn visit_catch_mut(&mut self, node: &mut expr::Catch) {
+        walk_catch_mut(self, node)
+    }
+    fn visit_local_call_mut(&mut self, node: &mut expr::LocalCall) {
+        walk_local_call_mut(self, node)
+    }
+    fn visit_remote_call_mut(&mut self, node: &mut expr::RemoteCall) {
+        walk_remote_call_mut(self, node)
+    }
+    fn visit_comprehension_mut(&mut self, node: &mut expr::Comprehension) {
+        walk_comprehension_mut(self, node)
+    }
+    fn visit_qualifier_mut(&mut self, node: &mut expr::Qualifier) {
+        walk_qualifier_mut(self, node)
+    }
+    fn visit_generator_mut(&mut self, node: &mut expr::Generator) {
+        walk_generator_mut(self, node)
+    }
+    fn visit_block_mut(&mut self, node: &mut expr::Block) {
+        walk_block_mut(self, node)
+    }
+    fn visit_if_mut(&mut self, node: &mut expr::If) {
+        walk_if_mut(self, node)
+    }
+    fn visit_case_mut(&mut self, node: &mut expr::Case) {
+        walk_case_mut(self, node)
+    }
+    fn visit_try_mut(&mut self, node: &mut expr::Try) {
+        walk_try_mut(self, node)
+    }
+    fn visit_receive_mut(&mut self, node: &mut expr::Receive) {
+        walk_receive_mut(self, node)
+    }
+    fn visit_internal_fun_mut(&mut self, _node: &mut common::InternalFun) {}
+    fn visit_external_fun_mut(&mut self, node: &mut common::ExternalFun) {
+        walk_external_fun_mut(self, node)
+    }
+    fn visit_anonymous_fun_mut(&mut self, node: &mut expr::AnonymousFun) {
+        walk_anonymous_fun_mut(self, node)
+    }
+
+    // Patterns
+    fn visit_pattern_mut(&mut self, node: &mut pat::Pattern) {
+        walk_pattern_mut(self, node)
+    }
+    fn visit_pat_match_mut(&mut self, node: &mut pat::Match) {
+        walk_pat_match_mut(self, node)
+    }
+    fn visit_pat_tuple_mut(&mut self, node: &mut pat::Tuple) {
+        walk_pat_tuple_mut(self, node)
+    }
+    fn visit_pat_cons_mut(&mut self, node: &mut pat::Cons) {
+        walk_pat_cons_mut(self, node)
+    }
+    fn visit_pat_binary_mut(&mut self, node: &mut pat::Binary) {
+        walk_pat_binary_mut(self, node)
+    }
+    fn visit_pat_unary_op_mut(&mut self, node: &mut pat::UnaryOp) {
+        walk_pat_unary_op_mut(self, node)
+    }
+    fn visit_pat_binary_op_mut(&mut self, node: &mut pat::BinaryOp) {
+        walk_pat_binary_op_mut(self, node)
+    }
+    fn visit_pat_record_mut(&mut self, node: &mut pat::Record) {
+        walk_pat_record_mut(self, node)
+    }
+    fn visit_pat_record_index_mut(&mut self, node: &mut pat::RecordIndex) {
+        walk_pat_record_index_mut(self, node)
+    }
+    fn visit_pat_map_mut(&mut self, node: &mut pat::Map) {
+        walk_pat_map_mut(self, node)
+    }
+
+    // Guards
+    fn visit_or_guard_mut(&mut self, node: &mut guard::OrGuard) {
+        walk_or_guard_mut(self, node)
+    }
+    fn visit_guard_mut(&mut self, node: &mut guard::Guard) {
+        walk_guard_mut(self, node)
+    }
+    fn visit_guard_tuple_mut(&mut self, node: &mut guard::Tuple) {
+        walk_guard_tuple_mut(self, node)
+    }
+    fn visit_guard_cons_mut(&mut self, node: &mut guard::Cons) {
+        walk_guard_cons_mut(self, node)
+    }
+    fn visit_guard_binary_mut(&mut self, node: &mut guard::Binary) {
+        walk_guard_binary_mut(self, node)
+    }
+    fn visit_guard_unary_op_mut(&mut self, node: &mut guard::UnaryOp) {
+        walk_guard_unary_op_mut(self, node)
+    }
+    fn visit_guard_binary_op_mut(&mut self, node: &mut guard::BinaryOp) {
+        walk_guard_binary_op_mut(self, node)
+    }
+    fn visit_guard_record_mut(&mut self, node: &mut guard::Record) {
+        walk_guard_record_mut(self, node)
+    }
+    fn visit_guard_record_index_mut(&mut self, node: &mut guard::RecordIndex) {
+        walk_guard_record_index_mut(self, node)
+    }
+    fn visit_guard_local_call_mut(&mut self, node: &mut guard::LocalCall) {
+        walk_guard_local_call_mut(self, node)
+    }
+    fn visit_guard_remote_call_mut(&mut self, node: &mut guard::RemoteCall) {
+        walk_guard_remote_call_mut(self, node)
+    }
+
+    // Types
+    fn visit_type_mut(&mut self, node: &mut ty::Type) {
+        walk_type_mut(self, node)
+    }
+    fn visit_ty_annotated_mut(&mut self, node: &mut ty::Annotated) {
+        walk_ty_annotated_mut(self, node)
+    }
+    fn visit_ty_unary_op_mut(&mut self, node: &mut ty::UnaryOp) {
+        walk_ty_unary_op_mut(self, node)
+    }
+    fn visit_ty_binary_op_mut(&mut self, node: &mut ty::BinaryOp) {
+        walk_ty_binary_op_mut(self, node)
+    }
+    fn visit_ty_bit_string_mut(&mut self, _node: &mut ty::BitString) {}
+    fn visit_ty_any_fun_mut(&mut self, node: &mut ty::AnyFun) {
+        walk_ty_any_fun_mut(self, node)
+    }
+    fn visit_ty_fun_mut(&mut self, node: &mut ty::Fun) {
+        walk_ty_fun_mut(self, node)
+    }
+    fn visit_ty_constraint_mut(&mut self, node: &mut ty::Constraint) {
+        walk_ty_constraint_mut(self, node)
+    }
+    fn visit_ty_range_mut(&mut self, node: &mut ty::Range) {
+        walk_ty_range_mut(self, node)
+    }
+    fn visit_ty_map_mut(&mut self, node: &mut ty::Map) {
+        walk_ty_map_mut(self, node)
+    }
+    fn visit_ty_map_pair_mut(&mut self, node: &mut ty::MapPair) {
+        walk_ty_map_pair_mut(self, node)
+    }
+    fn visit_ty_built_in_mut(&mut self, node: &mut ty::BuiltInType) {
+        walk_ty_built_in_mut(self, node)
+    }
+    fn visit_ty_record_mut(&mut self, node: &mut ty::Record) {
+        walk_ty_record_mut(self, node)
+    }
+    fn visit_ty_record_field_mut(&mut self, node: &mut ty::RecordField) {
+        walk_ty_record_field_mut(self, node)
+    }
+    fn visit_ty_remote_mut(&mut self, node: &mut ty::RemoteType) {
+        walk_ty_remote_mut(self, node)
+    }
+    fn visit_ty_any_tuple_mut(&mut self, _node: &mut ty::AnyTuple) {}
+    fn visit_ty_tuple_mut(&mut self, node: &mut ty::Tuple) {
+        walk_ty_tuple_mut(self, node)
+    }
+    fn visit_ty_union_mut(&mut self, node: &mut ty::Union) {
+        walk_ty_union_mut(self, node)
+    }
+    fn visit_ty_user_mut(&mut self, node: &mut ty::UserType) {
+        walk_ty_user_mut(self, node)
+    }
+}
+
+pub fn walk_module_decl_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ModuleDecl) {
+    for form in &mut node.forms {
+        v.visit_form_mut(form);
+    }
+}
+
+pub fn walk_form_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut form::Form) {
+    match *node {
+        form::Form::Module(ref mut x) => v.visit_module_attr_mut(x),
+        form::Form::Behaviour(ref mut x) => v.visit_behaviour_attr_mut(x),
+        form::Form::Export(ref mut x) => v.visit_export_attr_mut(x),
+        form::Form::Import(ref mut x) => v.visit_import_attr_mut(x),
+        form::Form::ExportType(ref mut x) => v.visit_export_type_attr_mut(x),
+        form::Form::Compile(ref mut x) => v.visit_compile_options_attr_mut(x),
+        form::Form::File(ref mut x) => v.visit_file_attr_mut(x),
+        form::Form::Record(ref mut x) => v.visit_record_decl_mut(x),
+        form::Form::Type(ref mut x) => v.visit_type_decl_mut(x),
+        form::Form::Spec(ref mut x) => v.visit_fun_spec_mut(x),
+        form::Form::Attr(ref mut x) => v.visit_wild_attr_mut(x),
+        form::Form::Fun(ref mut x) => v.visit_fun_decl_mut(x),
+        form::Form::Eof(ref mut x) => v.visit_eof_mut(x),
+    }
+}
+
+pub fn walk_record_decl_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut form::RecordDecl) {
+    for field in &mut node.fields {
+        v.visit_record_field_decl_mut(field);
+    }
+}
+
+pub fn walk_record_field_decl_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut form::RecordFieldDecl,
+) {
+    v.visit_type_mut(&mut node.ty);
+    v.visit_expression_mut(&mut node.default_value);
+}
+
+pub fn walk_type_decl_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut form::TypeDecl) {
+    for var in &mut node.vars {
+        v.visit_var_mut(var);
+    }
+    v.visit_type_mut(&mut node.ty);
+}
+
+pub fn walk_fun_spec_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut form::FunSpec) {
+    for ty in &mut node.types {
+        v.visit_ty_fun_mut(ty);
+    }
+}
+
+pub fn walk_fun_decl_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut form::FunDecl) {
+    for clause in &mut node.clauses {
+        v.visit_clause_mut(clause);
+    }
+}
+
+pub fn walk_clause_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut clause::Clause) {
+    for pattern in &mut node.patterns {
+        v.visit_pattern_mut(pattern);
+    }
+    for guard in &mut node.guards {
+        v.visit_or_guard_mut(guard);
+    }
+    for expr in &mut node.body {
+        v.visit_expression_mut(expr);
+    }
+}
+
+pub fn walk_expression_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Expression) {
+    use crate::syntax::ast::ast::expr::Expression as E;
+    match *node {
+        E::Integer(ref mut x) => v.visit_integer_mut(x),
+        E::Float(ref mut x) => v.visit_float_mut(x),
+        E::String(ref mut x) => v.visit_str_mut(x),
+        E::Char(ref mut x) => v.visit_char_mut(x),
+        E::Atom(ref mut x) => v.visit_atom_mut(x),
+        E::Match(ref mut x) => v.visit_expr_match_mut(x),
+        E::Var(ref mut x) => v.visit_var_mut(x),
+        E::Tuple(ref mut x) => v.visit_expr_tuple_mut(x),
+        E::Nil(ref mut x) => v.visit_nil_mut(x),
+        E::Cons(ref mut x) => v.visit_expr_cons_mut(x),
+        E::Binary(ref mut x) => v.visit_expr_binary_mut(x),
+        E::UnaryOp(ref mut x) => v.visit_expr_unary_op_mut(x),
+        E::BinaryOp(ref mut x) => v.visit_expr_binary_op_mut(x),
+        E::Record(ref mut x) => v.visit_expr_record_mut(x),
+        E::RecordIndex(ref mut x) => v.visit_expr_record_index_mut(x),
+        E::Map(ref mut x) => v.visit_expr_map_mut(x),
+        E::Catch(ref mut x) => v.visit_catch_mut(x),
+        E::LocalCall(ref mut x) => v.visit_local_call_mut(x),
+        E::RemoteCall(ref mut x) => v.visit_remote_call_mut(x),
+        E::Comprehension(ref mut x) => v.visit_comprehension_mut(x),
+        E::Block(ref mut x) => v.visit_block_mut(x),
+        E::If(ref mut x) => v.visit_if_mut(x),
+        E::Case(ref mut x) => v.visit_case_mut(x),
+        E::Try(ref mut x) => v.visit_try_mut(x),
+        E::Receive(ref mut x) => v.visit_receive_mut(x),
+        E::InternalFun(ref mut x) => v.visit_internal_fun_mut(x),
+        E::ExternalFun(ref mut x) => v.visit_external_fun_mut(x),
+        E::AnonymousFun(ref mut x) => v.visit_anonymous_fun_mut(x),
+    }
+}
+
+pub fn walk_expr_match_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Match) {
+    v.visit_pattern_mut(&mut node.left);
+    v.visit_expression_mut(&mut node.right);
+}
+
+pub fn walk_expr_tuple_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Tuple) {
+    for element in &mut node.elements {
+        v.visit_expression_mut(element);
+    }
+}
+
+pub fn walk_expr_cons_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Cons) {
+    v.visit_expression_mut(&mut node.head);
+    v.visit_expression_mut(&mut node.tail);
+}
+
+pub fn walk_expr_binary_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Binary) {
+    for element in &mut node.elements {
+        v.visit_expression_mut(&mut element.element);
+        if let Some(ref mut size) = element.size {
+            v.visit_expression_mut(size);
+        }
+    }
+}
+
+pub fn walk_expr_unary_op_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::UnaryOp) {
+    v.visit_expression_mut(&mut node.operand);
+}
+
+pub fn walk_expr_binary_op_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::BinaryOp) {
+    v.visit_expression_mut(&mut node.left_operand);
+    v.visit_expression_mut(&mut node.right_operand);
+}
+
+pub fn walk_expr_record_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Record) {
+    if let Some(ref mut base) = node.base {
+        v.visit_expression_mut(base);
+    }
+    for field in &mut node.fields {
+        v.visit_expression_mut(&mut field.value);
+    }
+}
+
+pub fn walk_expr_record_index_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::RecordIndex) {
+    if let Some(ref mut base) = node.base {
+        v.visit_expression_mut(base);
+    }
+}
+
+pub fn walk_expr_map_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Map) {
+    if let Some(ref mut base) = node.base {
+        v.visit_expression_mut(base);
+    }
+    for pair in &mut node.pairs {
+        v.visit_expression_mut(&mut pair.key);
+        v.visit_expression_mut(&mut pair.value);
+    }
+}
+
+pub fn walk_catch_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Catch) {
+    v.visit_expression_mut(&mut node.expr);
+}
+
+pub fn walk_local_call_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::LocalCall) {
+    v.visit_expression_mut(&mut node.function);
+    for arg in &mut node.args {
+        v.visit_expression_mut(arg);
+    }
+}
+
+pub fn walk_remote_call_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::RemoteCall) {
+    v.visit_expression_mut(&mut node.module);
+    v.visit_expression_mut(&mut node.function);
+    for arg in &mut node.args {
+        v.visit_expression_mut(arg);
+    }
+}
+
+pub fn walk_comprehension_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Comprehension) {
+    for qualifier in &mut node.qualifiers {
+        v.visit_qualifier_mut(qualifier);
+    }
+    v.visit_expression_mut(&mut node.expr);
+}
+
+pub fn walk_qualifier_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Qualifier) {
+    match *node {
+        expr::Qualifier::Generator(ref mut x) => v.visit_generator_mut(x),
+        expr::Qualifier::BitStringGenerator(ref mut x) => v.visit_generator_mut(x),
+        expr::Qualifier::Filter(ref mut x) => v.visit_expression_mut(x),
+    }
+}
+
+pub fn walk_generator_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Generator) {
+    v.visit_expression_mut(&mut node.expr);
+    v.visit_pattern_mut(&mut node.pattern);
+}
+
+pub fn walk_block_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Block) {
+    for expr in &mut node.body {
+        v.visit_expression_mut(expr);
+    }
+}
+
+pub fn walk_if_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::If) {
+    for clause in &mut node.clauses {
+        v.visit_clause_mut(clause);
+    }
+}
+
+pub fn walk_case_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Case) {
+    v.visit_expression_mut(&mut node.expr);
+    for clause in &mut node.clauses {
+        v.visit_clause_mut(clause);
+    }
+}
+
+pub fn walk_try_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Try) {
+    for expr in &mut node.body {
+        v.visit_expression_mut(expr);
+    }
+    for clause in &mut node.case_clauses {
+        v.visit_clause_mut(clause);
+    }
+    for clause in &mut node.catch_clauses {
+        v.visit_clause_mut(clause);
+    }
+    for expr in &mut node.after {
+        v.visit_expression_mut(expr);
+    }
+}
+
+pub fn walk_receive_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::Receive) {
+    for clause in &mut node.clauses {
+        v.visit_clause_mut(clause);
+    }
+    if let Some(ref mut timeout) = node.timeout {
+        v.visit_expression_mut(timeout);
+    }
+    for expr in &mut node.after {
+        v.visit_expression_mut(expr);
+    }
+}
+
+pub fn walk_external_fun_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut common::ExternalFun) {
+    v.visit_expression_mut(&mut node.module);
+    v.visit_expression_mut(&mut node.function);
+    v.visit_expression_mut(&mut node.arity);
+}
+
+pub fn walk_anonymous_fun_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut expr::AnonymousFun) {
+    for clause in &mut node.clauses {
+        v.visit_clause_mut(clause);
+    }
+}
+
+pub fn walk_pattern_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut pat::Pattern) {
+    use crate::syntax::ast::ast::pat::Pattern as P;
+    match *node {
+        P::Integer(ref mut x) => v.visit_integer_mut(x),
+        P::Float(ref mut x) => v.visit_float_mut(x),
+        P::String(ref mut x) => v.visit_str_mut(x),
+        P::Char(ref mut x) => v.visit_char_mut(x),
+        P::Atom(ref mut x) => v.visit_atom_mut(x),
+        P::Var(ref mut x) => v.visit_var_mut(x),
+        P::Match(ref mut x) => v.visit_pat_match_mut(x),
+        P::Tuple(ref mut x) => v.visit_pat_tuple_mut(x),
+        P::Nil(ref mut x) => v.visit_nil_mut(x),
+        P::Cons(ref mut x) => v.visit_pat_cons_mut(x),
+        P::Binary(ref mut x) => v.visit_pat_binary_mut(x),
+        P::UnaryOp(ref mut x) => v.visit_pat_unary_op_mut(x),
+        P::BinaryOp(ref mut x) => v.visit_pat_binary_op_mut(x),
+        P::Record(ref mut x) => v.visit_pat_record_mut(x),
+        P::RecordIndex(ref mut x) => v.visit_pat_record_index_mut(x),
+        P::Map(ref mut x) => v.visit_pat_map_mut(x),
+    }
+}
+
+pub fn walk_pat_match_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut pat::Match) {
+    v.visit_pattern_mut(&mut node.left);
+    v.visit_pattern_mut(&mut node.right);
+}
+
+pub fn walk_pat_tuple_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut pat::Tuple) {
+    for element in &mut node.elements {
+        v.visit_pattern_mut(element);
+    }
+}
+
+pub fn walk_pat_cons_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut pat::Cons) {
+    v.visit_pattern_mut(&mut node.head);
+    v.visit_pattern_mut(&mut node.tail);
+}
+
+pub fn walk_pat_binary_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut pat::Binary) {
+    for element in &mut node.elements {
+        v.visit_pattern_mut(&mut element.element);
+        if let Some(ref mut size) = element.size {
+            v.visit_pattern_mut(size);
+        }
+    }
+}
+
+pub fn walk_pat_unary_op_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut pat::UnaryOp) {
+    v.visit_pattern_mut(&mut node.operand);
+}
+
+pub fn walk_pat_binary_op_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut pat::BinaryOp) {
+    v.visit_pattern_mut(&mut node.left_operand);
+    v.visit_pattern_mut(&mut node.right_operand);
+}
+
+pub fn walk_pat_record_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut pat::Record) {
+    if let Some(ref mut base) = node.base {
+        v.visit_expression_mut(base);
+    }
+    for field in &mut node.fields {
+        v.visit_pattern_mut(&mut field.value);
+    }
+}
+
+pub fn walk_pat_record_index_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut pat::RecordIndex) {
+    if let Some(ref mut base) = node.base {
+        v.visit_pattern_mut(base);
+    }
+}
+
+pub fn walk_pat_map_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut pat::Map) {
+    if let Some(ref mut base) = node.base {
+        v.visit_expression_mut(base);
+    }
+    for pair in &mut node.pairs {
+        v.visit_pattern_mut(&mut pair.key);
+        v.visit_pattern_mut(&mut pair.value);
+    }
+}
+
+pub fn walk_or_guard_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::OrGuard) {
+    for guard in &mut node.and_guards {
+        v.visit_guard_mut(guard);
+    }
+}
+
+pub fn walk_guard_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::Guard) {
+    use crate::syntax::ast::ast::guard::Guard as G;
+    match *node {
+        G::Integer(ref mut x) => v.visit_integer_mut(x),
+        G::Float(ref mut x) => v.visit_float_mut(x),
+        G::String(ref mut x) => v.visit_str_mut(x),
+        G::Char(ref mut x) => v.visit_char_mut(x),
+        G::Atom(ref mut x) => v.visit_atom_mut(x),
+        G::Var(ref mut x) => v.visit_var_mut(x),
+        G::Tuple(ref mut x) => v.visit_guard_tuple_mut(x),
+        G::Nil(ref mut x) => v.visit_nil_mut(x),
+        G::Cons(ref mut x) => v.visit_guard_cons_mut(x),
+        G::Binary(ref mut x) => v.visit_guard_binary_mut(x),
+        G::UnaryOp(ref mut x) => v.visit_guard_unary_op_mut(x),
+        G::BinaryOp(ref mut x) => v.visit_guard_binary_op_mut(x),
+        G::Record(ref mut x) => v.visit_guard_record_mut(x),
+        G::RecordIndex(ref mut x) => v.visit_guard_record_index_mut(x),
+        G::LocalCall(ref mut x) => v.visit_guard_local_call_mut(x),
+        G::RemoteCall(ref mut x) => v.visit_guard_remote_call_mut(x),
+    }
+}
+
+pub fn walk_guard_tuple_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::Tuple) {
+    for element in &mut node.elements {
+        v.visit_guard_mut(element);
+    }
+}
+
+pub fn walk_guard_cons_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::Cons) {
+    v.visit_guard_mut(&mut node.head);
+    v.visit_guard_mut(&mut node.tail);
+}
+
+pub fn walk_guard_binary_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::Binary) {
+    for element in &mut node.elements {
+        v.visit_guard_mut(&mut element.element);
+        if let Some(ref mut size) = element.size {
+            v.visit_guard_mut(size);
+        }
+    }
+}
+
+pub fn walk_guard_unary_op_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::UnaryOp) {
+    v.visit_guard_mut(&mut node.operand);
+}
+
+pub fn walk_guard_binary_op_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::BinaryOp) {
+    v.visit_guard_mut(&mut node.left_operand);
+    v.visit_guard_mut(&mut node.right_operand);
+}
+
+pub fn walk_guard_record_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::Record) {
+    if let Some(ref mut base) = node.base {
+        v.visit_expression_mut(base);
+    }
+    for field in &mut node.fields {
+        v.visit_guard_mut(&mut field.value);
+    }
+}
+
+pub fn walk_guard_record_index_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::RecordIndex) {
+    if let Some(ref mut base) = node.base {
+        v.visit_guard_mut(base);
+    }
+}
+
+pub fn walk_guard_local_call_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::LocalCall) {
+    v.visit_guard_mut(&mut node.function);
+    for arg in &mut node.args {
+        v.visit_guard_mut(arg);
+    }
+}
+
+pub fn walk_guard_remote_call_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut guard::RemoteCall) {
+    v.visit_guard_mut(&mut node.module);
+    v.visit_guard_mut(&mut node.function);
+    for arg in &mut node.args {
+        v.visit_guard_mut(arg);
+    }
+}
+
+pub fn walk_type_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::Type) {
+    use crate::syntax::ast::ast::ty::Type as T;
+    match *node {
+        T::Atom(ref mut x) => v.visit_atom_mut(x),
+        T::Integer(ref mut x) => v.visit_integer_mut(x),
+        T::Var(ref mut x) => v.visit_var_mut(x),
+        T::Annotated(ref mut x) => v.visit_ty_annotated_mut(x),
+        T::UnaryOp(ref mut x) => v.visit_ty_unary_op_mut(x),
+        T::BinaryOp(ref mut x) => v.visit_ty_binary_op_mut(x),
+        T::BitString(ref mut x) => v.visit_ty_bit_string_mut(x),
+        T::Nil(ref mut x) => v.visit_nil_mut(x),
+        T::AnyFun(ref mut x) => v.visit_ty_any_fun_mut(x),
+        T::Function(ref mut x) => v.visit_ty_fun_mut(x),
+        T::Range(ref mut x) => v.visit_ty_range_mut(x),
+        T::Map(ref mut x) => v.visit_ty_map_mut(x),
+        T::BuiltIn(ref mut x) => v.visit_ty_built_in_mut(x),
+        T::Record(ref mut x) => v.visit_ty_record_mut(x),
+        T::Remote(ref mut x) => v.visit_ty_remote_mut(x),
+        T::AnyTuple(ref mut x) => v.visit_ty_any_tuple_mut(x),
+        T::Tuple(ref mut x) => v.visit_ty_tuple_mut(x),
+        T::Union(ref mut x) => v.visit_ty_union_mut(x),
+        T::User(ref mut x) => v.visit_ty_user_mut(x),
+    }
+}
+
+pub fn walk_ty_annotated_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::Annotated) {
+    v.visit_var_mut(&mut node.name);
+    v.visit_type_mut(&mut node.ty);
+}
+
+pub fn walk_ty_unary_op_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::UnaryOp) {
+    v.visit_type_mut(&mut node.operand);
+}
+
+pub fn walk_ty_binary_op_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::BinaryOp) {
+    v.visit_type_mut(&mut node.left_operand);
+    v.visit_type_mut(&mut node.right_operand);
+}
+
+pub fn walk_ty_any_fun_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::AnyFun) {
+    if let Some(ref mut return_type) = node.return_type {
+        v.visit_type_mut(return_type);
+    }
+}
+
+pub fn walk_ty_fun_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::Fun) {
+    for arg in &mut node.args {
+        v.visit_type_mut(arg);
+    }
+    v.visit_type_mut(&mut node.return_type);
+    for constraint in &mut node.constraints {
+        v.visit_ty_constraint_mut(constraint);
+    }
+}
+
+pub fn walk_ty_constraint_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::Constraint) {
+    v.visit_var_mut(&mut node.var);
+    v.visit_type_mut(&mut node.subtype);
+}
+
+pub fn walk_ty_range_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::Range) {
+    v.visit_type_mut(&mut node.low);
+    v.visit_type_mut(&mut node.high);
+}
+
+pub fn walk_ty_map_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::Map) {
+    for pair in &mut node.pairs {
+        v.visit_ty_map_pair_mut(pair);
+    }
+}
+
+pub fn walk_ty_map_pair_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::MapPair) {
+    v.visit_type_mut(&mut node.key);
+    v.visit_type_mut(&mut node.value);
+}
+
+pub fn walk_ty_built_in_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::BuiltInType) {
+    for arg in &mut node.args {
+        v.visit_type_mut(arg);
+    }
+}
+
+pub fn walk_ty_record_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::Record) {
+    for field in &mut node.fields {
+        v.visit_ty_record_field_mut(field);
+    }
+}
+
+pub fn walk_ty_record_field_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::RecordField) {
+    v.visit_type_mut(&mut node.ty);
+}
+
+pub fn walk_ty_remote_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::RemoteType) {
+    for arg in &mut node.args {
+        v.visit_type_mut(arg);
+    }
+}
+
+pub fn walk_ty_tuple_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::Tuple) {
+    for element in &mut node.elements {
+        v.visit_type_mut(element);
+    }
+}
+
+pub fn walk_ty_union_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::Union) {
+    for ty in &mut node.types {
+        v.visit_type_mut(ty);
+    }
+}
+
+pub fn walk_ty_user_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ty::UserType) {
+    for arg in &mut node.args {
+        v.visit_type_mut(arg);
+    }
+}
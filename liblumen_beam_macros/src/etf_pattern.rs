@@ -0,0 +1,387 @@
+use proc_macro::TokenStream;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Attribute, Data, DataEnum, DeriveInput, Error, Expr, Field, Fields, Ident,
+    Lit, LitStr, Meta, NestedMeta, Result,
+};
+
+pub fn derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The options given with `#[etf(...)]`
+#[derive(Default)]
+struct Options {
+    record: Option<LitStr>,
+    tag: Option<LitStr>,
+    atom: bool,
+    pattern: Option<LitStr>,
+}
+impl Options {
+    fn parse(attrs: &[Attribute], allowed: &[&str]) -> Result<Self> {
+        let mut options = Options::default();
+        for attr in attrs.iter().filter(|a| a.path.is_ident("etf")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(Error::new_spanned(meta, "expected `#[etf(...)]`")),
+            };
+            for nested in list.nested {
+                let meta = match nested {
+                    NestedMeta::Meta(meta) => meta,
+                    NestedMeta::Lit(lit) => {
+                        return Err(Error::new_spanned(lit, "expected an `etf` option"))
+                    }
+                };
+                let name = meta
+                    .path()
+                    .get_ident()
+                    .map(|i| i.to_string())
+                    .unwrap_or_default();
+                if !allowed.contains(&name.as_str()) {
+                    let message = format!(
+                        "unknown `etf` option, expected one of: {}",
+                        allowed.join(", ")
+                    );
+                    return Err(Error::new_spanned(meta.path(), message));
+                }
+                match (name.as_str(), &meta) {
+                    ("atom", Meta::Path(_)) => options.atom = true,
+                    (_, Meta::NameValue(nv)) => {
+                        let value = match nv.lit {
+                            Lit::Str(ref s) => s.clone(),
+                            ref lit => {
+                                return Err(Error::new_spanned(lit, "expected a string literal"))
+                            }
+                        };
+                        match name.as_str() {
+                            "record" => options.record = Some(value),
+                            "tag" => options.tag = Some(value),
+                            "pattern" => options.pattern = Some(value),
+                            _ => return Err(Error::new_spanned(meta, "expected `#[etf(atom)]`")),
+                        }
+                    }
+                    _ => {
+                        let message = format!("expected `{} = \"...\"`", name);
+                        return Err(Error::new_spanned(meta, message));
+                    }
+                }
+            }
+        }
+        if let (true, Some(ref pattern)) = (options.atom, &options.pattern) {
+            return Err(Error::new_spanned(
+                pattern,
+                "`atom` and `pattern` are mutually exclusive",
+            ));
+        }
+        Ok(options)
+    }
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "EtfPattern cannot be derived for generic types",
+        ));
+    }
+    let name = &input.ident;
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            let options = Options::parse(&input.attrs, &["record", "tag"])?;
+            expand_struct(name, &options, &data.fields)?
+        }
+        Data::Enum(ref data) => {
+            Options::parse(&input.attrs, &[])?;
+            expand_enum(data)?
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "EtfPattern cannot be derived for unions",
+            ))
+        }
+    };
+    Ok(quote! {
+        impl<'a> ::liblumen_beam::serialization::etf::pattern::FromTerm<'a> for #name {
+            fn try_from_term(
+                term: &'a ::liblumen_beam::serialization::etf::Term,
+            ) -> ::liblumen_beam::serialization::etf::pattern::Result<'a, Self> {
+                #[allow(unused_imports)]
+                use ::liblumen_beam::serialization::etf::pattern::Pattern as _;
+                #[allow(unused_imports)]
+                use ::liblumen_beam::serialization::etf::Term;
+                let unmatched = || {
+                    ::liblumen_beam::serialization::etf::pattern::typed::<Self>().unmatched(term)
+                };
+                #body
+            }
+        }
+
+        impl<'a> ::liblumen_beam::serialization::etf::pattern::Pattern<'a> for #name {
+            type Output = Self;
+            fn try_match(
+                &self,
+                input: &'a ::liblumen_beam::serialization::etf::Term,
+            ) -> ::liblumen_beam::serialization::etf::pattern::Result<'a, Self> {
+                let value =
+                    <Self as ::liblumen_beam::serialization::etf::pattern::FromTerm<'a>>::try_from_term(
+                        input,
+                    )?;
+                if value == *self {
+                    Ok(value)
+                } else {
+                    Err(::liblumen_beam::serialization::etf::pattern::Pattern::unmatched(
+                        &::liblumen_beam::serialization::etf::pattern::typed::<Self>(),
+                        input,
+                    ))
+                }
+            }
+        }
+    })
+}
+
+fn expand_struct(name: &Ident, options: &Options, fields: &Fields) -> Result<TokenStream2> {
+    match *fields {
+        Fields::Unit => {
+            if let Some(ref record) = options.record {
+                return Err(Error::new_spanned(
+                    record,
+                    "a unit struct is matched as an atom, use `tag` instead",
+                ));
+            }
+            let tag = tag_or_default(&options.tag, name);
+            Ok(quote! {
+                #tag.try_match(term).map_err(|_| unmatched())?;
+                Ok(#name)
+            })
+        }
+        _ if options.tag.is_some() => Err(Error::new_spanned(
+            &options.tag,
+            "`tag` is only supported on unit structs and enum variants, use `record` instead",
+        )),
+        _ if options.record.is_some() => Ok(expand_tagged(
+            &quote!(#name),
+            options.record.as_ref().unwrap(),
+            fields,
+            "",
+        )?),
+        Fields::Named(ref named) => {
+            let mut bindings = Vec::new();
+            let mut inits = Vec::new();
+            for (i, field) in named.named.iter().enumerate() {
+                let ident = field.ident.as_ref().unwrap();
+                let key = unraw(ident);
+                let binding = Ident::new(&format!("field{}", i), ident.span());
+                let value = field_value(field, quote!(value), &key)?;
+                let ty = &field.ty;
+                bindings.push(quote! {
+                    let #binding: #ty = {
+                        let value = entries
+                            .iter()
+                            .find(|(k, _)| match *k {
+                                Term::Atom(ref a) => a.name == #key,
+                                _ => false,
+                            })
+                            .map(|(_, v)| v)
+                            .ok_or_else(|| unmatched().in_field(#key))?;
+                        #value
+                    };
+                });
+                inits.push(quote!(#ident: #binding));
+            }
+            Ok(quote! {
+                let entries = match *term {
+                    Term::Map(ref m) => &m.entries,
+                    _ => return Err(unmatched()),
+                };
+                #(#bindings)*
+                Ok(#name { #(#inits),* })
+            })
+        }
+        Fields::Unnamed(ref unnamed) if unnamed.unnamed.len() == 1 => {
+            let field = &unnamed.unnamed[0];
+            let ty = &field.ty;
+            let value = field_value(field, quote!(term), "0")?;
+            Ok(quote! {
+                let field0: #ty = #value;
+                Ok(#name(field0))
+            })
+        }
+        Fields::Unnamed(_) => {
+            let (bindings, constructor) = expand_elements(&quote!(#name), fields, 0, "")?;
+            let len = fields.iter().len();
+            Ok(quote! {
+                let elements = match *term {
+                    Term::Tuple(ref t) if t.elements.len() == #len => &t.elements,
+                    _ => return Err(unmatched()),
+                };
+                #(#bindings)*
+                Ok(#constructor)
+            })
+        }
+    }
+}
+
+fn expand_enum(data: &DataEnum) -> Result<TokenStream2> {
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        let options = Options::parse(&variant.attrs, &["tag"])?;
+        let ident = &variant.ident;
+        let tag = tag_or_default(&options.tag, ident);
+        let body = match variant.fields {
+            Fields::Unit => quote! {
+                #tag.try_match(term).map_err(|_| unmatched())?;
+                Ok(Self::#ident)
+            },
+            _ => expand_tagged(&quote!(Self::#ident), &tag, &variant.fields, &unraw(ident))?,
+        };
+        variants.push(quote! {
+            let result: ::liblumen_beam::serialization::etf::pattern::Result<'a, Self> =
+                (|| { #body })();
+            match result {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    best = Some(match best {
+                        Some(best) => best.max_depth(e),
+                        None => e,
+                    })
+                }
+            }
+        });
+    }
+    // Only report a variant if its tag matched, i.e. one of its fields failed
+    Ok(quote! {
+        #[allow(unused_mut)]
+        let mut best: Option<::liblumen_beam::serialization::etf::pattern::Unmatch<'a>> = None;
+        #(#variants)*
+        Err(best.filter(|e| e.cause.is_some()).unwrap_or_else(unmatched))
+    })
+}
+
+/// Expands the matching of `{tag, Field1, ...}`
+fn expand_tagged(
+    constructor: &TokenStream2,
+    tag: &LitStr,
+    fields: &Fields,
+    prefix: &str,
+) -> Result<TokenStream2> {
+    let (bindings, constructor) = expand_elements(constructor, fields, 1, prefix)?;
+    let len = fields.iter().len() + 1;
+    Ok(quote! {
+        let elements = match *term {
+            Term::Tuple(ref t) if t.elements.len() == #len => &t.elements,
+            _ => return Err(unmatched()),
+        };
+        match elements[0] {
+            Term::Atom(ref a) if a.name == #tag => {}
+            _ => return Err(unmatched()),
+        }
+        #(#bindings)*
+        Ok(#constructor)
+    })
+}
+
+/// Binds the fields to the tuple `elements`, starting at `offset`
+fn expand_elements(
+    constructor: &TokenStream2,
+    fields: &Fields,
+    offset: usize,
+    prefix: &str,
+) -> Result<(Vec<TokenStream2>, TokenStream2)> {
+    let mut bindings = Vec::new();
+    let mut inits = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let name = field
+            .ident
+            .as_ref()
+            .map(unraw)
+            .unwrap_or_else(|| i.to_string());
+        let path = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        let index = i + offset;
+        let binding = Ident::new(&format!("field{}", i), field.span());
+        let value = field_value(field, quote!(&elements[#index]), &path)?;
+        let ty = &field.ty;
+        bindings.push(quote!(let #binding: #ty = #value;));
+        inits.push(match field.ident {
+            Some(ref ident) => quote!(#ident: #binding),
+            None => quote!(#binding),
+        });
+    }
+    let constructor = match *fields {
+        Fields::Named(_) => quote!(#constructor { #(#inits),* }),
+        _ => quote!(#constructor(#(#inits),*)),
+    };
+    Ok((bindings, constructor))
+}
+
+/// Expands the matching of a single field against `input`, labelling any failure with `path`
+fn field_value(field: &Field, input: TokenStream2, path: &str) -> Result<TokenStream2> {
+    let options = Options::parse(&field.attrs, &["atom", "pattern"])?;
+    let ty = &field.ty;
+    let value = if options.atom {
+        quote! {
+            ::liblumen_beam::serialization::etf::pattern::AtomName
+                .try_match(#input)
+                .map(::std::convert::From::from)
+        }
+    } else if let Some(ref pattern) = options.pattern {
+        let pattern: Expr = pattern.parse()?;
+        quote! {
+            (#pattern).try_match(#input).map(::std::convert::From::from)
+        }
+    } else {
+        quote! {
+            <#ty as ::liblumen_beam::serialization::etf::pattern::FromTerm<'a>>::try_from_term(
+                #input,
+            )
+        }
+    };
+    Ok(quote! {
+        #value.map_err(|e| unmatched().in_field(#path).cause(e))?
+    })
+}
+
+fn tag_or_default(tag: &Option<LitStr>, ident: &Ident) -> LitStr {
+    tag.clone()
+        .unwrap_or_else(|| LitStr::new(&snake_case(&unraw(ident)), ident.span()))
+}
+
+fn unraw(ident: &Ident) -> String {
+    let name = ident.to_string();
+    match name.get(..2) {
+        Some("r#") => name[2..].to_string(),
+        _ => name,
+    }
+}
+
+/// Converts a type or variant name such as `HttpServer` to `http_server`
+fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && !chars[i - 1].is_uppercase() && chars[i - 1] != '_';
+            let before_lower = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).map_or(false, |n| n.is_lowercase());
+            if after_lower || before_lower {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
@@ -0,0 +1,15 @@
+extern crate proc_macro;
+
+mod etf_pattern;
+
+use proc_macro::TokenStream;
+
+/// Derives `liblumen_beam::serialization::etf::pattern::FromTerm` and `Pattern` for a struct or
+/// enum, which must also implement `Debug`, `Clone` and `PartialEq`.  As a pattern, a value only
+/// matches terms that convert to an equal value.
+///
+/// See the documentation of `FromTerm` for the supported `#[etf(...)]` attributes.
+#[proc_macro_derive(EtfPattern, attributes(etf))]
+pub fn derive_etf_pattern(input: TokenStream) -> TokenStream {
+    self::etf_pattern::derive(input)
+}
@@ -0,0 +1,200 @@
+use std::path::{Path, PathBuf};
+
+use clap::ArgMatches;
+use failure::{format_err, Error};
+
+use liblumen_beam::beam::chunk::{Chunk, Id, StandardChunk};
+use liblumen_beam::beam::code_path::{CodePath, ModuleLocation};
+use liblumen_beam::beam::reader::{RawBeamFile, StandardBeamFile};
+use liblumen_beam::serialization::etf::{self, text};
+
+/// Dispatches `lumen beam` subcommands
+pub fn dispatch<'a>(args: &'a ArgMatches) -> Result<(), Error> {
+    let code_path = configure_code_path(args)?;
+    match args.subcommand() {
+        ("info", Some(args)) => info(&locate(&code_path, args, "file")?),
+        ("chunks", Some(args)) => chunks(&locate(&code_path, args, "file")?),
+        ("strip", Some(args)) => {
+            let input = locate(&code_path, args, "file")?;
+            let output = match (args.value_of_os("output"), &input) {
+                (Some(output), _) => PathBuf::from(output),
+                (None, ModuleLocation::File(path)) => path.clone(),
+                (None, ModuleLocation::Archive { .. }) => {
+                    return Err(format_err!(
+                        "{} is inside an archive, use --output to write the stripped module",
+                        input
+                    ));
+                }
+            };
+            strip(&input, &output)
+        }
+        ("diff", Some(args)) => diff(
+            &locate(&code_path, args, "a")?,
+            &locate(&code_path, args, "b")?,
+        ),
+        ("docs", Some(args)) => docs(&locate(&code_path, args, "file")?),
+        _ => Ok(()),
+    }
+}
+
+/// Builds the code path from the `-pa` and `-pz` options, in the order they were given
+fn configure_code_path<'a>(args: &'a ArgMatches) -> Result<CodePath, Error> {
+    let mut code_path = CodePath::new();
+    let prepend = args.values_of_os("prepend-path").into_iter().flatten();
+    let append = args.values_of_os("append-path").into_iter().flatten();
+    for path in prepend.chain(append) {
+        code_path.append(path)?;
+    }
+    Ok(code_path)
+}
+
+/// Resolves an argument which is either a path to a `.beam` file, or a module on the code path
+fn locate(code_path: &CodePath, args: &ArgMatches, name: &str) -> Result<ModuleLocation, Error> {
+    let value = args.value_of_os(name).unwrap();
+    let path = Path::new(value);
+    if path.is_file() {
+        return Ok(ModuleLocation::File(path.to_path_buf()));
+    }
+    let module = value.to_string_lossy();
+    code_path
+        .find_module(&module)?
+        .ok_or_else(|| format_err!("no such file or module on the code path: {}", module))
+}
+
+/// Prints the module name, exports, imports, attributes and compile info of a BEAM file
+fn info(location: &ModuleLocation) -> Result<(), Error> {
+    let beam: StandardBeamFile = location.read_beam()?;
+
+    let atoms = match beam.atoms() {
+        Some(StandardChunk::Atom(chunk)) => chunk
+            .atoms
+            .iter()
+            .map(|atom| atom.name.as_str())
+            .collect::<Vec<_>>(),
+        _ => return Err(format_err!("{} has no atom chunk", location)),
+    };
+    // Atom ids are one-based, and the first atom is the module name
+    let atom = |id: u32| atom_name(&atoms, id);
+
+    println!("Module: {}", atom(1));
+
+    if let Some(StandardChunk::ExpT(chunk)) = beam.get_chunk(b"ExpT") {
+        println!("Exports:");
+        for export in &chunk.exports {
+            println!("  {}/{}", atom(export.function), export.arity);
+        }
+    }
+    if let Some(StandardChunk::ImpT(chunk)) = beam.get_chunk(b"ImpT") {
+        println!("Imports:");
+        for import in &chunk.imports {
+            println!(
+                "  {}:{}/{}",
+                atom(import.module),
+                atom(import.function),
+                import.arity
+            );
+        }
+    }
+    if let Some(StandardChunk::Attr(chunk)) = beam.get_chunk(b"Attr") {
+        println!("Attributes:");
+        print_term_list(&chunk.term)?;
+    }
+    if let Some(StandardChunk::CInf(chunk)) = beam.get_chunk(b"CInf") {
+        println!("Compile info:");
+        print_term_list(&chunk.term)?;
+    }
+
+    Ok(())
+}
+
+/// Prints each element of an encoded list on its own line
+fn print_term_list(bytes: &[u8]) -> Result<(), Error> {
+    match etf::Term::decode(bytes)? {
+        etf::Term::List(list) => {
+            for element in &list.elements {
+                println!("  {}", text::to_string(element));
+            }
+        }
+        term => println!("  {}", text::to_string(&term)),
+    }
+    Ok(())
+}
+
+/// Prints the id and size of every chunk, in file order
+fn chunks(location: &ModuleLocation) -> Result<(), Error> {
+    let beam: RawBeamFile = location.read_beam()?;
+    for chunk in beam.chunks() {
+        println!("{} {:>8}", id_to_string(chunk.id()), chunk.data.len());
+    }
+    Ok(())
+}
+
+/// Removes every chunk which is not required to load the module
+fn strip(input: &ModuleLocation, output: &Path) -> Result<(), Error> {
+    let mut beam: RawBeamFile = input.read_beam()?;
+    beam.strip();
+    beam.to_file(output)?;
+    Ok(())
+}
+
+/// Compares two BEAM files chunk by chunk, failing if they differ
+fn diff(a: &ModuleLocation, b: &ModuleLocation) -> Result<(), Error> {
+    let beam_a: RawBeamFile = a.read_beam()?;
+    let beam_b: RawBeamFile = b.read_beam()?;
+
+    let mut differ = false;
+    for chunk in beam_a.chunks() {
+        match beam_b.get_chunk(chunk.id()) {
+            None => {
+                differ = true;
+                println!("- {} (only in {})", id_to_string(chunk.id()), a);
+            }
+            Some(other) if other.data != chunk.data => {
+                differ = true;
+                println!(
+                    "~ {} ({} bytes vs {} bytes)",
+                    id_to_string(chunk.id()),
+                    chunk.data.len(),
+                    other.data.len()
+                );
+            }
+            Some(_) => {}
+        }
+    }
+    for chunk in beam_b.chunks() {
+        if beam_a.get_chunk(chunk.id()).is_none() {
+            differ = true;
+            println!("+ {} (only in {})", id_to_string(chunk.id()), b);
+        }
+    }
+
+    if differ {
+        Err(format_err!("{} and {} differ", a, b))
+    } else {
+        Ok(())
+    }
+}
+
+/// Prints the documentation chunk of a BEAM file
+fn docs(location: &ModuleLocation) -> Result<(), Error> {
+    let beam: StandardBeamFile = location.read_beam()?;
+    match beam.get_chunk(b"Docs") {
+        Some(StandardChunk::Docs(chunk)) => {
+            let term = etf::Term::decode(&chunk.term[..])?;
+            println!("{}", text::to_string(&term));
+            Ok(())
+        }
+        _ => Err(format_err!("{} has no documentation chunk", location)),
+    }
+}
+
+fn atom_name<'a>(atoms: &[&'a str], id: u32) -> &'a str {
+    atoms
+        .get((id as usize).wrapping_sub(1))
+        .cloned()
+        .unwrap_or("?")
+}
+
+fn id_to_string(id: &Id) -> String {
+    String::from_utf8_lossy(id).into_owned()
+}
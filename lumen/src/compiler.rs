@@ -4,9 +4,10 @@ use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 use clap::{value_t, ArgMatches};
-use failure::Error;
+use failure::{format_err, Error};
 
 use libeir_diagnostics::{CodeMap, ColorChoice};
+use liblumen_beam::beam::code_path::{CodePath, CodePathEntry};
 use liblumen_compiler::{Compiler, CompilerMode, CompilerSettings, Verbosity};
 
 /// Dispatches command-line arguments to the compiler backend
@@ -29,15 +30,7 @@ fn configure<'a>(args: &'a ArgMatches) -> Result<CompilerSettings, Error> {
     let no_warn = args.is_present("no_warn");
     let verbosity = Verbosity::from_level(args.occurrences_of("verbose") as isize);
     let include_path = VecDeque::new();
-    let mut code_path = match args.values_of_os("prepend-path") {
-        None => Vec::new(),
-        Some(values) => values.map(PathBuf::from).collect(),
-    };
-    let mut append_dirs = match args.values_of_os("append-path") {
-        None => Vec::new(),
-        Some(values) => values.map(PathBuf::from).collect(),
-    };
-    code_path.append(&mut append_dirs);
+    let code_path = configure_code_path(args)?;
     Ok(CompilerSettings {
         mode,
         color: ColorChoice::Auto,
@@ -51,3 +44,27 @@ fn configure<'a>(args: &'a ArgMatches) -> Result<CompilerSettings, Error> {
         codemap,
     })
 }
+
+/// Resolves the `-pa` and `-pz` options through the code path, in the order they were given.
+///
+/// The parser only reads `-include_lib` files from directories on disk, so paths inside of
+/// archives are rejected.
+fn configure_code_path<'a>(args: &'a ArgMatches) -> Result<Vec<PathBuf>, Error> {
+    let mut code_path = CodePath::new();
+    let prepend = args.values_of_os("prepend-path").into_iter().flatten();
+    let append = args.values_of_os("append-path").into_iter().flatten();
+    for path in prepend.chain(append) {
+        code_path.append(path)?;
+    }
+    code_path
+        .entries()
+        .iter()
+        .map(|entry| match entry {
+            CodePathEntry::Dir(dir) => Ok(dir.clone()),
+            CodePathEntry::Archive { .. } => Err(format_err!(
+                "{} is inside an archive, which the compiler can not read from",
+                entry
+            )),
+        })
+        .collect()
+}
@@ -0,0 +1,455 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use clap::{value_t, ArgMatches};
+use failure::{format_err, Error};
+
+use liblumen_beam::beam::code_path::CodePathEntry;
+use liblumen_beam::beam::docs::{self, Doc, DocEntry, DocKind, DocValue, ModuleDocs};
+use liblumen_beam::beam::reader::chunk::StandardChunk;
+use liblumen_beam::beam::reader::StandardBeamFile;
+use liblumen_beam::serialization::etf::{text, Term};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Markdown,
+    Html,
+}
+impl OutputFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Markdown => "md",
+            OutputFormat::Html => "html",
+        }
+    }
+}
+impl std::str::FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" => Ok(OutputFormat::Markdown),
+            "html" => Ok(OutputFormat::Html),
+            _ => Err(format_err!("invalid documentation format '{}'", s)),
+        }
+    }
+}
+
+/// Dispatches `lumen doc`, rendering the documentation of every BEAM file in a directory or archive
+pub fn dispatch<'a>(args: &'a ArgMatches) -> Result<(), Error> {
+    let input_dir = args.value_of_os("path").map(PathBuf::from).unwrap();
+    let output_dir = args.value_of_os("output").map(PathBuf::from).unwrap();
+    let format = value_t!(args, "format", OutputFormat).unwrap_or_else(|e| e.exit());
+    let language = args.value_of("language").unwrap();
+
+    let mut modules = Vec::new();
+    for location in CodePathEntry::from_path(&input_dir)?.modules()? {
+        let beam: StandardBeamFile = location.read_beam()?;
+        let docs = match beam.get_chunk(b"Docs") {
+            Some(StandardChunk::Docs(chunk)) => chunk.docs()?,
+            _ => continue,
+        };
+        if docs.module_doc.is_hidden() {
+            continue;
+        }
+        let name = match beam.atoms() {
+            Some(StandardChunk::Atom(chunk)) if !chunk.atoms.is_empty() => {
+                chunk.atoms[0].name.clone()
+            }
+            _ => return Err(format_err!("{} has no atom chunk", location)),
+        };
+        // The name is used as the file name of the module's page
+        if !is_file_name(&name) {
+            return Err(format_err!(
+                "{} has module name '{}', which is not a valid file name",
+                location,
+                name
+            ));
+        }
+        modules.push((name, docs));
+    }
+
+    fs::create_dir_all(&output_dir)?;
+    for (name, docs) in &modules {
+        let page = render_module(format, name, docs, language);
+        let file = output_dir.join(format!("{}.{}", name, format.extension()));
+        fs::write(file, page)?;
+    }
+    let names = modules.iter().map(|(name, _)| name.as_str());
+    let index = render_index(format, names);
+    fs::write(
+        output_dir.join(format!("index.{}", format.extension())),
+        index,
+    )?;
+
+    Ok(())
+}
+
+/// Whether `name` is a single path component, so that it can not refer to a file outside of the
+/// output directory.  `:` is rejected as it starts a drive prefix on Windows.
+fn is_file_name(name: &str) -> bool {
+    !(name.is_empty()
+        || name == "."
+        || name == ".."
+        || name.contains(|c| c == '/' || c == '\\' || c == ':' || c == '\0'))
+}
+
+fn render_index<'a, I>(format: OutputFormat, names: I) -> String
+where
+    I: Iterator<Item = &'a str>,
+{
+    let ext = format.extension();
+    let mut out = String::new();
+    match format {
+        OutputFormat::Markdown => {
+            out.push_str("# Modules\n\n");
+            for name in names {
+                writeln!(out, "- [{}]({}.{})", name, name, ext).unwrap();
+            }
+        }
+        OutputFormat::Html => {
+            out.push_str(&html_header("Modules"));
+            out.push_str("<h1>Modules</h1>\n<ul>\n");
+            for name in names {
+                let name = escape_html(name);
+                writeln!(out, "<li><a href=\"{}.{}\">{}</a></li>", name, ext, name).unwrap();
+            }
+            out.push_str("</ul>\n");
+            out.push_str(HTML_FOOTER);
+        }
+    }
+    out
+}
+
+fn render_module(format: OutputFormat, name: &str, docs: &ModuleDocs, language: &str) -> String {
+    let mut out = String::new();
+    if format == OutputFormat::Html {
+        out.push_str(&html_header(name));
+    }
+    heading(&mut out, format, 1, name);
+    render_doc(&mut out, format, &docs.module_doc, &docs.format, language);
+
+    // Group entries by kind, in the order types, callbacks, functions, macros
+    let mut entries = docs
+        .entries
+        .iter()
+        .filter(|e| !e.doc.is_hidden())
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.kind.cmp(&b.kind));
+    let mut kind: Option<&DocKind> = None;
+    for entry in entries {
+        if kind != Some(&entry.kind) {
+            kind = Some(&entry.kind);
+            heading(&mut out, format, 2, section_title(&entry.kind));
+        }
+        render_entry(&mut out, format, entry, &docs.format, language);
+    }
+
+    if format == OutputFormat::Html {
+        out.push_str(HTML_FOOTER);
+    }
+    out
+}
+
+fn section_title(kind: &DocKind) -> &str {
+    match *kind {
+        DocKind::Type => "Types",
+        DocKind::Callback => "Callbacks",
+        DocKind::Function => "Functions",
+        DocKind::Macro => "Macros",
+        DocKind::Other(ref name) => name,
+    }
+}
+
+fn render_entry(
+    out: &mut String,
+    format: OutputFormat,
+    entry: &DocEntry,
+    doc_format: &str,
+    language: &str,
+) {
+    heading(out, format, 3, &entry.to_string());
+    if !entry.signature.is_empty() {
+        let signature = entry.signature.join("\n");
+        match format {
+            OutputFormat::Markdown => writeln!(out, "```\n{}\n```\n", signature).unwrap(),
+            OutputFormat::Html => {
+                writeln!(out, "<pre><code>{}</code></pre>", escape_html(&signature)).unwrap()
+            }
+        }
+    }
+    if let Some(deprecated) = entry.meta("deprecated") {
+        let note = format!("Deprecated: {}", meta_text(deprecated));
+        paragraph(out, format, &note);
+    }
+    if let Some(since) = entry.meta("since") {
+        let note = format!("Since {}", meta_text(since));
+        paragraph(out, format, &note);
+    }
+    render_doc(out, format, &entry.doc, doc_format, language);
+}
+
+fn render_doc(out: &mut String, format: OutputFormat, doc: &Doc, doc_format: &str, language: &str) {
+    match (doc.get(language), format) {
+        (None, _) => {}
+        (Some(DocValue::Text(text)), OutputFormat::Markdown) => {
+            out.push_str(text.trim_end());
+            out.push_str("\n\n");
+        }
+        (Some(DocValue::Text(text)), OutputFormat::Html) => {
+            if doc_format == docs::FORMAT_MARKDOWN {
+                let parser = pulldown_cmark::Parser::new(text);
+                pulldown_cmark::html::push_html(out, parser);
+            } else {
+                writeln!(out, "<pre>{}</pre>", escape_html(text)).unwrap();
+            }
+        }
+        (Some(DocValue::Term(term)), OutputFormat::Markdown) => {
+            let mut markdown = String::new();
+            erlang_html_to_markdown(&mut markdown, term);
+            out.push_str(markdown.trim_end());
+            out.push_str("\n\n");
+        }
+        (Some(DocValue::Term(term)), OutputFormat::Html) => {
+            erlang_html_to_html(out, term);
+            out.push('\n');
+        }
+    }
+}
+
+fn heading(out: &mut String, format: OutputFormat, level: usize, title: &str) {
+    match format {
+        OutputFormat::Markdown => writeln!(out, "{} {}\n", "#".repeat(level), title).unwrap(),
+        OutputFormat::Html => {
+            writeln!(out, "<h{}>{}</h{}>", level, escape_html(title), level).unwrap()
+        }
+    }
+}
+
+fn paragraph(out: &mut String, format: OutputFormat, text: &str) {
+    match format {
+        OutputFormat::Markdown => writeln!(out, "*{}*\n", text).unwrap(),
+        OutputFormat::Html => writeln!(out, "<p><em>{}</em></p>", escape_html(text)).unwrap(),
+    }
+}
+
+/// Metadata values are usually binaries, which are shown without quotes
+fn meta_text(term: &Term) -> String {
+    match *term {
+        Term::Binary(ref b) => String::from_utf8_lossy(&b.bytes).into_owned(),
+        _ => text::to_string(term),
+    }
+}
+
+/// The `application/erlang+html` tags which are rendered as HTML.  The content of other
+/// elements is rendered without their tags.
+const ERLANG_HTML_TAGS: &[&str] = &[
+    "a",
+    "p",
+    "div",
+    "br",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "i",
+    "b",
+    "em",
+    "strong",
+    "pre",
+    "code",
+    "ul",
+    "ol",
+    "li",
+    "dl",
+    "dt",
+    "dd",
+    "span",
+    "blockquote",
+];
+
+/// The attributes which are kept on rendered tags
+const ERLANG_HTML_ATTRS: &[&str] = &["href", "id"];
+
+/// Renders `application/erlang+html` content, a list of binaries and `{Tag, Attrs, Content}`
+/// elements, as HTML
+fn erlang_html_to_html(out: &mut String, content: &Term) {
+    match *content {
+        Term::List(ref list) => {
+            for item in &list.elements {
+                erlang_html_to_html(out, item);
+            }
+        }
+        Term::Binary(ref b) => out.push_str(&escape_html(&String::from_utf8_lossy(&b.bytes))),
+        Term::Tuple(ref t) if t.elements.len() == 3 => {
+            let tag = match t.elements[0] {
+                Term::Atom(ref a) => &a.name,
+                _ => return,
+            };
+            if !ERLANG_HTML_TAGS.contains(&tag.as_str()) {
+                erlang_html_to_html(out, &t.elements[2]);
+                return;
+            }
+            write!(out, "<{}", tag).unwrap();
+            if let Term::List(ref attrs) = t.elements[1] {
+                for attr in &attrs.elements {
+                    if let Some((name, value)) = erlang_html_attr(attr) {
+                        if ERLANG_HTML_ATTRS.contains(&name.as_str()) && is_safe_attr(&name, &value)
+                        {
+                            write!(out, " {}=\"{}\"", name, escape_html(&value)).unwrap();
+                        }
+                    }
+                }
+            }
+            if tag == "br" {
+                out.push_str("/>");
+                return;
+            }
+            out.push('>');
+            erlang_html_to_html(out, &t.elements[2]);
+            write!(out, "</{}>", tag).unwrap();
+        }
+        _ => {}
+    }
+}
+
+/// Renders `application/erlang+html` content as Markdown
+fn erlang_html_to_markdown(out: &mut String, content: &Term) {
+    match *content {
+        Term::List(ref list) => {
+            for item in &list.elements {
+                erlang_html_to_markdown(out, item);
+            }
+        }
+        Term::Binary(ref b) => out.push_str(&String::from_utf8_lossy(&b.bytes)),
+        Term::Tuple(ref t) if t.elements.len() == 3 => {
+            let tag = match t.elements[0] {
+                Term::Atom(ref a) => a.name.as_str(),
+                _ => return,
+            };
+            let inner = &t.elements[2];
+            let mut text = String::new();
+            erlang_html_to_markdown(&mut text, inner);
+            match tag {
+                "p" | "div" => write!(out, "{}\n\n", text.trim()).unwrap(),
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                    let level = tag[1..].parse::<usize>().unwrap();
+                    write!(out, "{} {}\n\n", "#".repeat(level), text.trim()).unwrap()
+                }
+                "pre" => {
+                    let mut code = String::new();
+                    erlang_html_text(&mut code, inner);
+                    write!(out, "```\n{}\n```\n\n", code.trim_end()).unwrap()
+                }
+                "code" => write!(out, "`{}`", text).unwrap(),
+                "em" | "i" => write!(out, "*{}*", text).unwrap(),
+                "strong" | "b" => write!(out, "**{}**", text).unwrap(),
+                "br" => out.push_str("  \n"),
+                "a" => {
+                    let href = match t.elements[1] {
+                        Term::List(ref attrs) => attrs
+                            .elements
+                            .iter()
+                            .filter_map(erlang_html_attr)
+                            .find(|(name, _)| name == "href")
+                            .map(|(_, value)| value),
+                        _ => None,
+                    };
+                    match href {
+                        Some(href) => write!(out, "[{}]({})", text, href).unwrap(),
+                        None => out.push_str(&text),
+                    }
+                }
+                "ul" | "ol" => {
+                    if let Term::List(ref items) = *inner {
+                        for (i, item) in items.elements.iter().enumerate() {
+                            let mut text = String::new();
+                            erlang_html_to_markdown(&mut text, item);
+                            let text = text.trim();
+                            if text.is_empty() {
+                                continue;
+                            }
+                            if tag == "ol" {
+                                writeln!(out, "{}. {}", i + 1, text).unwrap();
+                            } else {
+                                writeln!(out, "- {}", text).unwrap();
+                            }
+                        }
+                    }
+                    out.push('\n');
+                }
+                "dt" => write!(out, "**{}**\n\n", text.trim()).unwrap(),
+                "dd" => write!(out, ": {}\n\n", text.trim()).unwrap(),
+                _ => out.push_str(&text),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collects the text of `application/erlang+html` content, without any markup
+fn erlang_html_text(out: &mut String, content: &Term) {
+    match *content {
+        Term::List(ref list) => {
+            for item in &list.elements {
+                erlang_html_text(out, item);
+            }
+        }
+        Term::Binary(ref b) => out.push_str(&String::from_utf8_lossy(&b.bytes)),
+        Term::Tuple(ref t) if t.elements.len() == 3 => erlang_html_text(out, &t.elements[2]),
+        _ => {}
+    }
+}
+
+fn erlang_html_attr(attr: &Term) -> Option<(String, String)> {
+    match *attr {
+        Term::Tuple(ref t) if t.elements.len() == 2 => match (&t.elements[0], &t.elements[1]) {
+            (Term::Atom(name), Term::Binary(value)) => Some((
+                name.name.clone(),
+                String::from_utf8_lossy(&value.bytes).into_owned(),
+            )),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Links may only use web and mail schemes, so that `javascript:` URLs are dropped
+fn is_safe_attr(name: &str, value: &str) -> bool {
+    if name != "href" {
+        return true;
+    }
+    let value = value.trim_start().to_ascii_lowercase();
+    match value.find(|c: char| c == ':' || c == '/' || c == '?' || c == '#') {
+        Some(i) if value[i..].starts_with(':') => {
+            ["http", "https", "mailto"].contains(&&value[..i])
+        }
+        _ => true,
+    }
+}
+
+fn html_header(title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n",
+        escape_html(title)
+    )
+}
+
+const HTML_FOOTER: &str = "</body>\n</html>\n";
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,9 +1,12 @@
+mod beam;
 mod compiler;
+mod doc;
 
+use std::ffi::OsString;
 use std::process;
 
 use clap::{crate_description, crate_name, crate_version};
-use clap::{App, Arg, SubCommand};
+use clap::{App, AppSettings, Arg, SubCommand};
 use failure::Error;
 
 use libeir_diagnostics::{ColorChoice, Emitter, StandardStreamEmitter};
@@ -24,6 +27,7 @@ fn main() {
     };
 
     let output_dir = cwd.join("_build/target");
+    let doc_dir = cwd.join("_build/doc");
 
     // Build argument parser
     let matches = App::new(crate_name!())
@@ -86,8 +90,7 @@ fn main() {
                 )
                 .arg(
                     Arg::with_name("append-path")
-                        .help("Appends a path to the code path")
-                        .short("pz")
+                        .help("Appends a directory to the code path (-pz)")
                         .long("append-path")
                         .value_name("PATH")
                         .takes_value(true)
@@ -95,19 +98,118 @@ fn main() {
                 )
                 .arg(
                     Arg::with_name("prepend-path")
-                        .help("Prepends a path to the code path")
-                        .short("pa")
+                        .help("Prepends a directory to the code path (-pa)")
                         .long("prepend-path")
                         .value_name("PATH")
                         .takes_value(true)
                         .multiple(true),
                 ),
         )
-        .get_matches();
+        .subcommand(
+            SubCommand::with_name("beam")
+                .about("Inspects and manipulates compiled BEAM files")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .arg(
+                    Arg::with_name("append-path")
+                        .help("Appends a directory or .ez archive path to the code path (-pz)")
+                        .long("append-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                )
+                .arg(
+                    Arg::with_name("prepend-path")
+                        .help("Prepends a directory or .ez archive path to the code path (-pa)")
+                        .long("prepend-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                )
+                .subcommand(
+                    SubCommand::with_name("info")
+                        .about(
+                            "Prints the module name, exports, imports, attributes and compile info",
+                        )
+                        .arg(beam_file_arg("file")),
+                )
+                .subcommand(
+                    SubCommand::with_name("chunks")
+                        .about("Lists the id and size of each chunk")
+                        .arg(beam_file_arg("file")),
+                )
+                .subcommand(
+                    SubCommand::with_name("strip")
+                        .about("Removes all chunks which are not required to load the module")
+                        .arg(beam_file_arg("file"))
+                        .arg(
+                            Arg::with_name("output")
+                                .help("The file to write the stripped module to, instead of FILE")
+                                .short("o")
+                                .long("output")
+                                .value_name("FILE")
+                                .takes_value(true),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("diff")
+                        .about("Compares two BEAM files chunk by chunk")
+                        .arg(beam_file_arg("a").value_name("A"))
+                        .arg(beam_file_arg("b").value_name("B").index(2)),
+                )
+                .subcommand(
+                    SubCommand::with_name("docs")
+                        .about("Prints the documentation chunk")
+                        .arg(beam_file_arg("file")),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("doc")
+                .about("Generates documentation from the Docs chunks of compiled BEAM files")
+                .arg(
+                    Arg::with_name("path")
+                        .help("The directory or .ez archive containing the .beam files to document")
+                        .index(1)
+                        .takes_value(true)
+                        .value_name("DIR")
+                        .default_value_os(cwd.as_os_str())
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .help("The directory to place the generated documentation")
+                        .short("o")
+                        .long("output")
+                        .value_name("DIR")
+                        .default_value_os(doc_dir.as_os_str()),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .help("The format of the generated documentation")
+                        .short("f")
+                        .long("format")
+                        .takes_value(true)
+                        .value_name("FORMAT")
+                        .possible_values(&["markdown", "html"])
+                        .default_value("markdown"),
+                )
+                .arg(
+                    Arg::with_name("language")
+                        .help("The preferred documentation language")
+                        .long("language")
+                        .takes_value(true)
+                        .value_name("LANG")
+                        .default_value("en"),
+                ),
+        )
+        .get_matches_from(erlang_args());
 
     // Dispatch commands
     let result: Result<(), Error> = match matches.subcommand() {
         ("compile", Some(args)) => compiler::dispatch(&args),
+        ("beam", Some(args)) => beam::dispatch(&args),
+        ("doc", Some(args)) => doc::dispatch(&args),
         _ => Ok(()),
     };
 
@@ -135,3 +237,28 @@ fn main() {
         _ => return,
     };
 }
+
+/// Returns the command-line arguments, with the Erlang-style `-pa` and `-pz` flags
+/// rewritten to their long forms, since clap only supports single character short flags
+fn erlang_args() -> Vec<OsString> {
+    std::env::args_os()
+        .map(|arg| {
+            if arg.to_str() == Some("-pa") {
+                OsString::from("--prepend-path")
+            } else if arg.to_str() == Some("-pz") {
+                OsString::from("--append-path")
+            } else {
+                arg
+            }
+        })
+        .collect()
+}
+
+fn beam_file_arg<'a, 'b>(name: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .help("The path to a .beam file, or the name of a module on the code path")
+        .index(1)
+        .takes_value(true)
+        .value_name("FILE_OR_MODULE")
+        .required(true)
+}
@@ -1,7 +1,22 @@
+use liblumen_core::locks::RwLock;
+
+use lazy_static::lazy_static;
+
 use liblumen_alloc::erts::term::{AsTerm, Atom, Term};
 
 pub const DEAD: &str = "nonode@nohost";
 
+/// The id of the local node in `Node`, which identifies the node of external pids, ports and
+/// references.
+pub const LOCAL_ID: usize = 0;
+
+// TODO implement creation rotation
+// > A 32-bit big endian unsigned integer. All identifiers originating from the same node
+// > incarnation must have identical Creation values. This makes it possible to separate identifiers
+// > from old (crashed) nodes from a new one. The value zero should be avoided for normal operations
+// > as it is used as a wild card for debug purpose (like a pid returned by erlang:list_to_pid/1).
+pub const CREATION: u32 = 0;
+
 pub fn atom() -> Atom {
     Atom::try_from_str(DEAD).unwrap()
 }
@@ -9,3 +24,55 @@ pub fn atom() -> Atom {
 pub fn term() -> Term {
     unsafe { atom().as_term() }
 }
+
+/// Returns the id of the incarnation `creation` of the node named `atom`, assigning the next id
+/// the first time the incarnation is seen.
+pub fn id(atom: Atom, creation: u32) -> usize {
+    if let Some(id) = existing_id(atom, creation) {
+        return id;
+    }
+
+    let name_creation = (atom, creation);
+    let mut writable_name_creation_by_id = RW_LOCK_NAME_CREATION_BY_ID.write();
+
+    // another thread may have added the node between the locks
+    match writable_name_creation_by_id
+        .iter()
+        .position(|entry| *entry == name_creation)
+    {
+        Some(id) => id + 1,
+        None => {
+            writable_name_creation_by_id.push(name_creation);
+
+            writable_name_creation_by_id.len()
+        }
+    }
+}
+
+/// Returns the id of the incarnation `creation` of the node named `atom`, if it was already
+/// assigned by `id`.
+pub fn existing_id(atom: Atom, creation: u32) -> Option<usize> {
+    if atom == self::atom() && creation == CREATION {
+        return Some(LOCAL_ID);
+    }
+
+    RW_LOCK_NAME_CREATION_BY_ID
+        .read()
+        .iter()
+        .position(|entry| *entry == (atom, creation))
+        .map(|index| index + 1)
+}
+
+/// Returns the name and creation of the node with `id`, as previously assigned by `id`.
+pub fn name_creation_from_id(id: usize) -> Option<(Atom, u32)> {
+    if id == LOCAL_ID {
+        Some((atom(), CREATION))
+    } else {
+        RW_LOCK_NAME_CREATION_BY_ID.read().get(id - 1).cloned()
+    }
+}
+
+lazy_static! {
+    // Indexed by `id - 1`, as the local node is not stored.
+    static ref RW_LOCK_NAME_CREATION_BY_ID: RwLock<Vec<(Atom, u32)>> = Default::default();
+}
@@ -52,6 +52,9 @@ pub mod erase_1;
 pub mod error_1;
 pub mod error_2;
 pub mod exit_1;
+pub mod exit_2;
+pub mod external_size_1;
+pub mod external_size_2;
 pub mod float_1;
 pub mod float_to_binary_1;
 pub mod float_to_binary_2;
@@ -59,11 +62,16 @@ pub mod float_to_list_1;
 pub mod float_to_list_2;
 mod float_to_string;
 pub mod floor_1;
+pub mod fun_to_list_1;
+pub mod garbage_collect_0;
+pub mod garbage_collect_1;
+pub mod garbage_collect_2;
 pub mod get_0;
 pub mod get_1;
 pub mod get_keys_0;
 pub mod get_keys_1;
 pub mod hd_1;
+pub mod hibernate_3;
 pub mod insert_element_3;
 pub mod integer_to_binary_1;
 pub mod integer_to_binary_2;
@@ -103,6 +111,8 @@ pub mod list_to_float_1;
 pub mod list_to_integer_1;
 pub mod list_to_integer_2;
 pub mod list_to_pid_1;
+pub mod list_to_port_1;
+pub mod list_to_ref_1;
 mod list_to_string;
 pub mod list_to_tuple_1;
 pub mod localtime_0;
@@ -112,6 +122,8 @@ pub mod make_tuple_3;
 pub mod map_get_2;
 pub mod map_size_1;
 pub mod max_2;
+pub mod memory_0;
+pub mod memory_1;
 pub mod min_2;
 pub mod monitor_2;
 pub mod monotonic_time_0;
@@ -124,12 +136,19 @@ pub mod number_or_badarith_1;
 mod number_to_integer;
 pub mod or_2;
 pub mod orelse_2;
+pub mod pid_to_list_1;
+pub mod port_to_list_1;
 pub mod process_flag_2;
+pub mod process_flag_3;
+mod process_info;
+pub mod process_info_1;
 pub mod process_info_2;
+pub mod processes_0;
 pub mod put_2;
 pub mod raise_3;
 pub mod read_timer_1;
 pub mod read_timer_2;
+pub mod ref_to_list_1;
 pub mod register_2;
 pub mod registered_0;
 pub mod rem_2;
@@ -154,14 +173,20 @@ pub mod spawn_opt_4;
 pub mod split_binary_2;
 pub mod start_timer_3;
 pub mod start_timer_4;
+pub mod statistics_1;
 mod string_to_float;
 mod string_to_integer;
 pub mod subtract_2;
 pub mod subtract_list_2;
+pub mod system_flag_2;
+pub mod system_info_1;
 pub mod system_time_0;
 pub mod system_time_1;
 mod term_to_binary;
 pub mod term_to_binary_1;
+pub mod term_to_binary_2;
+pub mod term_to_iovec_1;
+pub mod term_to_iovec_2;
 pub mod throw_1;
 pub mod time_0;
 pub mod time_offset_0;
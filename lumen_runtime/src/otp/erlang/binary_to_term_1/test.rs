@@ -10,7 +10,6 @@ use crate::scheduler::with_process_arc;
 use crate::test::strategy;
 
 #[test]
-#[ignore]
 fn without_binary_errors_badarg() {
     with_process_arc(|arc_process| {
         TestRunner::new(Config::with_source_file(file!()))
@@ -27,7 +26,6 @@ fn without_binary_errors_badarg() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_atom_returns_atom() {
     with_binary_returns_term(
         // :erlang.term_to_binary(:atom)
@@ -37,7 +35,6 @@ fn with_binary_encoding_atom_returns_atom() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_empty_list_returns_empty_list() {
     with_binary_returns_term(
         // :erlang.term_to_binary([])
@@ -47,7 +44,6 @@ fn with_binary_encoding_empty_list_returns_empty_list() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_list_returns_list() {
     with_binary_returns_term(
         // :erlang.term_to_binary([:zero, 1])
@@ -68,7 +64,6 @@ fn with_binary_encoding_list_returns_list() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_small_integer_returns_small_integer() {
     with_binary_returns_term(
         // :erlang.term_to_binary(0)
@@ -78,7 +73,6 @@ fn with_binary_encoding_small_integer_returns_small_integer() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_integer_returns_integer() {
     with_binary_returns_term(
         // :erlang.term_to_binary(-2147483648)
@@ -88,7 +82,6 @@ fn with_binary_encoding_integer_returns_integer() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_new_float_returns_float() {
     with_binary_returns_term(
         // :erlang.term_to_binary(1.0)
@@ -98,7 +91,6 @@ fn with_binary_encoding_new_float_returns_float() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_small_tuple_returns_tuple() {
     with_binary_returns_term(
         // :erlang.term_to_binary({:zero, 1})
@@ -112,7 +104,6 @@ fn with_binary_encoding_small_tuple_returns_tuple() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_byte_list_returns_list() {
     with_binary_returns_term(
         // :erlang.term_to_binary([?0, ?1])
@@ -131,7 +122,6 @@ fn with_binary_encoding_byte_list_returns_list() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_binary_returns_binary() {
     with_binary_returns_term(
         // :erlang.term_to_binary(<<0, 1>>)
@@ -141,7 +131,6 @@ fn with_binary_encoding_binary_returns_binary() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_small_big_integer_returns_big_integer() {
     with_binary_returns_term(
         // :erlang.term_to_binary(4294967295)
@@ -151,7 +140,6 @@ fn with_binary_encoding_small_big_integer_returns_big_integer() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_bit_string_returns_subbinary() {
     with_binary_returns_term(
         // :erlang.term_to_binary(<<1, 2::3>>)
@@ -171,7 +159,6 @@ fn with_binary_encoding_bit_string_returns_subbinary() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_small_atom_utf8_returns_atom() {
     with_binary_returns_term(
         // :erlang.term_to_binary(:"😈")
@@ -180,6 +167,112 @@ fn with_binary_encoding_small_atom_utf8_returns_atom() {
     );
 }
 
+#[test]
+fn with_binary_encoding_map_returns_map() {
+    with_binary_returns_term(
+        // :erlang.term_to_binary(%{a: 1})
+        vec![131, 116, 0, 0, 0, 1, 100, 0, 1, 97, 97, 1],
+        |process| {
+            process
+                .map_from_slice(&[(atom_unchecked("a"), process.integer(1).unwrap())])
+                .unwrap()
+        },
+    );
+}
+
+#[test]
+fn with_binary_encoding_map_with_duplicate_keys_errors_badarg() {
+    with_binary_errors_badarg(vec![
+        131, 116, 0, 0, 0, 2, 100, 0, 1, 97, 97, 1, 100, 0, 1, 97, 97, 2,
+    ]);
+}
+
+#[test]
+fn with_binary_encoding_pid_returns_pid() {
+    with_binary_returns_term(
+        // :erlang.term_to_binary(:c.pid(0, 1, 2))
+        vec![
+            131, 103, 100, 0, 13, 110, 111, 110, 111, 100, 101, 64, 110, 111, 104, 111, 115, 116,
+            0, 0, 0, 1, 0, 0, 0, 2, 0,
+        ],
+        |process| process.pid_with_node_id(0, 1, 2).unwrap(),
+    );
+}
+
+#[test]
+fn with_binary_encoding_compressed_term_returns_term() {
+    with_binary_returns_term(
+        // :erlang.term_to_binary(:lists.duplicate(20, 0), [:compressed])
+        vec![
+            131, 80, 0, 0, 0, 23, 120, 156, 203, 102, 16, 97, 192, 2, 0, 11, 88, 0, 128,
+        ],
+        |process| {
+            let zero = process.integer(0).unwrap();
+
+            process.list_from_slice(&[zero; 20]).unwrap()
+        },
+    );
+}
+
+#[test]
+fn with_binary_encoding_compressed_term_with_wrong_size_errors_badarg() {
+    with_binary_errors_badarg(vec![
+        131, 80, 0, 0, 0, 24, 120, 156, 203, 102, 16, 97, 192, 2, 0, 11, 88, 0, 128,
+    ]);
+}
+
+#[test]
+fn with_truncated_binary_errors_badarg() {
+    // :erlang.term_to_binary({:zero, 1}) without the last byte
+    with_binary_errors_badarg(vec![131, 104, 2, 100, 0, 4, 122, 101, 114, 111, 97]);
+}
+
+#[test]
+fn with_binary_encoding_deeply_nested_tuples_errors_badarg() {
+    // 10000 nested `SMALL_TUPLE_EXT`s with arity 1 around `[]`
+    let mut byte_vec = vec![131];
+    for _ in 0..10_000 {
+        byte_vec.extend_from_slice(&[104, 1]);
+    }
+    byte_vec.push(106);
+
+    with_binary_errors_badarg(byte_vec);
+}
+
+#[test]
+fn with_binary_encoding_nested_tuples_returns_tuple() {
+    // 100 nested `SMALL_TUPLE_EXT`s with arity 1 around `[]`
+    let mut byte_vec = vec![131];
+    for _ in 0..100 {
+        byte_vec.extend_from_slice(&[104, 1]);
+    }
+    byte_vec.push(106);
+
+    with_binary_returns_term(byte_vec, |process| {
+        let mut term = Term::NIL;
+        for _ in 0..100 {
+            term = process.tuple_from_slice(&[term]).unwrap();
+        }
+
+        term
+    });
+}
+
+fn with_binary_errors_badarg(byte_vec: Vec<u8>) {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &strategy::term::binary::containing_bytes(byte_vec, arc_process.clone()),
+                |binary| {
+                    prop_assert_eq!(native(&arc_process, binary), Err(badarg!().into()));
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
 fn with_binary_returns_term<T>(byte_vec: Vec<u8>, term: T)
 where
     T: Fn(&Process) -> Term,
@@ -7,26 +7,28 @@ mod test;
 
 use std::convert::TryInto;
 
-use liblumen_alloc::badarg;
 use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::process::Process;
-use liblumen_alloc::erts::term::{Term, TypedTerm};
+use liblumen_alloc::erts::term::Term;
 
 use lumen_runtime_macros::native_implemented_function;
 
 use crate::binary::ToTermOptions;
+use crate::term::external_format;
 
 #[native_implemented_function(binary_to_term/2)]
-pub fn native(_process: &Process, binary: Term, options: Term) -> exception::Result {
-    let _to_term_options: ToTermOptions = options.try_into()?;
+pub fn native(process: &Process, binary: Term, options: Term) -> exception::Result {
+    let to_term_options: ToTermOptions = options.try_into()?;
+    let bytes = process.bytes_from_binary(binary)?;
+    let (term, used) = external_format::decode(process, to_term_options.existing, bytes)?;
 
-    match binary.to_typed_term().unwrap() {
-        TypedTerm::Boxed(boxed) => match boxed.to_typed_term().unwrap() {
-            TypedTerm::HeapBinary(_heap_binary) => unimplemented!(),
-            TypedTerm::ProcBin(_process_binary) => unimplemented!(),
-            TypedTerm::SubBinary(_subbinary) => unimplemented!(),
-            _ => Err(badarg!().into()),
-        },
-        _ => Err(badarg!().into()),
+    if to_term_options.used {
+        let used_term = process.integer(used)?;
+
+        process
+            .tuple_from_slice(&[term, used_term])
+            .map_err(|error| error.into())
+    } else {
+        Ok(term)
     }
 }
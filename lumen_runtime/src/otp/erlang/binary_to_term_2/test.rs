@@ -13,7 +13,6 @@ use crate::scheduler::with_process_arc;
 use crate::test::strategy;
 
 #[test]
-#[ignore]
 fn with_used_with_binary_returns_how_many_bytes_were_consumed_along_with_term() {
     // <<131,100,0,5,"hello","world">>
     let byte_vec = vec![
@@ -1,7 +1,10 @@
 use super::*;
 
+use liblumen_alloc::erts::term::Atom;
+
+use crate::node;
+
 #[test]
-#[ignore]
 fn with_binary_encoding_atom_that_does_not_exist_errors_badarg() {
     // :erlang.term_to_binary(:non_existent_0)
     let byte_vec = vec![
@@ -26,7 +29,6 @@ fn with_binary_encoding_atom_that_does_not_exist_errors_badarg() {
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_list_containing_atom_that_does_not_exist_errors_badarg() {
     // :erlang.term_to_binary([:non_existent_1])
     let byte_vec = vec![
@@ -52,7 +54,6 @@ fn with_binary_encoding_list_containing_atom_that_does_not_exist_errors_badarg()
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_small_tuple_containing_atom_that_does_not_exist_errors_badarg() {
     // :erlang.term_to_binary({:non_existent_2})
     let byte_vec = vec![
@@ -77,7 +78,6 @@ fn with_binary_encoding_small_tuple_containing_atom_that_does_not_exist_errors_b
 }
 
 #[test]
-#[ignore]
 fn with_binary_encoding_small_atom_utf8_that_does_not_exist_errors_badarg() {
     // :erlang.term_to_binary(:"non_existent_3_😈")
     let byte_vec = vec![
@@ -102,6 +102,64 @@ fn with_binary_encoding_small_atom_utf8_that_does_not_exist_errors_badarg() {
     });
 }
 
+#[test]
+fn with_binary_encoding_pid_of_unknown_node_errors_badarg_without_registering_node() {
+    let node = Atom::try_from_str(node::DEAD).unwrap();
+    let creation = 0x5AFE_0001;
+    let byte_vec = new_pid_ext(node::DEAD, creation);
+
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &strategy::term::binary::containing_bytes(byte_vec, arc_process.clone()),
+                |binary| {
+                    prop_assert_eq!(
+                        native(&arc_process, binary, options(&arc_process)),
+                        Err(badarg!().into())
+                    );
+                    prop_assert_eq!(node::existing_id(node, creation), None);
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_binary_encoding_pid_of_known_node_returns_pid() {
+    let creation = 0x5AFE_0002;
+    let node_id = node::id(Atom::try_from_str(node::DEAD).unwrap(), creation);
+    let byte_vec = new_pid_ext(node::DEAD, creation);
+
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &strategy::term::binary::containing_bytes(byte_vec, arc_process.clone()),
+                |binary| {
+                    prop_assert_eq!(
+                        native(&arc_process, binary, options(&arc_process)),
+                        Ok(arc_process.pid_with_node_id(node_id, 1, 2).unwrap())
+                    );
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
+// NEW_PID_EXT with number 1 and serial 2
+fn new_pid_ext(node: &str, creation: u32) -> Vec<u8> {
+    let mut byte_vec = vec![131, 88, 100, 0, node.len() as u8];
+    byte_vec.extend_from_slice(node.as_bytes());
+    byte_vec.extend_from_slice(&1_u32.to_be_bytes());
+    byte_vec.extend_from_slice(&2_u32.to_be_bytes());
+    byte_vec.extend_from_slice(&creation.to_be_bytes());
+
+    byte_vec
+}
+
 fn options(process: &Process) -> Term {
     process.cons(atom_unchecked("safe"), Term::NIL).unwrap()
 }
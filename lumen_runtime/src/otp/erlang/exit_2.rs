@@ -0,0 +1,59 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{atom_unchecked, Term, TypedTerm};
+use liblumen_alloc::{badarg, exit};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::process::{exit_signal, is_exit_reason};
+use crate::registry::pid_to_process;
+
+#[native_implemented_function(exit/2)]
+fn native(process: &Process, pid_or_port: Term, reason: Term) -> exception::Result {
+    match pid_or_port.to_typed_term().unwrap() {
+        TypedTerm::Pid(pid) => {
+            if pid == process.pid() {
+                exit_self(process, reason)
+            } else {
+                if let Some(pid_arc_process) = pid_to_process(&pid) {
+                    exit_signal(process, &pid_arc_process, reason);
+                }
+
+                Ok(true.into())
+            }
+        }
+        // Ports cannot be opened, so there is never a port alive to receive the signal
+        TypedTerm::Port(_) => Ok(true.into()),
+        TypedTerm::Boxed(boxed) => match boxed.to_typed_term().unwrap() {
+            // Distribution is not supported, so other nodes are never connected and, like for a
+            // node that is not connected, the signal is dropped
+            TypedTerm::ExternalPid(_) | TypedTerm::ExternalPort(_) => Ok(true.into()),
+            _ => Err(badarg!().into()),
+        },
+        _ => Err(badarg!().into()),
+    }
+}
+
+// Private
+
+/// Unlike other processes, `process` exits even if `reason` is `normal`.
+fn exit_self(process: &Process, reason: Term) -> exception::Result {
+    if is_exit_reason(reason, "kill") {
+        Err(exit!(atom_unchecked("killed")).into())
+    } else if process.traps_exit() {
+        let tag = atom_unchecked("EXIT");
+        let exit_message = process.tuple_from_slice(&[tag, process.pid_term(), reason])?;
+        process.send_from_self(exit_message);
+
+        Ok(true.into())
+    } else {
+        Err(exit!(reason).into())
+    }
+}
@@ -0,0 +1,75 @@
+mod with_local_pid;
+
+use proptest::prop_assert_eq;
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::term::{atom_unchecked, next_pid};
+
+use crate::otp::erlang::exit_2::native;
+use crate::scheduler::{with_process, with_process_arc};
+use crate::test::strategy;
+
+#[test]
+fn without_pid_or_port_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &(
+                    strategy::term(arc_process.clone())
+                        .prop_filter("Cannot be pid or port", |pid_or_port| {
+                            !(pid_or_port.is_pid() || pid_or_port.is_port())
+                        }),
+                    strategy::term(arc_process.clone()),
+                ),
+                |(pid_or_port, reason)| {
+                    prop_assert_eq!(
+                        native(&arc_process, pid_or_port, reason),
+                        Err(badarg!().into())
+                    );
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_non_existent_pid_returns_true() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, next_pid(), atom_unchecked("kill")),
+            Ok(true.into())
+        );
+    });
+}
+
+#[test]
+fn with_external_pid_returns_true() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                process.external_pid_with_node_id(1, 2, 3).unwrap(),
+                atom_unchecked("kill")
+            ),
+            Ok(true.into())
+        );
+    });
+}
+
+#[test]
+fn with_external_port_returns_true() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                process.external_port_with_node_id(1, 2).unwrap(),
+                atom_unchecked("kill")
+            ),
+            Ok(true.into())
+        );
+    });
+}
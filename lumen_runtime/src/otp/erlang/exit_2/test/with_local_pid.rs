@@ -0,0 +1,191 @@
+use super::*;
+
+use liblumen_alloc::erts::process::{Process, ProcessFlags, Status};
+use liblumen_alloc::erts::term::Term;
+use liblumen_alloc::exit;
+
+use crate::process;
+use crate::test::{has_message, has_no_message};
+
+#[test]
+fn with_self_and_normal_exits_normal() {
+    with_process(|process| {
+        let reason = atom_unchecked("normal");
+
+        assert_eq!(
+            native(process, process.pid_term(), reason),
+            Err(exit!(reason).into())
+        );
+    });
+}
+
+#[test]
+fn with_self_and_kill_exits_killed_even_if_trapping_exits() {
+    with_process(|process| {
+        process.trap_exit(true);
+
+        assert_eq!(
+            native(process, process.pid_term(), atom_unchecked("kill")),
+            Err(exit!(atom_unchecked("killed")).into())
+        );
+        assert!(has_no_message(process));
+    });
+}
+
+#[test]
+fn with_self_trapping_exits_sends_exit_message() {
+    with_process(|process| {
+        process.trap_exit(true);
+
+        let reason = atom_unchecked("normal");
+
+        assert_eq!(native(process, process.pid_term(), reason), Ok(true.into()));
+        assert!(has_message(
+            process,
+            exit_message(process, process.pid_term(), reason)
+        ));
+    });
+}
+
+#[test]
+fn with_other_not_trapping_exits_and_normal_does_not_exit() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+
+        assert_eq!(
+            native(
+                process,
+                other_arc_process.pid_term(),
+                atom_unchecked("normal")
+            ),
+            Ok(true.into())
+        );
+        assert!(!other_arc_process.is_exiting());
+    });
+}
+
+#[test]
+fn with_other_trapping_exits_and_normal_sends_exit_message() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+        other_arc_process.trap_exit(true);
+
+        let reason = atom_unchecked("normal");
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), reason),
+            Ok(true.into())
+        );
+        assert!(!other_arc_process.is_exiting());
+        assert!(has_message(
+            &other_arc_process,
+            exit_message(&other_arc_process, process.pid_term(), reason)
+        ));
+    });
+}
+
+#[test]
+fn with_other_trapping_exits_and_kill_exits_killed() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+        other_arc_process.trap_exit(true);
+
+        assert_eq!(
+            native(
+                process,
+                other_arc_process.pid_term(),
+                atom_unchecked("kill")
+            ),
+            Ok(true.into())
+        );
+        assert_eq!(
+            *other_arc_process.status.read(),
+            Status::Exiting(exit!(atom_unchecked("killed")))
+        );
+        assert!(has_no_message(&other_arc_process));
+    });
+}
+
+#[test]
+fn with_other_not_trapping_exits_exits_with_reason() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+        let reason = atom_unchecked("abnormal");
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), reason),
+            Ok(true.into())
+        );
+        assert_eq!(
+            *other_arc_process.status.read(),
+            Status::Exiting(exit!(reason))
+        );
+    });
+}
+
+#[test]
+fn with_other_trapping_exits_sends_exit_message() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+        other_arc_process.trap_exit(true);
+
+        let reason = atom_unchecked("abnormal");
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), reason),
+            Ok(true.into())
+        );
+        assert!(!other_arc_process.is_exiting());
+        assert!(has_message(
+            &other_arc_process,
+            exit_message(&other_arc_process, process.pid_term(), reason)
+        ));
+    });
+}
+
+#[test]
+fn with_other_trapping_exits_sends_exit_message_that_outlives_tuple_reason_on_sender_heap() {
+    with_process(|process| {
+        let sender_arc_process = process::test(process);
+        let other_arc_process = process::test(process);
+        other_arc_process.trap_exit(true);
+
+        let reason = sender_arc_process
+            .tuple_from_slice(&[
+                atom_unchecked("abnormal"),
+                sender_arc_process.integer(1).unwrap(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(&sender_arc_process, other_arc_process.pid_term(), reason),
+            Ok(true.into())
+        );
+
+        // `reason` is not a root, so the collection frees the heap it was on
+        sender_arc_process.set_flags(ProcessFlags::NeedFullSweep);
+        assert!(sender_arc_process.garbage_collect(0, &mut []).is_ok());
+
+        let other_reason = other_arc_process
+            .tuple_from_slice(&[
+                atom_unchecked("abnormal"),
+                other_arc_process.integer(1).unwrap(),
+            ])
+            .unwrap();
+
+        assert!(has_message(
+            &other_arc_process,
+            exit_message(
+                &other_arc_process,
+                sender_arc_process.pid_term(),
+                other_reason
+            )
+        ));
+    });
+}
+
+fn exit_message(process: &Process, from: Term, reason: Term) -> Term {
+    process
+        .tuple_from_slice(&[atom_unchecked("EXIT"), from, reason])
+        .unwrap()
+}
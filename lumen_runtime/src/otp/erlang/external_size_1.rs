@@ -0,0 +1,15 @@
+#[cfg(test)]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::Term;
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::otp::erlang::term_to_binary::external_size;
+
+#[native_implemented_function(external_size/1)]
+pub fn native(process: &Process, term: Term) -> exception::Result {
+    external_size(process, term, Default::default())
+}
@@ -0,0 +1,55 @@
+use num_bigint::BigInt;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{atom_unchecked, Term};
+
+use crate::otp::erlang::external_size_1::native;
+use crate::otp::erlang::term_to_binary_1;
+use crate::scheduler::with_process;
+
+#[test]
+fn with_atom_returns_size_of_atom_ext() {
+    with_process(|process| {
+        // VERSION_NUMBER, ATOM_EXT, the 16-bit length and "atom"
+        assert_eq!(
+            native(process, atom_unchecked("atom")),
+            Ok(process.integer(1 + 1 + 2 + 4).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_nested_terms_returns_byte_size_of_term_to_binary() {
+    with_process(|process| {
+        let big_int: BigInt = BigInt::from(std::i64::MAX) * BigInt::from(1_000);
+        let list = process
+            .list_from_slice(&[
+                process.integer(1_000).unwrap(),
+                process.float(1.5).unwrap(),
+                process.integer(big_int).unwrap(),
+                process.binary_from_bytes(&[1; 100]).unwrap(),
+            ])
+            .unwrap();
+        let map = process
+            .map_from_slice(&[(
+                atom_unchecked("key"),
+                process.charlist_from_str("value").unwrap(),
+            )])
+            .unwrap();
+        let term = process
+            .tuple_from_slice(&[list, map, process.pid_term(), Term::NIL])
+            .unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(term_to_binary_byte_size(process, term))
+        );
+    });
+}
+
+fn term_to_binary_byte_size(process: &Process, term: Term) -> Term {
+    let binary = term_to_binary_1::native(process, term).unwrap();
+    let byte_size = process.bytes_from_binary(binary).unwrap().len();
+
+    process.integer(byte_size).unwrap()
+}
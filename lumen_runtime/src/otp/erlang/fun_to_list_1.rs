@@ -0,0 +1,44 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{Boxed, Closure, Term};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::otp::erlang::term_to_binary::{is_export, local_fun_index};
+
+/// Exports are formatted as `fun Module:Function/Arity` and local funs as
+/// `#Fun<Module.Index.Uniq>`.  `Uniq` is always `0` because the BEAM file is not available to
+/// take the MD5 of.
+#[native_implemented_function(fun_to_list/1)]
+pub fn native(process: &Process, fun: Term) -> exception::Result {
+    let closure: Boxed<Closure> = fun.try_into()?;
+    let module_function_arity = closure.module_function_arity();
+
+    let string = if is_export(&closure) {
+        format!(
+            "fun {}:{}/{}",
+            module_function_arity.module.name(),
+            module_function_arity.function.name(),
+            module_function_arity.arity
+        )
+    } else {
+        format!(
+            "#Fun<{}.{}.0>",
+            module_function_arity.module.name(),
+            local_fun_index(module_function_arity.function.name())
+        )
+    };
+
+    process
+        .charlist_from_str(&string)
+        .map_err(|error| error.into())
+}
@@ -0,0 +1,53 @@
+use proptest::prop_assert_eq;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::term::Atom;
+
+use crate::otp::erlang::fun_to_list_1::native;
+use crate::scheduler::{with_process, with_process_arc};
+use crate::test::strategy;
+
+#[test]
+fn without_function_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &strategy::term::is_not_function(arc_process.clone()),
+                |function| {
+                    prop_assert_eq!(native(&arc_process, function), Err(badarg!().into()));
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_export_returns_module_function_arity() {
+    with_process(|process| {
+        let module = Atom::try_from_str("module").unwrap();
+        let function = Atom::try_from_str("function").unwrap();
+        let closure = strategy::term::closure(process, module, function, 2);
+
+        assert_eq!(
+            native(process, closure),
+            Ok(process.charlist_from_str("fun module:function/2").unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_local_fun_returns_module_and_index() {
+    with_process(|process| {
+        let module = Atom::try_from_str("module").unwrap();
+        let function = Atom::try_from_str("-function/0-fun-1-").unwrap();
+        let closure = strategy::term::closure(process, module, function, 0);
+
+        assert_eq!(
+            native(process, closure),
+            Ok(process.charlist_from_str("#Fun<module.1.0>").unwrap())
+        );
+    });
+}
@@ -0,0 +1,14 @@
+#[cfg(test)]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::otp::erlang::garbage_collect_2;
+
+#[native_implemented_function(garbage_collect/0)]
+pub fn native(process: &Process) -> exception::Result {
+    garbage_collect_2::garbage_collect(process, process.pid(), Default::default())
+}
@@ -0,0 +1,14 @@
+use crate::otp::erlang::garbage_collect_0::native;
+use crate::scheduler::with_process;
+
+#[test]
+fn returns_true_after_full_sweep() {
+    with_process(|process| {
+        assert_eq!(native(process), Ok(true.into()));
+
+        let statistics = process.gc_statistics();
+
+        assert_eq!(statistics.major_gcs, 1);
+        assert!(statistics.last_gc_time.is_some());
+    });
+}
@@ -0,0 +1,26 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{Pid, Term};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::otp::erlang::garbage_collect_2;
+
+/// `garbage_collect/1`
+///
+/// Like `garbage_collect/2` without options, another process is only requested to be collected.
+#[native_implemented_function(garbage_collect/1)]
+pub fn native(process: &Process, pid: Term) -> exception::Result {
+    let pid_pid: Pid = pid.try_into()?;
+
+    garbage_collect_2::garbage_collect(process, pid_pid, Default::default())
+}
@@ -0,0 +1,50 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::term::next_pid;
+
+use crate::otp::erlang::garbage_collect_1::native;
+use crate::process;
+use crate::scheduler::{with_process, with_process_arc, Scheduler};
+use crate::test::strategy;
+
+#[test]
+fn without_pid_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &strategy::term(arc_process.clone())
+                    .prop_filter("Cannot be pid", |pid| !pid.is_pid()),
+                |pid| {
+                    prop_assert_eq!(native(&arc_process, pid), Err(badarg!().into()));
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_non_existent_pid_returns_false() {
+    with_process(|process| {
+        assert_eq!(native(process, next_pid()), Ok(false.into()));
+    });
+}
+
+#[test]
+fn with_other_pid_returns_true_after_full_sweep() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term()),
+            Ok(true.into())
+        );
+        assert!(Scheduler::current().run_through(&other_arc_process));
+        assert_eq!(other_arc_process.gc_statistics().major_gcs, 1);
+        assert_eq!(process.gc_statistics().major_gcs, 0);
+    });
+}
@@ -0,0 +1,100 @@
+mod options;
+
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::exception::system::Alloc;
+use liblumen_alloc::erts::process::{Process, ProcessFlags};
+use liblumen_alloc::erts::term::{atom_unchecked, Pid, Term};
+use liblumen_alloc::exit;
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::otp::erlang::garbage_collect_2::options::{Options, Type};
+use crate::process;
+use crate::process::gc_request;
+use crate::registry::pid_to_process;
+
+/// `garbage_collect/2`
+///
+/// A process other than the caller is collected by its scheduler, which can only do so when it is
+/// not running the process, so unlike in BEAM the call does not wait for the collection.  A
+/// waiting process is made runnable to be collected.  Without `{async, RequestId}`, `true` only
+/// means that the process was alive when the collection was requested; it may still exit before
+/// it is collected.  Use `{async, RequestId}` to receive the result of the collection.
+#[native_implemented_function(garbage_collect/2)]
+pub fn native(process: &Process, pid: Term, options: Term) -> exception::Result {
+    let pid_pid: Pid = pid.try_into()?;
+    let options_options: Options = options.try_into()?;
+
+    garbage_collect(process, pid_pid, options_options)
+}
+
+// Private
+
+pub(in crate::otp::erlang) fn garbage_collect(
+    process: &Process,
+    pid: Pid,
+    Options {
+        r#type,
+        async_request_id,
+    }: Options,
+) -> exception::Result {
+    if pid == process.pid() {
+        // `async_request_id` is the only term still needed after the collection, so it is the
+        // only root
+        let mut roots = [async_request_id.unwrap_or(Term::NIL)];
+
+        if collect(process, r#type, &mut roots)? {
+            reply(process, async_request_id.map(|_| roots[0]), true)
+        } else {
+            Err(exit!(atom_unchecked("killed")).into())
+        }
+    } else {
+        match pid_to_process(&pid) {
+            Some(pid_arc_process) => {
+                if r#type == Type::Major {
+                    pid_arc_process.set_flags(ProcessFlags::NeedFullSweep);
+                }
+
+                gc_request::request(&pid_arc_process, process.pid(), async_request_id)?;
+
+                match async_request_id {
+                    Some(_) => Ok(atom_unchecked("async")),
+                    None => Ok(true.into()),
+                }
+            }
+            None => reply(process, async_request_id, false),
+        }
+    }
+}
+
+/// Returns `false` if `target` was killed for exceeding its `max_heap_size` instead of being
+/// collected
+fn collect(target: &Process, r#type: Type, roots: &mut [Term]) -> Result<bool, Alloc> {
+    if r#type == Type::Major {
+        target.set_flags(ProcessFlags::NeedFullSweep);
+    }
+
+    process::garbage_collect_or_full_sweep(target, roots)
+}
+
+fn reply(process: &Process, async_request_id: Option<Term>, collected: bool) -> exception::Result {
+    match async_request_id {
+        Some(request_id) => {
+            let tag = atom_unchecked("garbage_collect");
+            let message = process.tuple_from_slice(&[tag, request_id, collected.into()])?;
+            process.send_from_self(message);
+
+            Ok(atom_unchecked("async"))
+        }
+        None => Ok(collected.into()),
+    }
+}
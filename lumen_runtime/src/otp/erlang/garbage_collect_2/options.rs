@@ -0,0 +1,98 @@
+use std::convert::{TryFrom, TryInto};
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception::runtime;
+use liblumen_alloc::erts::term::{Atom, Boxed, Cons, Term, Tuple, TypedTerm};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Type {
+    Major,
+    Minor,
+}
+
+pub struct Options {
+    pub r#type: Type,
+    /// When set, the result is sent as `{garbage_collect, RequestId, Result}` instead of being
+    /// returned
+    pub async_request_id: Option<Term>,
+}
+
+impl Options {
+    fn put_option_from_tuple(&mut self, tuple: &Tuple) -> Result<(), runtime::Exception> {
+        if tuple.len() != 2 {
+            return Err(badarg!());
+        }
+
+        let name: Atom = tuple[0].try_into()?;
+
+        match name.name() {
+            "async" => {
+                self.async_request_id = Some(tuple[1]);
+
+                Ok(())
+            }
+            "type" => {
+                let type_atom: Atom = tuple[1].try_into()?;
+
+                self.r#type = match type_atom.name() {
+                    "major" => Type::Major,
+                    "minor" => Type::Minor,
+                    _ => return Err(badarg!()),
+                };
+
+                Ok(())
+            }
+            _ => Err(badarg!()),
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            r#type: Type::Major,
+            async_request_id: None,
+        }
+    }
+}
+
+impl TryFrom<Boxed<Cons>> for Options {
+    type Error = runtime::Exception;
+
+    fn try_from(cons: Boxed<Cons>) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+
+        for result in cons.into_iter() {
+            match result {
+                Ok(option) => {
+                    let tuple: Boxed<Tuple> = option.try_into()?;
+
+                    options.put_option_from_tuple(&tuple)?;
+                }
+                Err(_) => return Err(badarg!()),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = runtime::Exception;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        term.to_typed_term().unwrap().try_into()
+    }
+}
+
+impl TryFrom<TypedTerm> for Options {
+    type Error = runtime::Exception;
+
+    fn try_from(typed_term: TypedTerm) -> Result<Self, Self::Error> {
+        match typed_term {
+            TypedTerm::Nil => Ok(Default::default()),
+            TypedTerm::List(cons) => cons.try_into(),
+            _ => Err(badarg!()),
+        }
+    }
+}
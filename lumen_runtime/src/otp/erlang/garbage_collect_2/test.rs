@@ -0,0 +1,318 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::erts::process::code::stack::frame::Placement;
+use liblumen_alloc::erts::process::{MaxHeapSize, Process, Status};
+use liblumen_alloc::erts::term::{atom_unchecked, next_pid, Term};
+use liblumen_alloc::{badarg, exit};
+
+use crate::otp::erlang;
+use crate::otp::erlang::garbage_collect_2::native;
+use crate::process;
+use crate::scheduler::{with_process, with_process_arc, Scheduler};
+use crate::test::{has_message, has_no_message, strategy};
+
+#[test]
+fn without_pid_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &strategy::term(arc_process.clone())
+                    .prop_filter("Cannot be pid", |pid| !pid.is_pid()),
+                |pid| {
+                    prop_assert_eq!(native(&arc_process, pid, Term::NIL), Err(badarg!().into()));
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_invalid_option_errors_badarg() {
+    with_process(|process| {
+        let invalid_options = [
+            atom_unchecked("major"),
+            process
+                .tuple_from_slice(&[atom_unchecked("type"), atom_unchecked("full")])
+                .unwrap(),
+            process
+                .tuple_from_slice(&[atom_unchecked("async")])
+                .unwrap(),
+            process
+                .tuple_from_slice(&[atom_unchecked("sync"), Term::NIL])
+                .unwrap(),
+        ];
+
+        for invalid_option in invalid_options.iter() {
+            let options = process.list_from_slice(&[*invalid_option]).unwrap();
+
+            assert_eq!(
+                native(process, process.pid_term(), options),
+                Err(badarg!().into())
+            );
+        }
+
+        assert_eq!(
+            native(process, process.pid_term(), atom_unchecked("major")),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_minor_type_does_minor_collection() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+        let options = type_options(process, "minor");
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), options),
+            Ok(true.into())
+        );
+        assert!(Scheduler::current().run_through(&other_arc_process));
+
+        let statistics = other_arc_process.gc_statistics();
+
+        assert_eq!(statistics.minor_gcs, 1);
+        assert_eq!(statistics.major_gcs, 0);
+        assert!(statistics.last_gc_time.is_some());
+    });
+}
+
+#[test]
+fn with_major_type_does_full_sweep() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+        let options = type_options(process, "major");
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), options),
+            Ok(true.into())
+        );
+        assert!(Scheduler::current().run_through(&other_arc_process));
+
+        let statistics = other_arc_process.gc_statistics();
+
+        assert_eq!(statistics.minor_gcs, 0);
+        assert_eq!(statistics.major_gcs, 1);
+    });
+}
+
+#[test]
+fn with_self_and_async_sends_result_with_request_id_and_returns_async() {
+    with_process(|process| {
+        // boxed, so that it has to survive being moved by the collection
+        let request_id = process
+            .tuple_from_slice(&[atom_unchecked("request")])
+            .unwrap();
+        let options = async_options(process, request_id);
+
+        assert_eq!(
+            native(process, process.pid_term(), options),
+            Ok(atom_unchecked("async"))
+        );
+
+        let request_id = process
+            .tuple_from_slice(&[atom_unchecked("request")])
+            .unwrap();
+
+        assert!(has_message(
+            process,
+            process
+                .tuple_from_slice(&[atom_unchecked("garbage_collect"), request_id, true.into()])
+                .unwrap()
+        ));
+    });
+}
+
+#[test]
+fn with_other_does_not_collect_until_other_is_run_by_its_scheduler() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), Term::NIL),
+            Ok(true.into())
+        );
+        assert!(other_arc_process.gc_statistics().last_gc_time.is_none());
+
+        assert!(Scheduler::current().run_through(&other_arc_process));
+
+        assert!(other_arc_process.gc_statistics().last_gc_time.is_some());
+    });
+}
+
+#[test]
+fn with_other_waiting_makes_other_runnable_and_collects_it_without_running_it() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+
+        erlang::hibernate_3::place_frame_with_arguments(
+            &other_arc_process,
+            Placement::Replace,
+            atom_unchecked("erlang"),
+            atom_unchecked("self"),
+            Term::NIL,
+        )
+        .unwrap();
+
+        assert!(Scheduler::current().run_through(&other_arc_process));
+        assert_eq!(*other_arc_process.status.read(), Status::Waiting);
+
+        let gcs_before = gcs(&other_arc_process);
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), Term::NIL),
+            Ok(true.into())
+        );
+        assert_eq!(*other_arc_process.status.read(), Status::Runnable);
+
+        while gcs(&other_arc_process) == gcs_before {
+            assert!(Scheduler::current().run_once());
+        }
+
+        // Collected, but not run, as it received no message
+        assert_eq!(*other_arc_process.status.read(), Status::Waiting);
+        assert_eq!(
+            other_arc_process.current_module_function_arity(),
+            Some(erlang::apply_3::module_function_arity())
+        );
+    });
+
+    fn gcs(process: &Process) -> usize {
+        let statistics = process.gc_statistics();
+
+        statistics.minor_gcs + statistics.major_gcs
+    }
+}
+
+#[test]
+fn with_other_and_async_sends_result_with_request_id_after_other_is_collected() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+        // boxed, so that it has to be copied out of the heap of `process`
+        let request_id = process
+            .tuple_from_slice(&[atom_unchecked("request")])
+            .unwrap();
+        let options = async_options(process, request_id);
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), options),
+            Ok(atom_unchecked("async"))
+        );
+        assert!(has_no_message(process));
+
+        assert!(Scheduler::current().run_through(&other_arc_process));
+
+        assert!(has_message(
+            process,
+            process
+                .tuple_from_slice(&[atom_unchecked("garbage_collect"), request_id, true.into()])
+                .unwrap()
+        ));
+    });
+}
+
+#[test]
+fn with_non_existent_pid_and_async_sends_false() {
+    with_process(|process| {
+        let request_id = atom_unchecked("request");
+        let options = async_options(process, request_id);
+
+        assert_eq!(
+            native(process, next_pid(), options),
+            Ok(atom_unchecked("async"))
+        );
+        assert!(has_message(
+            process,
+            process
+                .tuple_from_slice(&[atom_unchecked("garbage_collect"), request_id, false.into()])
+                .unwrap()
+        ));
+    });
+}
+
+#[test]
+fn with_self_exceeding_max_heap_size_with_kill_exits_killed() {
+    with_process(|process| {
+        process.set_max_heap_size(MaxHeapSize {
+            size: 1,
+            kill: true,
+            error_logger: false,
+        });
+
+        assert_eq!(
+            native(process, process.pid_term(), Term::NIL),
+            Err(exit!(atom_unchecked("killed")).into())
+        );
+    });
+}
+
+#[test]
+fn with_other_exceeding_max_heap_size_with_kill_returns_true_and_kills_other_when_collected() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+        other_arc_process.set_max_heap_size(MaxHeapSize {
+            size: 1,
+            kill: true,
+            error_logger: false,
+        });
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), Term::NIL),
+            Ok(true.into())
+        );
+        assert!(!other_arc_process.is_exiting());
+
+        assert!(Scheduler::current().run_through(&other_arc_process));
+
+        assert!(other_arc_process.is_exiting());
+    });
+}
+
+#[test]
+fn with_other_exceeding_max_heap_size_with_kill_and_async_sends_false() {
+    with_process(|process| {
+        let other_arc_process = process::test(process);
+        other_arc_process.set_max_heap_size(MaxHeapSize {
+            size: 1,
+            kill: true,
+            error_logger: false,
+        });
+        let request_id = atom_unchecked("request");
+        let options = async_options(process, request_id);
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), options),
+            Ok(atom_unchecked("async"))
+        );
+
+        assert!(Scheduler::current().run_through(&other_arc_process));
+
+        assert!(has_message(
+            process,
+            process
+                .tuple_from_slice(&[atom_unchecked("garbage_collect"), request_id, false.into()])
+                .unwrap()
+        ));
+    });
+}
+
+fn async_options(process: &Process, request_id: Term) -> Term {
+    let option = process
+        .tuple_from_slice(&[atom_unchecked("async"), request_id])
+        .unwrap();
+
+    process.list_from_slice(&[option]).unwrap()
+}
+
+fn type_options(process: &Process, r#type: &str) -> Term {
+    let option = process
+        .tuple_from_slice(&[atom_unchecked("type"), atom_unchecked(r#type)])
+        .unwrap();
+
+    process.list_from_slice(&[option]).unwrap()
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod test;
+
+use std::sync::Arc;
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception::system::Alloc;
+use liblumen_alloc::erts::process::code::stack::frame::{Frame, Placement};
+use liblumen_alloc::erts::process::{code, GcError, Process, ProcessFlags};
+use liblumen_alloc::erts::term::{Atom, Term};
+use liblumen_alloc::ModuleFunctionArity;
+
+use crate::otp::erlang::apply_3;
+use crate::process;
+
+pub fn place_frame_with_arguments(
+    process: &Process,
+    placement: Placement,
+    module: Term,
+    function: Term,
+    arguments: Term,
+) -> Result<(), Alloc> {
+    process.stack_push(arguments)?;
+    process.stack_push(function)?;
+    process.stack_push(module)?;
+    process.place_frame(frame(), placement);
+
+    Ok(())
+}
+
+// Private
+
+/// Discards the call stack, shrinks the heap to fit only `module`, `function` and `arguments`,
+/// and then waits until a message arrives to call `apply(module, function, arguments)`.
+fn code(arc_process: &Arc<Process>) -> code::Result {
+    arc_process.reduce();
+
+    let module = arc_process.stack_pop().unwrap();
+    let function = arc_process.stack_pop().unwrap();
+    let arguments = arc_process.stack_pop().unwrap();
+
+    if !(module.is_atom() && function.is_atom() && arguments.is_proper_list()) {
+        arc_process.exception(badarg!());
+
+        return Ok(());
+    }
+
+    hibernate(arc_process, module, function, arguments, |process| {
+        process::garbage_collect(process, 0, &mut [])
+    })
+}
+
+/// `garbage_collect` is only a parameter so that tests can make the collection fail
+fn hibernate(
+    arc_process: &Arc<Process>,
+    module: Term,
+    function: Term,
+    arguments: Term,
+    garbage_collect: fn(&Process) -> Result<usize, GcError>,
+) -> code::Result {
+    arc_process.clear_code_stack();
+    // Place the continuation before collecting, so that its arguments are rooted on the stack and
+    // it is not lost if the collection fails
+    apply_3::place_frame_with_arguments(arc_process, Placement::Push, module, function, arguments)?;
+    arc_process.set_flags(ProcessFlags::NeedFullSweep | ProcessFlags::Hibernate);
+
+    match garbage_collect(arc_process) {
+        // If a smaller heap could not be allocated, hibernate without shrinking
+        Ok(_) | Err(GcError::Alloc(_)) => (),
+        // The process is already exiting with `killed`
+        Err(GcError::MaxHeapSizeExceeded) => return Ok(()),
+        Err(GcError::FullsweepRequired) => unreachable!(),
+    }
+
+    // Hold the mailbox lock, so that a message sent concurrently either is seen here or finds the
+    // process already waiting
+    let mailbox_guard = arc_process.mailbox.lock();
+
+    if mailbox_guard.borrow().len() == 0 {
+        arc_process.wait();
+    }
+
+    Ok(())
+}
+
+fn frame() -> Frame {
+    Frame::new(module_function_arity(), code)
+}
+
+fn function() -> Atom {
+    Atom::try_from_str("hibernate").unwrap()
+}
+
+fn module_function_arity() -> Arc<ModuleFunctionArity> {
+    Arc::new(ModuleFunctionArity {
+        module: super::module(),
+        function: function(),
+        arity: 3,
+    })
+}
@@ -0,0 +1,156 @@
+use liblumen_alloc::erts::process::code::stack::frame::Placement;
+use liblumen_alloc::erts::process::{GcError, Status};
+use liblumen_alloc::erts::term::{atom_unchecked, Term};
+use liblumen_alloc::{alloc, badarg, exit};
+
+use crate::otp::erlang;
+use crate::otp::erlang::hibernate_3::{hibernate, place_frame_with_arguments};
+use crate::process;
+use crate::scheduler::{with_process, Scheduler};
+
+#[test]
+fn without_atom_module_errors_badarg() {
+    with_process(|parent_process| {
+        let arc_process = process::test(parent_process);
+
+        place_frame_with_arguments(
+            &arc_process,
+            Placement::Replace,
+            arc_process.integer(0).unwrap(),
+            atom_unchecked("self"),
+            Term::NIL,
+        )
+        .unwrap();
+
+        assert!(Scheduler::current().run_through(&arc_process));
+
+        assert_eq!(*arc_process.status.read(), Status::Exiting(badarg!()));
+    });
+}
+
+#[test]
+fn without_proper_list_arguments_errors_badarg() {
+    with_process(|parent_process| {
+        let arc_process = process::test(parent_process);
+        let arguments = arc_process
+            .cons(atom_unchecked("head"), atom_unchecked("tail"))
+            .unwrap();
+
+        place_frame_with_arguments(
+            &arc_process,
+            Placement::Replace,
+            atom_unchecked("erlang"),
+            atom_unchecked("self"),
+            arguments,
+        )
+        .unwrap();
+
+        assert!(Scheduler::current().run_through(&arc_process));
+
+        assert_eq!(*arc_process.status.read(), Status::Exiting(badarg!()));
+    });
+}
+
+#[test]
+fn with_mfa_discards_frames_shrinks_heap_and_waits() {
+    with_process(|parent_process| {
+        let arc_process = process::test(parent_process);
+        let heap_size_before = arc_process.heap_size();
+
+        place_frame_with_arguments(
+            &arc_process,
+            Placement::Push,
+            atom_unchecked("erlang"),
+            atom_unchecked("self"),
+            Term::NIL,
+        )
+        .unwrap();
+
+        assert_eq!(arc_process.code_stack_len(), 2);
+
+        assert!(Scheduler::current().run_through(&arc_process));
+
+        assert_eq!(*arc_process.status.read(), Status::Waiting);
+        assert_eq!(arc_process.code_stack_len(), 1);
+        assert_eq!(
+            arc_process.current_module_function_arity(),
+            Some(erlang::apply_3::module_function_arity())
+        );
+        assert!(arc_process.heap_size() < heap_size_before);
+    });
+}
+
+#[test]
+fn with_message_calls_mfa() {
+    with_process(|parent_process| {
+        let arc_process = process::test(parent_process);
+
+        place_frame_with_arguments(
+            &arc_process,
+            Placement::Replace,
+            atom_unchecked("erlang"),
+            atom_unchecked("self"),
+            Term::NIL,
+        )
+        .unwrap();
+
+        assert!(Scheduler::current().run_through(&arc_process));
+        assert_eq!(*arc_process.status.read(), Status::Waiting);
+
+        erlang::send_2::native(
+            parent_process,
+            arc_process.pid_term(),
+            atom_unchecked("wake"),
+        )
+        .unwrap();
+
+        assert_eq!(*arc_process.status.read(), Status::Runnable);
+
+        assert!(Scheduler::current().run_through(&arc_process));
+
+        // `erlang:self/0` returns without a caller, so the process exits
+        assert_eq!(
+            *arc_process.status.read(),
+            Status::Exiting(exit!(atom_unchecked("normal")))
+        );
+    });
+}
+
+#[test]
+fn with_alloc_error_during_collection_keeps_mfa_and_waits() {
+    with_process(|parent_process| {
+        let arc_process = process::test(parent_process);
+        let heap_size_before = arc_process.heap_size();
+
+        assert!(hibernate(
+            &arc_process,
+            atom_unchecked("erlang"),
+            atom_unchecked("self"),
+            Term::NIL,
+            |_| Err(GcError::Alloc(alloc!())),
+        )
+        .is_ok());
+
+        assert_eq!(*arc_process.status.read(), Status::Waiting);
+        assert_eq!(arc_process.code_stack_len(), 1);
+        assert_eq!(
+            arc_process.current_module_function_arity(),
+            Some(erlang::apply_3::module_function_arity())
+        );
+        assert_eq!(arc_process.heap_size(), heap_size_before);
+
+        erlang::send_2::native(
+            parent_process,
+            arc_process.pid_term(),
+            atom_unchecked("wake"),
+        )
+        .unwrap();
+
+        assert!(Scheduler::current().run_through(&arc_process));
+
+        assert_eq!(
+            *arc_process.status.read(),
+            Status::Exiting(exit!(atom_unchecked("normal")))
+        );
+    });
+}
@@ -47,13 +47,37 @@ pub fn native(process: &Process, string: Term) -> exception::Result {
     }
 }
 
-// Private
-
-fn next_decimal(cons: Boxed<Cons>) -> Result<(usize, Term), exception::Exception> {
+pub(in crate::otp::erlang) fn next_decimal(
+    cons: Boxed<Cons>,
+) -> Result<(usize, Term), exception::Exception> {
     next_decimal_digit(cons)
         .and_then(|(first_digit, first_tail)| rest_decimal_digits(first_digit, first_tail))
 }
 
+pub(in crate::otp::erlang) fn skip_char(cons: Boxed<Cons>, skip: char) -> exception::Result {
+    let c: char = cons.head.try_into()?;
+
+    if c == skip {
+        Ok(cons.tail)
+    } else {
+        Err(badarg!().into())
+    }
+}
+
+/// Skips each `char` of `skip` in order
+pub(in crate::otp::erlang) fn skip_str(list: Term, skip: &str) -> exception::Result {
+    let mut tail = list;
+
+    for c in skip.chars() {
+        let cons: Boxed<Cons> = tail.try_into()?;
+        tail = skip_char(cons, c)?;
+    }
+
+    Ok(tail)
+}
+
+// Private
+
 fn next_decimal_digit(cons: Boxed<Cons>) -> Result<(u8, Term), exception::Exception> {
     let head_char: char = cons.head.try_into()?;
 
@@ -90,13 +114,3 @@ fn rest_decimal_digits(
         Err(_) => Ok((first_digit as usize, first_tail)),
     }
 }
-
-fn skip_char(cons: Boxed<Cons>, skip: char) -> exception::Result {
-    let c: char = cons.head.try_into()?;
-
-    if c == skip {
-        Ok(cons.tail)
-    } else {
-        Err(badarg!().into())
-    }
-}
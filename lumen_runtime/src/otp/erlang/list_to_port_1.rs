@@ -0,0 +1,46 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{AsTerm, Boxed, Cons, Port, Term};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::otp::erlang::list_to_pid_1::{next_decimal, skip_char, skip_str};
+
+#[native_implemented_function(list_to_port/1)]
+pub fn native(process: &Process, string: Term) -> exception::Result {
+    let prefix_tail = skip_str(string, "#Port<")?;
+    let prefix_tail_cons: Boxed<Cons> = prefix_tail.try_into()?;
+
+    let (node_id, node_tail) = next_decimal(prefix_tail_cons)?;
+    let node_tail_cons: Boxed<Cons> = node_tail.try_into()?;
+
+    let separator_tail = skip_char(node_tail_cons, '.')?;
+    let separator_tail_cons: Boxed<Cons> = separator_tail.try_into()?;
+
+    let (number, number_tail) = next_decimal(separator_tail_cons)?;
+    let number_tail_cons: Boxed<Cons> = number_tail.try_into()?;
+
+    let suffix_tail = skip_char(number_tail_cons, '>')?;
+
+    if suffix_tail.is_nil() && number <= Port::NUMBER_MAX {
+        if node_id == 0 {
+            Ok(unsafe { Port::from_raw(number).as_term() })
+        } else {
+            process
+                .external_port_with_node_id(node_id, number)
+                .map_err(|error| error.into())
+        }
+    } else {
+        Err(badarg!().into())
+    }
+}
@@ -0,0 +1,54 @@
+use proptest::prop_assert_eq;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::term::{AsTerm, Port};
+
+use crate::otp::erlang::list_to_port_1::native;
+use crate::scheduler::{with_process, with_process_arc};
+use crate::test::strategy;
+
+#[test]
+fn without_list_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(&strategy::term::is_not_list(arc_process.clone()), |list| {
+                prop_assert_eq!(native(&arc_process, list), Err(badarg!().into()));
+
+                Ok(())
+            })
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_list_encoding_local_port() {
+    with_process(|process| {
+        for invalid in &["#Port", "#Port<", "#Port<0", "#Port<0.", "#Port<0.1"] {
+            assert_badarg!(native(
+                &process,
+                process.charlist_from_str(invalid).unwrap()
+            ));
+        }
+
+        assert_eq!(
+            native(&process, process.charlist_from_str("#Port<0.1>").unwrap()),
+            Ok(unsafe { Port::from_raw(1).as_term() })
+        );
+
+        assert_badarg!(native(
+            &process,
+            process.charlist_from_str("#Port<0.1>?").unwrap(),
+        ));
+    })
+}
+
+#[test]
+fn with_list_encoding_external_port() {
+    with_process(|process| {
+        assert_eq!(
+            native(&process, process.charlist_from_str("#Port<1.2>").unwrap()),
+            Ok(process.external_port_with_node_id(1, 2).unwrap())
+        );
+    });
+}
@@ -0,0 +1,57 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::scheduler;
+use liblumen_alloc::erts::term::{reference, Boxed, Cons, Term};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::otp::erlang::list_to_pid_1::{next_decimal, skip_char, skip_str};
+
+#[native_implemented_function(list_to_ref/1)]
+pub fn native(process: &Process, string: Term) -> exception::Result {
+    let prefix_tail = skip_str(string, "#Ref<")?;
+    let prefix_tail_cons: Boxed<Cons> = prefix_tail.try_into()?;
+
+    let (node_id, node_tail) = next_decimal(prefix_tail_cons)?;
+    let node_tail_cons: Boxed<Cons> = node_tail.try_into()?;
+
+    let first_separator_tail = skip_char(node_tail_cons, '.')?;
+    let first_separator_tail_cons: Boxed<Cons> = first_separator_tail.try_into()?;
+
+    let (scheduler_id, scheduler_id_tail) = next_decimal(first_separator_tail_cons)?;
+    let scheduler_id_tail_cons: Boxed<Cons> = scheduler_id_tail.try_into()?;
+
+    let second_separator_tail = skip_char(scheduler_id_tail_cons, '.')?;
+    let second_separator_tail_cons: Boxed<Cons> = second_separator_tail.try_into()?;
+
+    let (number, number_tail) = next_decimal(second_separator_tail_cons)?;
+    let number_tail_cons: Boxed<Cons> = number_tail.try_into()?;
+
+    let suffix_tail = skip_char(number_tail_cons, '>')?;
+
+    if suffix_tail.is_nil() {
+        let scheduler_id_u32: u32 = scheduler_id.try_into().map_err(|_| badarg!())?;
+        let scheduler_id: scheduler::ID = scheduler_id_u32.into();
+        let number = number as reference::Number;
+
+        let result = if node_id == 0 {
+            process.reference_from_scheduler(scheduler_id, number)
+        } else {
+            process.external_reference_with_node_id(node_id, scheduler_id, number)
+        };
+
+        result.map_err(|error| error.into())
+    } else {
+        Err(badarg!().into())
+    }
+}
@@ -0,0 +1,63 @@
+use proptest::prop_assert_eq;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+
+use crate::otp::erlang::list_to_ref_1::native;
+use crate::scheduler::{with_process, with_process_arc};
+use crate::test::strategy;
+
+#[test]
+fn without_list_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(&strategy::term::is_not_list(arc_process.clone()), |list| {
+                prop_assert_eq!(native(&arc_process, list), Err(badarg!().into()));
+
+                Ok(())
+            })
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_list_encoding_local_reference() {
+    with_process(|process| {
+        for invalid in &[
+            "#Ref",
+            "#Ref<",
+            "#Ref<0",
+            "#Ref<0.",
+            "#Ref<0.1",
+            "#Ref<0.1.",
+            "#Ref<0.1.2",
+        ] {
+            assert_badarg!(native(
+                &process,
+                process.charlist_from_str(invalid).unwrap()
+            ));
+        }
+
+        assert_eq!(
+            native(&process, process.charlist_from_str("#Ref<0.1.2>").unwrap()),
+            Ok(process.reference_from_scheduler(1.into(), 2).unwrap())
+        );
+
+        assert_badarg!(native(
+            &process,
+            process.charlist_from_str("#Ref<0.1.2>?").unwrap(),
+        ));
+    })
+}
+
+#[test]
+fn with_list_encoding_external_reference() {
+    with_process(|process| {
+        assert_eq!(
+            native(&process, process.charlist_from_str("#Ref<1.2.3>").unwrap()),
+            Ok(process
+                .external_reference_with_node_id(1, 2.into(), 3)
+                .unwrap())
+        );
+    });
+}
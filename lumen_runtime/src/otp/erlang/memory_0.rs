@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::exception::system::Alloc;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{atom_unchecked, Term};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::system::memory::{Memory, TYPES};
+
+#[native_implemented_function(memory/0)]
+pub fn native(process: &Process) -> exception::Result {
+    let memory = Memory::current();
+    let mut type_size_vec = Vec::with_capacity(TYPES.len());
+
+    for r#type in TYPES.iter() {
+        type_size_vec.push(type_size(process, &memory, r#type)?);
+    }
+
+    process
+        .list_from_slice(&type_size_vec)
+        .map_err(|error| error.into())
+}
+
+/// `{Type, Size}`
+pub(in crate::otp::erlang) fn type_size(
+    process: &Process,
+    memory: &Memory,
+    r#type: &str,
+) -> Result<Term, Alloc> {
+    let size = memory.get(r#type).unwrap();
+
+    process.tuple_from_slice(&[atom_unchecked(r#type), process.integer(size)?])
+}
@@ -0,0 +1,51 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::term::{Atom, Boxed, Cons, SmallInteger, Tuple};
+
+use crate::otp::erlang::memory_0::native;
+use crate::scheduler::with_process;
+use crate::system::memory::TYPES;
+
+#[test]
+fn returns_size_of_each_type_in_order() {
+    with_process(|process| {
+        let list = native(process).unwrap();
+        let cons: Boxed<Cons> = list.try_into().unwrap();
+        let type_size_vec: Vec<(&str, usize)> = cons
+            .into_iter()
+            .map(|result| {
+                let tuple: Boxed<Tuple> = result.unwrap().try_into().unwrap();
+
+                assert_eq!(tuple.len(), 2);
+
+                let type_atom: Atom = tuple[0].try_into().unwrap();
+                let size_small_integer: SmallInteger = tuple[1].try_into().unwrap();
+
+                (type_atom.name(), size_small_integer.try_into().unwrap())
+            })
+            .collect();
+
+        assert_eq!(
+            type_size_vec
+                .iter()
+                .map(|(r#type, _)| *r#type)
+                .collect::<Vec<_>>(),
+            TYPES.to_vec()
+        );
+
+        let size = |r#type: &str| {
+            type_size_vec
+                .iter()
+                .find(|(other_type, _)| *other_type == r#type)
+                .unwrap()
+                .1
+        };
+
+        assert_eq!(size("total"), size("processes") + size("system"));
+        assert!(0 < size("processes_used"));
+        assert!(size("processes_used") <= size("processes"));
+        assert!(0 < size("atom_used"));
+        assert!(size("atom_used") <= size("atom"));
+        assert!(size("atom") + size("binary") <= size("system"));
+    });
+}
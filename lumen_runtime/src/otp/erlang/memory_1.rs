@@ -0,0 +1,52 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{Atom, Term, TypedTerm};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::otp::erlang::memory_0::type_size;
+use crate::system::memory::Memory;
+
+#[native_implemented_function(memory/1)]
+pub fn native(process: &Process, type_or_type_list: Term) -> exception::Result {
+    let memory = Memory::current();
+
+    match type_or_type_list.to_typed_term().unwrap() {
+        TypedTerm::Atom(type_atom) => match memory.get(type_atom.name()) {
+            Some(size) => process.integer(size).map_err(|error| error.into()),
+            None => Err(badarg!().into()),
+        },
+        TypedTerm::Nil => Ok(Term::NIL),
+        TypedTerm::List(cons) => {
+            let mut type_size_vec = Vec::new();
+
+            for result in cons.into_iter() {
+                let type_atom: Atom = match result {
+                    Ok(r#type) => r#type.try_into()?,
+                    Err(_) => return Err(badarg!().into()),
+                };
+
+                if memory.get(type_atom.name()).is_none() {
+                    return Err(badarg!().into());
+                }
+
+                type_size_vec.push(type_size(process, &memory, type_atom.name())?);
+            }
+
+            process
+                .list_from_slice(&type_size_vec)
+                .map_err(|error| error.into())
+        }
+        _ => Err(badarg!().into()),
+    }
+}
@@ -0,0 +1,128 @@
+use std::convert::TryInto;
+
+use proptest::prop_assert_eq;
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{
+    atom_unchecked, BinaryType, Boxed, Cons, ProcBin, SmallInteger, Term, Tuple,
+};
+
+use crate::otp::erlang::memory_1::native;
+use crate::scheduler::{with_process, with_process_arc};
+use crate::system::memory::TYPES;
+use crate::test::strategy;
+
+#[test]
+fn without_atom_or_list_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &strategy::term::is_not_list(arc_process.clone())
+                    .prop_filter("Type cannot be an atom", |r#type| !r#type.is_atom()),
+                |r#type| {
+                    prop_assert_eq!(native(&arc_process, r#type), Err(badarg!().into()));
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_unknown_type_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, atom_unchecked("unknown")),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_each_type_returns_integer() {
+    with_process(|process| {
+        for r#type in TYPES.iter() {
+            assert!(native(process, atom_unchecked(r#type))
+                .unwrap()
+                .is_integer());
+        }
+    });
+}
+
+#[test]
+fn with_binary_includes_refcounted_binaries() {
+    with_process(|process| {
+        let byte_len = 1 << 16;
+        let proc_bin = ProcBin::from_slice(&vec![0; byte_len], BinaryType::Raw).unwrap();
+
+        assert!(byte_len <= type_size(process, "binary"));
+
+        drop(proc_bin);
+    });
+}
+
+#[test]
+fn with_processes_used_is_at_most_processes() {
+    with_process(|process| {
+        let processes_used = type_size(process, "processes_used");
+
+        assert!(0 < processes_used);
+        assert!(processes_used <= type_size(process, "processes"));
+    });
+}
+
+#[test]
+fn with_empty_list_returns_empty_list() {
+    with_process(|process| {
+        assert_eq!(native(process, Term::NIL), Ok(Term::NIL));
+    });
+}
+
+#[test]
+fn with_list_of_types_returns_list_of_type_size_tuples() {
+    with_process(|process| {
+        let type_list = process
+            .list_from_slice(&[atom_unchecked("atom"), atom_unchecked("ets")])
+            .unwrap();
+
+        let list = native(process, type_list).unwrap();
+        let cons: Boxed<Cons> = list.try_into().unwrap();
+        let vec: Vec<Term> = cons.into_iter().map(|result| result.unwrap()).collect();
+
+        assert_eq!(vec.len(), 2);
+
+        let atom_tuple: Boxed<Tuple> = vec[0].try_into().unwrap();
+
+        assert_eq!(atom_tuple[0], atom_unchecked("atom"));
+        assert!(atom_tuple[1].is_integer());
+
+        assert_eq!(
+            vec[1],
+            process
+                .tuple_from_slice(&[atom_unchecked("ets"), process.integer(0).unwrap()])
+                .unwrap()
+        );
+    });
+}
+
+#[test]
+fn with_list_containing_unknown_type_errors_badarg() {
+    with_process(|process| {
+        let type_list = process
+            .list_from_slice(&[atom_unchecked("total"), atom_unchecked("unknown")])
+            .unwrap();
+
+        assert_eq!(native(process, type_list), Err(badarg!().into()));
+    });
+}
+
+fn type_size(process: &Process, r#type: &str) -> usize {
+    let term = native(process, atom_unchecked(r#type)).unwrap();
+    let small_integer: SmallInteger = term.try_into().unwrap();
+
+    small_integer.try_into().unwrap()
+}
@@ -0,0 +1,29 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{Term, TypedTerm};
+
+use lumen_runtime_macros::native_implemented_function;
+
+#[native_implemented_function(pid_to_list/1)]
+pub fn native(process: &Process, pid: Term) -> exception::Result {
+    let string = match pid.to_typed_term().unwrap() {
+        TypedTerm::Pid(pid) => pid.to_string(),
+        TypedTerm::Boxed(boxed) => match boxed.to_typed_term().unwrap() {
+            TypedTerm::ExternalPid(external_pid) => external_pid.to_string(),
+            _ => return Err(badarg!().into()),
+        },
+        _ => return Err(badarg!().into()),
+    };
+
+    process
+        .charlist_from_str(&string)
+        .map_err(|error| error.into())
+}
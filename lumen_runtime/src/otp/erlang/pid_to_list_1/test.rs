@@ -0,0 +1,58 @@
+use proptest::prop_assert_eq;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::term::make_pid;
+
+use crate::otp::erlang::list_to_pid_1;
+use crate::otp::erlang::pid_to_list_1::native;
+use crate::scheduler::{with_process, with_process_arc};
+use crate::test::strategy;
+
+#[test]
+fn without_pid_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(&strategy::term::is_not_pid(arc_process.clone()), |pid| {
+                prop_assert_eq!(native(&arc_process, pid), Err(badarg!().into()));
+
+                Ok(())
+            })
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_pid_returns_list_that_list_to_pid_converts_back() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(&strategy::term::is_pid(arc_process.clone()), |pid| {
+                let list = native(&arc_process, pid).unwrap();
+
+                prop_assert_eq!(list_to_pid_1::native(&arc_process, list), Ok(pid));
+
+                Ok(())
+            })
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_local_pid_returns_node_zero() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, make_pid(1, 2).unwrap()),
+            Ok(process.charlist_from_str("<0.1.2>").unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_external_pid_returns_node_id() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, process.external_pid_with_node_id(1, 2, 3).unwrap()),
+            Ok(process.charlist_from_str("<1.2.3>").unwrap())
+        );
+    });
+}
@@ -0,0 +1,49 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::term::{AsTerm, Port};
+
+use crate::otp::erlang::list_to_port_1;
+use crate::otp::erlang::port_to_list_1::native;
+use crate::scheduler::{with_process, with_process_arc};
+use crate::test::strategy;
+
+#[test]
+fn without_port_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &strategy::term(arc_process.clone())
+                    .prop_filter("Cannot be a port", |port| !port.is_port()),
+                |port| {
+                    prop_assert_eq!(native(&arc_process, port), Err(badarg!().into()));
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_local_port_returns_list_that_list_to_port_converts_back() {
+    with_process(|process| {
+        let port = unsafe { Port::from_raw(3).as_term() };
+        let list = native(process, port).unwrap();
+
+        assert_eq!(list, process.charlist_from_str("#Port<0.3>").unwrap());
+        assert_eq!(list_to_port_1::native(process, list), Ok(port));
+    });
+}
+
+#[test]
+fn with_external_port_returns_node_id() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, process.external_port_with_node_id(1, 2).unwrap()),
+            Ok(process.charlist_from_str("#Port<1.2>").unwrap())
+        );
+    });
+}
@@ -9,24 +9,83 @@ use std::convert::TryInto;
 
 use liblumen_alloc::badarg;
 use liblumen_alloc::erts::exception;
-use liblumen_alloc::erts::process::Process;
-use liblumen_alloc::erts::term::{Atom, Term};
+use liblumen_alloc::erts::process::alloc::next_heap_size;
+use liblumen_alloc::erts::process::{Priority, Process};
+use liblumen_alloc::erts::term::{atom_unchecked, AsTerm, Atom, Term};
 
 use lumen_runtime_macros::native_implemented_function;
 
+use crate::otp::erlang::process_info;
+use crate::process::spawn::options::{MaxHeapSize, MessageQueueData};
+use crate::scheduler::Scheduled;
+
+/// The maximum `save_calls` allowed by BEAM
+const MAX_SAVE_CALLS: usize = 10_000;
+
 #[native_implemented_function(process_flag/2)]
 pub fn native(process: &Process, flag: Term, value: Term) -> exception::Result {
     let flag_atom: Atom = flag.try_into()?;
 
     match flag_atom.name() {
-        "error_handler" => unimplemented!(),
-        "max_heap_size" => unimplemented!(),
-        "message_queue_data" => unimplemented!(),
-        "min_bin_vheap_size" => unimplemented!(),
-        "min_heap_size" => unimplemented!(),
-        "priority" => unimplemented!(),
-        "save_calls" => unimplemented!(),
-        "sensitive" => unimplemented!(),
+        "error_handler" => {
+            let error_handler: Atom = value.try_into()?;
+            let old_error_handler = process.set_error_handler(error_handler);
+
+            Ok(unsafe { old_error_handler.as_term() })
+        }
+        "max_heap_size" => {
+            let max_heap_size: MaxHeapSize = value.try_into()?;
+            let old_max_heap_size = process.set_max_heap_size(max_heap_size.into());
+
+            process_info::max_heap_size_map(process, old_max_heap_size)
+                .map_err(|error| error.into())
+        }
+        "message_queue_data" => {
+            let message_queue_data: MessageQueueData = value.try_into()?;
+            let off_heap = match message_queue_data {
+                MessageQueueData::OffHeap => true,
+                MessageQueueData::OnHeap => false,
+            };
+            let old_name = if process.message_queue_off_heap(off_heap) {
+                "off_heap"
+            } else {
+                "on_heap"
+            };
+
+            Ok(atom_unchecked(old_name))
+        }
+        "min_bin_vheap_size" => {
+            let min_bin_vheap_size: usize = value.try_into()?;
+            let old_min_bin_vheap_size = process.set_min_vheap_size(min_bin_vheap_size);
+
+            process
+                .integer(old_min_bin_vheap_size)
+                .map_err(|error| error.into())
+        }
+        "min_heap_size" => {
+            let min_heap_size: usize = value.try_into()?;
+            let old_min_heap_size = process.set_min_heap_size(next_heap_size(min_heap_size));
+
+            process
+                .integer(old_min_heap_size)
+                .map_err(|error| error.into())
+        }
+        "priority" => {
+            let priority: Priority = value.try_into()?;
+            let old_priority = process.set_priority(priority);
+
+            if let Some(arc_scheduler) = process.scheduler() {
+                arc_scheduler.reprioritize(process, old_priority);
+            }
+
+            Ok(old_priority.into())
+        }
+        "save_calls" => save_calls(process, process, value),
+        "sensitive" => {
+            let value_bool: bool = value.try_into()?;
+
+            Ok(process.sensitive(value_bool).into())
+        }
         "trap_exit" => {
             let value_bool: bool = value.try_into()?;
 
@@ -35,3 +94,23 @@ pub fn native(process: &Process, flag: Term, value: Term) -> exception::Result {
         _ => Err(badarg!().into()),
     }
 }
+
+/// Sets `save_calls` on `target` and returns the old value allocated on `process`.  Shared with
+/// `process_flag/3`.
+pub(in crate::otp::erlang) fn save_calls(
+    process: &Process,
+    target: &Process,
+    value: Term,
+) -> exception::Result {
+    let save_calls: usize = value.try_into()?;
+
+    if save_calls <= MAX_SAVE_CALLS {
+        let old_save_calls = target.set_save_calls(save_calls);
+
+        process
+            .integer(old_save_calls)
+            .map_err(|error| error.into())
+    } else {
+        Err(badarg!().into())
+    }
+}
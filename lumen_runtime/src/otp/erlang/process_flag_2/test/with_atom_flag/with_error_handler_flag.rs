@@ -0,0 +1,36 @@
+use super::*;
+
+use liblumen_alloc::erts::term::atom_unchecked;
+
+#[test]
+fn without_atom_value_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(&strategy::term::is_not_atom(arc_process.clone()), |value| {
+                prop_assert_eq!(native(&arc_process, flag(), value), Err(badarg!().into()));
+
+                Ok(())
+            })
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_atom_value_returns_old_value() {
+    with_process(|process| {
+        let value = atom_unchecked("custom_error_handler");
+
+        assert_eq!(
+            native(process, flag(), value),
+            Ok(atom_unchecked("error_handler"))
+        );
+        assert_eq!(
+            native(process, flag(), atom_unchecked("error_handler")),
+            Ok(value)
+        );
+    });
+}
+
+fn flag() -> Term {
+    atom_unchecked("error_handler")
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+use liblumen_alloc::erts::process::alloc::next_heap_size;
+use liblumen_alloc::erts::term::atom_unchecked;
+
+#[test]
+fn with_negative_size_errors_badarg() {
+    with_process(|process| {
+        let value = process.integer(-1).unwrap();
+
+        for name in &["max_heap_size", "min_bin_vheap_size", "min_heap_size"] {
+            assert_eq!(
+                native(process, atom_unchecked(name), value),
+                Err(badarg!().into())
+            );
+        }
+    });
+}
+
+#[test]
+fn with_max_heap_size_returns_old_value_as_map() {
+    with_process(|process| {
+        let flag = atom_unchecked("max_heap_size");
+        let value = process.integer(1_000_000).unwrap();
+
+        assert_eq!(
+            native(process, flag, value),
+            Ok(max_heap_size_map(process, 0, true, true))
+        );
+        assert_eq!(
+            native(process, flag, process.integer(0).unwrap()),
+            Ok(max_heap_size_map(process, 1_000_000, true, true))
+        );
+    });
+}
+
+#[test]
+fn with_max_heap_size_map_uses_defaults_for_missing_keys() {
+    with_process(|process| {
+        let flag = atom_unchecked("max_heap_size");
+        let size = process.integer(1_000_000).unwrap();
+        let value = process
+            .map_from_slice(&[
+                (atom_unchecked("kill"), false.into()),
+                (atom_unchecked("size"), size),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(process, flag, value),
+            Ok(max_heap_size_map(process, 0, true, true))
+        );
+        assert_eq!(
+            native(process, flag, process.integer(0).unwrap()),
+            Ok(max_heap_size_map(process, 1_000_000, false, true))
+        );
+    });
+}
+
+#[test]
+fn with_max_heap_size_map_with_invalid_key_or_value_errors_badarg() {
+    with_process(|process| {
+        let flag = atom_unchecked("max_heap_size");
+
+        for (key, value) in &[
+            (atom_unchecked("unknown"), true.into()),
+            (atom_unchecked("kill"), atom_unchecked("yes")),
+            (atom_unchecked("error_logger"), process.integer(1).unwrap()),
+            (atom_unchecked("size"), process.integer(-1).unwrap()),
+        ] {
+            let value = process.map_from_slice(&[(*key, *value)]).unwrap();
+
+            assert_eq!(native(process, flag, value), Err(badarg!().into()));
+        }
+    });
+}
+
+#[test]
+fn with_min_bin_vheap_size_returns_old_value() {
+    with_process(|process| {
+        let flag = atom_unchecked("min_bin_vheap_size");
+        let value = process.integer(46_422).unwrap();
+
+        assert_eq!(
+            native(process, flag, value),
+            Ok(process.integer(0).unwrap())
+        );
+        assert_eq!(
+            native(process, flag, process.integer(0).unwrap()),
+            Ok(value)
+        );
+    });
+}
+
+#[test]
+fn with_min_heap_size_returns_old_value_and_rounds_to_heap_size() {
+    with_process(|process| {
+        let flag = atom_unchecked("min_heap_size");
+        let old_min_heap_size = process.min_heap_size();
+
+        assert_eq!(
+            native(process, flag, process.integer(1_000).unwrap()),
+            Ok(process.integer(old_min_heap_size).unwrap())
+        );
+        assert_eq!(process.min_heap_size(), next_heap_size(1_000));
+    });
+}
+
+fn max_heap_size_map(process: &Process, size: usize, kill: bool, error_logger: bool) -> Term {
+    process
+        .map_from_slice(&[
+            (atom_unchecked("error_logger"), error_logger.into()),
+            (atom_unchecked("kill"), kill.into()),
+            (atom_unchecked("size"), process.integer(size).unwrap()),
+        ])
+        .unwrap()
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+use liblumen_alloc::erts::term::atom_unchecked;
+
+#[test]
+fn without_on_heap_or_off_heap_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, flag(), atom_unchecked("in_between")),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_off_heap_returns_old_value_on_heap() {
+    with_process(|process| {
+        let on_heap = atom_unchecked("on_heap");
+        let off_heap = atom_unchecked("off_heap");
+
+        assert_eq!(native(process, flag(), off_heap), Ok(on_heap));
+        assert!(process.is_message_queue_off_heap());
+
+        assert_eq!(native(process, flag(), on_heap), Ok(off_heap));
+        assert!(!process.is_message_queue_off_heap());
+    });
+}
+
+fn flag() -> Term {
+    atom_unchecked("message_queue_data")
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+use liblumen_alloc::erts::process::Priority;
+use liblumen_alloc::erts::term::atom_unchecked;
+
+use crate::process;
+use crate::scheduler::Scheduler;
+
+#[test]
+fn without_priority_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, flag(), atom_unchecked("urgent")),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_priority_returns_old_priority() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, flag(), atom_unchecked("low")),
+            Ok(atom_unchecked("normal"))
+        );
+        assert_eq!(process.priority(), Priority::Low);
+
+        assert_eq!(
+            native(process, flag(), atom_unchecked("max")),
+            Ok(atom_unchecked("low"))
+        );
+        assert_eq!(process.priority(), Priority::Max);
+    });
+}
+
+#[test]
+fn with_run_queued_process_moves_process_to_run_queue_for_priority() {
+    with_process(|parent_process| {
+        let arc_process = process::test(parent_process);
+        let arc_scheduler = Scheduler::current();
+
+        assert!(arc_scheduler.is_run_queued(&arc_process));
+
+        let normal_run_queue_len_before = arc_scheduler.run_queue_len(Priority::Normal);
+        let high_run_queue_len_before = arc_scheduler.run_queue_len(Priority::High);
+
+        assert_eq!(
+            native(&arc_process, flag(), atom_unchecked("high")),
+            Ok(atom_unchecked("normal"))
+        );
+
+        assert!(arc_scheduler.is_run_queued(&arc_process));
+        assert_eq!(
+            arc_scheduler.run_queue_len(Priority::Normal),
+            normal_run_queue_len_before - 1
+        );
+        assert_eq!(
+            arc_scheduler.run_queue_len(Priority::High),
+            high_run_queue_len_before + 1
+        );
+    });
+}
+
+fn flag() -> Term {
+    atom_unchecked("priority")
+}
@@ -0,0 +1,76 @@
+use super::*;
+
+use liblumen_alloc::erts::process::code::stack::frame::Placement;
+use liblumen_alloc::erts::term::atom_unchecked;
+
+use crate::otp::erlang::{self, process_info_2};
+use crate::process;
+
+#[test]
+fn with_more_than_10_000_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, flag(), process.integer(10_001).unwrap()),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_save_calls_returns_old_value() {
+    with_process(|process| {
+        let value = process.integer(10_000).unwrap();
+
+        assert_eq!(
+            native(process, flag(), value),
+            Ok(process.integer(0).unwrap())
+        );
+        assert_eq!(
+            native(process, flag(), process.integer(0).unwrap()),
+            Ok(value)
+        );
+    });
+}
+
+#[test]
+fn with_save_calls_saves_last_calls() {
+    with_process(|parent_process| {
+        let arc_process = process::test(parent_process);
+        let last_calls = atom_unchecked("last_calls");
+
+        assert_eq!(
+            process_info_2::native(&arc_process, arc_process.pid_term(), last_calls),
+            Ok(arc_process
+                .tuple_from_slice(&[last_calls, false.into()])
+                .unwrap())
+        );
+
+        assert_eq!(
+            native(&arc_process, flag(), arc_process.integer(1).unwrap()),
+            Ok(arc_process.integer(0).unwrap())
+        );
+
+        assert_eq!(
+            process_info_2::native(&arc_process, arc_process.pid_term(), last_calls),
+            Ok(arc_process
+                .tuple_from_slice(&[last_calls, Term::NIL])
+                .unwrap())
+        );
+
+        erlang::exit_1::place_frame_with_arguments(
+            &arc_process,
+            Placement::Push,
+            atom_unchecked("normal"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            arc_process.last_calls(),
+            Some(vec![arc_process.current_module_function_arity().unwrap()])
+        );
+    });
+}
+
+fn flag() -> Term {
+    atom_unchecked("save_calls")
+}
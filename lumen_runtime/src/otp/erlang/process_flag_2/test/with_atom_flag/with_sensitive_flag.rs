@@ -0,0 +1,18 @@
+use super::*;
+
+use liblumen_alloc::erts::term::atom_unchecked;
+
+#[test]
+fn with_true_value_returns_old_value_false() {
+    with_process(|process| {
+        assert_eq!(native(process, flag(), true.into()), Ok(false.into()));
+        assert!(process.is_sensitive());
+
+        assert_eq!(native(process, flag(), false.into()), Ok(true.into()));
+        assert!(!process.is_sensitive());
+    });
+}
+
+fn flag() -> Term {
+    atom_unchecked("sensitive")
+}
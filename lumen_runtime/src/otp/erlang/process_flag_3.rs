@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{Atom, Pid, Term};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::otp::erlang::process_flag_2;
+use crate::registry::pid_to_process;
+
+/// Only `save_calls` can be set on another process
+#[native_implemented_function(process_flag/3)]
+pub fn native(process: &Process, pid: Term, flag: Term, value: Term) -> exception::Result {
+    let pid_pid: Pid = pid.try_into()?;
+    let flag_atom: Atom = flag.try_into()?;
+
+    match flag_atom.name() {
+        "save_calls" => {
+            if process.pid() == pid_pid {
+                process_flag_2::save_calls(process, process, value)
+            } else {
+                match pid_to_process(&pid_pid) {
+                    Some(pid_arc_process) => {
+                        process_flag_2::save_calls(process, &pid_arc_process, value)
+                    }
+                    None => Err(badarg!().into()),
+                }
+            }
+        }
+        _ => Err(badarg!().into()),
+    }
+}
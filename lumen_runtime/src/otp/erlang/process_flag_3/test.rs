@@ -0,0 +1,57 @@
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::term::{atom_unchecked, next_pid};
+
+use crate::otp::erlang::process_flag_3::native;
+use crate::process;
+use crate::scheduler::with_process;
+
+#[test]
+fn without_save_calls_flag_errors_badarg() {
+    with_process(|parent_process| {
+        let arc_process = process::test(parent_process);
+
+        assert_eq!(
+            native(
+                parent_process,
+                arc_process.pid_term(),
+                atom_unchecked("trap_exit"),
+                true.into()
+            ),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn without_process_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                next_pid(),
+                atom_unchecked("save_calls"),
+                process.integer(1).unwrap()
+            ),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_save_calls_flag_sets_save_calls_on_other_process() {
+    with_process(|parent_process| {
+        let arc_process = process::test(parent_process);
+
+        assert_eq!(
+            native(
+                parent_process,
+                arc_process.pid_term(),
+                atom_unchecked("save_calls"),
+                parent_process.integer(10).unwrap()
+            ),
+            Ok(parent_process.integer(0).unwrap())
+        );
+        assert_eq!(arc_process.save_calls(), 10);
+        assert_eq!(parent_process.save_calls(), 0);
+    });
+}
@@ -0,0 +1,494 @@
+use std::convert::{TryFrom, TryInto};
+use std::mem;
+use std::sync::atomic::Ordering;
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception::{self, runtime, system::Alloc};
+use liblumen_alloc::erts::process::{MaxHeapSize, Process, Status};
+use liblumen_alloc::erts::term::{atom_unchecked, AsTerm, Atom, Boxed, Cons, Term};
+use liblumen_alloc::erts::ModuleFunctionArity;
+use liblumen_alloc::CloneToProcess;
+
+use crate::system;
+
+/// The items returned by `process_info/1`, in order.  `registered_name` is only included when the
+/// process is registered.
+pub const DEFAULT_ITEMS: &[Item] = &[
+    Item::CurrentFunction,
+    Item::InitialCall,
+    Item::Status,
+    Item::MessageQueueLen,
+    Item::Links,
+    Item::Dictionary,
+    Item::TrapExit,
+    Item::ErrorHandler,
+    Item::Priority,
+    Item::GroupLeader,
+    Item::TotalHeapSize,
+    Item::HeapSize,
+    Item::StackSize,
+    Item::Reductions,
+    Item::GarbageCollection,
+    Item::Suspending,
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Item {
+    Backtrace,
+    Binary,
+    CatchLevel,
+    CurrentFunction,
+    CurrentLocation,
+    CurrentStacktrace,
+    Dictionary,
+    ErrorHandler,
+    GarbageCollection,
+    GarbageCollectionInfo,
+    GroupLeader,
+    HeapSize,
+    InitialCall,
+    Links,
+    LastCalls,
+    Memory,
+    MessageQueueLen,
+    Messages,
+    MinHeapSize,
+    MinBinVheapSize,
+    MonitoredBy,
+    Monitors,
+    MessageQueueData,
+    Priority,
+    Reductions,
+    RegisteredName,
+    SequentialTraceToken,
+    StackSize,
+    Status,
+    Suspending,
+    TotalHeapSize,
+    Trace,
+    TrapExit,
+}
+
+impl Item {
+    pub fn name(self) -> &'static str {
+        match self {
+            Item::Backtrace => "backtrace",
+            Item::Binary => "binary",
+            Item::CatchLevel => "catchlevel",
+            Item::CurrentFunction => "current_function",
+            Item::CurrentLocation => "current_location",
+            Item::CurrentStacktrace => "current_stacktrace",
+            Item::Dictionary => "dictionary",
+            Item::ErrorHandler => "error_handler",
+            Item::GarbageCollection => "garbage_collection",
+            Item::GarbageCollectionInfo => "garbage_collection_info",
+            Item::GroupLeader => "group_leader",
+            Item::HeapSize => "heap_size",
+            Item::InitialCall => "initial_call",
+            Item::Links => "links",
+            Item::LastCalls => "last_calls",
+            Item::Memory => "memory",
+            Item::MessageQueueLen => "message_queue_len",
+            Item::Messages => "messages",
+            Item::MinHeapSize => "min_heap_size",
+            Item::MinBinVheapSize => "min_bin_vheap_size",
+            Item::MonitoredBy => "monitored_by",
+            Item::Monitors => "monitors",
+            Item::MessageQueueData => "message_queue_data",
+            Item::Priority => "priority",
+            Item::Reductions => "reductions",
+            Item::RegisteredName => "registered_name",
+            Item::SequentialTraceToken => "sequential_trace_token",
+            Item::StackSize => "stack_size",
+            Item::Status => "status",
+            Item::Suspending => "suspending",
+            Item::TotalHeapSize => "total_heap_size",
+            Item::Trace => "trace",
+            Item::TrapExit => "trap_exit",
+        }
+    }
+
+    /// `{Item, Value}` for `target` allocated on `process`.  Unlike `process_info/2` with a
+    /// single item, an unregistered `target` returns `{registered_name, []}`, as the list form
+    /// does.
+    pub fn info(self, process: &Process, target: &Process) -> exception::Result {
+        let tag = atom_unchecked(self.name());
+        let value = self.value(process, target)?;
+
+        process
+            .tuple_from_slice(&[tag, value])
+            .map_err(|error| error.into())
+    }
+
+    fn value(self, process: &Process, target: &Process) -> Result<Term, Alloc> {
+        match self {
+            Item::Backtrace => {
+                let backtrace = format!("{}", target.stacktrace());
+
+                process.binary_from_str(&backtrace)
+            }
+            Item::Binary => {
+                let mut binary_vec = Vec::new();
+
+                for (id, byte_len, ref_count) in target.virtual_binaries() {
+                    let id_term = process.integer(id)?;
+                    let byte_len_term = process.integer(byte_len)?;
+                    let ref_count_term = process.integer(ref_count)?;
+
+                    binary_vec.push(process.tuple_from_slice(&[
+                        id_term,
+                        byte_len_term,
+                        ref_count_term,
+                    ])?);
+                }
+
+                process.list_from_slice(&binary_vec)
+            }
+            // Lumen does not track `catch` nesting, so processes are never inside a `catch`
+            Item::CatchLevel => process.integer(0),
+            Item::CurrentFunction => match target.current_module_function_arity() {
+                Some(module_function_arity) => {
+                    module_function_arity_to_tuple(process, &module_function_arity)
+                }
+                None => Ok(atom_unchecked("undefined")),
+            },
+            Item::CurrentLocation => match target.current_module_function_arity() {
+                Some(module_function_arity) => location_tuple(process, &module_function_arity),
+                None => Ok(atom_unchecked("undefined")),
+            },
+            Item::CurrentStacktrace => {
+                let mut location_vec = Vec::new();
+
+                for module_function_arity in target
+                    .stacktrace()
+                    .iter()
+                    .take(system::flag::backtrace_depth())
+                {
+                    location_vec.push(location_tuple(process, module_function_arity)?);
+                }
+
+                process.list_from_slice(&location_vec)
+            }
+            Item::Dictionary => {
+                if process.pid() == target.pid() {
+                    target.get_entries()
+                } else {
+                    // `get_entries` would allocate the entries on the heap of `target`
+                    let (entries, mut heap_fragment) = target.get_entries_to_fragment()?;
+                    process.attach_fragment(unsafe { heap_fragment.as_mut() });
+
+                    Ok(entries)
+                }
+            }
+            Item::ErrorHandler => Ok(unsafe { target.error_handler().as_term() }),
+            Item::GarbageCollection => {
+                let max_heap_size_map = max_heap_size_map(process, target.max_heap_size())?;
+
+                keyword_list(
+                    process,
+                    &[
+                        ("max_heap_size", max_heap_size_map),
+                        (
+                            "min_bin_vheap_size",
+                            process.integer(target.min_vheap_size())?,
+                        ),
+                        ("min_heap_size", process.integer(target.min_heap_size())?),
+                        (
+                            "fullsweep_after",
+                            process.integer(target.fullsweep_after())?,
+                        ),
+                        ("minor_gcs", process.integer(target.minor_gcs())?),
+                        (
+                            "major_gcs",
+                            process.integer(target.gc_statistics().major_gcs)?,
+                        ),
+                    ],
+                )
+            }
+            Item::GarbageCollectionInfo => {
+                let statistics = target.gc_statistics();
+                let last_gc_time = match statistics.last_gc_time {
+                    Some(milliseconds) => process.integer(milliseconds)?,
+                    None => atom_unchecked("undefined"),
+                };
+
+                keyword_list(
+                    process,
+                    &[
+                        (
+                            "old_heap_block_size",
+                            process.integer(target.old_heap_size())?,
+                        ),
+                        ("heap_block_size", process.integer(target.heap_size())?),
+                        (
+                            "mbuf_size",
+                            process.integer(target.off_heap_size() / mem::size_of::<Term>())?,
+                        ),
+                        ("stack_size", process.integer(target.stack_used())?),
+                        (
+                            "bin_vheap_size",
+                            process.integer(target.virtual_heap_used() / mem::size_of::<Term>())?,
+                        ),
+                        // minor collections are counted since the last full sweep by `minor_gcs` in
+                        // `garbage_collection`, so the lifetime count needs a different key
+                        ("total_minor_gcs", process.integer(statistics.minor_gcs)?),
+                        ("major_gcs", process.integer(statistics.major_gcs)?),
+                        (
+                            "words_reclaimed",
+                            process.integer(statistics.words_reclaimed)?,
+                        ),
+                        ("last_gc_time", last_gc_time),
+                    ],
+                )
+            }
+            // Lumen does not track group leaders yet, so every process acts as its own, like
+            // `init` does on BEAM
+            Item::GroupLeader => Ok(target.pid_term()),
+            Item::HeapSize => process.integer(target.heap_size()),
+            Item::InitialCall => {
+                module_function_arity_to_tuple(process, &target.initial_module_function_arity)
+            }
+            Item::Links => {
+                let linked_pid_vec: Vec<Term> = target
+                    .linked_pid_set
+                    .lock()
+                    .iter()
+                    .map(|linked_pid| unsafe { linked_pid.as_term() })
+                    .collect();
+
+                process.list_from_slice(&linked_pid_vec)
+            }
+            Item::LastCalls => match target.last_calls() {
+                Some(last_calls) => {
+                    let mut call_vec = Vec::with_capacity(last_calls.len());
+
+                    for module_function_arity in last_calls {
+                        call_vec.push(module_function_arity_to_tuple(
+                            process,
+                            &module_function_arity,
+                        )?);
+                    }
+
+                    process.list_from_slice(&call_vec)
+                }
+                None => Ok(false.into()),
+            },
+            Item::Memory => process.integer(target.memory()),
+            Item::MessageQueueLen => {
+                let len = target.mailbox.lock().borrow().len();
+
+                process.integer(len)
+            }
+            Item::Messages => {
+                let locked_mailbox = target.mailbox.lock();
+                let mailbox = locked_mailbox.borrow();
+                let mut heap = process.acquire_heap();
+                let mut message_vec = Vec::with_capacity(mailbox.len());
+
+                // messages may be in heap fragments that are freed when they are received, so
+                // always copy them
+                for message in mailbox.iter() {
+                    message_vec.push(message.data().clone_to_heap(&mut heap)?);
+                }
+
+                drop(heap);
+
+                process.list_from_slice(&message_vec)
+            }
+            Item::MinHeapSize => process.integer(target.min_heap_size()),
+            Item::MinBinVheapSize => process.integer(target.min_vheap_size()),
+            Item::MonitoredBy => {
+                let monitoring_pid_vec: Vec<Term> = target
+                    .monitor_by_reference
+                    .lock()
+                    .values()
+                    .map(|monitor| unsafe { monitor.monitoring_pid().as_term() })
+                    .collect();
+
+                process.list_from_slice(&monitoring_pid_vec)
+            }
+            Item::Monitors => {
+                let monitored_pid_vec: Vec<Term> = target
+                    .monitored_pid_by_reference
+                    .lock()
+                    .values()
+                    .map(|monitored_pid| unsafe { monitored_pid.as_term() })
+                    .collect();
+                let tag = atom_unchecked("process");
+                let mut monitor_vec = Vec::with_capacity(monitored_pid_vec.len());
+
+                for monitored_pid in monitored_pid_vec {
+                    monitor_vec.push(process.tuple_from_slice(&[tag, monitored_pid])?);
+                }
+
+                process.list_from_slice(&monitor_vec)
+            }
+            Item::MessageQueueData => {
+                let name = if target.is_message_queue_off_heap() {
+                    "off_heap"
+                } else {
+                    "on_heap"
+                };
+
+                Ok(atom_unchecked(name))
+            }
+            Item::Priority => Ok(target.priority().into()),
+            Item::Reductions => process.integer(target.total_reductions.load(Ordering::SeqCst)),
+            Item::RegisteredName => match *target.registered_name.read() {
+                Some(registered_name) => Ok(unsafe { registered_name.as_term() }),
+                None => Ok(Term::NIL),
+            },
+            Item::SequentialTraceToken => Ok(Term::NIL),
+            Item::StackSize => process.integer(target.stack_used()),
+            Item::Status => {
+                let name = match *target.status.read() {
+                    Status::Runnable => "runnable",
+                    Status::Running => "running",
+                    Status::Waiting => "waiting",
+                    Status::Exiting(_) => "exiting",
+                };
+
+                Ok(atom_unchecked(name))
+            }
+            Item::Suspending => Ok(Term::NIL),
+            Item::TotalHeapSize => process.integer(target.total_heap_size()),
+            Item::Trace => process.integer(0),
+            Item::TrapExit => Ok(target.traps_exit().into()),
+        }
+    }
+}
+
+impl TryFrom<Atom> for Item {
+    type Error = runtime::Exception;
+
+    fn try_from(atom: Atom) -> Result<Self, Self::Error> {
+        match atom.name() {
+            "backtrace" => Ok(Item::Backtrace),
+            "binary" => Ok(Item::Binary),
+            "catchlevel" => Ok(Item::CatchLevel),
+            "current_function" => Ok(Item::CurrentFunction),
+            "current_location" => Ok(Item::CurrentLocation),
+            "current_stacktrace" => Ok(Item::CurrentStacktrace),
+            "dictionary" => Ok(Item::Dictionary),
+            "error_handler" => Ok(Item::ErrorHandler),
+            "garbage_collection" => Ok(Item::GarbageCollection),
+            "garbage_collection_info" => Ok(Item::GarbageCollectionInfo),
+            "group_leader" => Ok(Item::GroupLeader),
+            "heap_size" => Ok(Item::HeapSize),
+            "initial_call" => Ok(Item::InitialCall),
+            "links" => Ok(Item::Links),
+            "last_calls" => Ok(Item::LastCalls),
+            "memory" => Ok(Item::Memory),
+            "message_queue_len" => Ok(Item::MessageQueueLen),
+            "messages" => Ok(Item::Messages),
+            "min_heap_size" => Ok(Item::MinHeapSize),
+            "min_bin_vheap_size" => Ok(Item::MinBinVheapSize),
+            "monitored_by" => Ok(Item::MonitoredBy),
+            "monitors" => Ok(Item::Monitors),
+            "message_queue_data" => Ok(Item::MessageQueueData),
+            "priority" => Ok(Item::Priority),
+            "reductions" => Ok(Item::Reductions),
+            "registered_name" => Ok(Item::RegisteredName),
+            "sequential_trace_token" => Ok(Item::SequentialTraceToken),
+            "stack_size" => Ok(Item::StackSize),
+            "status" => Ok(Item::Status),
+            "suspending" => Ok(Item::Suspending),
+            "total_heap_size" => Ok(Item::TotalHeapSize),
+            "trace" => Ok(Item::Trace),
+            "trap_exit" => Ok(Item::TrapExit),
+            _ => Err(badarg!()),
+        }
+    }
+}
+
+impl TryFrom<Term> for Item {
+    type Error = runtime::Exception;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let atom: Atom = term.try_into()?;
+
+        atom.try_into()
+    }
+}
+
+/// Converts a proper list of item atoms
+pub fn item_vec(cons: Boxed<Cons>) -> Result<Vec<Item>, runtime::Exception> {
+    let mut item_vec = Vec::new();
+
+    for result in cons.into_iter() {
+        match result {
+            Ok(element) => item_vec.push(element.try_into()?),
+            Err(_) => return Err(badarg!()),
+        }
+    }
+
+    Ok(item_vec)
+}
+
+/// `[{Item, Value}]` for `target` allocated on `process`
+pub fn info_list(process: &Process, target: &Process, items: &[Item]) -> exception::Result {
+    let mut info_vec = Vec::with_capacity(items.len());
+
+    for item in items {
+        info_vec.push(item.info(process, target)?);
+    }
+
+    process
+        .list_from_slice(&info_vec)
+        .map_err(|error| error.into())
+}
+
+/// `#{error_logger => ErrorLogger, kill => Kill, size => Size}` allocated on `process`
+pub fn max_heap_size_map(process: &Process, max_heap_size: MaxHeapSize) -> Result<Term, Alloc> {
+    let size = process.integer(max_heap_size.size)?;
+
+    process.map_from_slice(&[
+        (
+            atom_unchecked("error_logger"),
+            max_heap_size.error_logger.into(),
+        ),
+        (atom_unchecked("kill"), max_heap_size.kill.into()),
+        (atom_unchecked("size"), size),
+    ])
+}
+
+// Private
+
+fn keyword_list(process: &Process, pairs: &[(&str, Term)]) -> Result<Term, Alloc> {
+    let mut tuple_vec = Vec::with_capacity(pairs.len());
+
+    for (key, value) in pairs {
+        tuple_vec.push(process.tuple_from_slice(&[atom_unchecked(key), *value])?);
+    }
+
+    process.list_from_slice(&tuple_vec)
+}
+
+/// `{Module, Function, Arity, Location}` with an empty `Location` because Lumen does not track
+/// file and line information
+fn location_tuple(
+    process: &Process,
+    module_function_arity: &ModuleFunctionArity,
+) -> Result<Term, Alloc> {
+    let arity = process.integer(module_function_arity.arity)?;
+
+    process.tuple_from_slice(&[
+        unsafe { module_function_arity.module.as_term() },
+        unsafe { module_function_arity.function.as_term() },
+        arity,
+        Term::NIL,
+    ])
+}
+
+fn module_function_arity_to_tuple(
+    process: &Process,
+    module_function_arity: &ModuleFunctionArity,
+) -> Result<Term, Alloc> {
+    let arity = process.integer(module_function_arity.arity)?;
+
+    process.tuple_from_slice(&[
+        unsafe { module_function_arity.module.as_term() },
+        unsafe { module_function_arity.function.as_term() },
+        arity,
+    ])
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{atom_unchecked, Pid, Term};
+
+use crate::otp::erlang::process_info::{self, Item, DEFAULT_ITEMS};
+use crate::registry::pid_to_process;
+
+use lumen_runtime_macros::native_implemented_function;
+
+#[native_implemented_function(process_info/1)]
+pub fn native(process: &Process, pid: Term) -> exception::Result {
+    let pid_pid: Pid = pid.try_into()?;
+
+    if process.pid() == pid_pid {
+        process_info(process, process)
+    } else {
+        match pid_to_process(&pid_pid) {
+            Some(pid_arc_process) => process_info(process, &pid_arc_process),
+            None => Ok(atom_unchecked("undefined")),
+        }
+    }
+}
+
+// Private
+
+fn process_info(process: &Process, target: &Process) -> exception::Result {
+    let mut item_vec = Vec::with_capacity(DEFAULT_ITEMS.len() + 1);
+
+    if target.registered_name.read().is_some() {
+        item_vec.push(Item::RegisteredName);
+    }
+
+    item_vec.extend_from_slice(DEFAULT_ITEMS);
+
+    process_info::info_list(process, target, &item_vec)
+}
@@ -0,0 +1,83 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::term::{atom_unchecked, next_pid, Atom, Boxed, Cons, Term, Tuple};
+
+use crate::otp::erlang::process_info_1::native;
+use crate::scheduler::with_process_arc;
+use crate::test::registered_name;
+use crate::{process, registry};
+
+#[test]
+fn without_process_returns_undefined() {
+    with_process_arc(|arc_process| {
+        assert_eq!(
+            native(&arc_process, next_pid()),
+            Ok(atom_unchecked("undefined"))
+        );
+    });
+}
+
+#[test]
+fn without_registered_name_returns_default_items() {
+    with_process_arc(|parent_arc_process| {
+        let arc_process = process::test(&parent_arc_process);
+
+        assert_eq!(
+            keys(native(&parent_arc_process, arc_process.pid_term()).unwrap()),
+            DEFAULT_KEYS
+        );
+    });
+}
+
+#[test]
+fn with_registered_name_returns_registered_name_before_default_items() {
+    with_process_arc(|parent_arc_process| {
+        let arc_process = process::test(&parent_arc_process);
+        let registered_name = registered_name();
+        let registered_name_atom: Atom = registered_name.try_into().unwrap();
+
+        assert!(registry::put_atom_to_process(
+            registered_name_atom,
+            arc_process.clone()
+        ));
+
+        let info = native(&parent_arc_process, arc_process.pid_term()).unwrap();
+        let mut expected_keys = vec!["registered_name"];
+        expected_keys.extend_from_slice(DEFAULT_KEYS);
+
+        assert_eq!(keys(info), expected_keys);
+    });
+}
+
+const DEFAULT_KEYS: &[&str] = &[
+    "current_function",
+    "initial_call",
+    "status",
+    "message_queue_len",
+    "links",
+    "dictionary",
+    "trap_exit",
+    "error_handler",
+    "priority",
+    "group_leader",
+    "total_heap_size",
+    "heap_size",
+    "stack_size",
+    "reductions",
+    "garbage_collection",
+    "suspending",
+];
+
+fn keys(info: Term) -> Vec<&'static str> {
+    let info_cons: Boxed<Cons> = info.try_into().unwrap();
+
+    info_cons
+        .into_iter()
+        .map(|result| {
+            let tuple: Boxed<Tuple> = result.unwrap().try_into().unwrap();
+            let key: Atom = tuple[0].try_into().unwrap();
+
+            key.name()
+        })
+        .collect()
+}
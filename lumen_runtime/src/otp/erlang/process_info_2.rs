@@ -7,80 +7,52 @@ mod test;
 
 use std::convert::TryInto;
 
-use crate::registry::pid_to_process;
-use liblumen_alloc::badarg;
 use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::process::Process;
-use liblumen_alloc::erts::term::{atom_unchecked, AsTerm, Atom, Pid, Term};
+use liblumen_alloc::erts::term::{atom_unchecked, Pid, Term, TypedTerm};
+
+use crate::otp::erlang::process_info::{self, Item};
+use crate::registry::pid_to_process;
 
 use lumen_runtime_macros::native_implemented_function;
 
 #[native_implemented_function(process_info/2)]
-pub fn native(process: &Process, pid: Term, item: Term) -> exception::Result {
+pub fn native(process: &Process, pid: Term, item_or_item_list: Term) -> exception::Result {
     let pid_pid: Pid = pid.try_into()?;
-    let item_atom: Atom = item.try_into()?;
 
-    if process.pid() == pid_pid {
-        process_info(process, item_atom)
-    } else {
-        match pid_to_process(&pid_pid) {
-            Some(pid_arc_process) => process_info(&pid_arc_process, item_atom),
-            None => Ok(atom_unchecked("undefined")),
+    match item_or_item_list.to_typed_term().unwrap() {
+        TypedTerm::Nil => with_process(process, pid_pid, |_| Ok(Term::NIL)),
+        TypedTerm::List(cons) => {
+            let item_vec = process_info::item_vec(cons)?;
+
+            with_process(process, pid_pid, |target| {
+                process_info::info_list(process, target, &item_vec)
+            })
+        }
+        _ => {
+            let item: Item = item_or_item_list.try_into()?;
+
+            with_process(process, pid_pid, |target| match item {
+                // only the list form returns `{registered_name, []}` when not registered
+                Item::RegisteredName if target.registered_name.read().is_none() => Ok(Term::NIL),
+                _ => item.info(process, target),
+            })
         }
     }
 }
 
 // Private
 
-fn process_info(process: &Process, item: Atom) -> exception::Result {
-    match item.name() {
-        "backtrace" => unimplemented!(),
-        "binary" => unimplemented!(),
-        "catchlevel" => unimplemented!(),
-        "current_function" => unimplemented!(),
-        "current_location" => unimplemented!(),
-        "current_stacktrace" => unimplemented!(),
-        "dictionary" => unimplemented!(),
-        "error_handler" => unimplemented!(),
-        "garbage_collection" => unimplemented!(),
-        "garbage_collection_info" => unimplemented!(),
-        "group_leader" => unimplemented!(),
-        "heap_size" => unimplemented!(),
-        "initial_call" => unimplemented!(),
-        "links" => unimplemented!(),
-        "last_calls" => unimplemented!(),
-        "memory" => unimplemented!(),
-        "message_queue_len" => unimplemented!(),
-        "messages" => unimplemented!(),
-        "min_heap_size" => unimplemented!(),
-        "min_bin_vheap_size" => unimplemented!(),
-        "monitored_by" => unimplemented!(),
-        "monitors" => unimplemented!(),
-        "message_queue_data" => unimplemented!(),
-        "priority" => unimplemented!(),
-        "reductions" => unimplemented!(),
-        "registered_name" => registered_name(process),
-        "sequential_trace_token" => unimplemented!(),
-        "stack_size" => unimplemented!(),
-        "status" => unimplemented!(),
-        "suspending" => unimplemented!(),
-        "total_heap_size" => unimplemented!(),
-        "trace" => unimplemented!(),
-        "trap_exit" => unimplemented!(),
-        _ => Err(badarg!().into()),
-    }
-}
-
-fn registered_name(process: &Process) -> exception::Result {
-    match *process.registered_name.read() {
-        Some(registered_name) => {
-            let tag = atom_unchecked("registered_name");
-            let value = unsafe { registered_name.as_term() };
-
-            process
-                .tuple_from_slice(&[tag, value])
-                .map_err(|error| error.into())
+fn with_process<F>(process: &Process, pid: Pid, f: F) -> exception::Result
+where
+    F: FnOnce(&Process) -> exception::Result,
+{
+    if process.pid() == pid {
+        f(process)
+    } else {
+        match pid_to_process(&pid) {
+            Some(pid_arc_process) => f(&pid_arc_process),
+            None => Ok(atom_unchecked("undefined")),
         }
-        None => Ok(Term::NIL),
     }
 }
@@ -1,7 +1,13 @@
+mod with_dictionary;
+mod with_garbage_collection_info;
+mod with_item_list;
+mod with_links;
+mod with_messages;
 mod with_registered_name;
 
 use super::*;
 
+use std::convert::TryFrom;
 use std::sync::Arc;
 
 use proptest::strategy::{BoxedStrategy, Strategy};
@@ -9,6 +15,8 @@ use proptest::strategy::{BoxedStrategy, Strategy};
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::{Term, TypedTerm};
 
+use crate::otp::erlang::process_info::{self, Item};
+
 #[test]
 fn without_supported_item_errors_badarg() {
     with_process_arc(|arc_process| {
@@ -27,10 +35,9 @@ fn unsupported_item(arc_process: Arc<Process>) -> BoxedStrategy<Term> {
     strategy::term(arc_process)
         .prop_filter("Item cannot be supported", |item| {
             match item.to_typed_term().unwrap() {
-                TypedTerm::Atom(atom) => match atom.name() {
-                    "registered_name" => false,
-                    _ => true,
-                },
+                TypedTerm::Atom(atom) => Item::try_from(atom).is_err(),
+                TypedTerm::Nil => false,
+                TypedTerm::List(cons) => process_info::item_vec(cons).is_err(),
                 _ => true,
             }
         })
@@ -0,0 +1,39 @@
+use super::*;
+
+use crate::process;
+
+#[test]
+fn with_other_returns_copy_of_entries_without_allocating_on_other_heap() {
+    with_process_arc(|parent_arc_process| {
+        let arc_process = process::test(&parent_arc_process);
+        let key = atom_unchecked("key");
+        let value = arc_process
+            .list_from_slice(&[arc_process.integer(1).unwrap()])
+            .unwrap();
+
+        arc_process.put(key, value).unwrap();
+
+        let heap_used_before = arc_process.total_heap_used();
+        let dictionary = atom_unchecked("dictionary");
+
+        assert_eq!(
+            native(&parent_arc_process, arc_process.pid_term(), dictionary),
+            Ok(parent_arc_process
+                .tuple_from_slice(&[
+                    dictionary,
+                    parent_arc_process
+                        .list_from_slice(&[parent_arc_process
+                            .tuple_from_slice(&[
+                                key,
+                                parent_arc_process
+                                    .list_from_slice(&[parent_arc_process.integer(1).unwrap()])
+                                    .unwrap()
+                            ])
+                            .unwrap()])
+                        .unwrap()
+                ])
+                .unwrap())
+        );
+        assert_eq!(arc_process.total_heap_used(), heap_used_before);
+    });
+}
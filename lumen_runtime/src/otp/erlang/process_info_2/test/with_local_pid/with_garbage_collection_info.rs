@@ -0,0 +1,66 @@
+use super::*;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::ProcessFlags;
+use liblumen_alloc::erts::term::{Boxed, Cons, Tuple};
+
+use crate::process;
+
+#[test]
+fn without_collection_returns_undefined_last_gc_time() {
+    with_process_arc(|arc_process| {
+        let info_list = info_list(&arc_process, arc_process.pid_term());
+
+        assert!(info_list.contains(counter(&arc_process, "major_gcs", 0)));
+        assert!(info_list.contains(pair(
+            &arc_process,
+            "last_gc_time",
+            atom_unchecked("undefined")
+        )));
+    });
+}
+
+#[test]
+fn with_collection_returns_counters() {
+    with_process_arc(|parent_arc_process| {
+        let collected_arc_process = process::test(&parent_arc_process);
+
+        assert!(process::garbage_collect(&collected_arc_process, 0, &mut []).is_ok());
+        collected_arc_process.set_flags(ProcessFlags::NeedFullSweep);
+        assert!(process::garbage_collect(&collected_arc_process, 0, &mut []).is_ok());
+
+        let info_list = info_list(&parent_arc_process, collected_arc_process.pid_term());
+
+        assert!(info_list.contains(counter(&parent_arc_process, "total_minor_gcs", 1)));
+        assert!(info_list.contains(counter(&parent_arc_process, "major_gcs", 1)));
+        assert!(!info_list.contains(pair(
+            &parent_arc_process,
+            "last_gc_time",
+            atom_unchecked("undefined")
+        )));
+    });
+}
+
+fn info_list(process: &Process, pid: Term) -> Boxed<Cons> {
+    let info = native(process, pid, item()).unwrap();
+    let info_tuple: Boxed<Tuple> = info.try_into().unwrap();
+
+    assert_eq!(info_tuple[0], item());
+
+    info_tuple[1].try_into().unwrap()
+}
+
+fn item() -> Term {
+    atom_unchecked("garbage_collection_info")
+}
+
+fn counter(process: &Process, key: &str, count: usize) -> Term {
+    pair(process, key, process.integer(count).unwrap())
+}
+
+fn pair(process: &Process, key: &str, value: Term) -> Term {
+    process
+        .tuple_from_slice(&[atom_unchecked(key), value])
+        .unwrap()
+}
@@ -0,0 +1,74 @@
+use super::*;
+
+use crate::process;
+
+#[test]
+fn with_empty_list_returns_empty_list() {
+    with_process_arc(|arc_process| {
+        assert_eq!(
+            native(&arc_process, arc_process.pid_term(), Term::NIL),
+            Ok(Term::NIL)
+        );
+    });
+}
+
+#[test]
+fn with_improper_list_errors_badarg() {
+    with_process_arc(|arc_process| {
+        let item_list = arc_process
+            .improper_list_from_slice(&[atom_unchecked("trap_exit")], atom_unchecked("status"))
+            .unwrap();
+
+        assert_eq!(
+            native(&arc_process, arc_process.pid_term(), item_list),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_unsupported_item_in_list_errors_badarg() {
+    with_process_arc(|arc_process| {
+        let item_list = arc_process
+            .list_from_slice(&[atom_unchecked("trap_exit"), atom_unchecked("unsupported")])
+            .unwrap();
+
+        assert_eq!(
+            native(&arc_process, arc_process.pid_term(), item_list),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_items_returns_list_of_item_value_tuples_in_order() {
+    with_process_arc(|parent_arc_process| {
+        let arc_process = process::test(&parent_arc_process);
+        let trap_exit = atom_unchecked("trap_exit");
+        let registered_name = atom_unchecked("registered_name");
+        let message_queue_len = atom_unchecked("message_queue_len");
+        let item_list = parent_arc_process
+            .list_from_slice(&[trap_exit, registered_name, message_queue_len])
+            .unwrap();
+
+        assert_eq!(
+            native(&parent_arc_process, arc_process.pid_term(), item_list),
+            Ok(parent_arc_process
+                .list_from_slice(&[
+                    parent_arc_process
+                        .tuple_from_slice(&[trap_exit, false.into()])
+                        .unwrap(),
+                    parent_arc_process
+                        .tuple_from_slice(&[registered_name, Term::NIL])
+                        .unwrap(),
+                    parent_arc_process
+                        .tuple_from_slice(&[
+                            message_queue_len,
+                            parent_arc_process.integer(0).unwrap()
+                        ])
+                        .unwrap()
+                ])
+                .unwrap())
+        );
+    });
+}
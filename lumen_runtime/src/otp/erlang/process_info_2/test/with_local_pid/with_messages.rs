@@ -0,0 +1,42 @@
+use super::*;
+
+use crate::process;
+
+#[test]
+fn with_messages_returns_messages_in_order() {
+    with_process_arc(|parent_arc_process| {
+        let arc_process = process::test(&parent_arc_process);
+        let first = arc_process.integer(1).unwrap();
+        let second = arc_process.list_from_slice(&[first]).unwrap();
+
+        arc_process.send_from_self(first);
+        arc_process.send_from_self(second);
+
+        let messages = atom_unchecked("messages");
+
+        assert_eq!(
+            native(&parent_arc_process, arc_process.pid_term(), messages),
+            Ok(parent_arc_process
+                .tuple_from_slice(&[
+                    messages,
+                    parent_arc_process
+                        .list_from_slice(&[first, second])
+                        .unwrap()
+                ])
+                .unwrap())
+        );
+
+        let message_queue_len = atom_unchecked("message_queue_len");
+
+        assert_eq!(
+            native(
+                &parent_arc_process,
+                arc_process.pid_term(),
+                message_queue_len
+            ),
+            Ok(parent_arc_process
+                .tuple_from_slice(&[message_queue_len, parent_arc_process.integer(2).unwrap()])
+                .unwrap())
+        );
+    });
+}
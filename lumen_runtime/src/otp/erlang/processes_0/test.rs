@@ -0,0 +1,23 @@
+// because registry is global and tests are concurrent, there is no way to test for the exact list
+// of processes
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::term::{Boxed, Cons};
+
+use crate::otp::erlang::processes_0::native;
+use crate::process;
+use crate::scheduler::with_process;
+
+#[test]
+fn includes_self_and_spawned_process() {
+    with_process(|process| {
+        let child_arc_process = process::test(process);
+
+        let processes = native(process).unwrap();
+        let processes_cons: Boxed<Cons> = processes.try_into().unwrap();
+
+        assert!(processes_cons.contains(process.pid_term()));
+        assert!(processes_cons.contains(child_arc_process.pid_term()));
+    });
+}
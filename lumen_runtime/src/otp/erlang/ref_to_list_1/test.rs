@@ -0,0 +1,71 @@
+use proptest::prop_assert_eq;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+
+use crate::otp::erlang::list_to_ref_1;
+use crate::otp::erlang::ref_to_list_1::native;
+use crate::scheduler::{with_process, with_process_arc};
+use crate::test::strategy;
+
+#[test]
+fn without_reference_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &strategy::term::is_not_reference(arc_process.clone()),
+                |reference| {
+                    prop_assert_eq!(native(&arc_process, reference), Err(badarg!().into()));
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_reference_returns_list_that_list_to_ref_converts_back() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(
+                &strategy::term::is_reference(arc_process.clone()),
+                |reference| {
+                    let list = native(&arc_process, reference).unwrap();
+
+                    prop_assert_eq!(list_to_ref_1::native(&arc_process, list), Ok(reference));
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_local_reference_returns_node_zero() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                process.reference_from_scheduler(1.into(), 2).unwrap()
+            ),
+            Ok(process.charlist_from_str("#Ref<0.1.2>").unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_external_reference_returns_node_id() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                process
+                    .external_reference_with_node_id(1, 2.into(), 3)
+                    .unwrap()
+            ),
+            Ok(process.charlist_from_str("#Ref<1.2.3>").unwrap())
+        );
+    });
+}
@@ -1,5 +1,7 @@
 mod with_empty_list_options;
+mod with_fullsweep_after_in_options_list;
 mod with_link_in_options_list;
+mod with_max_heap_size_in_options_list;
 
 use std::convert::TryInto;
 use std::sync::Arc;
@@ -0,0 +1,46 @@
+use super::*;
+
+use crate::system;
+
+#[test]
+fn with_non_negative_integer_sets_fullsweep_after() {
+    let parent_arc_process = process::test_init();
+    let value = parent_arc_process.integer(10).unwrap();
+    let options = parent_arc_process
+        .list_from_slice(&[parent_arc_process
+            .tuple_from_slice(&[atom_unchecked("fullsweep_after"), value])
+            .unwrap()])
+        .unwrap();
+
+    assert_eq!(child_fullsweep_after(&parent_arc_process, options), 10);
+}
+
+#[test]
+fn without_fullsweep_after_uses_system_fullsweep_after() {
+    let parent_arc_process = process::test_init();
+
+    assert_eq!(
+        child_fullsweep_after(&parent_arc_process, Term::NIL),
+        system::flag::fullsweep_after()
+    );
+}
+
+fn child_fullsweep_after(parent_process: &Process, options: Term) -> usize {
+    let child_pid = native(
+        parent_process,
+        atom_unchecked("erlang"),
+        atom_unchecked("+"),
+        parent_process
+            .list_from_slice(&[
+                parent_process.integer(0).unwrap(),
+                parent_process.integer(1).unwrap(),
+            ])
+            .unwrap(),
+        options,
+    )
+    .unwrap();
+    let child_pid_pid: Pid = child_pid.try_into().unwrap();
+    let child_arc_process = pid_to_process(&child_pid_pid).unwrap();
+
+    child_arc_process.fullsweep_after()
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+use liblumen_alloc::erts::process::MaxHeapSize;
+
+#[test]
+fn with_integer_sets_size_with_default_kill_and_error_logger() {
+    let parent_arc_process = process::test_init();
+    let value = parent_arc_process.integer(1_000_000).unwrap();
+
+    assert_eq!(
+        child_max_heap_size(&parent_arc_process, value),
+        MaxHeapSize {
+            size: 1_000_000,
+            kill: true,
+            error_logger: true
+        }
+    );
+}
+
+#[test]
+fn with_map_sets_size_kill_and_error_logger() {
+    let parent_arc_process = process::test_init();
+    let size = parent_arc_process.integer(1_000_000).unwrap();
+    let value = parent_arc_process
+        .map_from_slice(&[
+            (atom_unchecked("error_logger"), false.into()),
+            (atom_unchecked("kill"), false.into()),
+            (atom_unchecked("size"), size),
+        ])
+        .unwrap();
+
+    assert_eq!(
+        child_max_heap_size(&parent_arc_process, value),
+        MaxHeapSize {
+            size: 1_000_000,
+            kill: false,
+            error_logger: false
+        }
+    );
+}
+
+#[test]
+fn with_invalid_value_errors_badarg() {
+    let parent_arc_process = process::test_init();
+
+    for value in &[
+        parent_arc_process.integer(-1).unwrap(),
+        atom_unchecked("infinity"),
+        parent_arc_process
+            .map_from_slice(&[(atom_unchecked("kill"), atom_unchecked("yes"))])
+            .unwrap(),
+    ] {
+        let options = options(&parent_arc_process, *value);
+
+        assert_eq!(
+            native(
+                &parent_arc_process,
+                module(),
+                function(),
+                arguments(&parent_arc_process),
+                options
+            ),
+            Err(badarg!().into())
+        );
+    }
+}
+
+fn arguments(process: &Process) -> Term {
+    process
+        .list_from_slice(&[process.integer(0).unwrap(), process.integer(1).unwrap()])
+        .unwrap()
+}
+
+fn child_max_heap_size(parent_process: &Process, value: Term) -> MaxHeapSize {
+    let options = options(parent_process, value);
+    let child_pid = native(
+        parent_process,
+        module(),
+        function(),
+        arguments(parent_process),
+        options,
+    )
+    .unwrap();
+    let child_pid_pid: Pid = child_pid.try_into().unwrap();
+    let child_arc_process = pid_to_process(&child_pid_pid).unwrap();
+
+    child_arc_process.max_heap_size()
+}
+
+fn function() -> Term {
+    atom_unchecked("+")
+}
+
+fn module() -> Term {
+    atom_unchecked("erlang")
+}
+
+fn options(process: &Process, value: Term) -> Term {
+    process
+        .list_from_slice(&[process
+            .tuple_from_slice(&[atom_unchecked("max_heap_size"), value])
+            .unwrap()])
+        .unwrap()
+}
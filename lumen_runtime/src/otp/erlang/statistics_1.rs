@@ -0,0 +1,166 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::exception::system::Alloc;
+use liblumen_alloc::erts::process::{Priority, Process};
+use liblumen_alloc::erts::term::{Atom, Term};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::scheduler::Scheduler;
+use crate::time::monotonic;
+
+#[native_implemented_function(statistics/1)]
+pub fn native(process: &Process, item: Term) -> exception::Result {
+    let item_atom: Atom = item.try_into()?;
+    let scheduler_vec = Scheduler::all();
+
+    match item_atom.name() {
+        "active_tasks" => {
+            let mut active_task_vec = Vec::with_capacity(scheduler_vec.len());
+
+            for arc_scheduler in &scheduler_vec {
+                let running = if arc_scheduler.statistics.is_running() {
+                    1
+                } else {
+                    0
+                };
+
+                active_task_vec.push(process.integer(runnable_len(arc_scheduler) + running)?);
+            }
+
+            process
+                .list_from_slice(&active_task_vec)
+                .map_err(|error| error.into())
+        }
+        "context_switches" => {
+            let context_switches = scheduler_vec
+                .iter()
+                .map(|arc_scheduler| arc_scheduler.statistics.context_switches())
+                .sum::<u64>();
+
+            process
+                .tuple_from_slice(&[process.integer(context_switches)?, process.integer(0)?])
+                .map_err(|error| error.into())
+        }
+        "exact_reductions" => reductions(process, &scheduler_vec, &LAST_EXACT_REDUCTIONS),
+        "garbage_collection" => {
+            let garbage_collections = scheduler_vec
+                .iter()
+                .map(|arc_scheduler| arc_scheduler.statistics.garbage_collections())
+                .sum::<u64>();
+            let words_reclaimed = scheduler_vec
+                .iter()
+                .map(|arc_scheduler| arc_scheduler.statistics.words_reclaimed())
+                .sum::<u64>();
+
+            process
+                .tuple_from_slice(&[
+                    process.integer(garbage_collections)?,
+                    process.integer(words_reclaimed)?,
+                    process.integer(0)?,
+                ])
+                .map_err(|error| error.into())
+        }
+        "reductions" => reductions(process, &scheduler_vec, &LAST_REDUCTIONS),
+        "run_queue" => {
+            let run_queue = scheduler_vec.iter().map(runnable_len).sum::<usize>();
+
+            process.integer(run_queue).map_err(|error| error.into())
+        }
+        "run_queue_lengths" => {
+            let mut run_queue_length_vec = Vec::with_capacity(scheduler_vec.len());
+
+            for arc_scheduler in &scheduler_vec {
+                run_queue_length_vec.push(process.integer(runnable_len(arc_scheduler))?);
+            }
+
+            process
+                .list_from_slice(&run_queue_length_vec)
+                .map_err(|error| error.into())
+        }
+        "runtime" => {
+            // Lumen does not measure CPU time, so the time schedulers spent running processes is
+            // used instead
+            let runtime = scheduler_vec
+                .iter()
+                .map(|arc_scheduler| arc_scheduler.statistics.wall_time().0)
+                .sum::<u64>();
+
+            total_and_since_last_call(process, runtime, &LAST_RUNTIME).map_err(|error| error.into())
+        }
+        "scheduler_wall_time" => {
+            let mut scheduler_wall_time_vec = Vec::with_capacity(scheduler_vec.len());
+
+            for arc_scheduler in &scheduler_vec {
+                let id: u32 = arc_scheduler.id.into();
+                let (active, total) = arc_scheduler.statistics.wall_time();
+
+                scheduler_wall_time_vec.push(process.tuple_from_slice(&[
+                    process.integer(id as usize)?,
+                    process.integer(active)?,
+                    process.integer(total)?,
+                ])?);
+            }
+
+            process
+                .list_from_slice(&scheduler_wall_time_vec)
+                .map_err(|error| error.into())
+        }
+        "wall_clock" => {
+            total_and_since_last_call(process, monotonic::time_in_milliseconds(), &LAST_WALL_CLOCK)
+                .map_err(|error| error.into())
+        }
+        _ => Err(badarg!().into()),
+    }
+}
+
+// Private
+
+fn reductions(
+    process: &Process,
+    scheduler_vec: &[Arc<Scheduler>],
+    last: &AtomicU64,
+) -> exception::Result {
+    let reductions = scheduler_vec
+        .iter()
+        .map(|arc_scheduler| arc_scheduler.statistics.reductions())
+        .sum::<u64>();
+
+    total_and_since_last_call(process, reductions, last).map_err(|error| error.into())
+}
+
+/// The processes that are ready to run on `scheduler`, not counting the one running
+fn runnable_len(scheduler: &Arc<Scheduler>) -> usize {
+    // `Priority::Low` shares the `Priority::Normal` run queue, so it is not counted separately
+    [Priority::Normal, Priority::High, Priority::Max]
+        .iter()
+        .map(|priority| scheduler.run_queue_len(*priority))
+        .sum()
+}
+
+/// `{Total, SinceLastCall}`, where `SinceLastCall` is relative to the last call for the same item
+fn total_and_since_last_call(
+    process: &Process,
+    total: u64,
+    last: &AtomicU64,
+) -> Result<Term, Alloc> {
+    let since_last_call = total.saturating_sub(last.swap(total, Ordering::SeqCst));
+
+    process.tuple_from_slice(&[process.integer(total)?, process.integer(since_last_call)?])
+}
+
+static LAST_EXACT_REDUCTIONS: AtomicU64 = AtomicU64::new(0);
+static LAST_REDUCTIONS: AtomicU64 = AtomicU64::new(0);
+static LAST_RUNTIME: AtomicU64 = AtomicU64::new(0);
+static LAST_WALL_CLOCK: AtomicU64 = AtomicU64::new(0);
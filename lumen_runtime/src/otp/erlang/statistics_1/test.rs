@@ -0,0 +1,137 @@
+// because schedulers are per-thread and tests are concurrent, there is no way to test for exact
+// totals across all schedulers
+
+use std::convert::TryInto;
+
+use proptest::prop_assert_eq;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::process::{Process, ProcessFlags};
+use liblumen_alloc::erts::term::{atom_unchecked, Boxed, Cons, SmallInteger, Term, Tuple};
+
+use crate::otp::erlang::statistics_1::native;
+use crate::process;
+use crate::scheduler::{with_process, with_process_arc, Scheduler};
+use crate::test::strategy;
+
+#[test]
+fn without_atom_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(&strategy::term::is_not_atom(arc_process.clone()), |item| {
+                prop_assert_eq!(native(&arc_process, item), Err(badarg!().into()));
+
+                Ok(())
+            })
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_unknown_item_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, atom_unchecked("unknown")),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_total_and_since_last_call_item_returns_since_last_call_not_exceeding_total() {
+    with_process(|process| {
+        for name in &["exact_reductions", "reductions", "runtime", "wall_clock"] {
+            let tuple = item_tuple(process, name);
+
+            assert_eq!(tuple.len(), 2);
+
+            let total = to_u64(tuple[0]);
+            let since_last_call = to_u64(tuple[1]);
+
+            assert!(since_last_call <= total);
+        }
+    });
+}
+
+#[test]
+fn with_context_switches_returns_total_and_zero() {
+    with_process(|process| {
+        let tuple = item_tuple(process, "context_switches");
+
+        assert_eq!(tuple.len(), 2);
+        assert!(tuple[0].is_integer());
+        assert_eq!(tuple[1], process.integer(0).unwrap());
+    });
+}
+
+#[test]
+fn with_garbage_collection_counts_collections_on_all_schedulers() {
+    with_process(|process| {
+        let collected_arc_process = process::test(process);
+        collected_arc_process.set_flags(ProcessFlags::NeedFullSweep);
+
+        assert!(process::garbage_collect(&collected_arc_process, 0, &mut []).is_ok());
+
+        let tuple = item_tuple(process, "garbage_collection");
+
+        assert_eq!(tuple.len(), 3);
+
+        let garbage_collections = to_u64(tuple[0]);
+
+        assert!(Scheduler::current().statistics.garbage_collections() <= garbage_collections);
+        assert!(1 <= garbage_collections);
+        assert_eq!(tuple[2], process.integer(0).unwrap());
+    });
+}
+
+#[test]
+fn with_run_queue_returns_integer() {
+    with_process(|process| {
+        assert!(native(process, atom_unchecked("run_queue"))
+            .unwrap()
+            .is_integer());
+    });
+}
+
+#[test]
+fn with_per_scheduler_item_returns_list_of_integers() {
+    with_process(|process| {
+        for name in &["active_tasks", "run_queue_lengths"] {
+            let list = native(process, atom_unchecked(name)).unwrap();
+            let cons: Boxed<Cons> = list.try_into().unwrap();
+
+            for result in cons.into_iter() {
+                assert!(result.unwrap().is_integer());
+            }
+        }
+    });
+}
+
+#[test]
+fn with_scheduler_wall_time_includes_current_scheduler() {
+    with_process(|process| {
+        let id: u32 = Scheduler::current().id.into();
+        let id_term = process.integer(id as usize).unwrap();
+        let list = native(process, atom_unchecked("scheduler_wall_time")).unwrap();
+        let cons: Boxed<Cons> = list.try_into().unwrap();
+
+        assert!(cons.into_iter().any(|result| {
+            let tuple: Boxed<Tuple> = result.unwrap().try_into().unwrap();
+
+            tuple.len() == 3 && tuple[0] == id_term
+        }));
+    });
+}
+
+fn item_tuple(process: &Process, name: &str) -> Boxed<Tuple> {
+    let term: Term = native(process, atom_unchecked(name)).unwrap();
+
+    term.try_into().unwrap()
+}
+
+fn to_u64(term: Term) -> u64 {
+    let small_integer: SmallInteger = term.try_into().unwrap();
+
+    small_integer.try_into().unwrap()
+}
@@ -0,0 +1,51 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{atom_unchecked, Atom, Term};
+use liblumen_alloc::{badarg, error};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::system;
+
+#[native_implemented_function(system_flag/2)]
+pub fn native(process: &Process, flag: Term, value: Term) -> exception::Result {
+    let flag_atom: Atom = flag.try_into()?;
+
+    let old_value = match flag_atom.name() {
+        "backtrace_depth" => {
+            let backtrace_depth: usize = value.try_into()?;
+
+            system::flag::set_backtrace_depth(backtrace_depth)
+        }
+        "fullsweep_after" => {
+            let fullsweep_after: usize = value.try_into()?;
+
+            system::flag::set_fullsweep_after(fullsweep_after)
+        }
+        "schedulers_online" => {
+            let schedulers_online: usize = value.try_into()?;
+            let schedulers = system::flag::schedulers();
+
+            if schedulers_online < 1 || schedulers < schedulers_online {
+                return Err(badarg!().into());
+            } else if schedulers_online < schedulers {
+                // See `system::flag::schedulers_online`
+                return Err(error!(atom_unchecked("notsup")).into());
+            }
+
+            system::flag::schedulers_online()
+        }
+        _ => return Err(badarg!().into()),
+    };
+
+    process.integer(old_value).map_err(|error| error.into())
+}
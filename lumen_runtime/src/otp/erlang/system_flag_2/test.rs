@@ -0,0 +1,116 @@
+// The flags are system-wide and tests are concurrent, so flags are only ever set to their current
+// values
+
+use proptest::prop_assert_eq;
+use proptest::test_runner::{Config, TestRunner};
+
+use std::thread;
+
+use liblumen_alloc::erts::term::atom_unchecked;
+use liblumen_alloc::{badarg, error};
+
+use crate::otp::erlang::system_flag_2::native;
+use crate::scheduler::{with_process, with_process_arc, Scheduler};
+use crate::system;
+use crate::test::strategy;
+
+#[test]
+fn without_atom_flag_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(&strategy::term::is_not_atom(arc_process.clone()), |flag| {
+                let value = arc_process.integer(1).unwrap();
+
+                prop_assert_eq!(native(&arc_process, flag, value), Err(badarg!().into()));
+
+                Ok(())
+            })
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_unknown_flag_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                atom_unchecked("unknown"),
+                process.integer(1).unwrap()
+            ),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_flag_without_non_negative_integer_errors_badarg() {
+    with_process(|process| {
+        for name in &["backtrace_depth", "fullsweep_after", "schedulers_online"] {
+            for value in &[process.integer(-1).unwrap(), atom_unchecked("infinity")] {
+                assert_eq!(
+                    native(process, atom_unchecked(name), *value),
+                    Err(badarg!().into())
+                );
+            }
+        }
+    });
+}
+
+#[test]
+fn with_backtrace_depth_returns_old_backtrace_depth() {
+    with_process(|process| {
+        let backtrace_depth = process.integer(system::flag::backtrace_depth()).unwrap();
+
+        assert_eq!(
+            native(process, atom_unchecked("backtrace_depth"), backtrace_depth),
+            Ok(backtrace_depth)
+        );
+    });
+}
+
+#[test]
+fn with_fullsweep_after_returns_old_fullsweep_after() {
+    with_process(|process| {
+        let fullsweep_after = process.integer(system::flag::fullsweep_after()).unwrap();
+
+        assert_eq!(
+            native(process, atom_unchecked("fullsweep_after"), fullsweep_after),
+            Ok(fullsweep_after)
+        );
+    });
+}
+
+#[test]
+fn with_schedulers_online_outside_schedulers_errors_badarg() {
+    with_process(|process| {
+        // schedulers are created by test threads, so `schedulers() + 1` could become valid
+        for schedulers_online in &[0, 1 << 20] {
+            assert_eq!(
+                native(
+                    process,
+                    atom_unchecked("schedulers_online"),
+                    process.integer(*schedulers_online).unwrap()
+                ),
+                Err(badarg!().into())
+            );
+        }
+    });
+}
+
+#[test]
+fn with_schedulers_online_fewer_than_schedulers_errors_notsup() {
+    with_process(|process| {
+        // Keeps another scheduler, so that there are at least 2
+        let _other_scheduler = thread::spawn(Scheduler::current).join().unwrap();
+
+        assert_eq!(
+            native(
+                process,
+                atom_unchecked("schedulers_online"),
+                process.integer(1).unwrap()
+            ),
+            Err(error!(atom_unchecked("notsup")).into())
+        );
+    });
+}
@@ -0,0 +1,49 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+use std::env::consts::{ARCH, OS};
+use std::mem;
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{Atom, Pid, Term, MAX_ATOMS};
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::registry;
+use crate::system::flag;
+use crate::system::host::cpus;
+
+/// The OTP release whose external term format and BIF semantics Lumen follows
+pub const OTP_RELEASE: &str = "22";
+
+#[native_implemented_function(system_info/1)]
+pub fn native(process: &Process, item: Term) -> exception::Result {
+    let item_atom: Atom = item.try_into()?;
+
+    let result = match item_atom.name() {
+        "atom_count" => process.integer(Atom::count()),
+        "atom_limit" => process.integer(MAX_ATOMS),
+        "logical_processors" => process.integer(cpus::num_logical()),
+        "machine" => process.charlist_from_str("Lumen"),
+        "otp_release" => process.charlist_from_str(OTP_RELEASE),
+        "process_count" => process.integer(registry::process_count()),
+        "process_limit" => process.integer((Pid::NUMBER_MAX + 1) * (Pid::SERIAL_MAX + 1)),
+        "schedulers" => process.integer(flag::schedulers()),
+        "schedulers_online" => process.integer(flag::schedulers_online()),
+        "system_architecture" => process.charlist_from_str(&format!("{}-{}", ARCH, OS)),
+        // Lumen has no async thread pool
+        "thread_pool_size" => process.integer(0),
+        "version" => process.charlist_from_str(env!("CARGO_PKG_VERSION")),
+        "wordsize" => process.integer(mem::size_of::<Term>()),
+        _ => return Err(badarg!().into()),
+    };
+
+    result.map_err(|error| error.into())
+}
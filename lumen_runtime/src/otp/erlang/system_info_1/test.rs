@@ -0,0 +1,127 @@
+use std::convert::TryInto;
+use std::mem;
+
+use proptest::prop_assert_eq;
+use proptest::test_runner::{Config, TestRunner};
+
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{atom_unchecked, SmallInteger, Term};
+
+use crate::otp::erlang::system_info_1::native;
+use crate::scheduler::{with_process, with_process_arc};
+use crate::test::strategy;
+
+#[test]
+fn without_atom_errors_badarg() {
+    with_process_arc(|arc_process| {
+        TestRunner::new(Config::with_source_file(file!()))
+            .run(&strategy::term::is_not_atom(arc_process.clone()), |item| {
+                prop_assert_eq!(native(&arc_process, item), Err(badarg!().into()));
+
+                Ok(())
+            })
+            .unwrap();
+    });
+}
+
+#[test]
+fn with_unknown_item_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, atom_unchecked("unknown")),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_atom_count_and_atom_limit_returns_integers() {
+    with_process(|process| {
+        assert!(0 < info_usize(process, "atom_count"));
+        assert!(native(process, atom_unchecked("atom_limit"))
+            .unwrap()
+            .is_integer());
+    });
+}
+
+#[test]
+fn with_process_count_returns_at_least_one_and_less_than_process_limit() {
+    with_process(|process| {
+        let process_count = info_usize(process, "process_count");
+
+        assert!(1 <= process_count);
+        assert!(process_count < info_usize(process, "process_limit"));
+    });
+}
+
+#[test]
+fn with_schedulers_online_returns_at_most_schedulers() {
+    with_process(|process| {
+        let schedulers_online = info_usize(process, "schedulers_online");
+
+        assert!(1 <= schedulers_online);
+        assert!(schedulers_online <= info_usize(process, "schedulers"));
+    });
+}
+
+#[test]
+fn with_logical_processors_returns_at_least_one() {
+    with_process(|process| {
+        assert!(1 <= info_usize(process, "logical_processors"));
+    });
+}
+
+#[test]
+fn with_thread_pool_size_returns_zero() {
+    with_process(|process| {
+        assert_eq!(info_usize(process, "thread_pool_size"), 0);
+    });
+}
+
+#[test]
+fn with_wordsize_returns_bytes_per_term() {
+    with_process(|process| {
+        assert_eq!(info_usize(process, "wordsize"), mem::size_of::<Term>());
+    });
+}
+
+#[test]
+fn with_machine_returns_lumen_charlist() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, atom_unchecked("machine")),
+            Ok(process.charlist_from_str("Lumen").unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_otp_release_returns_charlist() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, atom_unchecked("otp_release")),
+            Ok(process.charlist_from_str("22").unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_charlist_item_returns_non_empty_list() {
+    with_process(|process| {
+        for name in &["system_architecture", "version"] {
+            assert!(native(process, atom_unchecked(name))
+                .unwrap()
+                .is_non_empty_list());
+        }
+    });
+}
+
+fn info_usize(process: &Process, name: &str) -> usize {
+    let small_integer: SmallInteger = native(process, atom_unchecked(name))
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    small_integer.try_into().unwrap()
+}
@@ -1,7 +1,12 @@
+use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::convert::{TryFrom, TryInto};
+use std::io::Write;
+use std::iter;
 use std::mem;
 
+use libflate::{lz77, zlib};
+
 use num_bigint::{BigInt, Sign};
 
 use liblumen_alloc::badarg;
@@ -11,34 +16,200 @@ use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::binary::aligned_binary::AlignedBinary;
 use liblumen_alloc::erts::term::binary::maybe_aligned_maybe_binary::MaybeAlignedMaybeBinary;
 use liblumen_alloc::erts::term::{
-    Atom, Bitstring, Cons, ImproperList, IterableBitstring, MaybePartialByte, Term, TypeError,
-    TypedTerm,
+    Atom, Bitstring, Closure, Cons, ImproperList, IterableBitstring, MaybePartialByte, Reference,
+    SubBinary, Term, TypeError, TypedTerm,
 };
 
 use crate::node;
 use hashbrown::HashMap;
 
-pub fn term_to_binary(process: &Process, term: Term, _options: Options) -> exception::Result {
-    let mut stack = VecDeque::new();
-    stack.push_front(term);
+pub fn term_to_binary(process: &Process, term: Term, options: Options) -> exception::Result {
+    let byte_vec = term_to_byte_vec(term, &options)?;
+
+    process
+        .binary_from_bytes(&byte_vec)
+        .map_err(|alloc| alloc.into())
+}
+
+/// The maximum byte size of `term` encoded with `options`, which is exact unless the term would be
+/// compressed, without allocating the encoded bytes.
+pub fn external_size(process: &Process, term: Term, options: Options) -> exception::Result {
+    let mut counter = Counter::default();
+    counter.push(VERSION_NUMBER);
+    encode(&mut counter, term, &options)?;
+
+    process.integer(counter.len).map_err(|alloc| alloc.into())
+}
+
+/// The bytes of `term_to_binary` as a list of binaries, where the bytes of `ProcBin`s are the
+/// `ProcBin`s themselves instead of copies.
+pub fn term_to_iovec(process: &Process, term: Term, options: Options) -> exception::Result {
+    // the compressed bytes cannot refer to the uncompressed `ProcBin`s
+    if 0 < options.compression.0 {
+        let binary = term_to_binary(process, term, options)?;
+
+        process
+            .list_from_slice(&[binary])
+            .map_err(|alloc| alloc.into())
+    } else {
+        let mut io_vec = IoVec::default();
+        io_vec.push(VERSION_NUMBER);
+        encode(&mut io_vec, term, &options)?;
+
+        io_vec.into_list(process)
+    }
+}
+
+// Private
+
+/// Where the encoded bytes of a term go, so that `external_size` can count them without
+/// allocating them and `term_to_iovec` can refer to `ProcBin`s instead of copying them.
+trait Sink {
+    fn push(&mut self, byte: u8);
+
+    fn extend_from_slice(&mut self, bytes: &[u8]);
+
+    fn extend_from_iter<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for byte in iter {
+            self.push(byte);
+        }
+    }
+
+    /// `bytes` are all the bytes of the `proc_bin` term.
+    fn extend_from_proc_bin(&mut self, _proc_bin: Term, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+impl Sink for Vec<u8> {
+    fn push(&mut self, byte: u8) {
+        Vec::push(self, byte)
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        Vec::extend_from_slice(self, bytes)
+    }
+}
+
+#[derive(Default)]
+struct Counter {
+    len: usize,
+}
+
+impl Sink for Counter {
+    fn push(&mut self, _byte: u8) {
+        self.len += 1;
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.len += bytes.len();
+    }
+
+    fn extend_from_iter<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        self.len += iter.into_iter().count();
+    }
+}
+
+/// The bytes since the last `ProcBin` are in `byte_vec`.
+#[derive(Default)]
+struct IoVec {
+    binary_vec: Vec<IoVecBinary>,
+    byte_vec: Vec<u8>,
+}
+
+impl IoVec {
+    fn into_list(mut self, process: &Process) -> exception::Result {
+        self.flush();
+
+        let mut binary_term_vec = Vec::with_capacity(self.binary_vec.len());
+
+        for binary in self.binary_vec {
+            let binary_term = match binary {
+                IoVecBinary::Bytes(byte_vec) => process.binary_from_bytes(&byte_vec)?,
+                IoVecBinary::ProcBin(proc_bin) => proc_bin,
+            };
+
+            binary_term_vec.push(binary_term);
+        }
+
+        process
+            .list_from_slice(&binary_term_vec)
+            .map_err(|alloc| alloc.into())
+    }
+
+    fn flush(&mut self) {
+        if !self.byte_vec.is_empty() {
+            let byte_vec = mem::replace(&mut self.byte_vec, Vec::new());
+            self.binary_vec.push(IoVecBinary::Bytes(byte_vec));
+        }
+    }
+}
+
+impl Sink for IoVec {
+    fn push(&mut self, byte: u8) {
+        self.byte_vec.push(byte);
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.byte_vec.extend_from_slice(bytes);
+    }
+
+    fn extend_from_proc_bin(&mut self, proc_bin: Term, _bytes: &[u8]) {
+        self.flush();
+        self.binary_vec.push(IoVecBinary::ProcBin(proc_bin));
+    }
+}
+
+enum IoVecBinary {
+    Bytes(Vec<u8>),
+    ProcBin(Term),
+}
+
+fn term_to_byte_vec(term: Term, options: &Options) -> Result<Vec<u8>, runtime::Exception> {
+    let mut uncompressed_byte_vec: Vec<u8> = Vec::new();
+    encode(&mut uncompressed_byte_vec, term, options)?;
 
     let mut byte_vec: Vec<u8> = vec![VERSION_NUMBER];
 
+    match compress(&uncompressed_byte_vec, &options.compression) {
+        // > If the compressed term is larger than the uncompressed term, the uncompressed term is
+        // > returned instead.
+        Some(compressed_byte_vec)
+            if (COMPRESSED_HEADER_LEN + compressed_byte_vec.len())
+                < uncompressed_byte_vec.len() =>
+        {
+            byte_vec.push(COMPRESSED);
+            append_usize_as_u32(&mut byte_vec, uncompressed_byte_vec.len());
+            byte_vec.extend_from_slice(&compressed_byte_vec);
+        }
+        _ => byte_vec.extend_from_slice(&uncompressed_byte_vec),
+    }
+
+    Ok(byte_vec)
+}
+
+/// `badarg` if `term` contains a pid, port or reference of a node that was never registered, such
+/// as the pid from `list_to_pid("<7.1.2>")`, or a resource.
+fn encode<S: Sink>(sink: &mut S, term: Term, options: &Options) -> Result<(), runtime::Exception> {
+    let minor_version = &options.minor_version;
+    let mut stack = VecDeque::new();
+    stack.push_front(term);
+
     while let Some(front_term) = stack.pop_front() {
         match front_term.to_typed_term().unwrap() {
             TypedTerm::Atom(atom) => {
-                byte_vec.extend_from_slice(&atom_to_byte_vec(atom));
+                append_atom(sink, atom, minor_version);
             }
             TypedTerm::List(cons) => {
                 match try_cons_to_string_ext_byte_vec(&cons) {
-                    Ok(mut string_ext_byte_vec) => byte_vec.append(&mut string_ext_byte_vec),
+                    Ok(string_ext_byte_vec) => sink.extend_from_slice(&string_ext_byte_vec),
                     Err(_) => {
-                        byte_vec.push(LIST_EXT);
+                        sink.push(LIST_EXT);
 
                         let (element_vec, tail) = cons_to_element_vec_tail(&cons);
 
                         let len_usize = element_vec.len();
-                        append_usize_as_u32(&mut byte_vec, len_usize);
+                        append_usize_as_u32(sink, len_usize);
 
                         stack.push_front(tail);
 
@@ -49,195 +220,154 @@ pub fn term_to_binary(process: &Process, term: Term, _options: Options) -> excep
                 };
             }
             TypedTerm::Nil => {
-                byte_vec.push(NIL_EXT);
+                sink.push(NIL_EXT);
             }
             TypedTerm::Pid(pid) => {
-                byte_vec.push(PID_EXT);
-                byte_vec.extend_from_slice(&atom_to_byte_vec(node::atom()));
-
-                let id = pid.number() as u32;
-                byte_vec.extend_from_slice(&id.to_be_bytes());
-
-                let serial = pid.serial() as u32;
-                byte_vec.extend_from_slice(&serial.to_be_bytes());
-
-                byte_vec.extend_from_slice(&CREATION.to_be_bytes());
+                append_pid(
+                    sink,
+                    node::LOCAL_ID,
+                    pid.number() as u32,
+                    pid.serial() as u32,
+                    minor_version,
+                )?;
+            }
+            TypedTerm::Port(port) => {
+                append_port(sink, node::LOCAL_ID, port.number(), minor_version)?;
             }
             TypedTerm::SmallInteger(small_integer) => {
-                let small_integer_isize: isize = small_integer.into();
-
-                if SMALL_INTEGER_EXT_MIN <= small_integer_isize
-                    && small_integer_isize <= SMALL_INTEGER_EXT_MAX
-                {
-                    let small_integer_u8: u8 = small_integer_isize as u8;
-
-                    byte_vec.push(SMALL_INTEGER_EXT);
-                    byte_vec.extend_from_slice(&small_integer_u8.to_be_bytes());
-                } else if INTEGER_EXT_MIN <= small_integer_isize
-                    && small_integer_isize <= INTEGER_EXT_MAX
-                {
-                    let small_integer_i32: i32 = small_integer_isize as i32;
-
-                    byte_vec.push(INTEGER_EXT);
-                    byte_vec.extend_from_slice(&small_integer_i32.to_be_bytes());
-                } else {
-                    let small_integer_i64 = small_integer_isize as i64;
-                    // convert to big int, so that the number of bytes is minimum instead of jumping
-                    // to 8 to hold i64.
-                    let small_integer_big_int: BigInt = small_integer_i64.into();
-
-                    append_big_int(&mut byte_vec, &small_integer_big_int);
-                }
+                append_isize(sink, small_integer.into());
             }
             TypedTerm::Boxed(boxed) => match boxed.to_typed_term().unwrap() {
+                TypedTerm::Closure(closure) => {
+                    append_closure(sink, &closure, options)?;
+                }
+                TypedTerm::ExternalPid(external_pid) => {
+                    let pid = external_pid.pid();
+
+                    append_pid(
+                        sink,
+                        external_pid.node().id(),
+                        pid.number() as u32,
+                        pid.serial() as u32,
+                        minor_version,
+                    )?;
+                }
+                TypedTerm::ExternalPort(external_port) => {
+                    append_port(
+                        sink,
+                        external_port.node().id(),
+                        external_port.port().number(),
+                        minor_version,
+                    )?;
+                }
+                TypedTerm::ExternalReference(external_reference) => {
+                    append_reference(
+                        sink,
+                        external_reference.node().id(),
+                        external_reference.reference(),
+                        minor_version,
+                    )?;
+                }
                 TypedTerm::BigInteger(big_integer) => {
                     let big_int: &BigInt = big_integer.as_ref().into();
 
-                    append_big_int(&mut byte_vec, big_int);
+                    append_big_int(sink, big_int);
                 }
                 TypedTerm::Float(float) => {
                     let float_f64: f64 = float.into();
 
-                    byte_vec.push(NEW_FLOAT_EXT);
-                    byte_vec.extend_from_slice(&float_f64.to_be_bytes());
+                    if minor_version.new_float_ext() {
+                        sink.push(NEW_FLOAT_EXT);
+                        sink.extend_from_slice(&float_f64.to_be_bytes());
+                    } else {
+                        sink.push(FLOAT_EXT);
+                        sink.extend_from_slice(&float_to_float_ext_bytes(float_f64));
+                    }
                 }
                 TypedTerm::HeapBinary(heap_bin) => {
-                    byte_vec.push(BINARY_EXT);
+                    sink.push(BINARY_EXT);
 
                     let len_usize = heap_bin.full_byte_len();
-                    append_usize_as_u32(&mut byte_vec, len_usize);
+                    append_usize_as_u32(sink, len_usize);
 
-                    byte_vec.extend_from_slice(heap_bin.as_bytes());
+                    sink.extend_from_slice(heap_bin.as_bytes());
                 }
                 TypedTerm::Map(map) => {
-                    byte_vec.push(MAP_EXT);
+                    sink.push(MAP_EXT);
 
                     let len_usize = map.len();
-                    append_usize_as_u32(&mut byte_vec, len_usize);
+                    append_usize_as_u32(sink, len_usize);
 
                     let hash_map: &HashMap<_, _> = map.as_ref();
+                    let mut entry_vec: Vec<(&Term, &Term)> = hash_map.iter().collect();
+
+                    if options.deterministic {
+                        entry_vec.sort_by(|(left_key, _), (right_key, _)| {
+                            map_key_cmp(**left_key, **right_key)
+                        });
+                    }
 
-                    for (key, value) in hash_map.iter() {
+                    for (key, value) in entry_vec.into_iter().rev() {
                         stack.push_front(*value);
                         stack.push_front(*key);
                     }
                 }
                 TypedTerm::MatchContext(match_context) => {
-                    if match_context.is_binary() {
-                        if match_context.is_aligned() {
-                            append_binary_bytes(&mut byte_vec, unsafe { match_context.as_bytes() });
-                        } else {
-                            unimplemented!()
-                        }
-                    } else {
-                        unimplemented!()
-                    }
+                    append_subbinary(sink, &match_context.unmatched_subbinary());
                 }
                 TypedTerm::ProcBin(proc_bin) => {
-                    byte_vec.push(BINARY_EXT);
+                    sink.push(BINARY_EXT);
 
                     let len_usize = proc_bin.full_byte_len();
-                    append_usize_as_u32(&mut byte_vec, len_usize);
+                    append_usize_as_u32(sink, len_usize);
 
-                    byte_vec.extend_from_slice(proc_bin.as_bytes());
+                    sink.extend_from_proc_bin(front_term, proc_bin.as_bytes());
                 }
                 TypedTerm::Reference(reference) => {
-                    let scheduler_id_u32: u32 = reference.scheduler_id().into();
-                    let number: u64 = reference.number().into();
-
-                    byte_vec.push(NEWER_REFERENCE_EXT);
-
-                    let u32_byte_len = mem::size_of::<u32>();
-                    let len_usize = (mem::size_of::<u32>() + mem::size_of::<u64>()) / u32_byte_len;
-                    // > Len - A 16-bit big endian unsigned integer not larger than 3.
-                    assert!(len_usize <= NEWER_REFERENCE_EXT_MAX_U32_LEN);
-                    append_usize_as_u16(&mut byte_vec, len_usize);
-
-                    byte_vec.extend_from_slice(&atom_to_byte_vec(node::atom()));
-
-                    let creation_u32 = CREATION as u32;
-                    byte_vec.extend_from_slice(&creation_u32.to_be_bytes());
-
-                    byte_vec.extend_from_slice(&scheduler_id_u32.to_be_bytes());
-                    byte_vec.extend_from_slice(&number.to_be_bytes());
+                    append_reference(sink, node::LOCAL_ID, &reference, minor_version)?;
                 }
                 TypedTerm::SubBinary(subbinary) => {
-                    if subbinary.is_binary() {
-                        byte_vec.push(BINARY_EXT);
-
-                        let len_usize = subbinary.full_byte_len();
-                        append_usize_as_u32(&mut byte_vec, len_usize);
-
-                        if subbinary.is_aligned() {
-                            byte_vec.extend_from_slice(unsafe { subbinary.as_bytes() });
-                        } else {
-                            byte_vec.extend(subbinary.full_byte_iter());
-                        }
-                    } else {
-                        byte_vec.push(BIT_BINARY_EXT);
-
-                        let len_usize = subbinary.total_byte_len();
-                        append_usize_as_u32(&mut byte_vec, len_usize);
-
-                        let bits_u8 = subbinary.partial_byte_bit_len();
-                        byte_vec.push(bits_u8);
-
-                        if subbinary.is_aligned() {
-                            byte_vec.extend_from_slice(unsafe { subbinary.as_bytes() });
-                        } else {
-                            byte_vec.extend(subbinary.full_byte_iter());
-                        }
-
-                        let mut last_byte: u8 = 0;
-
-                        for (index, bit) in subbinary.partial_byte_bit_iter().enumerate() {
-                            last_byte |= bit << (7 - index);
-                        }
-
-                        byte_vec.push(last_byte);
-                    }
+                    append_subbinary(sink, &subbinary);
                 }
                 TypedTerm::Tuple(tuple) => {
                     let len_usize = tuple.len();
 
                     if len_usize <= SMALL_TUPLE_EXT_MAX_LEN {
-                        byte_vec.push(SMALL_TUPLE_EXT);
-                        byte_vec.push(len_usize as u8);
+                        sink.push(SMALL_TUPLE_EXT);
+                        sink.push(len_usize as u8);
                     } else {
-                        byte_vec.push(LARGE_TUPLE_EXT);
-                        append_usize_as_u32(&mut byte_vec, len_usize);
+                        sink.push(LARGE_TUPLE_EXT);
+                        append_usize_as_u32(sink, len_usize);
                     }
 
                     for element in tuple.iter().rev() {
                         stack.push_front(element);
                     }
                 }
-                _ => unimplemented!("term_to_binary({:?})", front_term),
+                // Resources only exist on the local node, so they have no external format
+                _ => return Err(badarg!()),
             },
-            _ => unimplemented!("term_to_binary({:?})", front_term),
+            _ => return Err(badarg!()),
         };
     }
 
-    process
-        .binary_from_bytes(&byte_vec)
-        .map_err(|alloc| alloc.into())
+    Ok(())
 }
 
-// Private
-
-// TODO implement creation rotation
-// > A 32-bit big endian unsigned integer. All identifiers originating from the same node
-// > incarnation must have identical Creation values. This makes it possible to separate identifiers
-// > from old (crashed) nodes from a new one. The value zero should be avoided for normal operations
-// > as it is used as a wild card for debug purpose (like a pid returned by erlang:list_to_pid/1).
-const CREATION: u8 = 0;
-
 const VERSION_NUMBER: u8 = 131;
 
 const NEW_FLOAT_EXT: u8 = 70;
 
 const BIT_BINARY_EXT: u8 = 77;
 
+const COMPRESSED: u8 = 80;
+// The tag and the 32-bit uncompressed size
+const COMPRESSED_HEADER_LEN: usize = 1 + mem::size_of::<u32>();
+
+const NEW_PID_EXT: u8 = 88;
+
+const NEW_PORT_EXT: u8 = 89;
+
 const NEWER_REFERENCE_EXT: u8 = 90;
 const NEWER_REFERENCE_EXT_MAX_U32_LEN: usize = 3;
 
@@ -249,9 +379,14 @@ const INTEGER_EXT: u8 = 98;
 const INTEGER_EXT_MIN: isize = std::i32::MIN as isize;
 const INTEGER_EXT_MAX: isize = std::i32::MAX as isize;
 
+const FLOAT_EXT: u8 = 99;
+const FLOAT_EXT_LEN: usize = 31;
+
 // http://erlang.org/doc/apps/erts/erl_ext_dist.html#atom_ext--deprecated-
 const ATOM_EXT: u8 = 100;
 
+const PORT_EXT: u8 = 102;
+
 const PID_EXT: u8 = 103;
 
 const SMALL_TUPLE_EXT: u8 = 104;
@@ -273,6 +408,12 @@ const SMALL_BIG_EXT_MAX_LEN: usize = std::u8::MAX as usize;
 
 const LARGE_BIG_EXT: u8 = 111;
 
+const NEW_FUN_EXT: u8 = 112;
+// > Uniq - The 16 bytes MD5 of the significant parts of the Beam file.
+const NEW_FUN_EXT_UNIQ_LEN: usize = 16;
+
+const EXPORT_EXT: u8 = 113;
+
 const MAP_EXT: u8 = 116;
 
 const ATOM_UTF8_EXT: u8 = 118;
@@ -280,8 +421,37 @@ const ATOM_UTF8_EXT: u8 = 118;
 const SMALL_ATOM_UTF8_EXT: u8 = 119;
 const SMALL_ATOM_UTF8_EXT_MAX_LEN: usize = std::u8::MAX as usize;
 
-fn append_big_int(byte_vec: &mut Vec<u8>, big_int: &BigInt) {
-    let (sign, mut little_endian_bytes) = big_int.to_bytes_le();
+const V4_PORT_EXT: u8 = 120;
+
+fn append_atom<S: Sink>(sink: &mut S, atom: Atom, minor_version: &MinorVersion) {
+    let name = atom.name();
+
+    // Before minor version 2, atoms that can be encoded in Latin-1 use the deprecated `ATOM_EXT`,
+    // so that they can be decoded by older nodes.
+    if !minor_version.utf8_atoms() && name.chars().all(|c| (c as u32) <= (std::u8::MAX as u32)) {
+        sink.push(ATOM_EXT);
+        append_usize_as_u16(sink, name.chars().count());
+        sink.extend_from_iter(name.chars().map(|c| c as u8));
+    } else {
+        let bytes = name.as_bytes();
+        let len_usize = bytes.len();
+
+        if len_usize <= SMALL_ATOM_UTF8_EXT_MAX_LEN {
+            sink.push(SMALL_ATOM_UTF8_EXT);
+
+            let len_u8 = len_usize as u8;
+            sink.push(len_u8);
+        } else {
+            sink.push(ATOM_UTF8_EXT);
+            append_usize_as_u16(sink, len_usize);
+        }
+
+        sink.extend_from_slice(bytes);
+    }
+}
+
+fn append_big_int<S: Sink>(sink: &mut S, big_int: &BigInt) {
+    let (sign, little_endian_bytes) = big_int.to_bytes_le();
 
     let sign_byte: u8 = match sign {
         Sign::Minus => 1,
@@ -291,54 +461,273 @@ fn append_big_int(byte_vec: &mut Vec<u8>, big_int: &BigInt) {
     let len_usize = little_endian_bytes.len();
 
     if len_usize <= SMALL_BIG_EXT_MAX_LEN {
-        byte_vec.push(SMALL_BIG_EXT);
-        byte_vec.push(len_usize as u8);
+        sink.push(SMALL_BIG_EXT);
+        sink.push(len_usize as u8);
     } else {
-        byte_vec.push(LARGE_BIG_EXT);
-        append_usize_as_u32(byte_vec, len_usize);
+        sink.push(LARGE_BIG_EXT);
+        append_usize_as_u32(sink, len_usize);
+    }
+
+    sink.push(sign_byte);
+    sink.extend_from_slice(&little_endian_bytes);
+}
+
+/// Lumen does not record whether a closure was made with `fun Module:Function/Arity`, so closures
+/// named like the compiler names local funs (`-function/arity-fun-index-`) or that capture an
+/// environment are encoded as `NEW_FUN_EXT` and all others as `EXPORT_EXT`.
+fn append_closure<S: Sink>(
+    sink: &mut S,
+    closure: &Closure,
+    options: &Options,
+) -> Result<(), runtime::Exception> {
+    let minor_version = &options.minor_version;
+    let module_function_arity = closure.module_function_arity();
+    let function_name = module_function_arity.function.name();
+
+    if is_export(closure) {
+        sink.push(EXPORT_EXT);
+        append_atom(sink, module_function_arity.module, minor_version);
+        append_atom(sink, module_function_arity.function, minor_version);
+        sink.push(SMALL_INTEGER_EXT);
+        sink.push(module_function_arity.arity);
+    } else {
+        let index = local_fun_index(function_name);
+
+        // > Size - The total number of bytes, including field Size.
+        let mut counter = Counter::default();
+        append_new_fun_ext_after_size(&mut counter, closure, index, options)?;
+
+        sink.push(NEW_FUN_EXT);
+        append_usize_as_u32(sink, mem::size_of::<u32>() + counter.len);
+        append_new_fun_ext_after_size(sink, closure, index, options)?;
+    }
+
+    Ok(())
+}
+
+fn append_new_fun_ext_after_size<S: Sink>(
+    sink: &mut S,
+    closure: &Closure,
+    index: u32,
+    options: &Options,
+) -> Result<(), runtime::Exception> {
+    let module_function_arity = closure.module_function_arity();
+    let env_slice = closure.env_slice();
+
+    sink.push(module_function_arity.arity);
+    // The BEAM file is not available to take the MD5 of, and the `Index` alone identifies the fun
+    // when decoded by `binary_to_term`.
+    sink.extend_from_slice(&[0; NEW_FUN_EXT_UNIQ_LEN]);
+    sink.extend_from_slice(&index.to_be_bytes());
+    append_usize_as_u32(sink, env_slice.len());
+    append_atom(sink, module_function_arity.module, &options.minor_version);
+    // OldIndex and OldUniq
+    append_isize(sink, index as isize);
+    append_isize(sink, 0);
+    encode(sink, closure.creator(), options)?;
+
+    for term in env_slice {
+        encode(sink, *term, options)?;
+    }
+
+    Ok(())
+}
+
+fn append_isize<S: Sink>(sink: &mut S, integer: isize) {
+    if SMALL_INTEGER_EXT_MIN <= integer && integer <= SMALL_INTEGER_EXT_MAX {
+        sink.push(SMALL_INTEGER_EXT);
+        sink.push(integer as u8);
+    } else if INTEGER_EXT_MIN <= integer && integer <= INTEGER_EXT_MAX {
+        sink.push(INTEGER_EXT);
+        sink.extend_from_slice(&(integer as i32).to_be_bytes());
+    } else {
+        // convert to big int, so that the number of bytes is minimum instead of jumping to 8 to
+        // hold i64.
+        let big_int: BigInt = (integer as i64).into();
+
+        append_big_int(sink, &big_int);
+    }
+}
+
+/// `badarg` if `node_id` was never registered, such as for the pid from `list_to_pid("<7.1.2>")`,
+/// as there is no name to encode.
+fn node_atom_creation(node_id: usize) -> Result<(Atom, u32), runtime::Exception> {
+    node::name_creation_from_id(node_id).ok_or_else(|| badarg!())
+}
+
+fn append_pid<S: Sink>(
+    sink: &mut S,
+    node_id: usize,
+    number: u32,
+    serial: u32,
+    minor_version: &MinorVersion,
+) -> Result<(), runtime::Exception> {
+    let (node_atom, creation) = node_atom_creation(node_id)?;
+
+    // `PID_EXT` only has room for an 8-bit creation
+    if creation <= (std::u8::MAX as u32) {
+        sink.push(PID_EXT);
+    } else {
+        sink.push(NEW_PID_EXT);
+    }
+
+    append_atom(sink, node_atom, minor_version);
+    sink.extend_from_slice(&number.to_be_bytes());
+    sink.extend_from_slice(&serial.to_be_bytes());
+
+    if creation <= (std::u8::MAX as u32) {
+        sink.push(creation as u8);
+    } else {
+        sink.extend_from_slice(&creation.to_be_bytes());
+    }
+
+    Ok(())
+}
+
+fn append_port<S: Sink>(
+    sink: &mut S,
+    node_id: usize,
+    number: usize,
+    minor_version: &MinorVersion,
+) -> Result<(), runtime::Exception> {
+    let (node_atom, creation) = node_atom_creation(node_id)?;
+
+    if (std::u32::MAX as usize) < number {
+        sink.push(V4_PORT_EXT);
+        append_atom(sink, node_atom, minor_version);
+
+        let number_u64 = number as u64;
+        sink.extend_from_slice(&number_u64.to_be_bytes());
+        sink.extend_from_slice(&creation.to_be_bytes());
+    } else {
+        let number_u32 = number as u32;
+
+        // `PORT_EXT` only has room for an 8-bit creation
+        if creation <= (std::u8::MAX as u32) {
+            sink.push(PORT_EXT);
+            append_atom(sink, node_atom, minor_version);
+            sink.extend_from_slice(&number_u32.to_be_bytes());
+            sink.push(creation as u8);
+        } else {
+            sink.push(NEW_PORT_EXT);
+            append_atom(sink, node_atom, minor_version);
+            sink.extend_from_slice(&number_u32.to_be_bytes());
+            sink.extend_from_slice(&creation.to_be_bytes());
+        }
     }
 
-    byte_vec.push(sign_byte);
-    byte_vec.append(&mut little_endian_bytes);
+    Ok(())
 }
 
-fn append_binary_bytes(byte_vec: &mut Vec<u8>, binary_bytes: &[u8]) {
-    byte_vec.extend_from_slice(binary_bytes)
+fn append_reference<S: Sink>(
+    sink: &mut S,
+    node_id: usize,
+    reference: &Reference,
+    minor_version: &MinorVersion,
+) -> Result<(), runtime::Exception> {
+    let (node_atom, creation) = node_atom_creation(node_id)?;
+    let scheduler_id_u32: u32 = reference.scheduler_id().into();
+    let number: u64 = reference.number().into();
+
+    sink.push(NEWER_REFERENCE_EXT);
+
+    let u32_byte_len = mem::size_of::<u32>();
+    let len_usize = (mem::size_of::<u32>() + mem::size_of::<u64>()) / u32_byte_len;
+    // > Len - A 16-bit big endian unsigned integer not larger than 3.
+    assert!(len_usize <= NEWER_REFERENCE_EXT_MAX_U32_LEN);
+    append_usize_as_u16(sink, len_usize);
+
+    append_atom(sink, node_atom, minor_version);
+    sink.extend_from_slice(&creation.to_be_bytes());
+
+    sink.extend_from_slice(&scheduler_id_u32.to_be_bytes());
+    sink.extend_from_slice(&number.to_be_bytes());
+
+    Ok(())
+}
+
+fn append_subbinary<S: Sink>(sink: &mut S, subbinary: &SubBinary) {
+    if subbinary.is_binary() {
+        sink.push(BINARY_EXT);
+
+        let len_usize = subbinary.full_byte_len();
+        append_usize_as_u32(sink, len_usize);
+
+        if subbinary.is_aligned() {
+            sink.extend_from_slice(unsafe { subbinary.as_bytes() });
+        } else {
+            sink.extend_from_iter(subbinary.full_byte_iter());
+        }
+    } else {
+        sink.push(BIT_BINARY_EXT);
+
+        let len_usize = subbinary.total_byte_len();
+        append_usize_as_u32(sink, len_usize);
+
+        let bits_u8 = subbinary.partial_byte_bit_len();
+        sink.push(bits_u8);
+
+        if subbinary.is_aligned() {
+            sink.extend_from_slice(unsafe { subbinary.as_bytes() });
+        } else {
+            sink.extend_from_iter(subbinary.full_byte_iter());
+        }
+
+        let mut last_byte: u8 = 0;
+
+        for (index, bit) in subbinary.partial_byte_bit_iter().enumerate() {
+            last_byte |= bit << (7 - index);
+        }
+
+        sink.push(last_byte);
+    }
 }
 
-fn append_usize_as_u16(byte_vec: &mut Vec<u8>, len_usize: usize) {
+fn append_usize_as_u16<S: Sink>(sink: &mut S, len_usize: usize) {
     assert!(len_usize <= (std::u16::MAX as usize));
     let len_u16 = len_usize as u16;
-    byte_vec.extend_from_slice(&len_u16.to_be_bytes());
+    sink.extend_from_slice(&len_u16.to_be_bytes());
 }
 
-fn append_usize_as_u32(byte_vec: &mut Vec<u8>, len_usize: usize) {
+fn append_usize_as_u32<S: Sink>(sink: &mut S, len_usize: usize) {
     assert!(len_usize <= (std::u32::MAX as usize));
     let len_u32 = len_usize as u32;
-    byte_vec.extend_from_slice(&len_u32.to_be_bytes());
+    sink.extend_from_slice(&len_u32.to_be_bytes());
 }
 
-fn atom_to_byte_vec(atom: Atom) -> Vec<u8> {
-    let bytes = atom.name().as_bytes();
-    let len_usize = bytes.len();
-    let mut byte_vec: Vec<u8> = Vec::new();
-
-    if bytes.iter().all(|byte| byte.is_ascii()) {
-        byte_vec.push(ATOM_EXT);
-        append_usize_as_u16(&mut byte_vec, len_usize);
-    } else if len_usize <= SMALL_ATOM_UTF8_EXT_MAX_LEN {
-        byte_vec.push(SMALL_ATOM_UTF8_EXT);
+fn compress(byte_vec: &[u8], compression: &Compression) -> Option<Vec<u8>> {
+    let level = compression.0;
 
-        let len_u8 = len_usize as u8;
-        byte_vec.push(len_u8);
-    } else {
-        byte_vec.push(ATOM_UTF8_EXT);
-        append_usize_as_u16(&mut byte_vec, len_usize);
+    if level == 0 {
+        return None;
     }
 
-    byte_vec.extend_from_slice(bytes);
+    // zlib levels trade speed for a larger window to find repeats in
+    let window_size = 1 << (level + 6);
+    let options =
+        zlib::EncodeOptions::with_lz77(lz77::DefaultLz77Encoder::with_window_size(window_size));
+    let mut encoder = zlib::Encoder::with_options(Vec::new(), options).ok()?;
+    encoder.write_all(byte_vec).ok()?;
 
-    byte_vec
+    encoder.finish().into_result().ok()
+}
+
+// > A float is stored in string format. The format used in sprintf to format the float is
+// > "%.20e" (there are more bytes allocated than necessary). To unpack the float, use sscanf with
+// > format "%lf".
+// -- http://erlang.org/doc/apps/erts/erl_ext_dist.html#float_ext
+fn float_to_float_ext_bytes(float: f64) -> [u8; FLOAT_EXT_LEN] {
+    // Rust's exponent has no sign or zero-padding, unlike C's
+    let formatted = format!("{:.20e}", float);
+    let (mantissa, exponent) = formatted.split_at(formatted.find('e').unwrap());
+    let exponent_i32: i32 = exponent[1..].parse().unwrap();
+    let sign = if exponent_i32 < 0 { '-' } else { '+' };
+    let c_formatted = format!("{}e{}{:02}", mantissa, sign, exponent_i32.abs());
+
+    let mut bytes = [0; FLOAT_EXT_LEN];
+    bytes[..c_formatted.len()].copy_from_slice(c_formatted.as_bytes());
+
+    bytes
 }
 
 // Tail is the final tail  of the list; it is NIL_EXT for a proper list, but can be any type if the
@@ -360,6 +749,108 @@ fn cons_to_element_vec_tail(cons: &Cons) -> (Vec<Term>, Term) {
     (element_vec, tail)
 }
 
+/// Whether `closure` is treated like `fun Module:Function/Arity`.  See `append_closure`.
+/// Orders map keys for `deterministic`.  Keys that `Term::cmp` considers equal, such as `1` and
+/// `1.0` or `0.0` and `-0.0`, are still different keys, so they are ordered like in map key order:
+/// integers before floats, and `-0.0` before `0.0`, including when they are nested.
+fn map_key_cmp(left: Term, right: Term) -> Ordering {
+    left.cmp(&right).then_with(|| exact_cmp(left, right))
+}
+
+// Only called for terms that `Term::cmp` considers equal, so both have the same shape
+fn exact_cmp(left: Term, right: Term) -> Ordering {
+    if left.is_number() && right.is_number() {
+        return match (term_to_f64(left), term_to_f64(right)) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(left_f64), Some(right_f64)) => right_f64
+                .is_sign_negative()
+                .cmp(&left_f64.is_sign_negative()),
+        };
+    }
+
+    match (
+        left.to_typed_term().unwrap(),
+        right.to_typed_term().unwrap(),
+    ) {
+        (TypedTerm::List(left_cons), TypedTerm::List(right_cons)) => {
+            let (left_element_vec, left_tail) = cons_to_element_vec_tail(&left_cons);
+            let (right_element_vec, right_tail) = cons_to_element_vec_tail(&right_cons);
+
+            exact_cmp_iter(
+                left_element_vec.into_iter().chain(iter::once(left_tail)),
+                right_element_vec.into_iter().chain(iter::once(right_tail)),
+            )
+        }
+        (TypedTerm::Boxed(left_boxed), TypedTerm::Boxed(right_boxed)) => match (
+            left_boxed.to_typed_term().unwrap(),
+            right_boxed.to_typed_term().unwrap(),
+        ) {
+            (TypedTerm::Tuple(left_tuple), TypedTerm::Tuple(right_tuple)) => {
+                exact_cmp_iter(left_tuple.iter(), right_tuple.iter())
+            }
+            (TypedTerm::Map(left_map), TypedTerm::Map(right_map)) => exact_cmp_iter(
+                sorted_key_value_vec(left_map.as_ref()).into_iter(),
+                sorted_key_value_vec(right_map.as_ref()).into_iter(),
+            ),
+            _ => Ordering::Equal,
+        },
+        _ => Ordering::Equal,
+    }
+}
+
+fn exact_cmp_iter<L, R>(left: L, right: R) -> Ordering
+where
+    L: Iterator<Item = Term>,
+    R: Iterator<Item = Term>,
+{
+    left.zip(right)
+        .map(|(left_term, right_term)| exact_cmp(left_term, right_term))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+// The keys and values of `hash_map`, in map key order
+fn sorted_key_value_vec(hash_map: &HashMap<Term, Term>) -> Vec<Term> {
+    let mut entry_vec: Vec<(&Term, &Term)> = hash_map.iter().collect();
+    entry_vec.sort_by(|(left_key, _), (right_key, _)| map_key_cmp(**left_key, **right_key));
+
+    entry_vec
+        .into_iter()
+        .flat_map(|(key, value)| iter::once(*key).chain(iter::once(*value)))
+        .collect()
+}
+
+fn term_to_f64(term: Term) -> Option<f64> {
+    match term.to_typed_term().unwrap() {
+        TypedTerm::Boxed(boxed) => match boxed.to_typed_term().unwrap() {
+            TypedTerm::Float(float) => Some(float.into()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn is_export(closure: &Closure) -> bool {
+    !closure
+        .module_function_arity()
+        .function
+        .name()
+        .starts_with('-')
+        && closure.env_len() == 0
+}
+
+/// The `Index` the compiler put in the name of a local fun (`-function/arity-fun-index-`)
+pub fn local_fun_index(function_name: &str) -> u32 {
+    function_name
+        .trim_end_matches('-')
+        .rsplit("-fun-")
+        .next()
+        .and_then(|index| index.parse().ok())
+        .unwrap_or(0)
+}
+
 fn try_cons_to_string_ext_byte_vec(cons: &Cons) -> Result<Vec<u8>, TypeError> {
     let mut character_byte_vec: Vec<u8> = Vec::new();
 
@@ -424,6 +915,16 @@ pub struct MinorVersion(u8);
 impl MinorVersion {
     const MIN_U8: u8 = 0;
     const MAX_U8: u8 = 2;
+
+    /// Floats are encoded as `NEW_FLOAT_EXT` instead of `FLOAT_EXT` strings from minor version 1
+    fn new_float_ext(&self) -> bool {
+        1 <= self.0
+    }
+
+    /// All atoms are encoded as UTF-8 from minor version 2
+    fn utf8_atoms(&self) -> bool {
+        2 <= self.0
+    }
 }
 
 impl Default for MinorVersion {
@@ -449,6 +950,8 @@ impl TryFrom<Term> for MinorVersion {
 pub struct Options {
     compression: Compression,
     minor_version: MinorVersion,
+    /// Map entries are sorted by key, so that the same term always encodes to the same bytes.
+    deterministic: bool,
 }
 
 impl Default for Options {
@@ -457,6 +960,7 @@ impl Default for Options {
             // No compression is done (it is the same as giving no compressed option)
             compression: Compression(0),
             minor_version: Default::default(),
+            deterministic: false,
         }
     }
 }
@@ -470,6 +974,11 @@ impl Options {
 
                     Ok(self)
                 }
+                "deterministic" => {
+                    self.deterministic = true;
+
+                    Ok(self)
+                }
                 _ => Err(badarg!()),
             },
             TypedTerm::Boxed(boxed) => match boxed.to_typed_term().unwrap() {
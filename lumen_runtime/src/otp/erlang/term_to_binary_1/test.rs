@@ -1,7 +1,16 @@
+use std::sync::Arc;
+
+use liblumen_alloc::badarg;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::scheduler;
-use liblumen_alloc::erts::term::{atom_unchecked, AsTerm, Pid, Reference, Term};
-
+use liblumen_alloc::erts::term::{
+    atom_unchecked, AsTerm, Atom, MatchContext, Pid, Reference, Term,
+};
+use liblumen_alloc::erts::ModuleFunctionArity;
+use liblumen_alloc::CloneToProcess;
+
+use crate::node;
+use crate::otp::erlang::binary_to_term_1;
 use crate::otp::erlang::term_to_binary_1::native;
 use crate::scheduler::with_process;
 
@@ -113,6 +122,24 @@ fn with_subbinary_without_binary_without_aligned_returns_bit_binary_ext() {
     });
 }
 
+// BIT_BINARY_EXT (77)
+#[test]
+fn with_subbinary_without_binary_without_aligned_spanning_bytes_returns_bit_binary_ext() {
+    with_process(|process| {
+        let binary = process
+            .binary_from_bytes(&[0b1010_1010, 0b1100_1100, 0b1111_0000])
+            .unwrap();
+        let subbinary = process.subbinary_from_original(binary, 0, 4, 1, 4).unwrap();
+
+        assert_eq!(
+            native(process, subbinary),
+            Ok(process
+                .binary_from_bytes(&[131, 77, 0, 0, 0, 2, 4, 0b1010_1100, 0b1100_0000])
+                .unwrap())
+        );
+    });
+}
+
 // NEWER_REFERENCE_EXT (90)
 #[test]
 fn with_reference_returns_new_reference_ext() {
@@ -556,6 +583,45 @@ fn with_subbinary_with_binary_without_aligned_returns_binary_ext() {
     });
 }
 
+// BINARY_EXT (109)
+#[test]
+fn with_subbinary_with_binary_without_aligned_spanning_bytes_returns_binary_ext() {
+    with_process(|process| {
+        let binary = process
+            .binary_from_bytes(&[0b1010_1010, 0b1100_1100, 0b1111_0000])
+            .unwrap();
+        let subbinary = process.subbinary_from_original(binary, 0, 4, 2, 0).unwrap();
+
+        assert_eq!(
+            native(process, subbinary),
+            Ok(process
+                .binary_from_bytes(&[131, 109, 0, 0, 0, 2, 0b1010_1100, 0b1100_1111])
+                .unwrap())
+        );
+    });
+}
+
+// BINARY_EXT (109)
+#[test]
+fn with_match_context_returns_unmatched_bits_as_binary_ext() {
+    with_process(|process| {
+        let binary = process
+            .binary_from_bytes(&[0b1010_1010, 0b1100_1100])
+            .unwrap();
+        let subbinary = process.subbinary_from_original(binary, 0, 4, 1, 0).unwrap();
+        let match_context = MatchContext::new(subbinary)
+            .clone_to_heap(&mut process.acquire_heap())
+            .unwrap();
+
+        assert_eq!(
+            native(process, match_context),
+            Ok(process
+                .binary_from_bytes(&[131, 109, 0, 0, 0, 1, 0b1010_1100])
+                .unwrap())
+        );
+    });
+}
+
 // SMALL_BIG_EXT (110)
 #[test]
 fn with_small_integer_returns_small_big_ext() {
@@ -636,17 +702,236 @@ fn with_small_utf8_atom_returns_small_atom_utf8_ext() {
     });
 }
 
+// NEW_PID_EXT (88)
+#[test]
+fn with_external_pid_with_creation_above_u8_returns_new_pid_ext() {
+    with_process(|process| {
+        let node_id = node::id(Atom::try_from_str("node@host").unwrap(), 256);
+        let external_pid = process.pid_with_node_id(node_id, 1, 2).unwrap();
+
+        let mut expected_byte_vec = vec![VERSION_NUMBER, NEW_PID_EXT];
+        expected_byte_vec.append(&mut atom_byte_vec("node@host"));
+        expected_byte_vec.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 1, 0]);
+
+        assert_eq!(
+            native(process, external_pid),
+            Ok(process.binary_from_bytes(&expected_byte_vec).unwrap())
+        );
+    });
+}
+
+// NEW_PORT_EXT (89)
+#[test]
+fn with_external_port_with_creation_above_u8_returns_new_port_ext() {
+    with_process(|process| {
+        let node_id = node::id(Atom::try_from_str("node@host").unwrap(), 256);
+        let external_port = process.external_port_with_node_id(node_id, 3).unwrap();
+
+        let mut expected_byte_vec = vec![VERSION_NUMBER, NEW_PORT_EXT];
+        expected_byte_vec.append(&mut atom_byte_vec("node@host"));
+        expected_byte_vec.extend_from_slice(&[0, 0, 0, 3, 0, 0, 1, 0]);
+
+        assert_eq!(
+            native(process, external_port),
+            Ok(process.binary_from_bytes(&expected_byte_vec).unwrap())
+        );
+    });
+}
+
+// NEWER_REFERENCE_EXT (90)
+#[test]
+fn with_external_reference_returns_newer_reference_ext() {
+    with_process(|process| {
+        let node_id = node::id(Atom::try_from_str("node@host").unwrap(), 2);
+        let external_reference = process
+            .external_reference_with_node_id(node_id, 1.into(), 3)
+            .unwrap();
+
+        let mut expected_byte_vec = vec![VERSION_NUMBER, NEWER_REFERENCE_EXT, 0, 3];
+        expected_byte_vec.append(&mut atom_byte_vec("node@host"));
+        expected_byte_vec.extend_from_slice(&[0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 3]);
+
+        assert_eq!(
+            native(process, external_reference),
+            Ok(process.binary_from_bytes(&expected_byte_vec).unwrap())
+        );
+    });
+}
+
+// PORT_EXT (102)
+#[test]
+fn with_port_returns_port_ext() {
+    with_process(|process| {
+        let mut expected_byte_vec = vec![VERSION_NUMBER, PORT_EXT];
+        expected_byte_vec.append(&mut atom_byte_vec("nonode@nohost"));
+        expected_byte_vec.extend_from_slice(&[0, 0, 0, 1, 0]);
+
+        assert_eq!(
+            native(process, Term::make_port(1)),
+            Ok(process.binary_from_bytes(&expected_byte_vec).unwrap())
+        );
+    });
+}
+
+// PID_EXT (103)
+#[test]
+fn with_external_pid_returns_pid_ext() {
+    with_process(|process| {
+        let node_id = node::id(Atom::try_from_str("node@host").unwrap(), 2);
+        let external_pid = process.pid_with_node_id(node_id, 1, 2).unwrap();
+
+        let mut expected_byte_vec = vec![VERSION_NUMBER, PID_EXT];
+        expected_byte_vec.append(&mut atom_byte_vec("node@host"));
+        expected_byte_vec.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 2, 2]);
+
+        assert_eq!(
+            native(process, external_pid),
+            Ok(process.binary_from_bytes(&expected_byte_vec).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_external_pid_with_unregistered_node_errors_badarg() {
+    with_process(|process| {
+        let external_pid = process
+            .external_pid_with_node_id(UNREGISTERED_NODE_ID, 1, 2)
+            .unwrap();
+
+        assert_eq!(native(process, external_pid), Err(badarg!().into()));
+    });
+}
+
+#[test]
+fn with_external_port_with_unregistered_node_errors_badarg() {
+    with_process(|process| {
+        let external_port = process
+            .external_port_with_node_id(UNREGISTERED_NODE_ID, 3)
+            .unwrap();
+
+        assert_eq!(native(process, external_port), Err(badarg!().into()));
+    });
+}
+
+#[test]
+fn with_external_reference_with_unregistered_node_errors_badarg() {
+    with_process(|process| {
+        let external_reference = process
+            .external_reference_with_node_id(UNREGISTERED_NODE_ID, 1.into(), 3)
+            .unwrap();
+
+        assert_eq!(native(process, external_reference), Err(badarg!().into()));
+    });
+}
+
+#[test]
+fn with_resource_errors_badarg() {
+    with_process(|process| {
+        let resource = process.resource(Box::new(1)).unwrap();
+
+        assert_eq!(native(process, resource), Err(badarg!().into()));
+    });
+}
+
+// NEW_FUN_EXT (112)
+#[test]
+fn with_closure_with_environment_returns_new_fun_ext() {
+    with_process(|process| {
+        let closure = closure(process, "-main/0-fun-3-", &[atom_unchecked("free")]);
+        let binary = native(process, closure).unwrap();
+        let bytes = process.bytes_from_binary(binary).unwrap().to_vec();
+
+        let mut expected_prefix_byte_vec = vec![VERSION_NUMBER, NEW_FUN_EXT];
+        // > Size - The total number of bytes, including field Size.
+        expected_prefix_byte_vec.extend_from_slice(&((bytes.len() - 2) as u32).to_be_bytes());
+        // Arity
+        expected_prefix_byte_vec.push(1);
+        // Uniq
+        expected_prefix_byte_vec.extend_from_slice(&[0; 16]);
+        // Index and NumFree
+        expected_prefix_byte_vec.extend_from_slice(&[0, 0, 0, 3, 0, 0, 0, 1]);
+        // Module, OldIndex and OldUniq
+        expected_prefix_byte_vec.append(&mut atom_byte_vec("module"));
+        expected_prefix_byte_vec.extend_from_slice(&[SMALL_INTEGER_EXT, 3, SMALL_INTEGER_EXT, 0]);
+
+        assert_eq!(
+            bytes[..expected_prefix_byte_vec.len()],
+            expected_prefix_byte_vec[..]
+        );
+        assert!(bytes.ends_with(&atom_byte_vec("free")));
+
+        // The decoded closure is named `-fun-3-`, so its `Index` survives being decoded
+        let decoded = binary_to_term_1::native(process, binary).unwrap();
+
+        assert_eq!(
+            native(process, decoded),
+            Ok(process.binary_from_bytes(&bytes).unwrap())
+        );
+    });
+}
+
+// EXPORT_EXT (113)
+#[test]
+fn with_closure_without_environment_returns_export_ext() {
+    with_process(|process| {
+        let closure = closure(process, "function", &[]);
+
+        let mut expected_byte_vec = vec![VERSION_NUMBER, EXPORT_EXT];
+        expected_byte_vec.append(&mut atom_byte_vec("module"));
+        expected_byte_vec.append(&mut atom_byte_vec("function"));
+        expected_byte_vec.extend_from_slice(&[SMALL_INTEGER_EXT, 1]);
+
+        assert_eq!(
+            native(process, closure),
+            Ok(process.binary_from_bytes(&expected_byte_vec).unwrap())
+        );
+    });
+}
+
 const VERSION_NUMBER: u8 = 131;
 
 const NEW_FLOAT_EXT: u8 = 70;
+const NEW_PID_EXT: u8 = 88;
+const NEW_PORT_EXT: u8 = 89;
+const NEWER_REFERENCE_EXT: u8 = 90;
 const SMALL_INTEGER_EXT: u8 = 97;
 const INTEGER_EXT: u8 = 98;
 const ATOM_EXT: u8 = 100;
+const PORT_EXT: u8 = 102;
 const PID_EXT: u8 = 103;
 const SMALL_TUPLE_EXT: u8 = 104;
 const NIL_EXT: u8 = 106;
 const STRING_EXT: u8 = 107;
 const BINARY_EXT: u8 = 109;
+const NEW_FUN_EXT: u8 = 112;
+const EXPORT_EXT: u8 = 113;
+
+// Never assigned by `node::id`, like the node of `list_to_pid("<7.1.2>")` if 7 is not assigned
+const UNREGISTERED_NODE_ID: usize = std::usize::MAX;
+
+fn atom_byte_vec(name: &str) -> Vec<u8> {
+    let mut byte_vec = vec![ATOM_EXT, 0, name.len() as u8];
+    byte_vec.extend_from_slice(name.as_bytes());
+
+    byte_vec
+}
+
+fn closure(process: &Process, function: &str, env_slice: &[Term]) -> Term {
+    let module_function_arity = Arc::new(ModuleFunctionArity {
+        module: Atom::try_from_str("module").unwrap(),
+        function: Atom::try_from_str(function).unwrap(),
+        arity: 1,
+    });
+    let code = |arc_process: &Arc<Process>| {
+        arc_process.wait();
+
+        Ok(())
+    };
+
+    process
+        .closure_with_env_from_slice(module_function_arity, code, process.pid_term(), env_slice)
+        .unwrap()
+}
 
 fn non_empty_atom_term() -> Term {
     atom_unchecked("atom")
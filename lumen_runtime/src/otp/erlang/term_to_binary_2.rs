@@ -0,0 +1,19 @@
+#[cfg(test)]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::Term;
+
+use lumen_runtime_macros::native_implemented_function;
+
+use crate::otp::erlang::term_to_binary::{term_to_binary, Options};
+
+#[native_implemented_function(term_to_binary/2)]
+pub fn native(process: &Process, term: Term, options: Term) -> exception::Result {
+    let options: Options = options.try_into()?;
+
+    term_to_binary(process, term, options)
+}
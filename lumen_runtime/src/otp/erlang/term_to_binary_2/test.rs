@@ -0,0 +1,318 @@
+use liblumen_alloc::badarg;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{atom_unchecked, Term};
+
+use crate::otp::erlang::binary_to_term_1;
+use crate::otp::erlang::term_to_binary_2::native;
+use crate::scheduler::with_process;
+
+#[test]
+fn without_list_options_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                atom_unchecked("term"),
+                atom_unchecked("compressed")
+            ),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_unknown_option_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                atom_unchecked("term"),
+                options(process, &[atom_unchecked("unknown")])
+            ),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_compressed_level_above_9_errors_badarg() {
+    with_process(|process| {
+        let option = process
+            .tuple_from_slice(&[atom_unchecked("compressed"), process.integer(10).unwrap()])
+            .unwrap();
+
+        assert_eq!(
+            native(process, atom_unchecked("term"), options(process, &[option])),
+            Err(badarg!().into())
+        );
+    });
+}
+
+#[test]
+fn with_minor_version_above_2_errors_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                atom_unchecked("term"),
+                options(process, &[minor_version(process, 3)])
+            ),
+            Err(badarg!().into())
+        );
+    });
+}
+
+// COMPRESSED (80)
+#[test]
+fn with_compressed_with_compressible_term_returns_compressed() {
+    with_process(|process| {
+        let term = process.binary_from_bytes(&[0; 1000]).unwrap();
+
+        for compressed_option in &[
+            atom_unchecked("compressed"),
+            process
+                .tuple_from_slice(&[atom_unchecked("compressed"), process.integer(1).unwrap()])
+                .unwrap(),
+            process
+                .tuple_from_slice(&[atom_unchecked("compressed"), process.integer(9).unwrap()])
+                .unwrap(),
+        ] {
+            let binary = native(process, term, options(process, &[*compressed_option])).unwrap();
+            let bytes = process.bytes_from_binary(binary).unwrap();
+
+            // BINARY_EXT, the u32 length and the bytes
+            let uncompressed_len: u32 = 1 + 4 + 1000;
+
+            assert_eq!(bytes[0..2], [VERSION_NUMBER, COMPRESSED]);
+            assert_eq!(bytes[2..6], uncompressed_len.to_be_bytes());
+            assert!(bytes.len() < (1 + uncompressed_len as usize));
+
+            assert_eq!(binary_to_term_1::native(process, binary), Ok(term));
+        }
+    });
+}
+
+#[test]
+fn with_compressed_with_incompressible_term_returns_uncompressed() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                atom_unchecked("a"),
+                options(process, &[atom_unchecked("compressed")])
+            ),
+            Ok(process
+                .binary_from_bytes(&[VERSION_NUMBER, ATOM_EXT, 0, 1, 97])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_compressed_0_returns_uncompressed() {
+    with_process(|process| {
+        let term = process.binary_from_bytes(&[0; 1000]).unwrap();
+        let option = process
+            .tuple_from_slice(&[atom_unchecked("compressed"), process.integer(0).unwrap()])
+            .unwrap();
+
+        let binary = native(process, term, options(process, &[option])).unwrap();
+        let bytes = process.bytes_from_binary(binary).unwrap();
+
+        assert_eq!(bytes[0..2], [VERSION_NUMBER, BINARY_EXT]);
+        assert_eq!(bytes.len(), 1 + 1 + 4 + 1000);
+    });
+}
+
+// FLOAT_EXT (99)
+#[test]
+fn with_minor_version_0_with_float_returns_float_ext() {
+    with_process(|process| {
+        let mut expected_byte_vec = vec![VERSION_NUMBER, FLOAT_EXT];
+        expected_byte_vec.extend_from_slice(b"1.00000000000000000000e+00");
+        expected_byte_vec.resize(2 + 31, 0);
+
+        assert_eq!(
+            native(
+                process,
+                process.float(1.0).unwrap(),
+                options(process, &[minor_version(process, 0)])
+            ),
+            Ok(process.binary_from_bytes(&expected_byte_vec).unwrap())
+        );
+
+        let mut expected_byte_vec = vec![VERSION_NUMBER, FLOAT_EXT];
+        expected_byte_vec.extend_from_slice(b"-1.52587890625000000000e-05");
+        expected_byte_vec.resize(2 + 31, 0);
+
+        assert_eq!(
+            native(
+                process,
+                process.float(-0.0000152587890625).unwrap(),
+                options(process, &[minor_version(process, 0)])
+            ),
+            Ok(process.binary_from_bytes(&expected_byte_vec).unwrap())
+        );
+    });
+}
+
+// NEW_FLOAT_EXT (70)
+#[test]
+fn with_minor_version_1_with_float_returns_new_float_ext() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                process.float(1.0).unwrap(),
+                options(process, &[minor_version(process, 1)])
+            ),
+            Ok(process
+                .binary_from_bytes(&[VERSION_NUMBER, NEW_FLOAT_EXT, 63, 240, 0, 0, 0, 0, 0, 0])
+                .unwrap())
+        );
+    });
+}
+
+// ATOM_EXT (100)
+#[test]
+fn with_minor_version_1_with_latin1_atom_returns_atom_ext() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                atom_unchecked("é"),
+                options(process, &[minor_version(process, 1)])
+            ),
+            Ok(process
+                .binary_from_bytes(&[VERSION_NUMBER, ATOM_EXT, 0, 1, 233])
+                .unwrap())
+        );
+    });
+}
+
+// SMALL_ATOM_UTF8_EXT (119)
+#[test]
+fn with_minor_version_2_with_latin1_atom_returns_small_atom_utf8_ext() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                atom_unchecked("é"),
+                options(process, &[minor_version(process, 2)])
+            ),
+            Ok(process
+                .binary_from_bytes(&[VERSION_NUMBER, SMALL_ATOM_UTF8_EXT, 2, 195, 169])
+                .unwrap())
+        );
+    });
+}
+
+// MAP_EXT (116)
+#[test]
+fn with_deterministic_with_map_returns_map_ext_with_sorted_keys() {
+    with_process(|process| {
+        let map = process
+            .map_from_slice(&[
+                (atom_unchecked("c"), process.integer(3).unwrap()),
+                (atom_unchecked("a"), process.integer(1).unwrap()),
+                (atom_unchecked("b"), process.integer(2).unwrap()),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(
+                process,
+                map,
+                options(process, &[atom_unchecked("deterministic")])
+            ),
+            Ok(process
+                .binary_from_bytes(&[
+                    VERSION_NUMBER,
+                    MAP_EXT,
+                    0,
+                    0,
+                    0,
+                    3,
+                    ATOM_EXT,
+                    0,
+                    1,
+                    97,
+                    SMALL_INTEGER_EXT,
+                    1,
+                    ATOM_EXT,
+                    0,
+                    1,
+                    98,
+                    SMALL_INTEGER_EXT,
+                    2,
+                    ATOM_EXT,
+                    0,
+                    1,
+                    99,
+                    SMALL_INTEGER_EXT,
+                    3
+                ])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_deterministic_with_map_orders_keys_that_compare_equal() {
+    with_process(|process| {
+        let map = process
+            .map_from_slice(&[
+                (process.float(1.0).unwrap(), process.integer(4).unwrap()),
+                (process.float(0.0).unwrap(), process.integer(2).unwrap()),
+                (process.integer(1).unwrap(), process.integer(3).unwrap()),
+                (process.float(-0.0).unwrap(), process.integer(1).unwrap()),
+            ])
+            .unwrap();
+
+        // -0.0 => 1, 0.0 => 2, 1 => 3, 1.0 => 4
+        let mut byte_vec = vec![VERSION_NUMBER, MAP_EXT, 0, 0, 0, 4];
+        byte_vec.push(NEW_FLOAT_EXT);
+        byte_vec.extend_from_slice(&(-0.0_f64).to_be_bytes());
+        byte_vec.extend_from_slice(&[SMALL_INTEGER_EXT, 1]);
+        byte_vec.push(NEW_FLOAT_EXT);
+        byte_vec.extend_from_slice(&0.0_f64.to_be_bytes());
+        byte_vec.extend_from_slice(&[SMALL_INTEGER_EXT, 2]);
+        byte_vec.extend_from_slice(&[SMALL_INTEGER_EXT, 1, SMALL_INTEGER_EXT, 3]);
+        byte_vec.push(NEW_FLOAT_EXT);
+        byte_vec.extend_from_slice(&1.0_f64.to_be_bytes());
+        byte_vec.extend_from_slice(&[SMALL_INTEGER_EXT, 4]);
+
+        assert_eq!(
+            native(
+                process,
+                map,
+                options(process, &[atom_unchecked("deterministic")])
+            ),
+            Ok(process.binary_from_bytes(&byte_vec).unwrap())
+        );
+    });
+}
+
+const VERSION_NUMBER: u8 = 131;
+
+const NEW_FLOAT_EXT: u8 = 70;
+const COMPRESSED: u8 = 80;
+const SMALL_INTEGER_EXT: u8 = 97;
+const FLOAT_EXT: u8 = 99;
+const ATOM_EXT: u8 = 100;
+const BINARY_EXT: u8 = 109;
+const MAP_EXT: u8 = 116;
+const SMALL_ATOM_UTF8_EXT: u8 = 119;
+
+fn minor_version(process: &Process, version: u8) -> Term {
+    process
+        .tuple_from_slice(&[
+            atom_unchecked("minor_version"),
+            process.integer(version).unwrap(),
+        ])
+        .unwrap()
+}
+
+fn options(process: &Process, option_slice: &[Term]) -> Term {
+    process.list_from_slice(option_slice).unwrap()
+}
@@ -0,0 +1,67 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::term::{atom_unchecked, Boxed, Cons};
+
+use crate::otp::erlang::term_to_iovec_1::native;
+use crate::scheduler::with_process;
+
+#[test]
+fn without_proc_bin_returns_list_of_bytes_of_term_to_binary() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, atom_unchecked("atom")),
+            Ok(process
+                .list_from_slice(&[process
+                    .binary_from_bytes(&[VERSION_NUMBER, ATOM_EXT, 0, 4, 97, 116, 111, 109])
+                    .unwrap()])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_proc_bin_returns_proc_bin_between_bytes_before_and_after() {
+    with_process(|process| {
+        // larger than a heap binary
+        let proc_bin = process.binary_from_bytes(&[1; 100]).unwrap();
+        let term = process
+            .tuple_from_slice(&[proc_bin, atom_unchecked("a")])
+            .unwrap();
+
+        let iovec = native(process, term).unwrap();
+
+        assert_eq!(
+            iovec,
+            process
+                .list_from_slice(&[
+                    process
+                        .binary_from_bytes(&[
+                            VERSION_NUMBER,
+                            SMALL_TUPLE_EXT,
+                            2,
+                            BINARY_EXT,
+                            0,
+                            0,
+                            0,
+                            100
+                        ])
+                        .unwrap(),
+                    proc_bin,
+                    process.binary_from_bytes(&[ATOM_EXT, 0, 1, 97]).unwrap()
+                ])
+                .unwrap()
+        );
+
+        // The `ProcBin` itself is in the list instead of a copy
+        let cons: Boxed<Cons> = iovec.try_into().unwrap();
+        let element = cons.into_iter().nth(1).unwrap().unwrap();
+
+        assert_eq!(element.as_usize(), proc_bin.as_usize());
+    });
+}
+
+const VERSION_NUMBER: u8 = 131;
+
+const ATOM_EXT: u8 = 100;
+const SMALL_TUPLE_EXT: u8 = 104;
+const BINARY_EXT: u8 = 109;
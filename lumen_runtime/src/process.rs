@@ -1,3 +1,4 @@
+pub mod gc_request;
 pub mod monitor;
 pub mod spawn;
 
@@ -13,7 +14,7 @@ use liblumen_alloc::erts::exception::runtime;
 use liblumen_alloc::erts::exception::system::Alloc;
 use liblumen_alloc::erts::process::alloc::heap_alloc::HeapAlloc;
 use liblumen_alloc::erts::process::code::stack::frame::Frame;
-use liblumen_alloc::erts::process::{self, Process, ProcessHeap};
+use liblumen_alloc::erts::process::{self, GcError, Process, ProcessFlags, ProcessHeap};
 use liblumen_alloc::erts::term::{atom_unchecked, Atom, Boxed, Reference, Term, Tuple, TypedTerm};
 use liblumen_alloc::erts::ModuleFunctionArity;
 use liblumen_alloc::{CloneToProcess, HeapFragment, Monitor};
@@ -22,12 +23,13 @@ use crate::code;
 #[cfg(test)]
 use crate::process::spawn::options::Options;
 use crate::registry::*;
-use crate::scheduler::Scheduler;
 #[cfg(test)]
 use crate::scheduler::Spawned;
+use crate::scheduler::{Scheduled, Scheduler};
 use crate::system;
 #[cfg(test)]
 use crate::test;
+use crate::time::monotonic;
 
 fn is_expected_exception(exception: &runtime::Exception) -> bool {
     match exception.class {
@@ -99,62 +101,134 @@ pub fn propagate_exit(process: &Process, exception: &runtime::Exception) {
 
 pub fn propagate_exit_to_links(process: &Process, exception: &runtime::Exception) {
     if !is_expected_exception(exception) {
-        let tag = atom_unchecked("EXIT");
         let from = process.pid_term();
         let reason = exception.reason;
-        let reason_word_size = reason.size_in_words();
-        let exit_message_elements: &[Term] = &[tag, from, reason];
-        let exit_message_word_size = Tuple::need_in_words_from_elements(exit_message_elements);
 
         for linked_pid in process.linked_pid_set.lock().iter() {
             if let Some(linked_pid_arc_process) = pid_to_process(linked_pid) {
                 if linked_pid_arc_process.traps_exit() {
-                    match linked_pid_arc_process.try_acquire_heap() {
-                        Some(ref mut linked_pid_heap) => {
-                            if exit_message_word_size <= linked_pid_heap.heap_available() {
-                                send_self_exit_message(
-                                    &linked_pid_arc_process,
-                                    linked_pid_heap,
-                                    exit_message_elements,
-                                );
-                            } else {
-                                send_heap_exit_message(
-                                    &linked_pid_arc_process,
-                                    exit_message_elements,
-                                );
-                            }
-                        }
-                        None => {
-                            send_heap_exit_message(&linked_pid_arc_process, exit_message_elements);
-                        }
-                    }
+                    send_exit_message(&linked_pid_arc_process, from, reason);
                 } else {
                     // only tell the linked process to exit.  When it is run by its scheduler, it
                     // will go through propagating its own exit.
-                    match linked_pid_arc_process.try_acquire_heap() {
-                        Some(ref mut linked_pid_heap) => {
-                            if reason_word_size <= linked_pid_heap.heap_available() {
-                                exit_in_heap(&linked_pid_arc_process, linked_pid_heap, reason);
-                            } else {
-                                exit_in_heap_fragment(&linked_pid_arc_process, reason);
-                            }
-                        }
-                        None => {
-                            exit_in_heap_fragment(&linked_pid_arc_process, reason);
-                        }
-                    }
+                    exit_with_reason(&linked_pid_arc_process, reason);
                 }
             }
         }
     }
 }
 
+/// Sends the exit signal of `erlang:exit(To, Reason)` from `from` to `to` when `to` is not `from`.
+///
+/// * `kill` cannot be trapped and exits `to` with reason `killed`.
+/// * `normal` is ignored unless `to` traps exits.
+/// * A `to` that traps exits receives `{'EXIT', From, Reason}`; otherwise, it exits with `reason`.
+pub fn exit_signal(from: &Process, to: &Process, reason: Term) {
+    if is_exit_reason(reason, "kill") {
+        exit_with_reason(to, atom_unchecked("killed"));
+    } else if to.traps_exit() {
+        send_exit_message(to, from.pid_term(), reason);
+    } else if !is_exit_reason(reason, "normal") {
+        exit_with_reason(to, reason);
+    }
+}
+
+/// Collects `process` and records when the collection finished for
+/// `process_info(Pid, garbage_collection_info)` and what was reclaimed for
+/// `statistics(garbage_collection)`.
+///
+/// If `process` is killed for exceeding its `max_heap_size`, it is rescheduled, so that its exit
+/// is propagated even if it was waiting.
+pub fn garbage_collect(
+    process: &Process,
+    need: usize,
+    roots: &mut [Term],
+) -> Result<usize, GcError> {
+    let words_reclaimed_before = process.gc_statistics().words_reclaimed;
+    let result = process.garbage_collect(need, roots);
+
+    match result {
+        Ok(_) => {
+            process.set_last_gc_time(monotonic::time_in_milliseconds());
+
+            if let Some(arc_scheduler) = process.scheduler() {
+                let words_reclaimed =
+                    process.gc_statistics().words_reclaimed - words_reclaimed_before;
+
+                arc_scheduler
+                    .statistics
+                    .garbage_collected(words_reclaimed as u64);
+            }
+        }
+        Err(GcError::MaxHeapSizeExceeded) => reschedule(process),
+        Err(_) => (),
+    }
+
+    result
+}
+
+/// Like `garbage_collect` without `need`, but does a full sweep when a minor collection is not
+/// possible.
+///
+/// Returns `false` if `process` was killed for exceeding its `max_heap_size` instead of being
+/// collected.
+pub fn garbage_collect_or_full_sweep(process: &Process, roots: &mut [Term]) -> Result<bool, Alloc> {
+    let result = match garbage_collect(process, 0, roots) {
+        Err(GcError::FullsweepRequired) => {
+            process.set_flags(ProcessFlags::NeedFullSweep);
+
+            garbage_collect(process, 0, roots)
+        }
+        result => result,
+    };
+
+    match result {
+        Ok(_) => Ok(true),
+        Err(GcError::Alloc(alloc)) => Err(alloc),
+        Err(GcError::MaxHeapSizeExceeded) => Ok(false),
+        Err(GcError::FullsweepRequired) => unreachable!(),
+    }
+}
+
+pub fn is_exit_reason(reason: Term, name: &str) -> bool {
+    match reason.to_typed_term().unwrap() {
+        TypedTerm::Atom(atom) => atom.name() == name,
+        _ => false,
+    }
+}
+
+/// Sends `{'EXIT', from, reason}` to `process`
+fn send_exit_message(process: &Process, from: Term, reason: Term) {
+    let tag = atom_unchecked("EXIT");
+    let exit_message_elements: &[Term] = &[tag, from, reason];
+    let exit_message_word_size = Tuple::need_in_words_from_elements(exit_message_elements);
+
+    match process.try_acquire_heap() {
+        Some(ref mut heap) => {
+            if exit_message_word_size <= heap.heap_available() {
+                send_self_exit_message(process, heap, exit_message_elements);
+            } else {
+                send_heap_exit_message(process, exit_message_elements);
+            }
+        }
+        None => {
+            send_heap_exit_message(process, exit_message_elements);
+        }
+    }
+
+    if process.stop_waiting() {
+        reschedule(process);
+    }
+}
+
 fn send_self_exit_message(
     process: &Process,
     heap: &mut ProcessHeap,
     exit_message_elements: &[Term],
 ) {
-    let data = heap.tuple_from_slice(exit_message_elements).unwrap();
+    // Unlike `tuple_from_slice`, copies `from` and `reason` too, so that the message does not refer
+    // to the heap of the sender
+    let data = Tuple::clone_to_heap_from_elements(heap, exit_message_elements).unwrap();
 
     process.send_from_self(data);
 }
@@ -166,6 +240,27 @@ fn send_heap_exit_message(process: &Process, exit_message_elements: &[Term]) {
     process.send_heap_message(heap_fragment, heap_fragment_data);
 }
 
+/// Exits `process` with a copy of `reason`
+fn exit_with_reason(process: &Process, reason: Term) {
+    let reason_word_size = reason.size_in_words();
+
+    match process.try_acquire_heap() {
+        Some(ref mut heap) => {
+            if reason_word_size <= heap.heap_available() {
+                exit_in_heap(process, heap, reason);
+            } else {
+                exit_in_heap_fragment(process, reason);
+            }
+        }
+        None => {
+            exit_in_heap_fragment(process, reason);
+        }
+    }
+
+    // A waiting process needs to be run, so that its scheduler propagates its exit
+    reschedule(process);
+}
+
 fn exit_in_heap(process: &Process, heap: &mut ProcessHeap, reason: Term) {
     let data = reason.clone_to_heap(heap).unwrap();
 
@@ -179,6 +274,13 @@ fn exit_in_heap_fragment(process: &Process, reason: Term) {
     process.exit(heap_fragment_data);
 }
 
+/// Moves `process` from its scheduler's waiting processes to a run queue
+fn reschedule(process: &Process) {
+    if let Some(arc_scheduler) = process.scheduler() {
+        arc_scheduler.stop_waiting(process);
+    }
+}
+
 pub fn register_in(
     arc_process: Arc<Process>,
     mut writable_registry: RwLockWriteGuard<HashMap<Atom, Registered>>,
@@ -0,0 +1,168 @@
+//! Collections of other processes requested with `garbage_collect/1,2`.
+//!
+//! While a process runs, its code can hold terms that are not roots, so only its own scheduler can
+//! collect it, before it next runs or right after it ran.  A waiting process is made runnable, so
+//! that its scheduler collects it, and then waits again without running unless it received a
+//! message.
+
+use std::ptr::{self, NonNull};
+
+use hashbrown::HashMap;
+
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::Mutex;
+
+use liblumen_alloc::erts::exception::system::Alloc;
+use liblumen_alloc::erts::process::{Process, ProcessFlags};
+use liblumen_alloc::erts::term::{atom_unchecked, Pid, Term};
+use liblumen_alloc::{CloneToProcess, HeapFragment};
+
+use crate::process::{garbage_collect_or_full_sweep, reschedule};
+use crate::registry::pid_to_process;
+
+/// Requests that the scheduler of `target` collects it, making `target` runnable if it is waiting.
+/// If `async_request_id` is given, `{garbage_collect, RequestId, Result}` is sent to
+/// `requester_pid` after the collection.
+pub fn request(
+    target: &Process,
+    requester_pid: Pid,
+    async_request_id: Option<Term>,
+) -> Result<(), Alloc> {
+    let option_async_request = match async_request_id {
+        Some(request_id) => Some(AsyncRequest::new(requester_pid, request_id)?),
+        None => None,
+    };
+
+    let woken = {
+        let mut locked_request_by_pid = MUTEX_REQUEST_BY_PID.lock();
+        let request = locked_request_by_pid
+            .entry(target.pid())
+            .or_insert_with(Default::default);
+
+        if let Some(async_request) = option_async_request {
+            request.async_request_vec.push(async_request);
+        }
+
+        // Set after the request is stored, so that the scheduler finds the request when it sees
+        // the flag
+        target.set_flags(ProcessFlags::GcRequested);
+
+        // A waiting process is not dequeued by its scheduler until it receives a message
+        let woken = target.stop_waiting();
+        request.woken |= woken;
+
+        woken
+    };
+
+    if woken {
+        reschedule(target);
+    }
+
+    Ok(())
+}
+
+/// Does the collection requested with `request`, if any.  Only called by the scheduler of
+/// `process` when it is not running `process`.
+///
+/// An exiting `process` is not collected, so `false` is sent to asynchronous requesters, as if
+/// `process` was no longer alive.
+///
+/// Returns `true` if `process` was made runnable only to be collected and has no messages, so it
+/// is waiting again and should not be run.
+pub fn collect_if_requested(process: &Process) -> bool {
+    if process.are_flags_set(ProcessFlags::GcRequested) {
+        process.clear_flags(ProcessFlags::GcRequested);
+
+        // Taken before collecting, so that a request made during the collection waits for the next
+        // collection
+        let Request {
+            async_request_vec,
+            woken,
+        } = MUTEX_REQUEST_BY_PID
+            .lock()
+            .remove(&process.pid())
+            .unwrap_or_default();
+
+        let collected = !process.is_exiting()
+            && garbage_collect_or_full_sweep(process, &mut []).unwrap_or(false);
+
+        for async_request in async_request_vec {
+            async_request.reply(collected);
+        }
+
+        woken && !process.is_exiting() && wait_unless_messages(process)
+    } else {
+        false
+    }
+}
+
+// Private
+
+#[derive(Default)]
+struct Request {
+    async_request_vec: Vec<AsyncRequest>,
+    /// Whether `request` made the process runnable
+    woken: bool,
+}
+
+struct AsyncRequest {
+    requester_pid: Pid,
+    request_id: Term,
+    /// Holds a copy of `request_id`, so that it does not depend on the heap of the requester
+    heap_fragment: NonNull<HeapFragment>,
+}
+
+impl AsyncRequest {
+    fn new(requester_pid: Pid, request_id: Term) -> Result<Self, Alloc> {
+        let (request_id, heap_fragment) = request_id.clone_to_fragment()?;
+
+        Ok(Self {
+            requester_pid,
+            request_id,
+            heap_fragment,
+        })
+    }
+
+    fn reply(&self, collected: bool) {
+        if let Some(requester_arc_process) = pid_to_process(&self.requester_pid) {
+            let tag = atom_unchecked("garbage_collect");
+            let (heap_fragment_data, heap_fragment) =
+                HeapFragment::tuple_from_slice(&[tag, self.request_id, collected.into()]).unwrap();
+
+            requester_arc_process.send_heap_message(heap_fragment, heap_fragment_data);
+
+            if requester_arc_process.stop_waiting() {
+                reschedule(&requester_arc_process);
+            }
+        }
+    }
+}
+
+impl Drop for AsyncRequest {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.heap_fragment.as_ptr()) };
+    }
+}
+
+// `heap_fragment` is owned by the request and only used by the scheduler that removes it
+unsafe impl Send for AsyncRequest {}
+
+/// Returns `true` if `process` has no messages and so waits again
+fn wait_unless_messages(process: &Process) -> bool {
+    // Hold the mailbox lock, so that a message sent concurrently either is seen here or finds the
+    // process already waiting
+    let mailbox_guard = process.mailbox.lock();
+
+    if mailbox_guard.borrow().len() == 0 {
+        process.wait();
+
+        true
+    } else {
+        false
+    }
+}
+
+lazy_static! {
+    static ref MUTEX_REQUEST_BY_PID: Mutex<HashMap<Pid, Request>> = Default::default();
+}
@@ -5,10 +5,11 @@ use liblumen_alloc::erts::exception::system::Alloc;
 use liblumen_alloc::erts::exception::Exception;
 use liblumen_alloc::erts::process::alloc::{default_heap_size, heap, next_heap_size};
 use liblumen_alloc::erts::process::{Priority, Process};
-use liblumen_alloc::erts::term::{Atom, Boxed, Cons, Term, Tuple, TypedTerm};
+use liblumen_alloc::erts::term::{atom_unchecked, Atom, Boxed, Cons, Map, Term, Tuple, TypedTerm};
 use liblumen_alloc::{badarg, ModuleFunctionArity};
 
 use crate::process;
+use crate::system;
 
 #[must_use]
 pub struct Connection {
@@ -24,6 +25,75 @@ pub struct MaxHeapSize {
     error_logger: Option<bool>,
 }
 
+impl MaxHeapSize {
+    fn try_from_map(map: &Map) -> Result<Self, Exception> {
+        let keys = ["error_logger", "kill", "size"];
+
+        for key in map.keys() {
+            let key_atom: Atom = key.try_into()?;
+
+            if !keys.contains(&key_atom.name()) {
+                return Err(badarg!().into());
+            }
+        }
+
+        let size: Option<usize> = match map.get(atom_unchecked("size")) {
+            Some(size) => Some(size.try_into()?),
+            None => None,
+        };
+        let kill: Option<bool> = match map.get(atom_unchecked("kill")) {
+            Some(kill) => Some(kill.try_into()?),
+            None => None,
+        };
+        let error_logger: Option<bool> = match map.get(atom_unchecked("error_logger")) {
+            Some(error_logger) => Some(error_logger.try_into()?),
+            None => None,
+        };
+
+        Ok(Self {
+            size,
+            kill,
+            error_logger,
+        })
+    }
+}
+
+/// Keys that are not set use the defaults, not the process's current values
+impl From<MaxHeapSize> for liblumen_alloc::erts::process::MaxHeapSize {
+    fn from(max_heap_size: MaxHeapSize) -> Self {
+        let default: Self = Default::default();
+
+        Self {
+            size: max_heap_size.size.unwrap_or(default.size),
+            kill: max_heap_size.kill.unwrap_or(default.kill),
+            error_logger: max_heap_size.error_logger.unwrap_or(default.error_logger),
+        }
+    }
+}
+
+/// Either a non-negative integer size in words or a map with any of the `size`, `kill`, and
+/// `error_logger` keys.
+impl TryFrom<Term> for MaxHeapSize {
+    type Error = Exception;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let result_map: Result<Boxed<Map>, _> = term.try_into();
+
+        match result_map {
+            Ok(map) => Self::try_from_map(&map),
+            Err(_) => {
+                let size: usize = term.try_into()?;
+
+                Ok(Self {
+                    size: Some(size),
+                    kill: None,
+                    error_logger: None,
+                })
+            }
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum MessageQueueData {
     OnHeap,
@@ -119,6 +189,23 @@ impl Options {
             heap_size,
         );
 
+        process.set_fullsweep_after(
+            self.fullsweep_after
+                .unwrap_or_else(system::flag::fullsweep_after),
+        );
+
+        if let Some(max_heap_size) = self.max_heap_size {
+            process.set_max_heap_size(max_heap_size.into());
+        }
+
+        if let Some(min_bin_vheap_size) = self.min_bin_vheap_size {
+            process.set_min_vheap_size(min_bin_vheap_size);
+        }
+
+        if let MessageQueueData::OffHeap = self.message_queue_data {
+            process.message_queue_off_heap(true);
+        }
+
         Ok(process)
     }
 
@@ -128,7 +215,7 @@ impl Options {
         match self.priority {
             Some(priority) => priority,
             None => match parent_process {
-                Some(process) => process.priority,
+                Some(process) => process.priority(),
                 None => Default::default(),
             },
         }
@@ -188,7 +275,14 @@ impl Options {
                         }
                         Err(_) => false,
                     },
-                    "max_heap_size" => unimplemented!(),
+                    "max_heap_size" => match tuple[1].try_into() {
+                        Ok(max_heap_size) => {
+                            self.max_heap_size = Some(max_heap_size);
+
+                            true
+                        }
+                        Err(_) => false,
+                    },
                     "message_queue_data" => match tuple[1].try_into() {
                         Ok(message_queue_data) => {
                             self.message_queue_data = message_queue_data;
@@ -34,6 +34,46 @@ pub fn names(process: &Process) -> exception::Result {
     Ok(acc)
 }
 
+/// Pids of all processes that are still alive, in ascending order
+pub fn pids(process: &Process) -> exception::Result {
+    let mut pid_vec: Vec<Pid> = RW_LOCK_WEAK_PROCESS_CONTROL_BLOCK_BY_PID
+        .read()
+        .iter()
+        .filter(|(_, weak_process)| weak_process.upgrade().is_some())
+        .map(|(pid, _)| *pid)
+        .collect();
+    pid_vec.sort();
+
+    let mut acc = Term::NIL;
+    let mut heap = process.acquire_heap();
+
+    for pid in pid_vec.iter().rev() {
+        let pid_term = unsafe { pid.as_term() };
+
+        acc = heap.cons(pid_term, acc)?
+    }
+
+    Ok(acc)
+}
+
+/// The number of processes that are alive
+pub fn process_count() -> usize {
+    RW_LOCK_WEAK_PROCESS_CONTROL_BLOCK_BY_PID
+        .read()
+        .values()
+        .filter(|weak_process| weak_process.upgrade().is_some())
+        .count()
+}
+
+/// All processes that are alive
+pub fn processes() -> Vec<Arc<Process>> {
+    RW_LOCK_WEAK_PROCESS_CONTROL_BLOCK_BY_PID
+        .read()
+        .values()
+        .filter_map(|weak_process| weak_process.upgrade())
+        .collect()
+}
+
 pub fn pid_to_process(pid: &Pid) -> Option<Arc<Process>> {
     RW_LOCK_WEAK_PROCESS_CONTROL_BLOCK_BY_PID
         .read()
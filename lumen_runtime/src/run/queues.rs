@@ -32,7 +32,7 @@ impl Queues {
             || self.max.contains(value)
     }
 
-    #[cfg(test)]
+    /// `Priority::Low` and `Priority::Normal` share a run queue, so they have the same length
     pub fn run_queue_len(&self, priority: Priority) -> usize {
         match priority {
             Priority::Low | Priority::Normal => self.normal_low.len(),
@@ -54,7 +54,7 @@ impl Queues {
     }
 
     pub fn enqueue(&mut self, arc_process: Arc<Process>) {
-        match arc_process.priority {
+        match arc_process.priority() {
             Priority::Low | Priority::Normal => self.normal_low.enqueue(arc_process),
             Priority::High => self.high.enqueue(arc_process),
             Priority::Max => self.max.enqueue(arc_process),
@@ -65,6 +65,21 @@ impl Queues {
         self.waiting.len() + self.normal_low.len() + self.high.len() + self.max.len()
     }
 
+    /// Moves `process` from the run queue for `old_priority` to the run queue for its current
+    /// priority.  Running and waiting processes are not in a priority run queue, so they are put in
+    /// the run queue for their current priority when they are next `requeue`d or `stop_waiting`.
+    pub fn reprioritize(&mut self, process: &Process, old_priority: Priority) {
+        let option_arc_process = match old_priority {
+            Priority::Low | Priority::Normal => self.normal_low.remove(process),
+            Priority::High => self.high.remove(process),
+            Priority::Max => self.max.remove(process),
+        };
+
+        if let Some(arc_process) = option_arc_process {
+            self.enqueue(arc_process);
+        }
+    }
+
     /// Returns the process is not pushed back because it is exiting
     #[must_use]
     pub fn requeue(&mut self, arc_process: Arc<Process>) -> Option<Arc<Process>> {
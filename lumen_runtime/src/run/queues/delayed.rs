@@ -44,6 +44,14 @@ impl Delayed {
         let delayed_process = DelayedProcess::new(arc_process);
         self.0.push_back(delayed_process);
     }
+
+    pub fn remove(&mut self, process: &Process) -> Option<Arc<Process>> {
+        self.0
+            .iter()
+            .position(|delayed_process| *delayed_process.arc_process == *process)
+            .and_then(|index| self.0.remove(index))
+            .map(|delayed_process| delayed_process.arc_process)
+    }
 }
 
 type Delay = u8;
@@ -57,7 +65,7 @@ struct DelayedProcess {
 impl DelayedProcess {
     fn new(arc_process: Arc<Process>) -> DelayedProcess {
         DelayedProcess {
-            delay: Self::priority_to_delay(arc_process.priority),
+            delay: Self::priority_to_delay(arc_process.priority()),
             arc_process,
         }
     }
@@ -29,4 +29,11 @@ impl Immediate {
     pub fn enqueue(&mut self, process: Arc<Process>) {
         self.0.push_back(process);
     }
+
+    pub fn remove(&mut self, process: &Process) -> Option<Arc<Process>> {
+        self.0
+            .iter()
+            .position(|arc_process| **arc_process == *process)
+            .and_then(|index| self.0.remove(index))
+    }
 }
@@ -1,3 +1,4 @@
+mod statistics;
 #[cfg(test)]
 pub mod test;
 
@@ -12,19 +13,20 @@ use liblumen_core::locks::{Mutex, RwLock};
 
 use liblumen_alloc::erts::exception::system::{Alloc, Exception};
 use liblumen_alloc::erts::process::code::Code;
-#[cfg(test)]
-use liblumen_alloc::erts::process::Priority;
-use liblumen_alloc::erts::process::{Process, Status};
+use liblumen_alloc::erts::process::{GcError, Priority, Process, Status};
 pub use liblumen_alloc::erts::scheduler::{id, ID};
 use liblumen_alloc::erts::term::{reference, Atom, Reference, Term};
 
 use crate::process;
+use crate::process::gc_request;
 use crate::process::spawn;
 use crate::process::spawn::options::{Connection, Options};
 use crate::registry::put_pid_to_process;
 use crate::run::{self, Run};
 use crate::timer::Hierarchy;
 
+pub use self::statistics::Statistics;
+
 pub trait Scheduled {
     fn scheduler(&self) -> Option<Arc<Scheduler>>;
 }
@@ -48,9 +50,22 @@ pub struct Scheduler {
     // References are always 64-bits even on 32-bit platforms
     reference_count: AtomicU64,
     run_queues: RwLock<run::queues::Queues>,
+    pub statistics: Statistics,
 }
 
 impl Scheduler {
+    /// All registered schedulers, ordered by `id`
+    pub fn all() -> Vec<Arc<Scheduler>> {
+        let mut scheduler_vec: Vec<Arc<Scheduler>> = SCHEDULER_BY_ID
+            .lock()
+            .values()
+            .filter_map(|weak_scheduler| weak_scheduler.upgrade())
+            .collect();
+        scheduler_vec.sort_by_key(|arc_scheduler| arc_scheduler.id);
+
+        scheduler_vec
+    }
+
     pub fn current() -> Arc<Scheduler> {
         SCHEDULER.with(|thread_local_scheduler| thread_local_scheduler.clone())
     }
@@ -117,17 +132,29 @@ impl Scheduler {
 
             match run {
                 Run::Now(arc_process) => {
+                    // `arc_process` is not running, so its heap can be moved
+                    let collected_only = gc_request::collect_if_requested(&arc_process);
+
+                    self.statistics.start_run();
+                    let reductions_before = arc_process.total_reductions.load(Ordering::SeqCst);
+
                     // Don't allow exiting processes to run again.
                     //
                     // Without this check, a process.exit() from outside the process during WAITING
                     // will return to the Frame that called `process.wait()`
-                    if !arc_process.is_exiting() {
+                    if collected_only {
+                        // It was made runnable only to be collected, so it waits for a message
+                        // again
+                    } else if !arc_process.is_exiting() {
                         match Process::run(&arc_process) {
                             Ok(()) => (),
                             Err(exception) => match exception {
                                 Exception::Alloc(_inner) => {
-                                    match arc_process.garbage_collect(0, &mut []) {
+                                    match process::garbage_collect(&arc_process, 0, &mut []) {
                                         Ok(_freed) => (),
+                                        // The process was killed, so `requeue` will return it
+                                        // as exiting
+                                        Err(GcError::MaxHeapSizeExceeded) => (),
                                         Err(gc_err) => panic!("Gc error: {:?}", gc_err),
                                     }
                                 }
@@ -137,22 +164,41 @@ impl Scheduler {
                         arc_process.reduce()
                     }
 
-                    match self.run_queues.write().requeue(arc_process) {
-                        Some(exiting_arc_process) => match *exiting_arc_process.status.read() {
+                    let reductions_after = arc_process.total_reductions.load(Ordering::SeqCst);
+                    self.statistics
+                        .stop_run(reductions_after - reductions_before);
+
+                    // Requests made while `arc_process` ran are done now, as it may wait for a long
+                    // time before it next runs
+                    gc_request::collect_if_requested(&arc_process);
+
+                    // separate from `match` below so that the WriteGuard temporary is not held
+                    // while sending to other processes, which may need to be requeued
+                    let option_exiting_arc_process = self.run_queues.write().requeue(arc_process);
+
+                    if let Some(exiting_arc_process) = option_exiting_arc_process {
+                        // Requests made during the last run are answered with `false`, as the
+                        // process is no longer alive
+                        gc_request::collect_if_requested(&exiting_arc_process);
+
+                        match *exiting_arc_process.status.read() {
                             Status::Exiting(ref exception) => {
                                 process::log_exit(&exiting_arc_process, exception);
                                 process::propagate_exit(&exiting_arc_process, exception);
                             }
                             _ => unreachable!(),
-                        },
-                        None => (),
-                    };
+                        }
+                    }
 
                     break true;
                 }
                 Run::Delayed => continue,
                 // TODO steal processes or sleep if nothing to steal
-                Run::None => break false,
+                Run::None => {
+                    self.statistics.idle();
+
+                    break false;
+                }
             }
         }
     }
@@ -161,7 +207,6 @@ impl Scheduler {
         self.run_queues.read().len()
     }
 
-    #[cfg(test)]
     pub fn run_queue_len(&self, priority: Priority) -> usize {
         self.run_queues.read().run_queue_len(priority)
     }
@@ -281,6 +326,12 @@ impl Scheduler {
         Ok(arc_process)
     }
 
+    /// Moves `process` to the run queue for its current priority if it was queued under
+    /// `old_priority`
+    pub fn reprioritize(&self, process: &Process, old_priority: Priority) {
+        self.run_queues.write().reprioritize(process, old_priority);
+    }
+
     pub fn stop_waiting(&self, process: &Process) {
         self.run_queues.write().stop_waiting(process);
     }
@@ -293,6 +344,7 @@ impl Scheduler {
             hierarchy: Default::default(),
             reference_count: AtomicU64::new(0),
             run_queues: Default::default(),
+            statistics: Default::default(),
         }
     }
 
@@ -323,6 +375,7 @@ impl Debug for Scheduler {
             // The hiearchy slots take a lot of space, so don't print them by default
             .field("reference_count", &self.reference_count)
             .field("run_queues", &self.run_queues)
+            .field("statistics", &self.statistics)
             .finish()
     }
 }
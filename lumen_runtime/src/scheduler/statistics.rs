@@ -0,0 +1,102 @@
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use liblumen_core::locks::Mutex;
+
+use crate::time::monotonic::time_in_milliseconds;
+use crate::time::Milliseconds;
+
+/// Counters for `erlang:statistics/1` kept by each `Scheduler`
+#[derive(Debug)]
+pub struct Statistics {
+    created_at: Milliseconds,
+    /// The number of times a process was run
+    context_switches: AtomicU64,
+    /// The reductions of all processes run
+    reductions: AtomicU64,
+    garbage_collections: AtomicU64,
+    words_reclaimed: AtomicU64,
+    /// Whether a process is currently being run
+    running: AtomicBool,
+    /// When `run_once` last found nothing to run.  `None` when not idle.
+    idle_since: Mutex<Option<Milliseconds>>,
+    /// The total time spent idle, not including the current `idle_since` period
+    idle_milliseconds: AtomicU64,
+}
+
+impl Statistics {
+    pub fn context_switches(&self) -> u64 {
+        self.context_switches.load(Ordering::SeqCst)
+    }
+
+    pub fn garbage_collections(&self) -> u64 {
+        self.garbage_collections.load(Ordering::SeqCst)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    pub fn reductions(&self) -> u64 {
+        self.reductions.load(Ordering::SeqCst)
+    }
+
+    /// `(active, total)` milliseconds since the scheduler was created
+    pub fn wall_time(&self) -> (Milliseconds, Milliseconds) {
+        let now = time_in_milliseconds();
+        let total = now - self.created_at;
+        let mut idle = self.idle_milliseconds.load(Ordering::SeqCst);
+
+        if let Some(idle_since) = *self.idle_since.lock() {
+            idle += now - idle_since;
+        }
+
+        (total.saturating_sub(idle), total)
+    }
+
+    pub fn words_reclaimed(&self) -> u64 {
+        self.words_reclaimed.load(Ordering::SeqCst)
+    }
+
+    /// Counts a collection run on this scheduler that freed `words_reclaimed`
+    pub fn garbage_collected(&self, words_reclaimed: u64) {
+        self.garbage_collections.fetch_add(1, Ordering::SeqCst);
+        self.words_reclaimed
+            .fetch_add(words_reclaimed, Ordering::SeqCst);
+    }
+
+    pub(super) fn idle(&self) {
+        self.idle_since
+            .lock()
+            .get_or_insert_with(time_in_milliseconds);
+    }
+
+    pub(super) fn start_run(&self) {
+        if let Some(idle_since) = self.idle_since.lock().take() {
+            self.idle_milliseconds
+                .fetch_add(time_in_milliseconds() - idle_since, Ordering::SeqCst);
+        }
+
+        self.running.store(true, Ordering::SeqCst);
+    }
+
+    pub(super) fn stop_run(&self, reductions: u64) {
+        self.running.store(false, Ordering::SeqCst);
+        self.context_switches.fetch_add(1, Ordering::SeqCst);
+        self.reductions.fetch_add(reductions, Ordering::SeqCst);
+    }
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self {
+            created_at: time_in_milliseconds(),
+            context_switches: Default::default(),
+            reductions: Default::default(),
+            garbage_collections: Default::default(),
+            words_reclaimed: Default::default(),
+            running: Default::default(),
+            idle_since: Default::default(),
+            idle_milliseconds: Default::default(),
+        }
+    }
+}
@@ -42,3 +42,29 @@ fn scheduler_does_run_exiting_process() {
         assert!(!scheduler.is_run_queued(&arc_process));
     })
 }
+
+#[test]
+fn scheduler_counts_context_switches_and_reductions() {
+    with_process_arc(|arc_process| {
+        let scheduler = Scheduler::current();
+        let context_switches_before = scheduler.statistics.context_switches();
+        let reductions_before = scheduler.statistics.reductions();
+
+        assert!(scheduler.run_through(&arc_process));
+
+        assert!(context_switches_before < scheduler.statistics.context_switches());
+        assert!(reductions_before < scheduler.statistics.reductions());
+        assert!(!scheduler.statistics.is_running());
+    })
+}
+
+#[test]
+fn scheduler_active_time_does_not_exceed_total_time() {
+    let scheduler = Scheduler::current();
+
+    while scheduler.run_once() {}
+
+    let (active, total) = scheduler.statistics.wall_time();
+
+    assert!(active <= total);
+}
@@ -1,5 +1,7 @@
 pub mod break_handler;
+pub mod flag;
 pub mod host;
 pub mod io;
+pub mod memory;
 pub mod random;
 pub mod time;
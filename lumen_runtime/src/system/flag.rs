@@ -0,0 +1,43 @@
+//! System-wide settings that can be changed with `erlang:system_flag/2`
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use liblumen_alloc::erts::process::Process;
+
+use crate::scheduler::Scheduler;
+
+/// The maximum number of entries in stacktraces
+pub fn backtrace_depth() -> usize {
+    BACKTRACE_DEPTH.load(Ordering::SeqCst)
+}
+
+/// Returns the old depth
+pub fn set_backtrace_depth(backtrace_depth: usize) -> usize {
+    BACKTRACE_DEPTH.swap(backtrace_depth, Ordering::SeqCst)
+}
+
+/// The `fullsweep_after` of processes spawned without one
+pub fn fullsweep_after() -> usize {
+    FULLSWEEP_AFTER.load(Ordering::SeqCst)
+}
+
+/// Returns the old `fullsweep_after`.  Processes that are already running keep theirs.
+pub fn set_fullsweep_after(fullsweep_after: usize) -> usize {
+    FULLSWEEP_AFTER.swap(fullsweep_after, Ordering::SeqCst)
+}
+
+/// Schedulers are created for each thread that runs processes
+pub fn schedulers() -> usize {
+    Scheduler::all().len()
+}
+
+/// Processes are never migrated to another scheduler, so taking a scheduler offline would strand
+/// its processes.  All schedulers are always online.
+pub fn schedulers_online() -> usize {
+    schedulers()
+}
+
+// Private
+
+static BACKTRACE_DEPTH: AtomicUsize = AtomicUsize::new(8);
+static FULLSWEEP_AFTER: AtomicUsize = AtomicUsize::new(Process::DEFAULT_FULLSWEEP_AFTER);
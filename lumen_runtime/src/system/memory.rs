@@ -0,0 +1,100 @@
+//! Memory usage reported by `erlang:memory/0,1`
+
+use core::mem;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{Atom, ProcBin, Term};
+use liblumen_alloc::std_alloc;
+
+use crate::registry;
+
+/// The memory types in the order that `erlang:memory/0` returns them
+pub const TYPES: [&str; 9] = [
+    "total",
+    "processes",
+    "processes_used",
+    "system",
+    "atom",
+    "atom_used",
+    "binary",
+    "code",
+    "ets",
+];
+
+/// A snapshot of memory usage in bytes
+pub struct Memory {
+    processes: usize,
+    processes_used: usize,
+    atom: usize,
+    atom_used: usize,
+    binary: usize,
+    /// Allocated by the standard allocator and not counted elsewhere. Heap fragments are also
+    /// allocated by the standard allocator, but are counted in `processes`.
+    other: usize,
+}
+
+impl Memory {
+    pub fn current() -> Self {
+        let mut processes = 0;
+        let mut processes_used = 0;
+        let mut off_heap_size = 0;
+
+        for arc_process in registry::processes() {
+            processes += mem::size_of::<Process>() + words_to_bytes(arc_process.total_heap_size());
+            processes_used +=
+                mem::size_of::<Process>() + words_to_bytes(arc_process.total_heap_used());
+            off_heap_size += arc_process.off_heap_size();
+        }
+
+        let (atom, atom_used) = Atom::memory();
+
+        Self {
+            processes,
+            processes_used,
+            atom,
+            atom_used,
+            binary: ProcBin::total_byte_len(),
+            other: std_alloc::bytes_in_use().saturating_sub(off_heap_size),
+        }
+    }
+
+    /// `None` if `type` is not one of `TYPES`
+    pub fn get(&self, r#type: &str) -> Option<usize> {
+        match r#type {
+            "total" => Some(self.total()),
+            "processes" => Some(self.processes),
+            "processes_used" => Some(self.processes_used),
+            "system" => Some(self.system()),
+            "atom" => Some(self.atom),
+            "atom_used" => Some(self.atom_used),
+            "binary" => Some(self.binary),
+            "code" => Some(self.code()),
+            "ets" => Some(self.ets()),
+            _ => None,
+        }
+    }
+
+    /// Code is compiled into the executable instead of being loaded, so it is not allocated
+    fn code(&self) -> usize {
+        0
+    }
+
+    /// Lumen does not have ETS tables
+    fn ets(&self) -> usize {
+        0
+    }
+
+    fn system(&self) -> usize {
+        self.atom + self.binary + self.code() + self.ets() + self.other
+    }
+
+    fn total(&self) -> usize {
+        self.processes + self.system()
+    }
+}
+
+// Private
+
+fn words_to_bytes(words: usize) -> usize {
+    words * mem::size_of::<Term>()
+}
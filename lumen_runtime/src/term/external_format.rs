@@ -1,22 +1,60 @@
 //! [External Term Format](http://erlang.org/doc/apps/erts/erl_ext_dist.html)
-use core::convert::TryFrom;
+use core::convert::{TryFrom, TryInto};
+use core::mem;
+
+use std::collections::HashSet;
+use std::io::{Cursor, Read};
+use std::str;
+use std::sync::Arc;
+
+use num_bigint::{BigInt, Sign};
 
 use liblumen_alloc::badarg;
+use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::exception::runtime::Exception;
+use liblumen_alloc::erts::process::code;
+use liblumen_alloc::erts::process::code::stack::frame::Placement;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::{AsTerm, Atom, Term};
+use liblumen_alloc::ModuleFunctionArity;
+
+use crate::node;
+use crate::otp::erlang::apply_3;
+
+pub const VERSION_NUMBER: u8 = 131;
 
 pub enum Tag {
     NewFloat = 70,
     BitBinary = 77,
+    Compressed = 80,
+    AtomCacheReference = 82,
+    NewPid = 88,
+    NewPort = 89,
+    NewerReference = 90,
     SmallInteger = 97,
     Integer = 98,
+    Float = 99,
     Atom = 100,
+    Reference = 101,
+    Port = 102,
+    Pid = 103,
     SmallTuple = 104,
+    LargeTuple = 105,
     EmptyList = 106,
     ByteList = 107,
     List = 108,
     Binary = 109,
     SmallBigInteger = 110,
+    LargeBigInteger = 111,
+    NewFunction = 112,
+    Export = 113,
+    NewReference = 114,
+    SmallAtom = 115,
+    Map = 116,
+    Function = 117,
+    AtomUTF8 = 118,
     SmallAtomUTF8 = 119,
+    V4Port = 120,
 }
 
 impl TryFrom<u8> for Tag {
@@ -28,17 +66,614 @@ impl TryFrom<u8> for Tag {
         match tag_byte {
             70 => Ok(NewFloat),
             77 => Ok(BitBinary),
+            80 => Ok(Compressed),
+            82 => Ok(AtomCacheReference),
+            88 => Ok(NewPid),
+            89 => Ok(NewPort),
+            90 => Ok(NewerReference),
             97 => Ok(SmallInteger),
             98 => Ok(Integer),
+            99 => Ok(Float),
             100 => Ok(Atom),
+            101 => Ok(Reference),
+            102 => Ok(Port),
+            103 => Ok(Pid),
             104 => Ok(SmallTuple),
+            105 => Ok(LargeTuple),
             106 => Ok(EmptyList),
             107 => Ok(ByteList),
             108 => Ok(List),
             109 => Ok(Binary),
             110 => Ok(SmallBigInteger),
+            111 => Ok(LargeBigInteger),
+            112 => Ok(NewFunction),
+            113 => Ok(Export),
+            114 => Ok(NewReference),
+            115 => Ok(SmallAtom),
+            116 => Ok(Map),
+            117 => Ok(Function),
+            118 => Ok(AtomUTF8),
             119 => Ok(SmallAtomUTF8),
+            120 => Ok(V4Port),
             _ => Err(badarg!()),
         }
     }
 }
+
+/// Decodes the term at the start of `bytes` onto the `process` heap, returning the term and the
+/// number of bytes that were used.
+///
+/// When `existing` is `true` (the `safe` option of `binary_to_term/2`), atoms that do not exist
+/// yet are not created and nodes that are not known yet are not registered, so that untrusted
+/// input cannot exhaust the atom table or the node registry.
+pub fn decode(
+    process: &Process,
+    existing: bool,
+    bytes: &[u8],
+) -> Result<(Term, usize), exception::Exception> {
+    let mut decoder = Decoder {
+        process,
+        existing,
+        bytes,
+        position: 0,
+        depth: 0,
+    };
+
+    if decoder.u8()? != VERSION_NUMBER {
+        return Err(badarg!().into());
+    }
+
+    let term = match Tag::try_from(decoder.peek()?)? {
+        Tag::Compressed => {
+            decoder.position += 1;
+            let uncompressed_len = decoder.u32()? as usize;
+            let (uncompressed, compressed_len) = decompress(decoder.remaining(), uncompressed_len)?;
+            decoder.position += compressed_len;
+
+            let mut uncompressed_decoder = Decoder {
+                process,
+                existing,
+                bytes: &uncompressed,
+                position: 0,
+                depth: 0,
+            };
+            let term = uncompressed_decoder.term()?;
+
+            if uncompressed_decoder.position != uncompressed.len() {
+                return Err(badarg!().into());
+            }
+
+            term
+        }
+        _ => decoder.term()?,
+    };
+
+    Ok((term, decoder.position))
+}
+
+// Private
+
+const FLOAT_EXT_LEN: usize = 31;
+
+/// Terms are decoded recursively, so nesting is limited to keep deeply nested input, such as a
+/// long run of `SMALL_TUPLE_EXT` with arity 1, from overflowing the stack.
+const MAX_DEPTH: usize = 256;
+
+struct Decoder<'b, 'p> {
+    process: &'p Process,
+    existing: bool,
+    bytes: &'b [u8],
+    position: usize,
+    depth: usize,
+}
+
+impl<'b, 'p> Decoder<'b, 'p> {
+    fn term(&mut self) -> Result<Term, exception::Exception> {
+        if self.depth >= MAX_DEPTH {
+            return Err(badarg!().into());
+        }
+
+        self.depth += 1;
+        let result = self.term_inner();
+        self.depth -= 1;
+
+        result
+    }
+
+    fn term_inner(&mut self) -> Result<Term, exception::Exception> {
+        let term = match Tag::try_from(self.u8()?)? {
+            Tag::NewFloat => {
+                let bits = self.u64()?;
+
+                self.process.float(f64::from_bits(bits))?
+            }
+            Tag::Float => {
+                let bytes = self.take(FLOAT_EXT_LEN)?;
+                let float_str = str::from_utf8(bytes)
+                    .map_err(|_| badarg!())?
+                    .trim_end_matches('\0');
+                let float_f64: f64 = float_str.trim().parse().map_err(|_| badarg!())?;
+
+                self.process.float(float_f64)?
+            }
+            Tag::BitBinary => {
+                let len = self.u32()? as usize;
+                let bits = self.u8()?;
+                let bytes = self.take(len)?;
+
+                match (len, bits) {
+                    (0, 0) => self.process.binary_from_bytes(bytes)?,
+                    (_, 8) => self.process.binary_from_bytes(bytes)?,
+                    (len, bits) if 0 < len && 0 < bits && bits < 8 => {
+                        // the unused low bits of the last byte are ignored
+                        let original = self.process.binary_from_bytes(bytes)?;
+
+                        self.process
+                            .subbinary_from_original(original, 0, 0, len - 1, bits)?
+                    }
+                    _ => return Err(badarg!().into()),
+                }
+            }
+            Tag::SmallInteger => {
+                let small_integer_u8 = self.u8()?;
+
+                self.process.integer(small_integer_u8)?
+            }
+            Tag::Integer => {
+                let integer_i32 = self.u32()? as i32;
+
+                self.process.integer(integer_i32)?
+            }
+            Tag::SmallBigInteger => {
+                let len = self.u8()? as usize;
+
+                self.big_integer(len)?
+            }
+            Tag::LargeBigInteger => {
+                let len = self.u32()? as usize;
+
+                self.big_integer(len)?
+            }
+            Tag::Atom => {
+                let len = self.u16()? as usize;
+
+                self.latin1_atom(len)?
+            }
+            Tag::SmallAtom => {
+                let len = self.u8()? as usize;
+
+                self.latin1_atom(len)?
+            }
+            Tag::AtomUTF8 => {
+                let len = self.u16()? as usize;
+
+                self.utf8_atom(len)?
+            }
+            Tag::SmallAtomUTF8 => {
+                let len = self.u8()? as usize;
+
+                self.utf8_atom(len)?
+            }
+            Tag::SmallTuple => {
+                let len = self.u8()? as usize;
+
+                self.tuple(len)?
+            }
+            Tag::LargeTuple => {
+                let len = self.u32()? as usize;
+
+                self.tuple(len)?
+            }
+            Tag::EmptyList => Term::NIL,
+            Tag::ByteList => {
+                let len = self.u16()? as usize;
+                let mut element_vec = Vec::with_capacity(len);
+
+                for byte in self.take(len)? {
+                    element_vec.push(self.process.integer(*byte)?);
+                }
+
+                self.process.list_from_slice(&element_vec)?
+            }
+            Tag::List => {
+                let len = self.u32()? as usize;
+                let element_vec = self.terms(len)?;
+                let tail = self.term()?;
+
+                self.process.improper_list_from_slice(&element_vec, tail)?
+            }
+            Tag::Binary => {
+                let len = self.u32()? as usize;
+                let bytes = self.take(len)?;
+
+                self.process.binary_from_bytes(bytes)?
+            }
+            Tag::Map => {
+                let len = self.u32()? as usize;
+                let mut entry_vec = Vec::with_capacity(self.capacity(len, 2));
+                let mut key_set = HashSet::with_capacity(entry_vec.capacity());
+
+                for _ in 0..len {
+                    let key = self.term()?;
+                    let value = self.term()?;
+
+                    // > Duplicate keys are not allowed within the same map.
+                    if !key_set.insert(key) {
+                        return Err(badarg!().into());
+                    }
+
+                    entry_vec.push((key, value));
+                }
+
+                self.process.map_from_slice(&entry_vec)?
+            }
+            Tag::Pid => {
+                let node = self.atom()?;
+                let number = self.u32()? as usize;
+                let serial = self.u32()? as usize;
+                let node_id = self.node_id(node, self.u8()? as u32)?;
+
+                self.pid(node_id, number, serial)?
+            }
+            Tag::NewPid => {
+                let node = self.atom()?;
+                let number = self.u32()? as usize;
+                let serial = self.u32()? as usize;
+                let node_id = self.node_id(node, self.u32()?)?;
+
+                self.pid(node_id, number, serial)?
+            }
+            Tag::Port => {
+                let node = self.atom()?;
+                let number = self.u32()? as usize;
+                let node_id = self.node_id(node, self.u8()? as u32)?;
+
+                self.port(node_id, number)?
+            }
+            Tag::NewPort => {
+                let node = self.atom()?;
+                let number = self.u32()? as usize;
+                let node_id = self.node_id(node, self.u32()?)?;
+
+                self.port(node_id, number)?
+            }
+            Tag::V4Port => {
+                let node = self.atom()?;
+                let number: usize = self.u64()?.try_into().map_err(|_| badarg!())?;
+                let node_id = self.node_id(node, self.u32()?)?;
+
+                self.port(node_id, number)?
+            }
+            Tag::Reference => {
+                let node = self.atom()?;
+                let id = self.u32()?;
+                let node_id = self.node_id(node, self.u8()? as u32)?;
+
+                self.reference(node_id, &[id])?
+            }
+            Tag::NewReference => {
+                let len = self.u16()? as usize;
+                let node = self.atom()?;
+                let node_id = self.node_id(node, self.u8()? as u32)?;
+                let id_vec = self.u32s(len)?;
+
+                self.reference(node_id, &id_vec)?
+            }
+            Tag::NewerReference => {
+                let len = self.u16()? as usize;
+                let node = self.atom()?;
+                let node_id = self.node_id(node, self.u32()?)?;
+                let id_vec = self.u32s(len)?;
+
+                self.reference(node_id, &id_vec)?
+            }
+            Tag::Export => {
+                let module = self.atom()?;
+                let function = self.atom()?;
+                let arity = match Tag::try_from(self.u8()?)? {
+                    Tag::SmallInteger => self.u8()?,
+                    _ => return Err(badarg!().into()),
+                };
+                let module_function_arity = Arc::new(ModuleFunctionArity {
+                    module,
+                    function,
+                    arity,
+                });
+
+                self.process.closure_with_env_from_slice(
+                    module_function_arity,
+                    export_code,
+                    self.process.pid_term(),
+                    &[],
+                )?
+            }
+            Tag::NewFunction => {
+                let start = self.position;
+                let size = self.u32()? as usize;
+                let arity = self.u8()?;
+                let _uniq = self.take(16)?;
+                let index = self.u32()?;
+                let free_len = self.u32()? as usize;
+                let module = self.atom()?;
+                let _old_index = self.term()?;
+                let _old_uniq = self.term()?;
+                let creator = self.term()?;
+                let free_vec = self.terms(free_len)?;
+
+                // > Size - The total number of bytes, including field Size.
+                if self.position - start != size {
+                    return Err(badarg!().into());
+                }
+
+                let function = self.new_atom(&format!("-fun-{}-", index))?;
+                let module_function_arity = Arc::new(ModuleFunctionArity {
+                    module,
+                    function,
+                    arity,
+                });
+
+                self.process.closure_with_env_from_slice(
+                    module_function_arity,
+                    local_code,
+                    creator,
+                    &free_vec,
+                )?
+            }
+            // Only valid in the distribution protocol or no longer emitted since OTP R8
+            Tag::Compressed | Tag::AtomCacheReference | Tag::Function => {
+                return Err(badarg!().into())
+            }
+        };
+
+        Ok(term)
+    }
+
+    fn terms(&mut self, len: usize) -> Result<Vec<Term>, exception::Exception> {
+        let mut term_vec = Vec::with_capacity(self.capacity(len, 1));
+
+        for _ in 0..len {
+            term_vec.push(self.term()?);
+        }
+
+        Ok(term_vec)
+    }
+
+    fn tuple(&mut self, len: usize) -> Result<Term, exception::Exception> {
+        let element_vec = self.terms(len)?;
+
+        self.process
+            .tuple_from_slice(&element_vec)
+            .map_err(|alloc| alloc.into())
+    }
+
+    fn big_integer(&mut self, len: usize) -> Result<Term, exception::Exception> {
+        let sign = match self.u8()? {
+            0 => Sign::Plus,
+            1 => Sign::Minus,
+            _ => return Err(badarg!().into()),
+        };
+        let little_endian_bytes = self.take(len)?;
+        let big_int = BigInt::from_bytes_le(sign, little_endian_bytes);
+
+        // small values are normalized to small integers
+        self.process.integer(big_int).map_err(|alloc| alloc.into())
+    }
+
+    /// Decodes an atom term, such as the node of a pid or the module of a fun
+    fn atom(&mut self) -> Result<Atom, exception::Exception> {
+        let term = self.term()?;
+
+        term.try_into().map_err(|_| badarg!().into())
+    }
+
+    fn latin1_atom(&mut self, len: usize) -> Result<Term, exception::Exception> {
+        let name: String = self.take(len)?.iter().map(|byte| *byte as char).collect();
+
+        self.atom_from_str(&name)
+    }
+
+    fn utf8_atom(&mut self, len: usize) -> Result<Term, exception::Exception> {
+        let bytes = self.take(len)?;
+        let name = str::from_utf8(bytes).map_err(|_| badarg!())?;
+
+        self.atom_from_str(name)
+    }
+
+    fn atom_from_str(&self, name: &str) -> Result<Term, exception::Exception> {
+        self.new_atom(name).map(|atom| unsafe { atom.as_term() })
+    }
+
+    fn new_atom(&self, name: &str) -> Result<Atom, exception::Exception> {
+        let result = if self.existing {
+            Atom::try_from_str_existing(name)
+        } else {
+            Atom::try_from_str(name)
+        };
+
+        result.map_err(|_| badarg!().into())
+    }
+
+    /// Like `new_atom`, nodes that are not known yet are not registered when `existing` is `true`,
+    /// so that untrusted input cannot grow the node registry without bound.
+    fn node_id(&self, node: Atom, creation: u32) -> Result<usize, exception::Exception> {
+        if self.existing {
+            node::existing_id(node, creation).ok_or_else(|| badarg!().into())
+        } else {
+            Ok(node::id(node, creation))
+        }
+    }
+
+    fn pid(
+        &self,
+        node_id: usize,
+        number: usize,
+        serial: usize,
+    ) -> Result<Term, exception::Exception> {
+        self.process
+            .pid_with_node_id(node_id, number, serial)
+            .map_err(|_| badarg!().into())
+    }
+
+    fn port(&self, node_id: usize, number: usize) -> Result<Term, exception::Exception> {
+        if node_id == node::LOCAL_ID {
+            if number <= Term::MAX_IMMEDIATE1_VALUE {
+                Ok(Term::make_port(number))
+            } else {
+                Err(badarg!().into())
+            }
+        } else {
+            self.process
+                .external_port_with_node_id(node_id, number)
+                .map_err(|alloc| alloc.into())
+        }
+    }
+
+    /// `term_to_binary` encodes the scheduler ID followed by the big-endian words of the number,
+    /// which is also how the 3-word references of BEAM are mapped.
+    fn reference(&self, node_id: usize, id_slice: &[u32]) -> Result<Term, exception::Exception> {
+        let (scheduler_id, number) = match *id_slice {
+            [id] => (0, id as u64),
+            [scheduler_id, number] => (scheduler_id, number as u64),
+            [scheduler_id, high, low] => (scheduler_id, ((high as u64) << 32) | (low as u64)),
+            _ => return Err(badarg!().into()),
+        };
+
+        let result = if node_id == node::LOCAL_ID {
+            self.process
+                .reference_from_scheduler(scheduler_id.into(), number)
+        } else {
+            self.process
+                .external_reference_with_node_id(node_id, scheduler_id.into(), number)
+        };
+
+        result.map_err(|alloc| alloc.into())
+    }
+
+    /// Guards against length fields that are larger than the remaining input, so that malformed
+    /// input cannot cause huge up-front allocations
+    fn capacity(&self, len: usize, min_bytes_per_element: usize) -> usize {
+        len.min(self.remaining().len() / min_bytes_per_element)
+    }
+
+    fn remaining(&self) -> &'b [u8] {
+        &self.bytes[self.position..]
+    }
+
+    fn peek(&self) -> Result<u8, Exception> {
+        self.remaining().first().cloned().ok_or_else(|| badarg!())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'b [u8], Exception> {
+        if len <= self.remaining().len() {
+            let bytes = &self.bytes[self.position..self.position + len];
+            self.position += len;
+
+            Ok(bytes)
+        } else {
+            Err(badarg!())
+        }
+    }
+
+    fn u8(&mut self) -> Result<u8, Exception> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Exception> {
+        let mut buffer = [0; mem::size_of::<u16>()];
+        buffer.copy_from_slice(self.take(buffer.len())?);
+
+        Ok(u16::from_be_bytes(buffer))
+    }
+
+    fn u32(&mut self) -> Result<u32, Exception> {
+        let mut buffer = [0; mem::size_of::<u32>()];
+        buffer.copy_from_slice(self.take(buffer.len())?);
+
+        Ok(u32::from_be_bytes(buffer))
+    }
+
+    fn u32s(&mut self, len: usize) -> Result<Vec<u32>, Exception> {
+        let mut u32_vec = Vec::with_capacity(self.capacity(len, mem::size_of::<u32>()));
+
+        for _ in 0..len {
+            u32_vec.push(self.u32()?);
+        }
+
+        Ok(u32_vec)
+    }
+
+    fn u64(&mut self) -> Result<u64, Exception> {
+        let mut buffer = [0; mem::size_of::<u64>()];
+        buffer.copy_from_slice(self.take(buffer.len())?);
+
+        Ok(u64::from_be_bytes(buffer))
+    }
+}
+
+/// Returns the uncompressed bytes and the number of compressed bytes that were read
+fn decompress(compressed: &[u8], uncompressed_len: usize) -> Result<(Vec<u8>, usize), Exception> {
+    let mut cursor = Cursor::new(compressed);
+    let mut uncompressed = Vec::with_capacity(uncompressed_len.min(compressed.len() * 1032));
+
+    {
+        let decoder = libflate::zlib::Decoder::new(&mut cursor).map_err(|_| badarg!())?;
+        decoder
+            .take(uncompressed_len as u64 + 1)
+            .read_to_end(&mut uncompressed)
+            .map_err(|_| badarg!())?;
+    }
+
+    if uncompressed.len() == uncompressed_len {
+        Ok((uncompressed, cursor.position() as usize))
+    } else {
+        Err(badarg!())
+    }
+}
+
+/// Calls the `module:function/arity` of an `EXPORT_EXT` fun through `apply/3`, so that it is
+/// resolved when called like any other remote call.
+fn export_code(arc_process: &Arc<Process>) -> code::Result {
+    let module_function_arity = arc_process.current_module_function_arity().unwrap();
+    let arity = module_function_arity.arity as usize;
+    let mut argument_vec = Vec::with_capacity(arity);
+
+    for _ in 0..arity {
+        argument_vec.push(arc_process.stack_pop().unwrap());
+    }
+
+    let argument_list = arc_process.list_from_slice(&argument_vec)?;
+
+    apply_3::place_frame_with_arguments(
+        arc_process,
+        Placement::Replace,
+        unsafe { module_function_arity.module.as_term() },
+        unsafe { module_function_arity.function.as_term() },
+        argument_list,
+    )?;
+
+    Process::call_code(arc_process)
+}
+
+/// The code of a `NEW_FUN_EXT` fun cannot be found from its `Uniq` and `Index`, as the module's
+/// funs are not loaded at runtime, so calling it raises `undef`, as BEAM does when the fun's
+/// module is not loaded.
+fn local_code(arc_process: &Arc<Process>) -> code::Result {
+    let module_function_arity = arc_process.current_module_function_arity().unwrap();
+    arc_process.reduce();
+
+    let arity = arc_process.integer(module_function_arity.arity)?;
+
+    match liblumen_alloc::undef!(
+        arc_process,
+        unsafe { module_function_arity.module.as_term() },
+        unsafe { module_function_arity.function.as_term() },
+        arity
+    ) {
+        exception::Exception::Runtime(runtime_exception) => {
+            arc_process.exception(runtime_exception);
+
+            Ok(())
+        }
+        exception::Exception::System(system_exception) => Err(system_exception),
+    }
+}